@@ -0,0 +1,438 @@
+use dialoguer::{Confirm, Input};
+use near_primitives::borsh::BorshSerialize;
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+use structopt::StructOpt;
+
+/// Overrides the socket path of the signing agent
+pub const AGENT_SOCKET_ENV_VAR: &str = "NEAR_CLI_AGENT_SOCKET";
+/// How long a client has to send its request and read the response
+const CONNECTION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Keep the keychain keys unlocked in memory and sign for the other near-cli
+/// processes of this user over a unix socket, checking every transaction
+/// against the policy (the `sign-keychain` option uses a running agent); with
+/// a master password the agent stops after `unlock_timeout_secs`
+#[derive(Debug)]
+pub struct AgentCommand {
+    /// Only sign transactions to these receivers (any receiver when empty)
+    pub allowed_receivers: Vec<String>,
+    /// The most yoctoNEAR a transaction can transfer, attach or stake
+    pub max_deposit: Option<u128>,
+    /// Also sign AddKey, DeleteKey, DeployContract and DeleteAccount actions
+    pub allow_account_changes: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliAgentCommand {
+    #[structopt(long, use_delimiter = true)]
+    allowed_receivers: Vec<String>,
+    /// e.g. `10 NEAR`
+    #[structopt(long, parse(try_from_str = parse_near_amount))]
+    max_deposit: Option<u128>,
+    #[structopt(long)]
+    allow_account_changes: bool,
+}
+
+fn parse_near_amount(amount: &str) -> Result<u128, String> {
+    crate::amount::AmountExpression::from_str(amount)?.evaluate(None)
+}
+
+impl From<CliAgentCommand> for AgentCommand {
+    fn from(item: CliAgentCommand) -> Self {
+        AgentCommand {
+            allowed_receivers: item.allowed_receivers,
+            max_deposit: item.max_deposit,
+            allow_account_changes: item.allow_account_changes,
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+enum AgentRequest {
+    Keys {
+        network: String,
+        account_id: String,
+    },
+    Sign {
+        network: String,
+        unsigned_transaction: String,
+    },
+}
+
+pub fn socket_path() -> std::path::PathBuf {
+    if let Some(socket_path) = std::env::var_os(AGENT_SOCKET_ENV_VAR) {
+        return std::path::PathBuf::from(socket_path);
+    }
+    let mut socket_path = dirs::runtime_dir()
+        .or_else(dirs::data_dir)
+        .expect("Impossible to get your data dir!");
+    socket_path.push("near-cli");
+    socket_path.push("agent.sock");
+    socket_path
+}
+
+impl AgentCommand {
+    pub fn input_allowed_receivers() -> Vec<String> {
+        let allowed_receivers: String = Input::new()
+            .with_prompt("Only sign for these receivers (comma-separated, leave empty for any)")
+            .allow_empty(true)
+            .interact_text()
+            .unwrap();
+        allowed_receivers
+            .split(',')
+            .map(|receiver_id| receiver_id.trim().to_string())
+            .filter(|receiver_id| !receiver_id.is_empty())
+            .collect()
+    }
+    pub fn input_max_deposit() -> Option<u128> {
+        loop {
+            let max_deposit: String = Input::new()
+                .with_prompt("The most a transaction can send (e.g. 10 NEAR, leave empty for any)")
+                .allow_empty(true)
+                .interact_text()
+                .unwrap();
+            if max_deposit.trim().is_empty() {
+                break None;
+            }
+            match parse_near_amount(&max_deposit) {
+                Ok(max_deposit) => break Some(max_deposit),
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+    }
+    pub fn input_allow_account_changes() -> bool {
+        Confirm::new()
+            .with_prompt("Also sign key, contract and account deletion changes?")
+            .default(false)
+            .interact()
+            .unwrap()
+    }
+
+    #[cfg(unix)]
+    pub async fn process(self) {
+        if let Err(err) = crate::master_password::unlock_if_set() {
+            return eprintln!("{}", err);
+        }
+        let access_keys = std::sync::Arc::new(read_all_access_keys());
+        let socket_path = socket_path();
+        if client_stream().is_some() {
+            return eprintln!("An agent is already listening on {:?}", socket_path);
+        }
+        std::fs::remove_file(&socket_path).ok();
+        let listener = match bind_private_socket(&socket_path) {
+            Ok(listener) => listener,
+            Err(err) => return eprintln!("Error listening on {:?}:  {}", socket_path, err),
+        };
+        eprintln!(
+            "{} with {} keys on {:?} (export {}={:?} if it is not the default path)",
+            crate::style::success("The agent is signing"),
+            access_keys.len(),
+            socket_path,
+            AGENT_SOCKET_ENV_VAR,
+            socket_path
+        );
+        // Accepting blocks, so it runs on its own thread and leaves the runtime
+        // free (e.g. for --max-duration); a slow client only holds up its own thread
+        let agent = std::sync::Arc::new(self);
+        let (stopped_sender, stopped_receiver) = futures::channel::oneshot::channel::<()>();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(err) => {
+                        eprintln!("Error accepting a connection:  {:?}", err);
+                        continue;
+                    }
+                };
+                let agent = agent.clone();
+                let access_keys = access_keys.clone();
+                std::thread::spawn(move || {
+                    if let Err(err) = agent.handle_connection(stream, &access_keys) {
+                        eprintln!("Error handling a request:  {}", err);
+                    }
+                });
+            }
+            stopped_sender.send(()).ok();
+        });
+        // With a master password the keys stay unlocked only until the timeout
+        let relocked =
+            futures::future::select(stopped_receiver, Box::pin(crate::master_password::relock()));
+        if let futures::future::Either::Right(_) = relocked.await {
+            std::fs::remove_file(&socket_path).ok();
+            eprintln!("The keys are locked again; restart the agent to unlock them");
+        }
+    }
+    #[cfg(not(unix))]
+    pub async fn process(self) {
+        eprintln!("The signing agent is only supported on Unix")
+    }
+
+    #[cfg(unix)]
+    fn handle_connection(
+        &self,
+        stream: std::os::unix::net::UnixStream,
+        access_keys: &[(String, crate::common::KeychainAccessKey)],
+    ) -> Result<(), String> {
+        stream
+            .set_read_timeout(Some(CONNECTION_TIMEOUT))
+            .and_then(|_| stream.set_write_timeout(Some(CONNECTION_TIMEOUT)))
+            .map_err(|err| format!("{:?}", err))?;
+        let mut request = String::new();
+        std::io::BufReader::new(&stream)
+            .read_line(&mut request)
+            .map_err(|err| format!("{:?}", err))?;
+        let response = match serde_json::from_str::<AgentRequest>(&request) {
+            Ok(request) => self.handle_request(request, access_keys),
+            Err(err) => Err(format!("Invalid request: {}", err)),
+        };
+        let response = match response {
+            Ok(response) => response,
+            Err(err) => {
+                eprintln!("{}", crate::style::warning(&err));
+                serde_json::json!({ "error": err })
+            }
+        };
+        writeln!(&stream, "{}", response).map_err(|err| format!("{:?}", err))
+    }
+
+    fn handle_request(
+        &self,
+        request: AgentRequest,
+        access_keys: &[(String, crate::common::KeychainAccessKey)],
+    ) -> Result<serde_json::Value, String> {
+        match request {
+            AgentRequest::Keys {
+                network,
+                account_id,
+            } => {
+                let public_keys = access_keys
+                    .iter()
+                    .filter(|(key_network, access_key)| {
+                        key_network == &network && access_key.account_id == account_id
+                    })
+                    .map(|(_, access_key)| access_key.public_key.clone())
+                    .collect::<Vec<_>>();
+                Ok(serde_json::json!({ "public_keys": public_keys }))
+            }
+            AgentRequest::Sign {
+                network,
+                unsigned_transaction,
+            } => {
+                let unsigned_transaction = crate::common::from_base64_borsh::<
+                    near_primitives::transaction::Transaction,
+                >(&unsigned_transaction)
+                .map_err(|err| format!("Invalid unsigned transaction: {}", err))?;
+                self.check_policy(&unsigned_transaction)?;
+                let public_key = unsigned_transaction.public_key.to_string();
+                let (_, access_key) = access_keys
+                    .iter()
+                    .find(|(key_network, access_key)| {
+                        key_network == &network
+                            && access_key.account_id == unsigned_transaction.signer_id
+                            && access_key.public_key == public_key
+                    })
+                    .ok_or_else(|| {
+                        format!("The agent has no key {} for {}", public_key, network)
+                    })?;
+                let signer_secret_key =
+                    near_crypto::SecretKey::from_str(&access_key.private_key)
+                        .map_err(|err| format!("Invalid secret key in the keychain: {:?}", err))?;
+                let transaction_hash = unsigned_transaction.get_hash();
+                let signature = signer_secret_key.sign(transaction_hash.as_ref());
+                eprintln!(
+                    "Signed {} from <{}> to <{}>",
+                    crate::style::hash(transaction_hash),
+                    unsigned_transaction.signer_id,
+                    unsigned_transaction.receiver_id
+                );
+                crate::common::append_to_audit_log(serde_json::json!({
+                    "event": "agent_signature",
+                    "signer_id": &unsigned_transaction.signer_id,
+                    "receiver_id": &unsigned_transaction.receiver_id,
+                    "public_key": &public_key,
+                    "transaction_hash": transaction_hash.to_string(),
+                }));
+                Ok(serde_json::json!({ "signature": signature.to_string() }))
+            }
+        }
+    }
+
+    fn check_policy(
+        &self,
+        unsigned_transaction: &near_primitives::transaction::Transaction,
+    ) -> Result<(), String> {
+        if !self.allowed_receivers.is_empty()
+            && !self
+                .allowed_receivers
+                .contains(&unsigned_transaction.receiver_id)
+        {
+            return Err(format!(
+                "Policy: the receiver <{}> is not allowed",
+                unsigned_transaction.receiver_id
+            ));
+        }
+        let mut deposit: u128 = 0;
+        for action in &unsigned_transaction.actions {
+            match action {
+                near_primitives::transaction::Action::Transfer(transfer) => {
+                    deposit = deposit.saturating_add(transfer.deposit)
+                }
+                near_primitives::transaction::Action::FunctionCall(function_call) => {
+                    deposit = deposit.saturating_add(function_call.deposit)
+                }
+                near_primitives::transaction::Action::Stake(stake) => {
+                    deposit = deposit.saturating_add(stake.stake)
+                }
+                near_primitives::transaction::Action::AddKey(_)
+                | near_primitives::transaction::Action::DeleteKey(_)
+                | near_primitives::transaction::Action::DeployContract(_)
+                | near_primitives::transaction::Action::DeleteAccount(_)
+                    if !self.allow_account_changes =>
+                {
+                    return Err(
+                        "Policy: key, contract and account deletion changes are not allowed"
+                            .to_string(),
+                    )
+                }
+                _ => {}
+            }
+        }
+        match self.max_deposit {
+            Some(max_deposit) if deposit > max_deposit => Err(format!(
+                "Policy: the transaction sends {}, more than {}",
+                crate::common::format_near_amount(deposit),
+                crate::common::format_near_amount(max_deposit)
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Binds the socket in a new directory only the user can enter and moves it to
+/// `socket_path` once it is readable only by the user, so no other user can
+/// connect in between
+#[cfg(unix)]
+fn bind_private_socket(
+    socket_path: &std::path::Path,
+) -> Result<std::os::unix::net::UnixListener, String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let socket_dir = socket_path
+        .parent()
+        .ok_or_else(|| "The socket path has no directory".to_string())?;
+    crate::common::create_private_dir_all(socket_dir)
+        .map_err(|err| format!("Failed to create {:?}: {:?}", socket_dir, err))?;
+    let private_dir = socket_dir.join(format!(".agent-{}", std::process::id()));
+    std::fs::remove_dir_all(&private_dir).ok();
+    crate::common::create_private_dir_all(&private_dir)
+        .map_err(|err| format!("Failed to create {:?}: {:?}", private_dir, err))?;
+    let private_socket_path = private_dir.join("agent.sock");
+    let result = std::os::unix::net::UnixListener::bind(&private_socket_path)
+        .and_then(|listener| {
+            std::fs::set_permissions(&private_socket_path, std::fs::Permissions::from_mode(0o600))?;
+            std::fs::rename(&private_socket_path, socket_path)?;
+            Ok(listener)
+        })
+        .map_err(|err| format!("{:?}", err));
+    std::fs::remove_dir_all(&private_dir).ok();
+    result
+}
+
+/// Reads every key of every network from the credentials directory
+fn read_all_access_keys() -> Vec<(String, crate::common::KeychainAccessKey)> {
+    let network_dirs = match std::fs::read_dir(crate::common::get_credentials_home_dir()) {
+        Ok(network_dirs) => network_dirs,
+        Err(_) => return vec![],
+    };
+    let mut access_keys = vec![];
+    for network_dir in network_dirs.filter_map(|entry| entry.ok()) {
+        let network = network_dir.file_name().to_string_lossy().into_owned();
+        let account_dirs = match std::fs::read_dir(network_dir.path()) {
+            Ok(account_dirs) => account_dirs,
+            Err(_) => continue,
+        };
+        for account_dir in account_dirs.filter_map(|entry| entry.ok()) {
+            let account_id = account_dir.file_name().to_string_lossy().into_owned();
+            access_keys.extend(
+                crate::common::read_access_keys_from_keychain(&network, &account_id)
+                    .into_iter()
+                    .map(|access_key| (network.clone(), access_key)),
+            );
+        }
+    }
+    access_keys
+}
+
+#[cfg(unix)]
+fn client_stream() -> Option<std::os::unix::net::UnixStream> {
+    std::os::unix::net::UnixStream::connect(socket_path()).ok()
+}
+
+#[cfg(unix)]
+fn call_agent(request: &AgentRequest) -> Result<serde_json::Value, String> {
+    let stream = client_stream().ok_or_else(|| "The agent is not running".to_string())?;
+    let request = serde_json::to_string(request).map_err(|err| format!("{:?}", err))?;
+    writeln!(&stream, "{}", request).map_err(|err| format!("{:?}", err))?;
+    let mut response = String::new();
+    std::io::BufReader::new(&stream)
+        .read_line(&mut response)
+        .map_err(|err| format!("{:?}", err))?;
+    let response: serde_json::Value =
+        serde_json::from_str(&response).map_err(|err| format!("{:?}", err))?;
+    match response["error"].as_str() {
+        Some(err) => Err(err.to_string()),
+        None => Ok(response),
+    }
+}
+
+/// `None` when no agent is running, otherwise the public keys it holds for the account
+#[cfg(unix)]
+pub fn agent_public_keys(network: &str, account_id: &str) -> Option<Vec<String>> {
+    let response = call_agent(&AgentRequest::Keys {
+        network: network.to_string(),
+        account_id: account_id.to_string(),
+    })
+    .ok()?;
+    Some(
+        response["public_keys"]
+            .as_array()
+            .map(|public_keys| {
+                public_keys
+                    .iter()
+                    .filter_map(|public_key| public_key.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    )
+}
+#[cfg(not(unix))]
+pub fn agent_public_keys(_network: &str, _account_id: &str) -> Option<Vec<String>> {
+    None
+}
+
+#[cfg(unix)]
+pub fn agent_signature(
+    network: &str,
+    unsigned_transaction: &near_primitives::transaction::Transaction,
+) -> Result<near_crypto::Signature, String> {
+    let response = call_agent(&AgentRequest::Sign {
+        network: network.to_string(),
+        unsigned_transaction: near_primitives::serialize::to_base64(
+            unsigned_transaction
+                .try_to_vec()
+                .expect("Transaction is not expected to fail on serialization"),
+        ),
+    })?;
+    let signature = response["signature"].as_str().unwrap_or_default();
+    near_crypto::Signature::from_str(signature)
+        .map_err(|err| format!("invalid signature {:?}: {:?}", signature, err))
+}
+#[cfg(not(unix))]
+pub fn agent_signature(
+    _network: &str,
+    _unsigned_transaction: &near_primitives::transaction::Transaction,
+) -> Result<near_crypto::Signature, String> {
+    Err("The signing agent is only supported on Unix".to_string())
+}
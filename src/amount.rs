@@ -0,0 +1,452 @@
+use crate::common::ONE_NEAR;
+
+/// Balance entered as an amount (`10NEAR`, `0.5 N`, `100 yoctoNEAR`) or as a
+/// simple expression (`0.1*3 NEAR`, `all-0.05N`), where `all` is the full
+/// account balance and numbers without a unit are plain multipliers
+#[derive(Debug, Clone)]
+pub struct AmountExpression {
+    source: String,
+    expression: AmountExpressionNode,
+}
+
+#[derive(Debug, Clone)]
+enum AmountExpressionNode {
+    Number {
+        mantissa: u128,
+        decimals: u32,
+        unit: Option<AmountUnit>,
+    },
+    All,
+    BinaryOperation(Box<AmountExpressionNode>, char, Box<AmountExpressionNode>),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum AmountUnit {
+    Near,
+    YoctoNear,
+}
+
+enum AmountValue {
+    Amount(u128),
+    Scalar { mantissa: u128, decimals: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum AmountToken {
+    Number(String),
+    Word(String),
+    Symbol(char),
+}
+
+impl std::str::FromStr for AmountExpression {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize_amount_expression(s)?;
+        let mut position = 0;
+        let expression = parse_amount_sum(&tokens, &mut position)?;
+        if position != tokens.len() {
+            return Err(format!("Unexpected {:?} in the amount", tokens[position]));
+        }
+        Ok(Self {
+            source: s.trim().to_string(),
+            expression,
+        })
+    }
+}
+
+impl std::fmt::Display for AmountExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl AmountExpression {
+    pub fn uses_all(&self) -> bool {
+        self.expression.uses_all()
+    }
+
+    /// Evaluates the expression to yoctoNEAR, `balance` is required for `all`
+    pub fn evaluate(&self, balance: Option<u128>) -> Result<u128, String> {
+        match self.expression.evaluate(balance)? {
+            AmountValue::Amount(amount) => Ok(amount),
+            // A plain number without a unit is an amount in yoctoNEAR
+            AmountValue::Scalar { mantissa, decimals } => {
+                let divisor = pow10(decimals)?;
+                if mantissa % divisor != 0 {
+                    return Err(format!(
+                        "{} is not a whole number of yoctoNEAR, specify the unit (e.g. 0.5NEAR)",
+                        self.source
+                    ));
+                }
+                Ok(mantissa / divisor)
+            }
+        }
+    }
+}
+
+impl AmountExpressionNode {
+    fn uses_all(&self) -> bool {
+        match self {
+            Self::All => true,
+            Self::Number { .. } => false,
+            Self::BinaryOperation(left, _, right) => left.uses_all() || right.uses_all(),
+        }
+    }
+
+    fn evaluate(&self, balance: Option<u128>) -> Result<AmountValue, String> {
+        let overflow = || "The amount is out of range".to_string();
+        match self {
+            Self::All => balance
+                .map(AmountValue::Amount)
+                .ok_or_else(|| "`all` can only be used in the online mode".to_string()),
+            Self::Number {
+                mantissa,
+                decimals,
+                unit: None,
+            } => Ok(AmountValue::Scalar {
+                mantissa: *mantissa,
+                decimals: *decimals,
+            }),
+            Self::Number {
+                mantissa,
+                decimals,
+                unit: Some(AmountUnit::Near),
+            } => {
+                if *decimals > 24 {
+                    return Err("NEAR amounts have at most 24 decimal places".to_string());
+                }
+                mantissa
+                    .checked_mul(pow10(24 - decimals)?)
+                    .map(AmountValue::Amount)
+                    .ok_or_else(overflow)
+            }
+            Self::Number {
+                mantissa,
+                decimals,
+                unit: Some(AmountUnit::YoctoNear),
+            } => {
+                let divisor = pow10(*decimals)?;
+                if mantissa % divisor != 0 {
+                    return Err("yoctoNEAR amounts cannot be fractional".to_string());
+                }
+                Ok(AmountValue::Amount(mantissa / divisor))
+            }
+            Self::BinaryOperation(left, operation, right) => apply_amount_operation(
+                left.evaluate(balance)?,
+                *operation,
+                right.evaluate(balance)?,
+            ),
+        }
+    }
+}
+
+fn apply_amount_operation(
+    left: AmountValue,
+    operation: char,
+    right: AmountValue,
+) -> Result<AmountValue, String> {
+    let overflow = || "The amount is out of range".to_string();
+    let negative = || "The amount cannot be negative".to_string();
+    match (left, operation, right) {
+        (AmountValue::Amount(left), '+', AmountValue::Amount(right)) => left
+            .checked_add(right)
+            .map(AmountValue::Amount)
+            .ok_or_else(overflow),
+        (AmountValue::Amount(left), '-', AmountValue::Amount(right)) => left
+            .checked_sub(right)
+            .map(AmountValue::Amount)
+            .ok_or_else(negative),
+        (AmountValue::Amount(amount), '*', AmountValue::Scalar { mantissa, decimals })
+        | (AmountValue::Scalar { mantissa, decimals }, '*', AmountValue::Amount(amount)) => {
+            let amount = amount.checked_mul(mantissa).ok_or_else(overflow)?;
+            Ok(AmountValue::Amount(amount / pow10(decimals)?))
+        }
+        (_, '/', AmountValue::Scalar { mantissa: 0, .. }) => Err("Division by zero".to_string()),
+        (AmountValue::Amount(amount), '/', AmountValue::Scalar { mantissa, decimals }) => {
+            let amount = amount.checked_mul(pow10(decimals)?).ok_or_else(overflow)?;
+            Ok(AmountValue::Amount(amount / mantissa))
+        }
+        (
+            AmountValue::Scalar {
+                mantissa: left_mantissa,
+                decimals: left_decimals,
+            },
+            operation,
+            AmountValue::Scalar {
+                mantissa: right_mantissa,
+                decimals: right_decimals,
+            },
+        ) => {
+            let decimals = std::cmp::max(left_decimals, right_decimals);
+            let left_aligned = left_mantissa
+                .checked_mul(pow10(decimals - left_decimals)?)
+                .ok_or_else(overflow)?;
+            let right_aligned = right_mantissa
+                .checked_mul(pow10(decimals - right_decimals)?)
+                .ok_or_else(overflow)?;
+            let (mantissa, decimals) = match operation {
+                '+' => (
+                    left_aligned.checked_add(right_aligned).ok_or_else(overflow)?,
+                    decimals,
+                ),
+                '-' => (
+                    left_aligned.checked_sub(right_aligned).ok_or_else(negative)?,
+                    decimals,
+                ),
+                '*' => (
+                    left_mantissa
+                        .checked_mul(right_mantissa)
+                        .ok_or_else(overflow)?,
+                    left_decimals
+                        .checked_add(right_decimals)
+                        .ok_or_else(overflow)?,
+                ),
+                // Keep 24 decimal places of the quotient
+                _ => (
+                    left_aligned
+                        .checked_mul(ONE_NEAR)
+                        .ok_or_else(overflow)?
+                        / right_aligned,
+                    24,
+                ),
+            };
+            Ok(AmountValue::Scalar { mantissa, decimals })
+        }
+        (_, operation, _) => Err(format!(
+            "Cannot apply `{}` here, only add amounts to amounts and multiply or divide them by numbers",
+            operation
+        )),
+    }
+}
+
+fn pow10(exponent: u32) -> Result<u128, String> {
+    10u128
+        .checked_pow(exponent)
+        .ok_or_else(|| "The amount has too many decimal places".to_string())
+}
+
+/// Deeper nesting is rejected, the parser is recursive
+const MAX_AMOUNT_NESTING: usize = 32;
+
+fn tokenize_amount_expression(s: &str) -> Result<Vec<AmountToken>, String> {
+    let mut tokens = vec![];
+    let mut nesting = 0;
+    let mut chars = s.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut number = String::new();
+            while let Some(&c) = chars.peek() {
+                if !(c.is_ascii_digit() || c == '.' || c == '_') {
+                    break;
+                }
+                if c != '_' {
+                    number.push(c);
+                }
+                chars.next();
+            }
+            tokens.push(AmountToken::Number(number));
+        } else if c.is_ascii_alphabetic() {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if !c.is_ascii_alphabetic() {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(AmountToken::Word(word.to_lowercase()));
+        } else if "+-*/()".contains(c) {
+            if c == '(' {
+                nesting += 1;
+                if nesting > MAX_AMOUNT_NESTING {
+                    return Err("The amount has too many nested parentheses".to_string());
+                }
+            } else if c == ')' {
+                nesting = std::cmp::max(nesting, 1) - 1;
+            }
+            tokens.push(AmountToken::Symbol(c));
+            chars.next();
+        } else {
+            return Err(format!("Unexpected character '{}' in the amount", c));
+        }
+    }
+    if tokens.is_empty() {
+        return Err("The amount is empty".to_string());
+    }
+    Ok(tokens)
+}
+
+fn parse_amount_sum(
+    tokens: &[AmountToken],
+    position: &mut usize,
+) -> Result<AmountExpressionNode, String> {
+    let mut node = parse_amount_product(tokens, position)?;
+    while let Some(AmountToken::Symbol(operation @ '+'))
+    | Some(AmountToken::Symbol(operation @ '-')) = tokens.get(*position)
+    {
+        *position += 1;
+        let right = parse_amount_product(tokens, position)?;
+        node = AmountExpressionNode::BinaryOperation(Box::new(node), *operation, Box::new(right));
+    }
+    Ok(node)
+}
+
+fn parse_amount_product(
+    tokens: &[AmountToken],
+    position: &mut usize,
+) -> Result<AmountExpressionNode, String> {
+    let mut node = parse_amount_factor(tokens, position)?;
+    while let Some(AmountToken::Symbol(operation @ '*'))
+    | Some(AmountToken::Symbol(operation @ '/')) = tokens.get(*position)
+    {
+        *position += 1;
+        let right = parse_amount_factor(tokens, position)?;
+        node = AmountExpressionNode::BinaryOperation(Box::new(node), *operation, Box::new(right));
+    }
+    Ok(node)
+}
+
+fn parse_amount_factor(
+    tokens: &[AmountToken],
+    position: &mut usize,
+) -> Result<AmountExpressionNode, String> {
+    let token = tokens
+        .get(*position)
+        .ok_or_else(|| "The amount ends unexpectedly".to_string())?;
+    *position += 1;
+    match token {
+        AmountToken::Word(word) if word == "all" => Ok(AmountExpressionNode::All),
+        AmountToken::Symbol('(') => {
+            let node = parse_amount_sum(tokens, position)?;
+            match tokens.get(*position) {
+                Some(AmountToken::Symbol(')')) => {
+                    *position += 1;
+                    Ok(node)
+                }
+                _ => Err("Missing ')' in the amount".to_string()),
+            }
+        }
+        AmountToken::Number(number) => {
+            let mut parts = number.splitn(2, '.');
+            let integer = parts.next().unwrap_or_default();
+            let fraction = parts.next().unwrap_or_default();
+            if fraction.contains('.') || (integer.is_empty() && fraction.is_empty()) {
+                return Err(format!("Invalid number {}", number));
+            }
+            let mantissa = format!("{}{}", integer, fraction)
+                .parse::<u128>()
+                .map_err(|err| format!("Invalid number {}: {}", number, err))?;
+            let unit = match tokens.get(*position) {
+                Some(AmountToken::Word(word)) if word == "near" || word == "n" => {
+                    Some(AmountUnit::Near)
+                }
+                Some(AmountToken::Word(word))
+                    if word == "yoctonear" || word == "yocto" || word == "yn" =>
+                {
+                    Some(AmountUnit::YoctoNear)
+                }
+                _ => None,
+            };
+            if unit.is_some() {
+                *position += 1;
+            }
+            Ok(AmountExpressionNode::Number {
+                mantissa,
+                decimals: fraction.len() as u32,
+                unit,
+            })
+        }
+        token => Err(format!("Unexpected {:?} in the amount", token)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::str::FromStr;
+
+    proptest! {
+        #[test]
+        fn yocto_near_amounts_round_trip_through_text(amount in any::<u128>()) {
+            let text = format!("{} yoctoNEAR", amount);
+            prop_assert_eq!(
+                AmountExpression::from_str(&text).and_then(|amount| amount.evaluate(None)),
+                Ok(amount)
+            );
+        }
+
+        #[test]
+        fn near_amounts_round_trip_through_text(amount in any::<u128>()) {
+            let text = format!("{}.{:024} NEAR", amount / ONE_NEAR, amount % ONE_NEAR);
+            prop_assert_eq!(
+                AmountExpression::from_str(&text).and_then(|amount| amount.evaluate(None)),
+                Ok(amount)
+            );
+        }
+
+        #[test]
+        fn amount_expression_never_panics(
+            amount in "[0-9._+*/() a-zA-Z]{0,64}|\\PC{0,64}",
+            balance in any::<u128>(),
+        ) {
+            if let Ok(expression) = AmountExpression::from_str(&amount) {
+                expression.evaluate(Some(balance)).ok();
+            }
+        }
+    }
+
+    #[test]
+    fn amount_expression_evaluates_to_yocto_near() {
+        let evaluate = |amount: &str, balance: Option<u128>| {
+            AmountExpression::from_str(amount).and_then(|amount| amount.evaluate(balance))
+        };
+        assert_eq!(evaluate("10NEAR", None), Ok(10 * ONE_NEAR));
+        assert_eq!(evaluate("0.5 N", None), Ok(ONE_NEAR / 2));
+        assert_eq!(evaluate("100 yoctoNEAR", None), Ok(100));
+        assert_eq!(evaluate("1_000", None), Ok(1000));
+        assert_eq!(evaluate("0.1*3 NEAR", None), Ok(3 * ONE_NEAR / 10));
+        assert_eq!(evaluate("1 NEAR / 4", None), Ok(ONE_NEAR / 4));
+        assert_eq!(
+            evaluate("all-0.05N", Some(ONE_NEAR)),
+            Ok(ONE_NEAR - ONE_NEAR / 20)
+        );
+        assert!(evaluate("all", None).is_err());
+        assert!(evaluate("1 NEAR - 2 NEAR", None).is_err());
+        assert!(evaluate("1 NEAR / 0", None).is_err());
+        assert!(evaluate("0.5", None).is_err());
+        assert!(evaluate("0.5 yoctoNEAR", None).is_err());
+        assert!(evaluate("1 NEAR * 1 NEAR", None).is_err());
+    }
+
+    #[test]
+    fn amount_expression_tells_whether_it_needs_the_balance() {
+        assert!(AmountExpression::from_str("all").unwrap().uses_all());
+        assert!(AmountExpression::from_str("(all - 1 NEAR) / 2")
+            .unwrap()
+            .uses_all());
+        assert!(!AmountExpression::from_str("0.1*3 NEAR").unwrap().uses_all());
+    }
+
+    #[test]
+    fn amount_expression_rejects_malformed_input() {
+        for amount in &[
+            "",
+            "   ",
+            "1..2 NEAR",
+            ".",
+            "1 NEAR +",
+            "(1 NEAR",
+            "1 NEAR)",
+            "1 EUR",
+            "1 NEAR $",
+            "340282366920938463463374607431768211456",
+        ] {
+            assert!(AmountExpression::from_str(amount).is_err(), "{:?}", amount);
+        }
+        let deeply_nested = format!("{}1{}", "(".repeat(1000), ")".repeat(1000));
+        assert!(AmountExpression::from_str(&deeply_nested).is_err());
+    }
+}
@@ -0,0 +1,142 @@
+/// A subcommand or a flag (without the leading `--`) that was renamed; the old
+/// name keeps working with a deprecation warning for at least one release
+struct ArgRename {
+    old: &'static str,
+    new: &'static str,
+    deprecated_since: &'static str,
+}
+
+const ARG_RENAMES: &[ArgRename] = &[ArgRename {
+    old: "sign-transaction-command",
+    new: "sign-transaction",
+    deprecated_since: "0.1.0",
+}];
+
+fn find_rename(name: &str) -> Option<&'static ArgRename> {
+    ARG_RENAMES.iter().find(|rename| rename.old == name)
+}
+
+fn warn_deprecated(old: &str, rename: &ArgRename) {
+    eprintln!(
+        "{}",
+        crate::style::warning(format!(
+            "WARNING: `{}` is deprecated since {} and will be removed, use `{}` instead",
+            old, rename.deprecated_since, rename.new
+        ))
+    );
+}
+
+/// Rewrites the deprecated subcommands and flags to their current names before
+/// the arguments reach structopt
+pub fn upgrade_args(args: impl Iterator<Item = std::ffi::OsString>) -> Vec<std::ffi::OsString> {
+    args.map(|arg| {
+        let arg_str = match arg.to_str() {
+            Some(arg_str) => arg_str,
+            None => return arg,
+        };
+        if arg_str.starts_with("--") {
+            let mut parts = arg_str[2..].splitn(2, '=');
+            let flag = parts.next().unwrap_or_default();
+            let value = parts.next();
+            return match find_rename(flag) {
+                Some(rename) => {
+                    warn_deprecated(&format!("--{}", flag), rename);
+                    match value {
+                        Some(value) => format!("--{}={}", rename.new, value).into(),
+                        None => format!("--{}", rename.new).into(),
+                    }
+                }
+                None => arg,
+            };
+        }
+        match find_rename(arg_str) {
+            Some(rename) => {
+                warn_deprecated(arg_str, rename);
+                rename.new.into()
+            }
+            None => arg,
+        }
+    })
+    .collect()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous_row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current_row = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_row[j] + if a_char == *b_char { 0 } else { 1 };
+            current_row.push(
+                substitution
+                    .min(previous_row[j + 1] + 1)
+                    .min(current_row[j] + 1),
+            );
+        }
+        previous_row = current_row;
+    }
+    previous_row[b.len()]
+}
+
+/// Collects the full paths of all the nested subcommands, e.g. `utils wallet-url`
+fn subcommand_paths(app: &structopt::clap::App, prefix: &str, paths: &mut Vec<String>) {
+    for subcommand in app.p.subcommands.iter() {
+        let path = format!("{}{}", prefix, subcommand.get_name());
+        paths.push(path.clone());
+        subcommand_paths(subcommand, &format!("{} ", path), paths);
+    }
+}
+
+/// Finds the subcommands (and the renamed ones) whose name is close to the mistyped one
+fn suggest_subcommands(app: &structopt::clap::App, typo: &str) -> Vec<String> {
+    let mut paths = vec![];
+    subcommand_paths(app, "", &mut paths);
+    let max_distance = std::cmp::max(2, typo.len() / 3);
+    let mut suggestions = paths
+        .into_iter()
+        .map(|path| {
+            let name = path.rsplit(' ').next().unwrap_or_default().to_string();
+            (edit_distance(typo, &name), path)
+        })
+        .chain(
+            ARG_RENAMES
+                .iter()
+                .map(|rename| (edit_distance(typo, rename.old), rename.new.to_string())),
+        )
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect::<Vec<_>>();
+    suggestions.sort();
+    let mut unique_suggestions: Vec<String> = vec![];
+    for (_, suggestion) in suggestions {
+        if !unique_suggestions.contains(&suggestion) {
+            unique_suggestions.push(suggestion);
+        }
+    }
+    unique_suggestions.truncate(3);
+    unique_suggestions
+}
+
+/// Prints the parsing error with did-you-mean suggestions from the whole
+/// subcommand tree, since clap only looks at the current level
+pub fn exit_with_suggestions(app: &structopt::clap::App, err: structopt::clap::Error) -> ! {
+    let typo = match err.kind {
+        structopt::clap::ErrorKind::UnrecognizedSubcommand
+        | structopt::clap::ErrorKind::InvalidSubcommand
+        | structopt::clap::ErrorKind::UnknownArgument => err
+            .info
+            .as_ref()
+            .and_then(|info| info.first())
+            .filter(|typo| !typo.starts_with('-'))
+            .cloned(),
+        _ => None,
+    };
+    let suggestions = match typo {
+        Some(typo) => suggest_subcommands(app, &typo),
+        None => err.exit(),
+    };
+    eprintln!("{}", err.message);
+    for suggestion in suggestions {
+        eprintln!("\n\tdid you mean `{}`?", suggestion);
+    }
+    std::process::exit(1)
+}
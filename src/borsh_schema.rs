@@ -0,0 +1,134 @@
+use near_primitives::borsh::BorshDeserialize;
+
+/// JSON representation of `borsh::schema::BorshSchemaContainer`
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct BorshSchemaContainer {
+    pub declaration: String,
+    pub definitions: std::collections::HashMap<String, Definition>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub enum Definition {
+    Array { length: u32, elements: String },
+    Sequence { elements: String },
+    Tuple { elements: Vec<String> },
+    Enum { variants: Vec<(String, String)> },
+    Struct { fields: Fields },
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub enum Fields {
+    NamedFields(Vec<(String, String)>),
+    UnnamedFields(Vec<String>),
+    Empty,
+}
+
+impl BorshSchemaContainer {
+    pub fn from_file(path: &std::path::Path) -> Result<Self, String> {
+        let buf = std::fs::read_to_string(path).map_err(|err| format!("{:?}", err))?;
+        serde_json::from_str(&buf).map_err(|err| format!("{:?}", err))
+    }
+
+    /// Decodes the whole buffer as `declaration` (the container declaration if
+    /// `None`) into a JSON value with the named fields
+    pub fn decode(
+        &self,
+        declaration: Option<&str>,
+        bytes: &[u8],
+    ) -> Result<serde_json::Value, String> {
+        let mut buf = bytes;
+        let value = self.decode_declaration(declaration.unwrap_or(&self.declaration), &mut buf)?;
+        if !buf.is_empty() {
+            return Err(format!("{} bytes left after decoding", buf.len()));
+        }
+        Ok(value)
+    }
+
+    fn decode_declaration(
+        &self,
+        declaration: &str,
+        buf: &mut &[u8],
+    ) -> Result<serde_json::Value, String> {
+        if let Some(value) = decode_primitive(declaration, buf)? {
+            return Ok(value);
+        }
+        let definition = self
+            .definitions
+            .get(declaration)
+            .ok_or_else(|| format!("There is no definition for `{}`", declaration))?;
+        match definition {
+            Definition::Array { length, elements } => (0..*length)
+                .map(|_| self.decode_declaration(elements, buf))
+                .collect::<Result<Vec<_>, _>>()
+                .map(serde_json::Value::Array),
+            Definition::Sequence { elements } => {
+                let length = deserialize::<u32>(buf)?;
+                (0..length)
+                    .map(|_| self.decode_declaration(elements, buf))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(serde_json::Value::Array)
+            }
+            Definition::Tuple { elements } => elements
+                .iter()
+                .map(|element| self.decode_declaration(element, buf))
+                .collect::<Result<Vec<_>, _>>()
+                .map(serde_json::Value::Array),
+            Definition::Enum { variants } => {
+                let index = deserialize::<u8>(buf)? as usize;
+                let (variant_name, variant_declaration) = variants
+                    .get(index)
+                    .ok_or_else(|| format!("Invalid `{}` variant index {}", declaration, index))?;
+                let variant_value = self.decode_declaration(variant_declaration, buf)?;
+                Ok(serde_json::json!({ variant_name: variant_value }))
+            }
+            Definition::Struct { fields } => match fields {
+                Fields::NamedFields(fields) => {
+                    let mut object = serde_json::Map::new();
+                    for (field_name, field_declaration) in fields {
+                        object.insert(
+                            field_name.clone(),
+                            self.decode_declaration(field_declaration, buf)?,
+                        );
+                    }
+                    Ok(serde_json::Value::Object(object))
+                }
+                Fields::UnnamedFields(fields) => fields
+                    .iter()
+                    .map(|field_declaration| self.decode_declaration(field_declaration, buf))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(serde_json::Value::Array),
+                Fields::Empty => Ok(serde_json::Value::Null),
+            },
+        }
+    }
+}
+
+fn deserialize<T: BorshDeserialize>(buf: &mut &[u8]) -> Result<T, String> {
+    T::deserialize(buf).map_err(|err| format!("{:?}", err))
+}
+
+fn decode_primitive(
+    declaration: &str,
+    buf: &mut &[u8],
+) -> Result<Option<serde_json::Value>, String> {
+    let value = match declaration {
+        "bool" => serde_json::json!(deserialize::<bool>(buf)?),
+        "u8" => serde_json::json!(deserialize::<u8>(buf)?),
+        "u16" => serde_json::json!(deserialize::<u16>(buf)?),
+        "u32" => serde_json::json!(deserialize::<u32>(buf)?),
+        "u64" => serde_json::json!(deserialize::<u64>(buf)?),
+        // Large integers are rendered as strings, the same way NEAR JSON APIs do
+        "u128" => serde_json::json!(deserialize::<u128>(buf)?.to_string()),
+        "i8" => serde_json::json!(deserialize::<i8>(buf)?),
+        "i16" => serde_json::json!(deserialize::<i16>(buf)?),
+        "i32" => serde_json::json!(deserialize::<i32>(buf)?),
+        "i64" => serde_json::json!(deserialize::<i64>(buf)?),
+        "i128" => serde_json::json!(deserialize::<i128>(buf)?.to_string()),
+        "f32" => serde_json::json!(deserialize::<f32>(buf)?),
+        "f64" => serde_json::json!(deserialize::<f64>(buf)?),
+        "string" => serde_json::json!(deserialize::<String>(buf)?),
+        "()" => serde_json::Value::Null,
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
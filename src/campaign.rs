@@ -0,0 +1,129 @@
+use near_primitives::borsh::BorshDeserialize;
+
+/// The outcome of one transaction of a campaign
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum CampaignItemStatus {
+    Succeeded { outcome: String },
+    Failed { error: String },
+}
+
+/// A named batch of signed transactions whose progress survives interruptions,
+/// so `utils batch-submit --resume <name>` only sends what has not succeeded yet
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Campaign {
+    pub name: String,
+    pub file: std::path::PathBuf,
+    pub rpc_url: url::Url,
+    /// By transaction hash, so reordering the file does not confuse the progress
+    #[serde(default)]
+    pub items: std::collections::BTreeMap<String, CampaignItemStatus>,
+}
+
+impl Campaign {
+    pub fn path(name: &str) -> std::path::PathBuf {
+        let mut campaign_path = dirs::data_dir().expect("Impossible to get your data dir!");
+        campaign_path.push("near-cli");
+        campaign_path.push("campaigns");
+        campaign_path.push(format!("{}.json", name));
+        campaign_path
+    }
+    pub fn new(name: &str, file: &std::path::Path, rpc_url: &url::Url) -> Result<Self, String> {
+        if Campaign::path(name).exists() {
+            return Err(format!(
+                "The campaign <{}> already exists, continue it with --resume {}",
+                name, name
+            ));
+        }
+        let campaign = Campaign {
+            name: name.to_string(),
+            file: file.to_path_buf(),
+            rpc_url: rpc_url.clone(),
+            items: Default::default(),
+        };
+        campaign.save()?;
+        Ok(campaign)
+    }
+    pub fn load(name: &str) -> Result<Self, String> {
+        let campaign_path = Campaign::path(name);
+        let buf = std::fs::read_to_string(&campaign_path)
+            .map_err(|err| format!("Failed to read the campaign {:?}: {:?}", campaign_path, err))?;
+        serde_json::from_str(&buf).map_err(|err| {
+            format!(
+                "Failed to parse the campaign {:?}: {:?}",
+                campaign_path, err
+            )
+        })
+    }
+    /// Saved after every outcome, so an interruption loses nothing
+    pub fn save(&self) -> Result<(), String> {
+        let campaign_path = Campaign::path(&self.name);
+        std::fs::create_dir_all(campaign_path.parent().unwrap())
+            .map_err(|err| format!("Failed to create the campaigns dir: {:?}", err))?;
+        let buf = serde_json::to_string_pretty(self)
+            .map_err(|err| format!("Failed to serialize the campaign: {:?}", err))?;
+        crate::common::write_file_atomically(&campaign_path, &buf).map_err(|err| {
+            format!(
+                "Failed to write the campaign {:?}: {:?}",
+                campaign_path, err
+            )
+        })
+    }
+    pub fn is_succeeded(&self, signed_transaction: &str) -> bool {
+        match transaction_hash(signed_transaction) {
+            Some(transaction_hash) => match self.items.get(&transaction_hash) {
+                Some(CampaignItemStatus::Succeeded { .. }) => true,
+                _ => false,
+            },
+            None => false,
+        }
+    }
+    pub fn record(&mut self, signed_transaction: &str, status: CampaignItemStatus) {
+        if let Some(transaction_hash) = transaction_hash(signed_transaction) {
+            self.items.insert(transaction_hash, status);
+            if let Err(err) = self.save() {
+                eprintln!("{}", crate::style::warning(err));
+            }
+        }
+    }
+    /// Every transaction of the file with its latest outcome
+    pub fn print_report(&self, signed_transactions: &[String]) {
+        let mut succeeded = 0;
+        let mut failed = vec![];
+        let mut not_attempted = 0;
+        for (index, signed_transaction) in signed_transactions.iter().enumerate() {
+            let status = transaction_hash(signed_transaction)
+                .and_then(|transaction_hash| self.items.get(&transaction_hash).cloned());
+            match status {
+                Some(CampaignItemStatus::Succeeded { .. }) => succeeded += 1,
+                Some(CampaignItemStatus::Failed { error }) => failed.push((index, error)),
+                None => not_attempted += 1,
+            }
+        }
+        eprintln!();
+        println!(
+            "Campaign <{}> ({:?}):",
+            self.name,
+            Campaign::path(&self.name)
+        );
+        println!("  {}: {}", crate::style::success("succeeded"), succeeded);
+        println!("  failed: {}", failed.len());
+        println!("  not attempted: {}", not_attempted);
+        for (index, error) in &failed {
+            println!("  #{}: {}", index, crate::style::warning(error));
+        }
+        if !failed.is_empty() || not_attempted > 0 {
+            eprintln!(
+                "Run `utils batch-submit --resume {}` to retry the rest",
+                self.name
+            );
+        }
+    }
+}
+
+fn transaction_hash(signed_transaction: &str) -> Option<String> {
+    let signed_transaction_borsh = base64::decode(signed_transaction).ok()?;
+    near_primitives::transaction::SignedTransaction::try_from_slice(&signed_transaction_borsh)
+        .ok()
+        .map(|signed_transaction| signed_transaction.get_hash().to_string())
+}
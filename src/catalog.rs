@@ -0,0 +1,213 @@
+/// How careful the user has to be with an operation; `dangerous` ones ask for
+/// a confirmation before they are added to the transaction
+#[derive(
+    Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, smart_default::SmartDefault,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum DangerLevel {
+    #[default]
+    Safe,
+    Caution,
+    Dangerous,
+}
+
+/// A predefined contract call; the `{name}` placeholders in the strings of
+/// `args_template` are asked when the operation is used
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CatalogOperation {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// The operation is offered only for this receiver (for any receiver if unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract_id: Option<String>,
+    pub method_name: String,
+    #[serde(default = "empty_args_template")]
+    pub args_template: serde_json::Value,
+    /// Asked when the operation is used, if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas: Option<near_primitives::types::Gas>,
+    /// An amount like `0.01 NEAR`; asked when the operation is used, if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit: Option<String>,
+    /// Bytes of contract storage the operation adds, checked against the deposit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_bytes: Option<u64>,
+    #[serde(default)]
+    pub danger_level: DangerLevel,
+}
+
+fn empty_args_template() -> serde_json::Value {
+    serde_json::json!({})
+}
+
+/// A file of predefined contract interactions that a project ships to its
+/// users, imported with `catalog use`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Catalog {
+    pub name: String,
+    pub operations: Vec<CatalogOperation>,
+}
+
+impl Catalog {
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let buf = std::fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read the file {:?}: {:?}", path, err))?;
+        let catalog: Catalog = serde_json::from_str(&buf)
+            .map_err(|err| format!("Failed to parse the catalog {:?}: {:?}", path, err))?;
+        catalog.validate()?;
+        Ok(catalog)
+    }
+    fn validate(&self) -> Result<(), String> {
+        if !Catalog::is_valid_name(&self.name) {
+            return Err(format!(
+                "The catalog name \"{}\" may only have letters, digits, '-' and '_'",
+                self.name
+            ));
+        }
+        let mut names = std::collections::HashSet::new();
+        for operation in &self.operations {
+            if !names.insert(&operation.name) {
+                return Err(format!(
+                    "The operation \"{}\" is defined twice",
+                    operation.name
+                ));
+            }
+            if let Some(deposit) = &operation.deposit {
+                deposit
+                    .parse::<crate::amount::AmountExpression>()
+                    .map_err(|err| format!("Invalid deposit of \"{}\": {}", operation.name, err))?;
+            }
+        }
+        Ok(())
+    }
+    /// The name is also the file name of the imported catalog
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+    /// Where the imported catalogs are kept, next to the config file
+    pub fn catalogs_dir() -> std::path::PathBuf {
+        crate::config::Config::config_file_path().with_file_name("catalogs")
+    }
+    pub fn path(&self) -> std::path::PathBuf {
+        Catalog::catalogs_dir().join(format!("{}.json", self.name))
+    }
+    /// The imported catalogs; broken files are reported and skipped
+    pub fn load_imported() -> Vec<Self> {
+        let entries = match std::fs::read_dir(Catalog::catalogs_dir()) {
+            Ok(entries) => entries,
+            Err(_) => return vec![],
+        };
+        let mut catalogs = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .map_or(false, |extension| extension == "json")
+            })
+            .filter_map(|path| match Catalog::load(&path) {
+                Ok(catalog) => Some(catalog),
+                Err(err) => {
+                    eprintln!("{}", err);
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        catalogs.sort_by(|a, b| a.name.cmp(&b.name));
+        catalogs
+    }
+}
+
+impl CatalogOperation {
+    pub fn is_for(&self, receiver_id: &str) -> bool {
+        self.contract_id
+            .as_ref()
+            .map_or(true, |contract_id| contract_id == receiver_id)
+    }
+    /// The names of the `{name}` placeholders, in the order they appear
+    pub fn placeholders(&self) -> Vec<String> {
+        let mut placeholders = vec![];
+        collect_placeholders(&self.args_template, &mut placeholders);
+        placeholders
+    }
+    /// The arguments with the placeholders replaced by the given values
+    pub fn fill_args(
+        &self,
+        values: &std::collections::HashMap<String, String>,
+    ) -> serde_json::Value {
+        fill_placeholders(&self.args_template, values)
+    }
+}
+
+fn collect_placeholders(value: &serde_json::Value, placeholders: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(string) => {
+            for placeholder in string_placeholders(string) {
+                if !placeholders.contains(&placeholder) {
+                    placeholders.push(placeholder);
+                }
+            }
+        }
+        serde_json::Value::Array(values) => values
+            .iter()
+            .for_each(|value| collect_placeholders(value, placeholders)),
+        serde_json::Value::Object(values) => values
+            .values()
+            .for_each(|value| collect_placeholders(value, placeholders)),
+        _ => {}
+    }
+}
+
+fn fill_placeholders(
+    value: &serde_json::Value,
+    values: &std::collections::HashMap<String, String>,
+) -> serde_json::Value {
+    match value {
+        serde_json::Value::String(string) => {
+            // A single pass, so values with braces are not filled again
+            let mut parts = string.split('{');
+            let mut filled = parts.next().unwrap_or_default().to_string();
+            for part in parts {
+                match part.find('}').map(|end| (&part[..end], &part[end + 1..])) {
+                    Some((name, rest)) if values.contains_key(name) => {
+                        filled.push_str(&values[name]);
+                        filled.push_str(rest);
+                    }
+                    _ => {
+                        filled.push('{');
+                        filled.push_str(part);
+                    }
+                }
+            }
+            serde_json::Value::String(filled)
+        }
+        serde_json::Value::Array(array) => serde_json::Value::Array(
+            array
+                .iter()
+                .map(|value| fill_placeholders(value, values))
+                .collect(),
+        ),
+        serde_json::Value::Object(object) => serde_json::Value::Object(
+            object
+                .iter()
+                .map(|(key, value)| (key.clone(), fill_placeholders(value, values)))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+fn string_placeholders(string: &str) -> Vec<String> {
+    string
+        .split('{')
+        .skip(1)
+        .filter_map(|part| part.find('}').map(|end| &part[..end]))
+        .filter(|name| {
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+        .map(str::to_string)
+        .collect()
+}
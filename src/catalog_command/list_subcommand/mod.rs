@@ -0,0 +1,45 @@
+use structopt::StructOpt;
+
+/// Print the operations of the imported catalogs
+#[derive(Debug)]
+pub struct List {}
+
+#[derive(Debug, StructOpt)]
+pub struct CliList {}
+
+impl From<CliList> for List {
+    fn from(_: CliList) -> Self {
+        List {}
+    }
+}
+
+impl List {
+    pub fn process(self) {
+        let catalogs = crate::catalog::Catalog::load_imported();
+        if catalogs.is_empty() {
+            return eprintln!("There are no imported catalogs, import one with `catalog use`");
+        }
+        for catalog in catalogs {
+            println!("{}:", catalog.name);
+            for operation in &catalog.operations {
+                let danger_level = match operation.danger_level {
+                    crate::catalog::DangerLevel::Safe => String::new(),
+                    crate::catalog::DangerLevel::Caution => {
+                        format!(" {}", crate::style::warning("[caution]"))
+                    }
+                    crate::catalog::DangerLevel::Dangerous => {
+                        format!(" {}", crate::style::warning("[dangerous]"))
+                    }
+                };
+                println!(
+                    "  {} ({} on {}){}  {}",
+                    operation.name,
+                    operation.method_name,
+                    operation.contract_id.as_deref().unwrap_or("any contract"),
+                    danger_level,
+                    operation.description
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use dialoguer::{theme::ColorfulTheme, Select};
+use structopt::StructOpt;
+use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
+
+pub mod list_subcommand;
+pub mod remove_subcommand;
+pub mod use_subcommand;
+
+/// Catalogs of predefined contract operations shipped by projects; the
+/// imported operations are offered as actions when constructing a transaction
+#[derive(Debug)]
+pub struct CatalogCommand {
+    pub action: CatalogAction,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliCatalogCommand {
+    #[structopt(subcommand)]
+    action: Option<CliCatalogAction>,
+}
+
+impl From<CliCatalogCommand> for CatalogCommand {
+    fn from(item: CliCatalogCommand) -> Self {
+        let action = match item.action {
+            Some(cli_action) => CatalogAction::from(cli_action),
+            None => CatalogAction::choose_action(),
+        };
+        CatalogCommand { action }
+    }
+}
+
+impl CatalogCommand {
+    pub fn process(self) {
+        self.action.process()
+    }
+}
+
+#[derive(Debug, EnumDiscriminants)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+pub enum CatalogAction {
+    #[strum_discriminants(strum(message = "Import a catalog file"))]
+    Use(use_subcommand::Use),
+    #[strum_discriminants(strum(message = "List the imported operations"))]
+    List(list_subcommand::List),
+    #[strum_discriminants(strum(message = "Remove an imported catalog"))]
+    Remove(remove_subcommand::Remove),
+}
+
+#[derive(Debug, StructOpt)]
+pub enum CliCatalogAction {
+    Use(use_subcommand::CliUse),
+    List(list_subcommand::CliList),
+    Remove(remove_subcommand::CliRemove),
+}
+
+impl From<CliCatalogAction> for CatalogAction {
+    fn from(item: CliCatalogAction) -> Self {
+        match item {
+            CliCatalogAction::Use(cli_use) => {
+                CatalogAction::Use(use_subcommand::Use::from(cli_use))
+            }
+            CliCatalogAction::List(cli_list) => {
+                CatalogAction::List(list_subcommand::List::from(cli_list))
+            }
+            CliCatalogAction::Remove(cli_remove) => {
+                CatalogAction::Remove(remove_subcommand::Remove::from(cli_remove))
+            }
+        }
+    }
+}
+
+impl CatalogAction {
+    pub fn process(self) {
+        match self {
+            CatalogAction::Use(use_catalog) => use_catalog.process(),
+            CatalogAction::List(list) => list.process(),
+            CatalogAction::Remove(remove) => remove.process(),
+        }
+    }
+    pub fn choose_action() -> Self {
+        eprintln!();
+        let variants = CatalogActionDiscriminants::iter().collect::<Vec<_>>();
+        let actions = variants
+            .iter()
+            .map(|p| p.get_message().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Choose your action")
+            .items(&actions)
+            .default(0)
+            .interact()
+            .unwrap();
+        match variants[selection] {
+            CatalogActionDiscriminants::Use => CatalogAction::Use(use_subcommand::Use {
+                catalog_file: use_subcommand::Use::input_catalog_file(),
+            }),
+            CatalogActionDiscriminants::List => CatalogAction::List(list_subcommand::List {}),
+            CatalogActionDiscriminants::Remove => {
+                CatalogAction::Remove(remove_subcommand::Remove {
+                    catalog_name: remove_subcommand::Remove::input_catalog_name(),
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+/// Remove an imported catalog, its operations are no longer offered
+#[derive(Debug)]
+pub struct Remove {
+    pub catalog_name: String,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliRemove {
+    catalog_name: Option<String>,
+}
+
+impl From<CliRemove> for Remove {
+    fn from(item: CliRemove) -> Self {
+        let catalog_name = match item.catalog_name {
+            Some(cli_catalog_name) => cli_catalog_name,
+            None => Remove::input_catalog_name(),
+        };
+        Remove { catalog_name }
+    }
+}
+
+impl Remove {
+    pub fn process(self) {
+        let path =
+            crate::catalog::Catalog::catalogs_dir().join(format!("{}.json", self.catalog_name));
+        if !crate::catalog::Catalog::is_valid_name(&self.catalog_name) || !path.is_file() {
+            return eprintln!("There is no imported catalog \"{}\"", self.catalog_name);
+        }
+        match std::fs::remove_file(&path) {
+            Ok(()) => eprintln!(
+                "{} the catalog \"{}\"",
+                crate::style::success("Removed"),
+                self.catalog_name
+            ),
+            Err(err) => eprintln!("Error removing the file {:?}:  {:?}", path, err),
+        }
+    }
+    pub fn input_catalog_name() -> String {
+        Input::new()
+            .with_prompt("Which catalog do you want to remove?")
+            .interact_text()
+            .unwrap()
+    }
+}
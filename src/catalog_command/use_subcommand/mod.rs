@@ -0,0 +1,55 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+/// Validate a catalog file and import it, replacing an earlier import with the
+/// same name
+#[derive(Debug)]
+pub struct Use {
+    pub catalog_file: std::path::PathBuf,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliUse {
+    catalog_file: Option<std::path::PathBuf>,
+}
+
+impl From<CliUse> for Use {
+    fn from(item: CliUse) -> Self {
+        let catalog_file = match item.catalog_file {
+            Some(cli_catalog_file) => cli_catalog_file,
+            None => Use::input_catalog_file(),
+        };
+        Use { catalog_file }
+    }
+}
+
+impl Use {
+    pub fn process(self) {
+        let catalog = match crate::catalog::Catalog::load(&self.catalog_file) {
+            Ok(catalog) => catalog,
+            Err(err) => return eprintln!("{}", err),
+        };
+        let path = catalog.path();
+        if let Err(err) = std::fs::create_dir_all(crate::catalog::Catalog::catalogs_dir()) {
+            return eprintln!("Error creating the catalogs directory:  {:?}", err);
+        }
+        let buf = serde_json::to_string_pretty(&catalog).unwrap();
+        match crate::common::write_file_atomically(&path, &buf) {
+            Ok(()) => eprintln!(
+                "{} the catalog \"{}\" with {} operations, they are offered as \
+                 \"Use an operation from a catalog\" actions",
+                crate::style::success("Imported"),
+                catalog.name,
+                catalog.operations.len()
+            ),
+            Err(err) => eprintln!("Error writing the file {:?}:  {:?}", path, err),
+        }
+    }
+    pub fn input_catalog_file() -> std::path::PathBuf {
+        let catalog_file: String = Input::new()
+            .with_prompt("Enter the path to the catalog file")
+            .interact_text()
+            .unwrap();
+        std::path::PathBuf::from(catalog_file)
+    }
+}
@@ -1,4 +1,5 @@
 use std::convert::TryInto;
+use std::str::FromStr;
 
 #[derive(
     Debug,
@@ -28,6 +29,69 @@ pub enum TransactionFormat {
     Hex,
 }
 
+#[derive(
+    Debug,
+    Clone,
+    strum_macros::IntoStaticStr,
+    strum_macros::EnumString,
+    strum_macros::EnumVariantNames,
+    smart_default::SmartDefault,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum KeyType {
+    #[default]
+    Ed25519,
+    Secp256k1,
+}
+
+impl From<&KeyType> for near_crypto::KeyType {
+    fn from(item: &KeyType) -> Self {
+        match item {
+            KeyType::Ed25519 => near_crypto::KeyType::ED25519,
+            KeyType::Secp256k1 => near_crypto::KeyType::SECP256K1,
+        }
+    }
+}
+
+impl KeyType {
+    pub fn input_key_type() -> Self {
+        use strum::VariantNames;
+
+        let key_types = KeyType::VARIANTS;
+        let selection = dialoguer::Select::with_theme(&dialoguer::theme::ColorfulTheme::default())
+            .with_prompt("Select the key type")
+            .items(&key_types)
+            .default(0)
+            .interact()
+            .unwrap();
+        KeyType::from_str(key_types[selection]).unwrap()
+    }
+    /// Turns 32 bytes of derived key material into a secret key of this type
+    pub fn secret_key_from_seed(&self, seed: &[u8; 32]) -> near_crypto::SecretKey {
+        let secret_key_str = match self {
+            KeyType::Ed25519 => {
+                let secret = ed25519_dalek::SecretKey::from_bytes(seed).unwrap();
+                let public = ed25519_dalek::PublicKey::from(&secret);
+                let keypair = ed25519_dalek::Keypair { secret, public };
+                format!("ed25519:{}", bs58::encode(keypair.to_bytes()).into_string())
+            }
+            KeyType::Secp256k1 => format!("secp256k1:{}", bs58::encode(seed).into_string()),
+        };
+        near_crypto::SecretKey::from_str(&secret_key_str).unwrap()
+    }
+}
+
+/// Parses `ed25519:...` and `secp256k1:...` public keys (a key without the
+/// prefix is taken as ED25519)
+pub fn parse_public_key(public_key: &str) -> Result<near_crypto::PublicKey, String> {
+    near_crypto::PublicKey::from_str(public_key.trim()).map_err(|err| {
+        format!(
+            "<{}> is not a valid ed25519 or secp256k1 public key: {}",
+            public_key, err
+        )
+    })
+}
+
 #[derive(derive_more::AsRef)]
 pub struct BlobAsBase58String<T>
 where
@@ -49,19 +113,36 @@ impl<T> std::str::FromStr for BlobAsBase58String<T>
 where
     for<'a> T: std::convert::TryFrom<&'a [u8]> + AsRef<[u8]>,
 {
-    type Err = &'static str;
+    type Err = String;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let bytes = near_primitives::serialize::from_base(value.trim())
+            .map_err(|_| format!("<{}> is not a valid base58 string", value))?;
         Ok(Self {
-            inner: near_primitives::serialize::from_base(value)
-                .map_err(|_| "err")?
-                .as_slice()
-                .try_into()
-                .map_err(|_| "err")?,
+            inner: bytes.as_slice().try_into().map_err(|_| {
+                format!(
+                    "<{}> has an unexpected length of {} bytes",
+                    value,
+                    bytes.len()
+                )
+            })?,
         })
     }
 }
 
+impl<T> std::fmt::Display for BlobAsBase58String<T>
+where
+    for<'a> T: std::convert::TryFrom<&'a [u8]> + AsRef<[u8]>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            near_primitives::serialize::to_base(self.inner.as_ref())
+        )
+    }
+}
+
 impl<T> BlobAsBase58String<T>
 where
     for<'a> T: std::convert::TryFrom<&'a [u8]> + AsRef<[u8]>,
@@ -70,3 +151,1906 @@ where
         self.inner
     }
 }
+
+/// Decodes a base64-encoded borsh value, e.g. an unsigned or a signed transaction
+pub fn from_base64_borsh<T>(value: &str) -> Result<T, String>
+where
+    T: near_primitives::borsh::BorshDeserialize,
+{
+    let bytes = base64::decode(value.trim()).map_err(|err| format!("Invalid base64: {}", err))?;
+    T::try_from_slice(&bytes).map_err(|err| format!("Invalid borsh encoding: {}", err))
+}
+
+/// A block height or a block hash, to view data as of that block
+#[derive(Debug, Clone)]
+pub struct BlockIdArg(pub near_primitives::types::BlockId);
+
+impl std::str::FromStr for BlockIdArg {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Ok(block_height) = value.trim().parse::<near_primitives::types::BlockHeight>() {
+            return Ok(Self(near_primitives::types::BlockId::Height(block_height)));
+        }
+        BlobAsBase58String::<near_primitives::hash::CryptoHash>::from_str(value)
+            .map(|block_hash| {
+                Self(near_primitives::types::BlockId::Hash(
+                    block_hash.into_inner(),
+                ))
+            })
+            .map_err(|_| format!("<{}> is neither a block height nor a block hash", value))
+    }
+}
+
+/// Asks for a block height or hash, `None` for the latest block
+pub fn input_block_id() -> Option<BlockIdArg> {
+    let block_id: String = dialoguer::Input::new()
+        .with_prompt("Enter the block height or hash (leave empty for the latest block)")
+        .allow_empty(true)
+        .interact_text()
+        .unwrap();
+    if block_id.trim().is_empty() {
+        return None;
+    }
+    match block_id.parse() {
+        Ok(block_id) => Some(block_id),
+        Err(err) => {
+            eprintln!("{}", err);
+            input_block_id()
+        }
+    }
+}
+
+/// The block to view data at: the given one, or the latest one with the view finality
+pub fn view_block_reference(
+    block_id: Option<&BlockIdArg>,
+) -> near_primitives::types::BlockReference {
+    match block_id {
+        Some(BlockIdArg(block_id)) => {
+            near_primitives::types::BlockReference::BlockId(block_id.clone())
+        }
+        None => view_finality().into(),
+    }
+}
+
+/// A transaction hash, or a NEAR Explorer / NearBlocks link to a transaction
+/// (which also tells the network)
+#[derive(Debug)]
+pub struct TransactionReference {
+    pub transaction_hash: near_primitives::hash::CryptoHash,
+    pub connection_config: Option<ConnectionConfig>,
+}
+
+impl std::str::FromStr for TransactionReference {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let (transaction_hash, connection_config) = if value.contains("://") {
+            let url = url::Url::parse(value).map_err(|err| format!("Invalid URL: {}", err))?;
+            let connection_config = match url.host_str().unwrap_or_default() {
+                "explorer.near.org"
+                | "explorer.mainnet.near.org"
+                | "nearblocks.io"
+                | "www.nearblocks.io" => ConnectionConfig::Mainnet,
+                "explorer.testnet.near.org" | "testnet.nearblocks.io" => ConnectionConfig::Testnet,
+                "explorer.betanet.near.org" => ConnectionConfig::Betanet,
+                host => return Err(format!("Unknown explorer <{}>", host)),
+            };
+            // https://explorer.near.org/transactions/<hash>, https://nearblocks.io/txns/<hash>
+            let mut path_segments = url.path_segments().into_iter().flatten();
+            path_segments
+                .find(|segment| *segment == "transactions" || *segment == "txns")
+                .ok_or_else(|| format!("<{}> is not a link to a transaction", value))?;
+            let transaction_hash = path_segments
+                .next()
+                .ok_or_else(|| format!("<{}> is not a link to a transaction", value))?;
+            (transaction_hash.to_string(), Some(connection_config))
+        } else {
+            (value.to_string(), None)
+        };
+        let transaction_hash =
+            BlobAsBase58String::<near_primitives::hash::CryptoHash>::from_str(&transaction_hash)
+                .map_err(|_| format!("Invalid transaction hash <{}>", transaction_hash))?
+                .into_inner();
+        Ok(Self {
+            transaction_hash,
+            connection_config,
+        })
+    }
+}
+
+/// Format of the progress events of long-running and batch operations
+#[derive(
+    Debug,
+    strum_macros::IntoStaticStr,
+    strum_macros::EnumString,
+    strum_macros::EnumVariantNames,
+    smart_default::SmartDefault,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum ProgressOutputFormat {
+    #[default]
+    Plaintext,
+    Jsonl,
+}
+
+static JSONL_EVENTS: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+pub fn set_progress_output_format(format: &ProgressOutputFormat) {
+    let jsonl_events = match format {
+        ProgressOutputFormat::Plaintext => false,
+        ProgressOutputFormat::Jsonl => true,
+    };
+    JSONL_EVENTS.store(jsonl_events, std::sync::atomic::Ordering::Relaxed);
+}
+
+thread_local! {
+    /// The last progress event, for the summary of a run stopped by `--max-duration`
+    static LAST_EVENT: std::cell::RefCell<Option<serde_json::Value>> =
+        std::cell::RefCell::new(None);
+}
+
+/// With `--output jsonl`, prints one JSON object per event (prompt answered,
+/// transaction signed, submitted, finality reached) to stdout as it happens
+pub fn emit_event(event: &str, mut data: serde_json::Value) {
+    if let serde_json::Value::Object(ref mut data) = data {
+        data.insert("event".to_string(), serde_json::json!(event));
+        data.insert(
+            "timestamp".to_string(),
+            serde_json::json!(std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64),
+        );
+    }
+    LAST_EVENT.with(|last_event| *last_event.borrow_mut() = Some(data.clone()));
+    if !JSONL_EVENTS.load(std::sync::atomic::Ordering::Relaxed) {
+        return;
+    }
+    use std::io::Write;
+    let stdout = std::io::stdout();
+    let mut stdout = stdout.lock();
+    writeln!(stdout, "{}", data)
+        .and_then(|_| stdout.flush())
+        .ok();
+}
+
+/// The longest duration `parse_duration` accepts, so that adding it to the
+/// current time cannot overflow
+const LONGEST_DURATION: std::time::Duration = std::time::Duration::from_secs(365 * 24 * 60 * 60);
+
+/// `90s`, `30m`, `24h` or `7d`, up to a year
+pub fn parse_duration(s: &str) -> Result<std::time::Duration, String> {
+    let s = s.trim();
+    let (number, multiplier) = match s.chars().last() {
+        Some('s') => (&s[..s.len() - 1], 1),
+        Some('m') => (&s[..s.len() - 1], 60),
+        Some('h') => (&s[..s.len() - 1], 60 * 60),
+        Some('d') => (&s[..s.len() - 1], 24 * 60 * 60),
+        _ => {
+            return Err(format!(
+                "Invalid duration {:?}, expected e.g. 30m, 24h or 7d",
+                s
+            ))
+        }
+    };
+    let number =
+        u64::from_str(number.trim()).map_err(|err| format!("Invalid duration {:?}: {}", s, err))?;
+    number
+        .checked_mul(multiplier)
+        .map(std::time::Duration::from_secs)
+        .filter(|duration| *duration <= LONGEST_DURATION)
+        .ok_or_else(|| format!("The duration {:?} is too long, the limit is 365d", s))
+}
+
+/// Exit code of a run stopped by `--max-duration`, the one of `timeout(1)`
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// The `--max-duration` in milliseconds, `u64::MAX` without a limit; a static
+/// rather than a thread local, as the agent and the servers run on threads
+static MAX_DURATION_MS: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(u64::MAX);
+/// When the run started, in milliseconds since the Unix epoch
+static STARTED_AT_MS: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+fn unix_time_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// How long the run has taken so far
+fn elapsed() -> std::time::Duration {
+    std::time::Duration::from_millis(
+        unix_time_ms().saturating_sub(STARTED_AT_MS.load(std::sync::atomic::Ordering::Relaxed)),
+    )
+}
+
+/// The time left before `--max-duration` stops the run, `None` without a limit
+pub fn time_left() -> Option<std::time::Duration> {
+    match MAX_DURATION_MS.load(std::sync::atomic::Ordering::Relaxed) {
+        u64::MAX => None,
+        max_duration_ms => Some(
+            std::time::Duration::from_millis(max_duration_ms)
+                .checked_sub(elapsed())
+                .unwrap_or_default(),
+        ),
+    }
+}
+
+/// Whether waiting `wait` (e.g. before a retry) still ends before `--max-duration`
+pub fn has_time_for(wait: std::time::Duration) -> bool {
+    time_left().map_or(true, |time_left| time_left > wait)
+}
+
+/// For the loops that block the thread, which the timer of
+/// `run_with_max_duration` cannot interrupt
+pub fn stop_if_out_of_time() {
+    if time_left() == Some(std::time::Duration::default()) {
+        stop_on_timeout();
+    }
+}
+
+static COMMAND_HANDLES_TIMEOUT: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+/// For a command that has to clean up when `--max-duration` is up (e.g. delete
+/// the account it funded): the run is not stopped at the deadline, the command
+/// calls `stop_on_timeout` itself once it has cleaned up
+pub fn let_command_handle_timeout() {
+    COMMAND_HANDLES_TIMEOUT.store(true, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// Runs the command until `max_duration` after `started_at` has passed; then
+/// prints a JSON summary with the last progress event to stdout and exits with
+/// `TIMEOUT_EXIT_CODE`, so unattended runs never hang on a stuck network
+pub async fn run_with_max_duration<F>(
+    command: F,
+    started_at: std::time::Instant,
+    max_duration: std::time::Duration,
+) where
+    F: std::future::Future<Output = ()>,
+{
+    STARTED_AT_MS.store(
+        unix_time_ms().saturating_sub(started_at.elapsed().as_millis() as u64),
+        std::sync::atomic::Ordering::Relaxed,
+    );
+    MAX_DURATION_MS.store(
+        max_duration.as_millis() as u64,
+        std::sync::atomic::Ordering::Relaxed,
+    );
+    let timeout = actix_rt::time::delay_for(time_left().unwrap_or_default());
+    match futures::future::select(Box::pin(command), Box::pin(timeout)).await {
+        futures::future::Either::Left(_) => return,
+        futures::future::Either::Right((_, command))
+            if COMMAND_HANDLES_TIMEOUT.load(std::sync::atomic::Ordering::Relaxed) =>
+        {
+            command.await
+        }
+        futures::future::Either::Right(_) => {}
+    }
+    stop_on_timeout();
+}
+
+/// Prints the summary of a run stopped by `--max-duration` and exits
+pub fn stop_on_timeout() -> ! {
+    let max_duration = std::time::Duration::from_millis(
+        MAX_DURATION_MS.load(std::sync::atomic::Ordering::Relaxed),
+    );
+    eprintln!(
+        "{}",
+        crate::style::warning(format!(
+            "Stopped: the run took longer than --max-duration {}s",
+            max_duration.as_secs()
+        ))
+    );
+    let last_event = LAST_EVENT.with(|last_event| last_event.borrow().clone());
+    println!(
+        "{}",
+        serde_json::json!({
+            "event": "timeout",
+            "max_duration_secs": max_duration.as_secs(),
+            "elapsed_secs": elapsed().as_secs(),
+            "last_event": last_event,
+        })
+    );
+    std::process::exit(TIMEOUT_EXIT_CODE)
+}
+
+/// Guess the network from the top-level account, as offline transactions carry
+/// no RPC URL
+pub fn network_hint(account_id: &str) -> Option<&'static str> {
+    match account_id.rsplit('.').next() {
+        Some("near") => Some("mainnet"),
+        Some("testnet") => Some("testnet"),
+        Some("betanet") => Some("betanet"),
+        _ => None,
+    }
+}
+
+/// Everything downstream tooling needs about a transaction prepared in the
+/// offline mode, the signed one is `null` until it is signed
+pub fn offline_transaction_data(
+    unsigned_transaction: &near_primitives::transaction::Transaction,
+    signed_transaction: Option<&near_primitives::transaction::SignedTransaction>,
+) -> serde_json::Value {
+    use near_primitives::borsh::BorshSerialize;
+
+    let to_base64 = |bytes: Vec<u8>| near_primitives::serialize::to_base64(bytes);
+    serde_json::json!({
+        "unsigned_transaction": to_base64(
+            unsigned_transaction
+                .try_to_vec()
+                .expect("Transaction is not expected to fail on serialization")
+        ),
+        "signed_transaction": signed_transaction.map(|signed_transaction| to_base64(
+            signed_transaction
+                .try_to_vec()
+                .expect("Transaction is not expected to fail on serialization")
+        )),
+        "transaction_hash": unsigned_transaction.get_hash().to_string(),
+        "signer_id": &unsigned_transaction.signer_id,
+        "receiver_id": &unsigned_transaction.receiver_id,
+        "public_key": unsigned_transaction.public_key.to_string(),
+        "nonce": unsigned_transaction.nonce,
+        "network_hint": network_hint(&unsigned_transaction.signer_id),
+    })
+}
+
+static SHOW_SECRETS: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+pub fn set_show_secrets(show_secrets: bool) {
+    SHOW_SECRETS.store(show_secrets, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// Secret keys and seed phrases are only revealed with `--show-secrets` or after
+/// an explicit interactive confirmation
+pub fn reveal_secrets() -> bool {
+    if SHOW_SECRETS.load(std::sync::atomic::Ordering::Relaxed) {
+        return true;
+    }
+    dialoguer::Confirm::new()
+        .with_prompt("Do you want to reveal the secrets on the screen?")
+        .default(false)
+        .interact()
+        .unwrap_or(false)
+}
+
+pub fn redact_secret(secret: &str) -> String {
+    match secret.find(':') {
+        Some(index) => format!("{}<redacted>", &secret[..=index]),
+        None => "<redacted>".to_string(),
+    }
+}
+
+pub fn display_secret(secret: &str, reveal: bool) -> String {
+    if reveal {
+        secret.to_string()
+    } else {
+        redact_secret(secret)
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct KeychainAccessKey {
+    pub account_id: String,
+    pub public_key: String,
+    pub private_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// The private key is in the OS keyring, not in the file
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub in_os_keyring: bool,
+    /// The private key is encrypted with the master password
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub encrypted: bool,
+}
+
+impl std::fmt::Debug for KeychainAccessKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeychainAccessKey")
+            .field("account_id", &self.account_id)
+            .field("public_key", &self.public_key)
+            .field("private_key", &redact_secret(&self.private_key))
+            .field("label", &self.label)
+            .field("in_os_keyring", &self.in_os_keyring)
+            .field("encrypted", &self.encrypted)
+            .finish()
+    }
+}
+
+/// Overrides `~/.near-credentials`, set by `--credentials-dir`
+pub const CREDENTIALS_DIR_ENV_VAR: &str = "NEAR_CLI_CREDENTIALS_DIR";
+
+pub fn get_credentials_home_dir() -> std::path::PathBuf {
+    if let Some(credentials_dir) = std::env::var_os(CREDENTIALS_DIR_ENV_VAR) {
+        return std::path::PathBuf::from(credentials_dir);
+    }
+    let mut credentials_home_dir = dirs::home_dir().expect("Impossible to get your home dir!");
+    credentials_home_dir.push(".near-credentials");
+    credentials_home_dir
+}
+
+pub fn network_dir_name(selected_server_url: &url::Url) -> String {
+    ConnectionConfig::from_rpc_url(selected_server_url.clone()).dir_name()
+}
+
+static VIEW_FINALITY: std::sync::atomic::AtomicU8 = std::sync::atomic::AtomicU8::new(2);
+
+/// Set by the `view` commands; everything else keeps reading final data
+pub fn set_view_finality(finality: &crate::config::ViewFinality) {
+    let finality = match finality {
+        crate::config::ViewFinality::Optimistic => 0,
+        crate::config::ViewFinality::NearFinal => 1,
+        crate::config::ViewFinality::Final => 2,
+    };
+    VIEW_FINALITY.store(finality, std::sync::atomic::Ordering::Relaxed);
+}
+
+pub fn view_finality() -> near_primitives::types::Finality {
+    match VIEW_FINALITY.load(std::sync::atomic::Ordering::Relaxed) {
+        0 => near_primitives::types::Finality::None,
+        1 => near_primitives::types::Finality::DoomSlug,
+        _ => near_primitives::types::Finality::Final,
+    }
+}
+
+/// The `finality` parameter of the JSON RPC queries
+pub fn view_finality_name() -> &'static str {
+    match view_finality() {
+        near_primitives::types::Finality::None => "optimistic",
+        near_primitives::types::Finality::DoomSlug => "near-final",
+        near_primitives::types::Finality::Final => "final",
+    }
+}
+
+static LOCK_TIMEOUT_MS: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(10_000);
+
+pub fn set_lock_timeout(lock_timeout: std::time::Duration) {
+    LOCK_TIMEOUT_MS.store(
+        lock_timeout.as_millis() as u64,
+        std::sync::atomic::Ordering::Relaxed,
+    );
+}
+
+/// A `<path>.lock` file that keeps parallel near-cli processes (e.g. CI matrix
+/// jobs) off the same file or access key; it is removed when dropped
+#[derive(Debug)]
+pub struct FileLock {
+    lock_path: std::path::PathBuf,
+}
+
+impl FileLock {
+    /// Waits for the lock up to `--lock-timeout`
+    pub fn acquire(path: &std::path::Path) -> Result<Self, String> {
+        let mut lock_path = path.as_os_str().to_owned();
+        lock_path.push(".lock");
+        let lock_path = std::path::PathBuf::from(lock_path);
+        if let Some(parent) = lock_path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| format!("Failed to create the directory {:?}: {:?}", parent, err))?;
+        }
+        let lock_timeout = std::time::Duration::from_millis(
+            LOCK_TIMEOUT_MS.load(std::sync::atomic::Ordering::Relaxed),
+        );
+        let started_at = std::time::Instant::now();
+        loop {
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&lock_path)
+            {
+                Ok(mut lock_file) => {
+                    use std::io::Write;
+                    write!(lock_file, "{}", std::process::id()).ok();
+                    return Ok(FileLock { lock_path });
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+                    stop_if_out_of_time();
+                    if started_at.elapsed() >= lock_timeout {
+                        return Err(format!(
+                            "Timed out after {:?} waiting for the lock {:?} (remove it if no other near-cli is running)",
+                            lock_timeout, &lock_path
+                        ));
+                    }
+                    std::thread::sleep(std::time::Duration::from_millis(50));
+                }
+                Err(err) => {
+                    return Err(format!(
+                        "Failed to create the lock {:?}: {:?}",
+                        &lock_path, err
+                    ))
+                }
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        std::fs::remove_file(&self.lock_path).ok();
+    }
+}
+
+/// Writes a temporary file next to the target and renames it, so readers never
+/// see a partially written file; the file is readable only by the user, as it
+/// may hold a private key
+pub fn write_file_atomically(path: &std::path::Path, contents: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut temporary_path = path.as_os_str().to_owned();
+    temporary_path.push(format!(".tmp-{}", std::process::id()));
+    let temporary_path = std::path::PathBuf::from(temporary_path);
+    // A leftover of a crashed run would keep its old permissions
+    std::fs::remove_file(&temporary_path).ok();
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(&temporary_path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))?;
+    std::fs::rename(&temporary_path, path).map_err(|err| {
+        std::fs::remove_file(&temporary_path).ok();
+        err
+    })
+}
+
+/// Creates the directory and its missing parents, accessible only by the user
+pub fn create_private_dir_all(path: &std::path::Path) -> std::io::Result<()> {
+    let mut dir_builder = std::fs::DirBuilder::new();
+    dir_builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        dir_builder.mode(0o700);
+    }
+    dir_builder.create(path)
+}
+
+/// The lock held from reading the nonce of an access key until the transaction
+/// using it is sent
+pub fn lock_access_key(
+    network_dir_name: &str,
+    account_id: &str,
+    public_key: &str,
+) -> Result<FileLock, String> {
+    let mut key_path = get_credentials_home_dir();
+    key_path.push(network_dir_name);
+    key_path.push(account_id);
+    create_private_dir_all(&key_path).map_err(|err| {
+        format!(
+            "Failed to create the keychain directory {:?}: {:?}",
+            &key_path, err
+        )
+    })?;
+    key_path.push(format!(".{}.nonce", public_key.replace(":", "_")));
+    FileLock::acquire(&key_path)
+}
+
+/// The label is kept only in the local file and is never sent on chain.
+pub fn save_access_key_to_keychain(
+    network_dir_name: &str,
+    access_key: &KeychainAccessKey,
+) -> Result<std::path::PathBuf, String> {
+    let mut file_path = get_credentials_home_dir();
+    file_path.push(network_dir_name);
+    file_path.push(&access_key.account_id);
+    create_private_dir_all(&file_path).map_err(|err| {
+        format!(
+            "Failed to create the keychain directory {:?}: {:?}",
+            &file_path, err
+        )
+    })?;
+    file_path.push(format!("{}.json", access_key.public_key.replace(":", "_")));
+    let buf = if crate::config::Config::load().secret_storage
+        == crate::config::SecretStorage::OsKeyring
+    {
+        let username = os_keyring_username(network_dir_name, access_key);
+        keyring::Keyring::new(OS_KEYRING_SERVICE, &username)
+            .set_password(&access_key.private_key)
+            .map_err(|err| format!("Failed to save the key to the OS keyring: {}", err))?;
+        serde_json::to_string_pretty(&KeychainAccessKey {
+            account_id: access_key.account_id.clone(),
+            public_key: access_key.public_key.clone(),
+            private_key: String::new(),
+            label: access_key.label.clone(),
+            in_os_keyring: true,
+            encrypted: false,
+        })
+    } else if crate::master_password::is_set() {
+        serde_json::to_string_pretty(&KeychainAccessKey {
+            account_id: access_key.account_id.clone(),
+            public_key: access_key.public_key.clone(),
+            private_key: crate::master_password::encrypt_private_key(
+                &access_key.public_key,
+                &access_key.private_key,
+            )?,
+            label: access_key.label.clone(),
+            in_os_keyring: false,
+            encrypted: true,
+        })
+    } else {
+        serde_json::to_string_pretty(access_key)
+    }
+    .map_err(|err| format!("Failed to serialize the access key: {:?}", err))?;
+    let _lock = FileLock::acquire(&file_path)?;
+    write_file_atomically(&file_path, &buf)
+        .map_err(|err| format!("Failed to write the file {:?}: {:?}", &file_path, err))?;
+    Ok(file_path)
+}
+
+pub fn read_access_keys_from_keychain(
+    network_dir_name: &str,
+    account_id: &str,
+) -> Vec<KeychainAccessKey> {
+    let mut account_dir = get_credentials_home_dir();
+    account_dir.push(network_dir_name);
+    account_dir.push(account_id);
+    let entries = match std::fs::read_dir(&account_dir) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension() == Some(std::ffi::OsStr::new("json")))
+        .filter_map(|entry| std::fs::read_to_string(entry.path()).ok())
+        .filter_map(|buf| serde_json::from_str::<KeychainAccessKey>(&buf).ok())
+        .filter_map(|mut access_key| {
+            if access_key.in_os_keyring {
+                let username = os_keyring_username(network_dir_name, &access_key);
+                match keyring::Keyring::new(OS_KEYRING_SERVICE, &username).get_password() {
+                    Ok(private_key) => access_key.private_key = private_key,
+                    Err(err) => {
+                        eprintln!(
+                            "Failed to read {} from the OS keyring: {}",
+                            access_key.public_key, err
+                        );
+                        return None;
+                    }
+                }
+            } else if access_key.encrypted {
+                match crate::master_password::decrypt_private_key(
+                    &access_key.public_key,
+                    &access_key.private_key,
+                ) {
+                    Ok(private_key) => {
+                        access_key.private_key = private_key;
+                        access_key.encrypted = false;
+                    }
+                    Err(err) => {
+                        eprintln!("Failed to decrypt {}: {}", access_key.public_key, err);
+                        return None;
+                    }
+                }
+            }
+            Some(access_key)
+        })
+        .collect()
+}
+
+/// Deletes the key file (and the OS keyring entry) of a keychain key
+pub fn remove_access_key_from_keychain(
+    network_dir_name: &str,
+    access_key: &KeychainAccessKey,
+) -> Result<(), String> {
+    let mut file_path = get_credentials_home_dir();
+    file_path.push(network_dir_name);
+    file_path.push(&access_key.account_id);
+    file_path.push(format!("{}.json", access_key.public_key.replace(":", "_")));
+    if access_key.in_os_keyring {
+        let username = os_keyring_username(network_dir_name, access_key);
+        keyring::Keyring::new(OS_KEYRING_SERVICE, &username)
+            .delete_password()
+            .map_err(|err| format!("Failed to delete the key from the OS keyring: {}", err))?;
+    }
+    let _lock = FileLock::acquire(&file_path)?;
+    std::fs::remove_file(&file_path)
+        .map_err(|err| format!("Failed to remove the file {:?}: {:?}", &file_path, err))
+}
+
+const OS_KEYRING_SERVICE: &str = "near-cli";
+
+fn os_keyring_username(network_dir_name: &str, access_key: &KeychainAccessKey) -> String {
+    format!(
+        "{}/{}/{}",
+        network_dir_name, access_key.account_id, access_key.public_key
+    )
+}
+
+pub fn input_key_label() -> Option<String> {
+    let label: String = dialoguer::Input::new()
+        .with_prompt("Enter a label for this key (e.g. \"alice laptop\"), or leave it empty")
+        .allow_empty(true)
+        .interact_text()
+        .unwrap();
+    if label.trim().is_empty() {
+        None
+    } else {
+        Some(label.trim().to_string())
+    }
+}
+
+/// Set by `--tag` (comma-separated), attached to the audit log entries
+pub const TAGS_ENV_VAR: &str = "NEAR_CLI_TAGS";
+/// Set by `--note`, attached to the audit log entries
+pub const NOTE_ENV_VAR: &str = "NEAR_CLI_NOTE";
+
+/// The tags of the current command, for the audit log
+pub fn audit_log_tags() -> Vec<String> {
+    std::env::var(TAGS_ENV_VAR)
+        .unwrap_or_default()
+        .split(',')
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+pub fn get_audit_log_path() -> std::path::PathBuf {
+    let mut audit_log_path = dirs::data_dir().expect("Impossible to get your data dir!");
+    audit_log_path.push("near-cli");
+    audit_log_path.push("audit.log");
+    audit_log_path
+}
+
+/// Appends one JSON object per line to the local audit log (never sent on chain)
+pub fn append_to_audit_log(mut entry: serde_json::Value) {
+    if let serde_json::Value::Object(ref mut entry) = entry {
+        entry.insert(
+            "timestamp".to_string(),
+            serde_json::json!(std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs()),
+        );
+        let tags = audit_log_tags();
+        if !tags.is_empty() {
+            entry.insert("tags".to_string(), serde_json::json!(tags));
+        }
+        if let Ok(note) = std::env::var(NOTE_ENV_VAR) {
+            entry.insert("note".to_string(), serde_json::json!(note));
+        }
+    }
+    let audit_log_path = get_audit_log_path();
+    let _lock = match FileLock::acquire(&audit_log_path) {
+        Ok(lock) => lock,
+        Err(err) => return eprintln!("Error writing to the audit log:  {}", err),
+    };
+    let result = std::fs::create_dir_all(audit_log_path.parent().unwrap()).and_then(|_| {
+        use std::io::Write;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&audit_log_path)?;
+        writeln!(file, "{}", entry)
+    });
+    if let Err(err) = result {
+        eprintln!(
+            "Error writing to the audit log {:?}:  {:?}",
+            &audit_log_path, err
+        );
+    }
+}
+
+pub fn read_audit_log() -> Vec<serde_json::Value> {
+    match std::fs::read_to_string(get_audit_log_path()) {
+        Ok(buf) => buf
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+/// Writes a base64-encoded transaction to a file for another machine or a later
+/// `utils batch-submit`, instead of printing it
+pub fn save_transaction(path: &std::path::Path, serialize_to_base64: &str) {
+    match write_file_atomically(path, &format!("{}\n", serialize_to_base64)) {
+        Ok(()) => eprintln!(
+            "{} the transaction to {:?}",
+            crate::style::success("Saved"),
+            path
+        ),
+        Err(err) => eprintln!("Error writing the file {:?}:  {:?}", path, err),
+    }
+}
+
+/// Records a signed but not yet submitted transaction, so `utils nonce-doctor`
+/// can later check it against the on-chain access key nonce
+pub fn record_signed_transaction(
+    signed_transaction: &near_primitives::transaction::SignedTransaction,
+    serialize_to_base64: &str,
+) {
+    append_to_audit_log(serde_json::json!({
+        "event": "signed_transaction",
+        "signer_id": signed_transaction.transaction.signer_id,
+        "receiver_id": signed_transaction.transaction.receiver_id,
+        "public_key": signed_transaction.transaction.public_key.to_string(),
+        "nonce": signed_transaction.transaction.nonce,
+        "transaction_hash": signed_transaction.transaction.get_hash().to_string(),
+        "signed_transaction": serialize_to_base64,
+    }));
+}
+
+/// Records a transaction submitted to the network, for `history`
+pub fn record_sent_transaction(
+    signed_transaction: &near_primitives::transaction::SignedTransaction,
+    server_url: &url::Url,
+) {
+    append_to_audit_log(serde_json::json!({
+        "event": "sent_transaction",
+        "signer_id": signed_transaction.transaction.signer_id,
+        "receiver_id": signed_transaction.transaction.receiver_id,
+        "transaction_hash": signed_transaction.get_hash().to_string(),
+        "rpc_url": server_url.as_str(),
+    }));
+}
+
+/// How many blocks a transaction stays valid after the block of its block hash,
+/// unless the node or the exported protocol config tells otherwise
+pub const DEFAULT_TRANSACTION_VALIDITY_PERIOD: near_primitives::types::BlockHeightDelta = 86400;
+
+/// Blocks as a rough wall-clock time, as the networks produce about a block a second
+pub fn format_blocks_as_time(blocks: near_primitives::types::BlockHeightDelta) -> String {
+    match blocks {
+        blocks if blocks < 60 => format!("{} blocks, less than a minute", blocks),
+        blocks if blocks < 60 * 60 => format!("{} blocks, about {} minutes", blocks, blocks / 60),
+        blocks => format!("{} blocks, about {} hours", blocks, blocks / (60 * 60)),
+    }
+}
+
+/// Tells the courier how long a transaction signed offline can wait before it
+/// is broadcast
+pub fn print_offline_transaction_deadline(transaction: &near_primitives::transaction::Transaction) {
+    let validity_period = crate::protocol_config::ProtocolConfig::offline()
+        .map(|protocol_config| protocol_config.transaction_validity_period)
+        .unwrap_or(DEFAULT_TRANSACTION_VALIDITY_PERIOD);
+    eprintln!(
+        "Broadcast the transaction within {} after block {}",
+        format_blocks_as_time(validity_period),
+        transaction.block_hash
+    );
+}
+
+/// Blocks left before the block hash of a transaction falls out of the
+/// validity window (zero once it has expired)
+pub async fn transaction_blocks_left(
+    server_url: &url::Url,
+    transaction: &near_primitives::transaction::Transaction,
+) -> Result<near_primitives::types::BlockHeightDelta, String> {
+    let block_height = |block: serde_json::Value| {
+        block["header"]["height"]
+            .as_u64()
+            .ok_or_else(|| "The node did not report the block height".to_string())
+    };
+    let transaction_block_height = block_height(
+        call_json_rpc(
+            server_url,
+            "block",
+            serde_json::json!({ "block_id": transaction.block_hash.to_string() }),
+        )
+        .await?,
+    )?;
+    let final_block_height = block_height(
+        call_json_rpc(
+            server_url,
+            "block",
+            serde_json::json!({ "finality": "final" }),
+        )
+        .await?,
+    )?;
+    let validity_period = call_json_rpc(
+        server_url,
+        "EXPERIMENTAL_genesis_config",
+        serde_json::json!(null),
+    )
+    .await
+    .ok()
+    .and_then(|genesis_config| genesis_config["transaction_validity_period"].as_u64())
+    .unwrap_or(DEFAULT_TRANSACTION_VALIDITY_PERIOD);
+    Ok((transaction_block_height + validity_period).saturating_sub(final_block_height))
+}
+
+#[derive(Debug, Clone)]
+pub enum ConnectionConfig {
+    Testnet,
+    Mainnet,
+    Betanet,
+    Custom {
+        url: url::Url,
+        archival_url: Option<url::Url>,
+    },
+}
+
+impl ConnectionConfig {
+    pub fn from_network_name(network_name: &str) -> Option<Self> {
+        match network_name {
+            "testnet" => Some(ConnectionConfig::Testnet),
+            "mainnet" => Some(ConnectionConfig::Mainnet),
+            "betanet" => Some(ConnectionConfig::Betanet),
+            profile_name => crate::config::Config::load()
+                .chain_profiles
+                .remove(profile_name)
+                .and_then(|chain_profile| chain_profile.rpc_url)
+                .map(|url| ConnectionConfig::Custom {
+                    url,
+                    archival_url: None,
+                }),
+        }
+    }
+    pub fn chain_profile(&self) -> Option<crate::config::ChainProfile> {
+        let mut chain_profiles = crate::config::Config::load().chain_profiles;
+        match self {
+            ConnectionConfig::Testnet => chain_profiles.remove("testnet"),
+            ConnectionConfig::Mainnet => chain_profiles.remove("mainnet"),
+            ConnectionConfig::Betanet => chain_profiles.remove("betanet"),
+            ConnectionConfig::Custom { url, .. } => chain_profiles
+                .into_iter()
+                .map(|(_, chain_profile)| chain_profile)
+                .find(|chain_profile| chain_profile.rpc_url.as_ref() == Some(url)),
+        }
+    }
+    pub fn account_id_rules(&self) -> crate::config::AccountIdRules {
+        self.chain_profile()
+            .map(|chain_profile| chain_profile.account_id_rules)
+            .unwrap_or_default()
+    }
+    pub fn seed_phrase_hd_path(&self) -> String {
+        let coin_type = self
+            .chain_profile()
+            .and_then(|chain_profile| chain_profile.hd_path_coin_type)
+            .unwrap_or(397);
+        format!("m/44'/{}'/0'", coin_type)
+    }
+    pub fn helper_url(&self) -> Option<url::Url> {
+        if let Some(helper_url) = self
+            .chain_profile()
+            .and_then(|chain_profile| chain_profile.helper_url)
+        {
+            return Some(helper_url);
+        }
+        match self {
+            ConnectionConfig::Testnet => Some(crate::consts::TESTNET_HELPER_URL),
+            ConnectionConfig::Betanet => Some(crate::consts::BETANET_HELPER_URL),
+            ConnectionConfig::Mainnet | ConnectionConfig::Custom { .. } => None,
+        }
+        .map(|helper_url| url::Url::parse(helper_url).unwrap())
+    }
+    pub fn from_rpc_url(url: url::Url) -> Self {
+        match url.as_str().trim_end_matches('/') {
+            crate::consts::TESTNET_API_SERVER_URL => ConnectionConfig::Testnet,
+            crate::consts::MAINNET_API_SERVER_URL => ConnectionConfig::Mainnet,
+            crate::consts::BETANET_API_SERVER_URL => ConnectionConfig::Betanet,
+            _ => ConnectionConfig::Custom {
+                url,
+                archival_url: None,
+            },
+        }
+    }
+    /// The main RPC endpoint, or the fastest healthy one of the network
+    /// according to the latest `utils benchmark-rpc`
+    pub fn rpc_url(&self) -> url::Url {
+        let rpc_urls = self.rpc_urls();
+        if rpc_urls.len() > 1 {
+            if let Some(rpc_url) =
+                crate::rpc_benchmark::fastest_healthy_endpoint(&self.dir_name(), &rpc_urls)
+            {
+                return rpc_url;
+            }
+        }
+        self.main_rpc_url()
+    }
+    /// The main RPC endpoint followed by the fallback ones of the chain profile
+    pub fn rpc_urls(&self) -> Vec<url::Url> {
+        let mut rpc_urls = vec![self.main_rpc_url()];
+        for fallback_rpc_url in self
+            .chain_profile()
+            .map(|chain_profile| chain_profile.fallback_rpc_urls)
+            .unwrap_or_default()
+        {
+            if !rpc_urls.contains(&fallback_rpc_url) {
+                rpc_urls.push(fallback_rpc_url);
+            }
+        }
+        rpc_urls
+    }
+    fn main_rpc_url(&self) -> url::Url {
+        match self {
+            ConnectionConfig::Testnet => {
+                url::Url::parse(crate::consts::TESTNET_API_SERVER_URL).unwrap()
+            }
+            ConnectionConfig::Mainnet => {
+                url::Url::parse(crate::consts::MAINNET_API_SERVER_URL).unwrap()
+            }
+            ConnectionConfig::Betanet => {
+                url::Url::parse(crate::consts::BETANET_API_SERVER_URL).unwrap()
+            }
+            ConnectionConfig::Custom { url, .. } => url.clone(),
+        }
+    }
+    pub fn archival_rpc_url(&self) -> url::Url {
+        match self {
+            ConnectionConfig::Testnet => {
+                url::Url::parse(crate::consts::TESTNET_ARCHIVAL_API_SERVER_URL).unwrap()
+            }
+            ConnectionConfig::Mainnet => {
+                url::Url::parse(crate::consts::MAINNET_ARCHIVAL_API_SERVER_URL).unwrap()
+            }
+            ConnectionConfig::Betanet => {
+                url::Url::parse(crate::consts::BETANET_API_SERVER_URL).unwrap()
+            }
+            ConnectionConfig::Custom { url, archival_url } => {
+                archival_url.clone().unwrap_or_else(|| url.clone())
+            }
+        }
+    }
+    /// Custom networks only have a web wallet if their chain profile sets it
+    pub fn wallet_url(&self) -> Option<url::Url> {
+        if let Some(wallet_url) = self
+            .chain_profile()
+            .and_then(|chain_profile| chain_profile.wallet_url)
+        {
+            return Some(wallet_url);
+        }
+        let wallet_url = match self {
+            ConnectionConfig::Testnet => crate::consts::TESTNET_WALLET_URL,
+            ConnectionConfig::Mainnet => crate::consts::MAINNET_WALLET_URL,
+            ConnectionConfig::Betanet => crate::consts::BETANET_WALLET_URL,
+            ConnectionConfig::Custom { .. } => return None,
+        };
+        Some(url::Url::parse(wallet_url).unwrap())
+    }
+    pub fn dir_name(&self) -> String {
+        match self {
+            ConnectionConfig::Testnet => "testnet".to_string(),
+            ConnectionConfig::Mainnet => "mainnet".to_string(),
+            ConnectionConfig::Betanet => "betanet".to_string(),
+            ConnectionConfig::Custom { url, .. } => url.host_str().unwrap_or("custom").to_string(),
+        }
+    }
+    pub fn input_connection_config() -> Self {
+        eprintln!();
+        // `None` asks for a custom RPC endpoint; the profiles named after the
+        // built-in networks only override them, so they are not listed again
+        let mut choices: Vec<(String, Option<ConnectionConfig>)> = vec![
+            ("testnet".to_string(), Some(ConnectionConfig::Testnet)),
+            ("mainnet".to_string(), Some(ConnectionConfig::Mainnet)),
+            ("betanet".to_string(), Some(ConnectionConfig::Betanet)),
+        ];
+        for (profile_name, chain_profile) in crate::config::Config::load().chain_profiles {
+            if ["testnet", "mainnet", "betanet"].contains(&profile_name.as_str()) {
+                continue;
+            }
+            if let Some(url) = chain_profile.rpc_url {
+                choices.push((
+                    profile_name,
+                    Some(ConnectionConfig::Custom {
+                        url,
+                        archival_url: None,
+                    }),
+                ));
+            }
+        }
+        choices.push(("custom".to_string(), None));
+        let select_network =
+            dialoguer::Select::with_theme(&dialoguer::theme::ColorfulTheme::default())
+                .with_prompt("Select NEAR protocol RPC server:")
+                .items(
+                    &choices
+                        .iter()
+                        .map(|(name, _)| name.as_str())
+                        .collect::<Vec<_>>(),
+                )
+                .default(0)
+                .interact()
+                .unwrap();
+        match choices.swap_remove(select_network).1 {
+            Some(connection_config) => connection_config,
+            None => ConnectionConfig::Custom {
+                url: dialoguer::Input::new()
+                    .with_prompt("What is the RPC endpoint?")
+                    .interact_text()
+                    .unwrap(),
+                archival_url: None,
+            },
+        }
+    }
+}
+
+/// The web wallet's `/sign` page for base64-encoded unsigned transactions
+pub fn wallet_sign_url(
+    wallet_url: &url::Url,
+    unsigned_transactions: &[String],
+    callback_url: Option<&url::Url>,
+) -> url::Url {
+    let mut sign_url = wallet_url.join("sign").unwrap();
+    sign_url
+        .query_pairs_mut()
+        .append_pair("transactions", &unsigned_transactions.join(","));
+    if let Some(callback_url) = callback_url {
+        sign_url
+            .query_pairs_mut()
+            .append_pair("callbackUrl", callback_url.as_str());
+    }
+    sign_url
+}
+
+pub fn open_in_browser(url: &url::Url) -> Result<(), String> {
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = std::process::Command::new("cmd");
+        command.args(&["/C", "start", ""]);
+        command
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    command
+        .arg(url.as_str())
+        .status()
+        .map_err(|err| format!("{:?}", err))
+        .and_then(|status| {
+            if status.success() {
+                Ok(())
+            } else {
+                Err(format!("the browser launcher exited with {}", status))
+            }
+        })
+}
+
+const MISSING_DATA_ERRORS: &[&str] = &[
+    "unknown_block",
+    "unknownblock",
+    "unknown block",
+    "block not found",
+    "unknown_chunk",
+    "unknownchunk",
+    "chunk missing",
+    "unknown_transaction",
+    "unknowntransaction",
+    "garbage collected",
+    "garbagecollected",
+];
+
+pub fn is_missing_data_error(err: &str) -> bool {
+    let err = err.to_lowercase();
+    MISSING_DATA_ERRORS
+        .iter()
+        .any(|missing_data_error| err.contains(missing_data_error))
+}
+
+/// Runs a query against the regular RPC server, and retries it against the
+/// archival one when the data at the requested height/hash is no longer there
+pub async fn with_archival_fallback<T, E, F, Fut>(
+    connection_config: &ConnectionConfig,
+    query: F,
+) -> Result<T, E>
+where
+    E: std::fmt::Debug,
+    F: Fn(url::Url) -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+{
+    let rpc_url = connection_config.rpc_url();
+    let archival_rpc_url = connection_config.archival_rpc_url();
+    match query(rpc_url.clone()).await {
+        Err(err) if rpc_url != archival_rpc_url && is_missing_data_error(&format!("{:?}", err)) => {
+            eprintln!(
+                "The data is not available on {} anymore ({:?}), retrying with the archival RPC server {}",
+                rpc_url, err, archival_rpc_url
+            );
+            query(archival_rpc_url).await
+        }
+        result => result,
+    }
+}
+
+/// Runs a view at the given block (or the latest one) with the archival
+/// fallback; when no server has the data at that block, explains why and
+/// offers to retry the view at the latest block with the view finality
+pub async fn view_at_block<T, E, F, Fut>(
+    connection_config: &ConnectionConfig,
+    block_id: Option<&BlockIdArg>,
+    query: F,
+) -> Result<T, E>
+where
+    E: std::fmt::Debug,
+    F: Fn(url::Url, near_primitives::types::BlockReference) -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+{
+    let block_reference = view_block_reference(block_id);
+    let result =
+        with_archival_fallback(connection_config, |url| query(url, block_reference.clone())).await;
+    let (err, block_id) = match (result, block_id) {
+        (Err(err), Some(BlockIdArg(block_id))) if is_missing_data_error(&format!("{:?}", err)) => {
+            (err, block_id)
+        }
+        (result, _) => return result,
+    };
+    let rpc_url = connection_config.rpc_url();
+    let archival_rpc_url = connection_config.archival_rpc_url();
+    let block = match block_id {
+        near_primitives::types::BlockId::Height(height) => format!("height {}", height),
+        near_primitives::types::BlockId::Hash(hash) => format!("hash {}", hash),
+    };
+    if rpc_url == archival_rpc_url {
+        eprintln!(
+            "{} has no data at the block with {}: it is probably not an archival node, \
+             and no archival RPC server is set for this network (see `--archival-rpc-url`)",
+            rpc_url, block
+        );
+    } else {
+        eprintln!(
+            "Neither {} nor the archival RPC server {} has data at the block with {}",
+            rpc_url, archival_rpc_url, block
+        );
+    }
+    let retry = dialoguer::Confirm::new()
+        .with_prompt(format!(
+            "Do you want to retry at the latest {} block instead?",
+            view_finality_name()
+        ))
+        .default(false)
+        .interact()
+        .unwrap_or(false);
+    if !retry {
+        return Err(err);
+    }
+    query(rpc_url, view_block_reference(None)).await
+}
+
+/// The names of the functions a wasm module exports, read from its export
+/// section
+pub fn wasm_exported_functions(code: &[u8]) -> Result<Vec<String>, String> {
+    fn read_leb128(code: &[u8], position: &mut usize) -> Result<usize, String> {
+        let mut result = 0usize;
+        for shift in (0..35).step_by(7) {
+            let byte = *code
+                .get(*position)
+                .ok_or_else(|| "Unexpected end of the wasm module".to_string())?;
+            *position += 1;
+            result |= usize::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+        }
+        Err("Invalid LEB128 number in the wasm module".to_string())
+    }
+
+    if code.len() < 8 || &code[..4] != b"\0asm" {
+        return Err("The contract code is not a wasm module".to_string());
+    }
+    let mut position = 8;
+    while position < code.len() {
+        let section_id = code[position];
+        position += 1;
+        let section_size = read_leb128(code, &mut position)?;
+        let section_end = position + section_size;
+        if section_end > code.len() {
+            return Err("Unexpected end of the wasm module".to_string());
+        }
+        // The export section
+        if section_id != 7 {
+            position = section_end;
+            continue;
+        }
+        let mut function_names = vec![];
+        for _ in 0..read_leb128(code, &mut position)? {
+            let name_length = read_leb128(code, &mut position)?;
+            let name = code
+                .get(position..position + name_length)
+                .ok_or_else(|| "Unexpected end of the wasm module".to_string())?;
+            position += name_length;
+            let kind = *code
+                .get(position)
+                .ok_or_else(|| "Unexpected end of the wasm module".to_string())?;
+            position += 1;
+            read_leb128(code, &mut position)?;
+            if kind == 0 {
+                function_names.push(String::from_utf8_lossy(name).into_owned());
+            }
+        }
+        return Ok(function_names);
+    }
+    Ok(vec![])
+}
+
+/// Contracts carry no ABI, so the exported functions are the best list of the
+/// methods that can be called
+pub async fn contract_method_names(
+    server_url: &url::Url,
+    contract_id: &str,
+) -> Result<Vec<String>, String> {
+    let query_view_code_response = near_jsonrpc_client::new_client(server_url.as_str())
+        .query(near_primitives::rpc::RpcQueryRequest {
+            block_reference: near_primitives::types::Finality::Final.into(),
+            request: near_primitives::views::QueryRequest::ViewCode {
+                account_id: contract_id.to_string(),
+            },
+        })
+        .await
+        .map_err(|err| format!("{:?}", err))?;
+    match query_view_code_response.kind {
+        near_primitives::views::QueryResponseKind::ViewCode(contract_code_view) => {
+            let mut method_names = wasm_exported_functions(&contract_code_view.code)?;
+            method_names.sort();
+            Ok(method_names)
+        }
+        _ => Err("Unexpected response to the view_code query".to_string()),
+    }
+}
+
+/// The standard account ID rules, narrowed down by the chain profile ones
+pub fn validate_account_id(
+    account_id: &str,
+    rules: &crate::config::AccountIdRules,
+) -> Result<(), String> {
+    let min_length = rules.min_length.unwrap_or(2);
+    let max_length = rules.max_length.unwrap_or(64);
+    if account_id.len() < min_length || account_id.len() > max_length {
+        return Err(format!(
+            "Account ID <{}> must be {} to {} characters long",
+            account_id, min_length, max_length
+        ));
+    }
+    let is_separator = |c: char| c == '-' || c == '_' || c == '.';
+    let mut previous_is_separator = true;
+    for c in account_id.chars() {
+        let current_is_separator = is_separator(c);
+        if !(c.is_ascii_lowercase() || c.is_ascii_digit() || current_is_separator)
+            || (previous_is_separator && current_is_separator)
+        {
+            return Err(format!(
+                "Account ID <{}> may only contain lowercase letters, digits and single separators (-_.) between them",
+                account_id
+            ));
+        }
+        previous_is_separator = current_is_separator;
+    }
+    if previous_is_separator {
+        return Err(format!(
+            "Account ID <{}> must not end with a separator",
+            account_id
+        ));
+    }
+    let is_implicit = account_id.len() == 64 && account_id.chars().all(|c| c.is_ascii_hexdigit());
+    if !rules.top_level_accounts.is_empty()
+        && !is_implicit
+        && !rules.top_level_accounts.iter().any(|top_level_account| {
+            account_id == top_level_account
+                || account_id.ends_with(&format!(".{}", top_level_account))
+        })
+    {
+        return Err(format!(
+            "Account ID <{}> must be one of {:?} or their sub-account",
+            account_id, rules.top_level_accounts
+        ));
+    }
+    Ok(())
+}
+
+/// Validates against the rules of the selected network (the standard rules in
+/// the offline mode)
+pub fn validate_account_id_for(
+    account_id: &str,
+    selected_server_url: Option<&url::Url>,
+) -> Result<(), String> {
+    let rules = match selected_server_url {
+        Some(server_url) => ConnectionConfig::from_rpc_url(server_url.clone()).account_id_rules(),
+        None => Default::default(),
+    };
+    validate_account_id(account_id, &rules)
+}
+
+/// The address book label of an account, if it has one
+pub fn account_label(account_id: &str) -> Option<String> {
+    crate::config::Config::with(|config| config.address_book.get(account_id).cloned())
+}
+
+/// `alice.near`, or `alice.near (Treasury)` when the account has a label
+pub fn format_account_id(account_id: &str) -> String {
+    match account_label(account_id) {
+        Some(label) => format!("{} ({})", account_id, label),
+        None => account_id.to_string(),
+    }
+}
+
+/// Resolves an account ID or an address book label: `@label` always means a
+/// label, a bare label only if it is not an account ID itself, and a label of
+/// several accounts is rejected (labels are case-insensitive)
+pub fn resolve_account_id(account_id_or_label: &str) -> Result<String, String> {
+    let address_book = crate::config::Config::load().address_book;
+    let (label, explicit) = match account_id_or_label.trim().strip_prefix('@') {
+        Some(label) => (label, true),
+        None => (account_id_or_label.trim(), false),
+    };
+    if !explicit && address_book.contains_key(label) {
+        return Ok(label.to_string());
+    }
+    let account_ids = address_book
+        .iter()
+        .filter(|(_, account_label)| account_label.eq_ignore_ascii_case(label))
+        .map(|(account_id, _)| account_id.clone())
+        .collect::<Vec<_>>();
+    let is_account_id = validate_account_id(label, &Default::default()).is_ok();
+    match account_ids.as_slice() {
+        [] if explicit => Err(format!("There is no account labelled \"{}\"", label)),
+        [] => Ok(label.to_string()),
+        [_] if !explicit && is_account_id => Err(format!(
+            "<{}> is both an account ID and a label in the address book, enter @{} for the label",
+            label, label
+        )),
+        [account_id] => {
+            eprintln!("Using <{}> labelled \"{}\"", account_id, label);
+            Ok(account_id.clone())
+        }
+        _ => Err(format!(
+            "The label \"{}\" is ambiguous, it is used by <{}>",
+            label,
+            account_ids.join(">, <")
+        )),
+    }
+}
+
+/// Default RPC endpoint, exported by `near-cli env`
+pub const RPC_URL_ENV_VAR: &str = "NEAR_CLI_RPC_URL";
+/// Default sender account ID, exported by `near-cli env`
+pub const ACCOUNT_ID_ENV_VAR: &str = "NEAR_CLI_ACCOUNT_ID";
+
+/// `--rpc-url` and `--archival-rpc-url` let one-off queries skip the network
+/// selection
+#[derive(Debug, structopt::StructOpt)]
+pub struct CliConnectionConfig {
+    #[structopt(long, env = RPC_URL_ENV_VAR)]
+    rpc_url: Option<url::Url>,
+    #[structopt(long)]
+    archival_rpc_url: Option<url::Url>,
+}
+
+impl CliConnectionConfig {
+    /// Uses the network of an explorer link unless `--rpc-url` is given
+    pub fn into_connection_config_or(self, network: Option<ConnectionConfig>) -> ConnectionConfig {
+        match (&self.rpc_url, network) {
+            (None, Some(network)) => network,
+            _ => ConnectionConfig::from(self),
+        }
+    }
+}
+
+impl From<CliConnectionConfig> for ConnectionConfig {
+    fn from(item: CliConnectionConfig) -> Self {
+        match (item.rpc_url, item.archival_rpc_url) {
+            (Some(cli_rpc_url), None) => ConnectionConfig::from_rpc_url(cli_rpc_url),
+            (Some(cli_rpc_url), archival_url) => ConnectionConfig::Custom {
+                url: cli_rpc_url,
+                archival_url,
+            },
+            (None, _) => ConnectionConfig::input_connection_config(),
+        }
+    }
+}
+
+/// Warns when the transaction calls a contract pinned in the config whose
+/// code hash differs from the expected one; `false` means the user backed out
+pub async fn confirm_pinned_contract(
+    server_url: &url::Url,
+    unsigned_transaction: &near_primitives::transaction::Transaction,
+) -> bool {
+    let has_function_calls = unsigned_transaction
+        .actions
+        .iter()
+        .any(|action| match action {
+            near_primitives::transaction::Action::FunctionCall(_) => true,
+            _ => false,
+        });
+    if !has_function_calls {
+        return true;
+    }
+    let config = crate::config::Config::load();
+    let expected_code_hash = match config
+        .pinned_contracts
+        .get(&unsigned_transaction.receiver_id)
+    {
+        Some(expected_code_hash) => expected_code_hash,
+        None => return true,
+    };
+    let code_hash = match call_json_rpc(
+        server_url,
+        "query",
+        serde_json::json!({
+            "request_type": "view_account",
+            "finality": "final",
+            "account_id": &unsigned_transaction.receiver_id,
+        }),
+    )
+    .await
+    {
+        Ok(account) => account["code_hash"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        Err(err) => format!("unknown ({})", err),
+    };
+    if &code_hash == expected_code_hash {
+        return true;
+    }
+    eprintln!();
+    eprintln!(
+        "{}",
+        crate::style::warning(format!(
+            "!!! The code of the pinned contract <{}> has changed !!!",
+            &unsigned_transaction.receiver_id
+        ))
+    );
+    eprintln!("  expected code hash: {}", expected_code_hash);
+    eprintln!("  current code hash:  {}", code_hash);
+    eprintln!(
+        "The contract may have been upgraded to behave differently, update `pinned_contracts` \
+         in {:?} once you have reviewed the new code",
+        crate::config::Config::config_file_path()
+    );
+    dialoguer::Confirm::new()
+        .with_prompt("Do you still want to sign the transaction?")
+        .default(false)
+        .interact()
+        .unwrap_or(false)
+}
+
+/// A transaction feature that networks only accept from some protocol version on
+struct ProtocolFeature {
+    name: &'static str,
+    min_protocol_version: u32,
+    is_used: fn(&near_primitives::transaction::Transaction) -> bool,
+}
+
+/// Add the new actions here as they get supported, so they are gated on the
+/// networks that have not upgraded yet (e.g. betanet lagging behind or ahead)
+const PROTOCOL_FEATURES: &[ProtocolFeature] = &[ProtocolFeature {
+    name: "Creating an implicit account with a transfer",
+    min_protocol_version: 35,
+    is_used: |unsigned_transaction| {
+        unsigned_transaction.receiver_id.len() == 64
+            && unsigned_transaction
+                .receiver_id
+                .chars()
+                .all(|c| c.is_ascii_hexdigit())
+            && unsigned_transaction
+                .actions
+                .iter()
+                .any(|action| match action {
+                    near_primitives::transaction::Action::Transfer(_) => true,
+                    _ => false,
+                })
+    },
+}];
+
+pub async fn protocol_version(server_url: &url::Url) -> Result<u32, String> {
+    let status = call_json_rpc(server_url, "status", serde_json::json!([])).await?;
+    status["protocol_version"]
+        .as_u64()
+        .map(|protocol_version| protocol_version as u32)
+        .ok_or_else(|| "The node did not report its protocol version".to_string())
+}
+
+/// Warns about the features of the transaction that the network does not
+/// support yet, which would get the transaction rejected; `false` means the
+/// user backed out
+pub async fn confirm_protocol_features(
+    server_url: &url::Url,
+    unsigned_transaction: &near_primitives::transaction::Transaction,
+) -> bool {
+    if !PROTOCOL_FEATURES
+        .iter()
+        .any(|feature| (feature.is_used)(unsigned_transaction))
+    {
+        return true;
+    }
+    match protocol_version(server_url).await {
+        Ok(protocol_version) => {
+            confirm_protocol_features_for(protocol_version, unsigned_transaction)
+        }
+        Err(err) => {
+            eprintln!("Could not check the protocol version:  {}", err);
+            true
+        }
+    }
+}
+
+/// Same as `confirm_protocol_features` for a known protocol version (e.g. from
+/// the protocol config bundle of the offline mode)
+pub fn confirm_protocol_features_for(
+    protocol_version: u32,
+    unsigned_transaction: &near_primitives::transaction::Transaction,
+) -> bool {
+    let unsupported_features = PROTOCOL_FEATURES
+        .iter()
+        .filter(|feature| (feature.is_used)(unsigned_transaction))
+        .filter(|feature| feature.min_protocol_version > protocol_version)
+        .collect::<Vec<_>>();
+    if unsupported_features.is_empty() {
+        return true;
+    }
+    eprintln!();
+    for feature in unsupported_features {
+        eprintln!(
+            "{}",
+            crate::style::warning(format!(
+                "{} needs protocol version {}, the network is on {}",
+                feature.name, feature.min_protocol_version, protocol_version
+            ))
+        );
+    }
+    dialoguer::Confirm::new()
+        .with_prompt("The network is likely to reject the transaction, sign it anyway?")
+        .default(false)
+        .interact()
+        .unwrap_or(false)
+}
+
+/// Whether the account exists on the network, as of the final block
+pub async fn account_exists(server_url: &url::Url, account_id: &str) -> Result<bool, String> {
+    let result = call_json_rpc(
+        server_url,
+        "query",
+        serde_json::json!({
+            "request_type": "view_account",
+            "finality": "final",
+            "account_id": account_id,
+        }),
+    )
+    .await;
+    match result {
+        Ok(_) => Ok(true),
+        Err(err) if err.contains("does not exist") || err.contains("UNKNOWN_ACCOUNT") => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// The hash of the contract code deployed to the account, `None` if the
+/// account does not exist (yet)
+pub async fn contract_code_hash(
+    server_url: &url::Url,
+    account_id: &str,
+) -> Result<Option<String>, String> {
+    let result = call_json_rpc(
+        server_url,
+        "query",
+        serde_json::json!({
+            "request_type": "view_account",
+            "finality": "final",
+            "account_id": account_id,
+        }),
+    )
+    .await;
+    match result {
+        Ok(account) => Ok(account["code_hash"].as_str().map(String::from)),
+        Err(err) if err.contains("does not exist") || err.contains("UNKNOWN_ACCOUNT") => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+pub async fn access_key_exists(
+    server_url: &url::Url,
+    account_id: &str,
+    public_key: &str,
+) -> Result<bool, String> {
+    let result = call_json_rpc(
+        server_url,
+        "query",
+        serde_json::json!({
+            "request_type": "view_access_key",
+            "finality": "final",
+            "account_id": account_id,
+            "public_key": public_key,
+        }),
+    )
+    .await;
+    // Older nodes report a missing access key in the result instead of an error
+    let result = result.and_then(|result| match result.get("error") {
+        Some(error) => Err(error.to_string()),
+        None => Ok(result),
+    });
+    match result {
+        Ok(_) => Ok(true),
+        Err(err) if err.contains("does not exist") || err.contains("UNKNOWN_ACCESS_KEY") => {
+            Ok(false)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// The signer key does not exist (any more) or its permission does not allow
+/// the transaction, e.g. a function-call key used for a transfer
+pub fn is_access_key_permission_error(err: &str) -> bool {
+    err.contains("InvalidAccessKeyError")
+        || err.contains("AccessKeyNotFound")
+        || err.contains("UNKNOWN_ACCESS_KEY")
+}
+
+/// Calls a JSON RPC method that is not covered by `near_jsonrpc_client` (e.g.
+/// EXPERIMENTAL_* methods) and returns its `result`
+pub async fn call_json_rpc(
+    server_url: &url::Url,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    let mut response = actix_web::client::Client::default()
+        .post(server_url.as_str())
+        .send_json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "dontcare",
+            "method": method,
+            "params": params,
+        }))
+        .await
+        .map_err(|err| format!("{:?}", err))?;
+    let mut response: serde_json::Value = response
+        .json()
+        .limit(64 * 1024 * 1024)
+        .await
+        .map_err(|err| format!("{:?}", err))?;
+    if let Some(error) = response.get("error") {
+        return Err(error.to_string());
+    }
+    Ok(response["result"].take())
+}
+
+/// Calls a view method with JSON arguments and parses its result as JSON
+pub async fn call_view_function(
+    server_url: &url::Url,
+    contract_id: &str,
+    method_name: &str,
+    args: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    let call_result = call_json_rpc(
+        server_url,
+        "query",
+        serde_json::json!({
+            "request_type": "call_function",
+            "finality": view_finality_name(),
+            "account_id": contract_id,
+            "method_name": method_name,
+            "args_base64": base64::encode(args.to_string()),
+        }),
+    )
+    .await?;
+    if let Some(error) = call_result.get("error") {
+        return Err(error.to_string());
+    }
+    let result = call_result["result"]
+        .as_array()
+        .ok_or_else(|| format!("Unexpected response to the view call: {}", call_result))?
+        .iter()
+        .map(|byte| byte.as_u64().unwrap_or_default() as u8)
+        .collect::<Vec<u8>>();
+    serde_json::from_slice(&result).map_err(|err| format!("The result is not JSON: {}", err))
+}
+
+pub const ONE_NEAR: u128 = 10u128.pow(24);
+
+/// Human-readable amount, e.g. `1,234.567 NEAR`, followed by the approximate
+/// fiat amount if a fiat price is configured (never use it for machine-readable output)
+pub fn format_near_amount(yocto_near: u128) -> String {
+    let near_amount = format!(
+        "{} NEAR",
+        crate::style::format_decimal(
+            yocto_near / ONE_NEAR,
+            &format!("{:024}", yocto_near % ONE_NEAR)
+        )
+    );
+    match crate::fiat::format_fiat_amount(yocto_near) {
+        Some(fiat_amount) => format!("{} ({}, approximate)", near_amount, fiat_amount),
+        None => near_amount,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_primitives::borsh::BorshSerialize;
+    use proptest::prelude::*;
+
+    #[test]
+    fn parse_duration_rejects_durations_over_a_year() {
+        assert_eq!(parse_duration("365d"), Ok(LONGEST_DURATION));
+        assert!(parse_duration("366d").is_err());
+        assert!(parse_duration("8761h").is_err());
+        assert!(parse_duration(&format!("{}s", u64::MAX)).is_err());
+        assert!(parse_duration(&format!("{}m", u64::MAX)).is_err());
+        assert!(parse_duration(&format!("{}d", u64::MAX / 86_400 + 1)).is_err());
+    }
+
+    #[test]
+    fn parse_duration_accepts_every_unit() {
+        assert_eq!(
+            parse_duration("90s"),
+            Ok(std::time::Duration::from_secs(90))
+        );
+        assert_eq!(
+            parse_duration(" 30m "),
+            Ok(std::time::Duration::from_secs(30 * 60))
+        );
+        assert_eq!(
+            parse_duration("24h"),
+            Ok(std::time::Duration::from_secs(24 * 60 * 60))
+        );
+        assert_eq!(
+            parse_duration("7d"),
+            Ok(std::time::Duration::from_secs(7 * 24 * 60 * 60))
+        );
+        for duration in &["", "d", "7", "7w", "-1s", "1.5h"] {
+            assert!(parse_duration(duration).is_err(), "{:?}", duration);
+        }
+    }
+
+    #[test]
+    fn format_blocks_as_time_never_says_zero_minutes() {
+        assert_eq!(format_blocks_as_time(59), "59 blocks, less than a minute");
+        assert_eq!(format_blocks_as_time(120), "120 blocks, about 2 minutes");
+        assert_eq!(
+            format_blocks_as_time(86_400),
+            "86400 blocks, about 24 hours"
+        );
+    }
+
+    #[test]
+    fn blob_as_base58_string_round_trips() {
+        let hash = near_primitives::hash::hash(b"near-cli");
+        let blob =
+            BlobAsBase58String::<near_primitives::hash::CryptoHash>::from_str(&hash.to_string())
+                .unwrap();
+        assert_eq!(blob.to_string(), hash.to_string());
+        assert_eq!(blob.into_inner(), hash);
+        assert!(BlobAsBase58String::<near_primitives::hash::CryptoHash>::from_str("0OIl").is_err());
+        assert!(BlobAsBase58String::<near_primitives::hash::CryptoHash>::from_str("11").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn block_hashes_round_trip_through_base58(bytes in any::<[u8; 32]>()) {
+            let text = near_primitives::serialize::to_base(&bytes);
+            let blob =
+                BlobAsBase58String::<near_primitives::hash::CryptoHash>::from_str(&text).unwrap();
+            prop_assert_eq!(blob.to_string(), text);
+            prop_assert_eq!(blob.into_inner().as_ref(), &bytes[..]);
+        }
+
+        #[test]
+        fn base58_parser_never_panics(text in "\\PC{0,64}") {
+            BlobAsBase58String::<near_primitives::hash::CryptoHash>::from_str(&text).ok();
+        }
+
+        #[test]
+        fn transactions_round_trip_through_base64(
+            nonce in any::<u64>(),
+            deposit in any::<u128>(),
+            block_hash_seed in any::<Vec<u8>>(),
+        ) {
+            let transaction = near_primitives::transaction::Transaction {
+                signer_id: "alice.near".to_string(),
+                public_key: near_crypto::PublicKey::empty(near_crypto::KeyType::ED25519),
+                nonce,
+                receiver_id: "bob.near".to_string(),
+                block_hash: near_primitives::hash::hash(&block_hash_seed),
+                actions: vec![near_primitives::transaction::Action::Transfer(
+                    near_primitives::transaction::TransferAction { deposit },
+                )],
+            };
+            let text = near_primitives::serialize::to_base64(transaction.try_to_vec().unwrap());
+            let decoded = from_base64_borsh::<near_primitives::transaction::Transaction>(&text);
+            prop_assert_eq!(
+                decoded.map(|decoded| decoded.get_hash()),
+                Ok(transaction.get_hash())
+            );
+        }
+
+        #[test]
+        fn base64_borsh_parser_never_panics(text in "[A-Za-z0-9+/=]{0,128}|\\PC{0,64}") {
+            from_base64_borsh::<near_primitives::transaction::Transaction>(&text).ok();
+            from_base64_borsh::<near_primitives::transaction::SignedTransaction>(&text).ok();
+        }
+    }
+}
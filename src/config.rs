@@ -0,0 +1,264 @@
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DefaultSigner {
+    SignKeychain,
+    SignManually,
+}
+
+/// Where the secret keys of the keychain are kept; the key files stay in the
+/// credentials directory either way
+#[derive(
+    Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, smart_default::SmartDefault,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum SecretStorage {
+    #[default]
+    File,
+    /// macOS Keychain, Windows Credential Manager or Secret Service on Linux
+    OsKeyring,
+}
+
+/// Storage layout of the well-known near-sdk collections
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StorageCollectionKind {
+    LookupMap,
+    LookupSet,
+    UnorderedMap,
+    UnorderedSet,
+    Vector,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StorageKeyDecoder {
+    /// UTF-8 storage key prefix the collection was created with
+    pub prefix: String,
+    pub name: String,
+    pub kind: StorageCollectionKind,
+    /// BorshSchema declaration of the collection values, used together with
+    /// `view contract-state --borsh-schema`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_declaration: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, smart_default::SmartDefault)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    #[default]
+    Colorful,
+    Plain,
+}
+
+/// Display format of NEAR amounts, the separators default to the ones of the
+/// current locale
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct NumberFormat {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thousands_separator: Option<char>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimal_separator: Option<char>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_decimals: Option<u32>,
+}
+
+/// Account ID rules of a private NEAR-based chain on top of the standard ones
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct AccountIdRules {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    /// Named accounts have to be one of these or their sub-accounts (any
+    /// top-level account is allowed when the list is empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub top_level_accounts: Vec<String>,
+}
+
+/// Overrides for a network; a profile named `testnet`, `mainnet` or `betanet`
+/// adjusts that network, any other name adds a custom chain with `rpc_url`
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ChainProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<url::Url>,
+    #[serde(default)]
+    pub account_id_rules: AccountIdRules,
+    /// The coin type of the default seed phrase HD path `m/44'/<coin type>'/0'`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hd_path_coin_type: Option<u32>,
+    /// The helper (faucet) URL used to create accounts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub helper_url: Option<url::Url>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet_url: Option<url::Url>,
+    /// More RPC endpoints of the network; the fastest healthy one measured by
+    /// `utils benchmark-rpc` is used instead of the main one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_rpc_urls: Vec<url::Url>,
+}
+
+/// How final the block of the view queries has to be; `optimistic` gives the
+/// freshest data, which may still be rolled back
+#[derive(
+    Debug,
+    Clone,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::IntoStaticStr,
+    strum_macros::EnumString,
+    strum_macros::EnumVariantNames,
+    smart_default::SmartDefault,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum ViewFinality {
+    Optimistic,
+    NearFinal,
+    #[default]
+    Final,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, smart_default::SmartDefault)]
+#[serde(rename_all = "kebab-case")]
+pub enum IndexerBackend {
+    #[default]
+    NearBlocks,
+    EnhancedApi,
+}
+
+/// The indexer behind `view account-history`, `view sub-accounts` and
+/// `view accounts-by-key`; the URL defaults to the public one of the network
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct IndexerConfig {
+    #[serde(default)]
+    pub backend: IndexerBackend,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<url::Url>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// The GraphQL endpoint of `utils indexer-query`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graphql_url: Option<url::Url>,
+}
+
+/// Where the price of NEAR in a fiat currency comes from: a static `rate`, or a
+/// `url` returning JSON with the price at `json_pointer` (e.g. `/near/usd`)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FiatPrice {
+    pub currency: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<url::Url>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_pointer: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct Config {
+    /// Signing option per signer account ID, used to skip the "Would you like
+    /// to sign the transaction?" step
+    #[serde(default)]
+    pub default_signers: std::collections::BTreeMap<String, DefaultSigner>,
+    /// Receiver account IDs with a payable method accepting `{"memo": "..."}`,
+    /// used to attach transfer memos on chain
+    #[serde(default)]
+    pub memo_contracts: std::collections::BTreeMap<String, String>,
+    /// Collection prefixes per contract account ID, used to render the
+    /// storage keys and values in `view contract-state`
+    #[serde(default)]
+    pub storage_key_decoders: std::collections::BTreeMap<String, Vec<StorageKeyDecoder>>,
+    /// `colorful` (default) or `plain` output
+    #[serde(default)]
+    pub theme: Theme,
+    #[serde(default)]
+    pub number_format: NumberFormat,
+    /// Network overrides and private chains by profile name, offered in the
+    /// network selection
+    #[serde(default)]
+    pub chain_profiles: std::collections::BTreeMap<String, ChainProfile>,
+    #[serde(default)]
+    pub indexer: IndexerConfig,
+    /// The command of the `sign-external` signing option
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_signer_command: Option<String>,
+    /// The `signer serve` URL of the `sign-remote` signing option
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_signer_url: Option<url::Url>,
+    /// Expected code hashes of trusted contracts by account ID; calling a pinned
+    /// contract whose code has changed asks for a confirmation
+    #[serde(default)]
+    pub pinned_contracts: std::collections::BTreeMap<String, String>,
+    /// How long the agent and `signer serve` keep the keys unlocked after
+    /// entering the master password (15 minutes by default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unlock_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub secret_storage: SecretStorage,
+    /// The default of `view --finality`
+    #[serde(default)]
+    pub view_finality: ViewFinality,
+    /// Labels by account ID, shown next to the account IDs and accepted instead
+    /// of them (as `@label`, or as a bare label that is not an account ID itself)
+    #[serde(default)]
+    pub address_book: std::collections::BTreeMap<String, String>,
+    /// Shows the NEAR amounts also in this fiat currency, marked as approximate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fiat_price: Option<FiatPrice>,
+}
+
+/// Overrides the default config file path, set by `--config`
+pub const CONFIG_FILE_ENV_VAR: &str = "NEAR_CLI_CONFIG";
+
+thread_local! {
+    /// The config file is read once per run, nothing writes it meanwhile
+    static CONFIG: std::cell::RefCell<Option<Config>> = std::cell::RefCell::new(None);
+}
+
+impl Config {
+    pub fn config_file_path() -> std::path::PathBuf {
+        if let Some(config_file_path) = std::env::var_os(CONFIG_FILE_ENV_VAR) {
+            return std::path::PathBuf::from(config_file_path);
+        }
+        let mut config_file_path = dirs::config_dir().expect("Impossible to get your config dir!");
+        config_file_path.push("near-cli");
+        config_file_path.push("config.json");
+        config_file_path
+    }
+    pub fn load() -> Self {
+        Config::with(Config::clone)
+    }
+    /// Borrows the config instead of copying it, for the frequent lookups
+    pub fn with<R>(f: impl FnOnce(&Config) -> R) -> R {
+        CONFIG.with(|config| {
+            if config.borrow().is_none() {
+                let read_config = Config::read();
+                *config.borrow_mut() = Some(read_config);
+            }
+            f(config.borrow().as_ref().unwrap())
+        })
+    }
+    /// Falling back to the defaults on a broken config file would quietly
+    /// switch the secret storage back to plaintext files and drop the pinned
+    /// contracts and default signers, so only a missing file means defaults
+    fn read() -> Self {
+        let config_file_path = Config::config_file_path();
+        let buf = match std::fs::read_to_string(&config_file_path) {
+            Ok(buf) => buf,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Config::default(),
+            Err(err) => {
+                eprintln!(
+                    "Error reading the config file {:?}:  {:?}",
+                    &config_file_path, err
+                );
+                std::process::exit(1);
+            }
+        };
+        serde_json::from_str(&buf).unwrap_or_else(|err| {
+            eprintln!(
+                "Error parsing the config file {:?}:  {}\nFix or remove the file and try again.",
+                &config_file_path, err
+            );
+            std::process::exit(1);
+        })
+    }
+}
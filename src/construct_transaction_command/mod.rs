@@ -1,5 +1,8 @@
 pub mod operation_mode;
 mod receiver;
 mod sender;
-mod sign_transaction;
+pub mod sign_transaction;
 mod transaction_actions;
+
+pub use receiver::ActionSubcommandDiscriminants;
+pub use transaction_actions::call_function_type::CallFunctionAction;
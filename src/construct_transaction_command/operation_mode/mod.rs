@@ -4,11 +4,17 @@ use std::str::FromStr;
 use structopt::StructOpt;
 
 mod select_server;
-use select_server::server::{CliSendFrom, SendFrom};
+use select_server::server::{CliSendFrom, SendFrom, Server};
 use select_server::{CliSelectServer, SelectServer};
 
 #[derive(Debug, StructOpt)]
 pub struct CliOperationMode {
+    /// Send the transaction through this RPC endpoint without choosing the mode and the network
+    #[structopt(long, env = crate::common::RPC_URL_ENV_VAR)]
+    pub rpc_url: Option<url::Url>,
+    /// Continue the transaction saved with "Save a draft to continue later"
+    #[structopt(long)]
+    pub from_draft: Option<std::path::PathBuf>,
     #[structopt(subcommand)]
     pub mode: Option<CliMode>,
 }
@@ -19,6 +25,29 @@ pub struct OperationMode {
 }
 
 impl OperationMode {
+    /// The online mode with the network, the sender and the kind of the action
+    /// already chosen, the rest is asked for
+    pub fn online_with_action(
+        connection_config: crate::common::ConnectionConfig,
+        sender_account_id: String,
+        action: super::ActionSubcommandDiscriminants,
+    ) -> Self {
+        let receiver_account_id = super::receiver::Receiver::input_receiver_account_id();
+        let send_from = SendFrom::Sender(super::sender::Sender {
+            sender_account_id,
+            send_to: super::sender::SendTo::Receiver(super::receiver::Receiver {
+                receiver_account_id,
+                action: super::receiver::NextAction::AddAction(
+                    super::receiver::SelectAction::from_action(action),
+                ),
+            }),
+        });
+        Self {
+            mode: Mode::Online(OnlineArgs {
+                selected_server: SelectServer::from_connection_config(connection_config, send_from),
+            }),
+        }
+    }
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
@@ -32,15 +61,39 @@ impl OperationMode {
                     .process(prepopulated_unsigned_transaction)
                     .await
             }
+            Mode::FromDraft(draft_args) => draft_args.process().await,
         }
     }
 }
 
 impl From<CliOperationMode> for OperationMode {
     fn from(item: CliOperationMode) -> Self {
-        let mode = match item.mode {
-            Some(cli_mode) => Mode::from(cli_mode),
-            None => Mode::choose_mode(),
+        if let Some(draft_file) = item.from_draft {
+            if item.mode.is_some() || item.rpc_url.is_some() {
+                eprintln!("The mode and --rpc-url are ignored, the draft keeps its own");
+            }
+            return Self {
+                mode: Mode::FromDraft(DraftArgs { draft_file }),
+            };
+        }
+        let mode = match (item.mode, item.rpc_url) {
+            (Some(cli_mode), None) => Mode::from(cli_mode),
+            (Some(cli_mode), Some(rpc_url)) => match Mode::from(cli_mode) {
+                Mode::Online(online_args) => Mode::Online(OnlineArgs {
+                    selected_server: online_args.selected_server.with_url(rpc_url),
+                }),
+                Mode::Offline(offline_args) => {
+                    eprintln!("--rpc-url is ignored in the offline mode");
+                    Mode::Offline(offline_args)
+                }
+            },
+            (None, Some(rpc_url)) => Mode::Online(OnlineArgs {
+                selected_server: SelectServer::Custom(Server {
+                    url: Some(rpc_url),
+                    send_from: SendFrom::send_from(),
+                }),
+            }),
+            (None, None) => Mode::choose_mode(),
         };
         Self { mode }
     }
@@ -50,6 +103,7 @@ impl From<CliOperationMode> for OperationMode {
 pub enum Mode {
     Online(OnlineArgs),
     Offline(OfflineArgs),
+    FromDraft(DraftArgs),
 }
 
 impl Mode {
@@ -58,7 +112,7 @@ impl Mode {
             "Yes, I keep it simple",
             "No, I want to work in no-network (air-gapped) environment",
         ];
-        println!();
+        eprintln!();
         let select_mode = Select::with_theme(&ColorfulTheme::default())
             .with_prompt(
                 "To construct a transaction you will need to provide information about sender (signer) and receiver accounts, and actions that needs to be performed.
@@ -101,6 +155,9 @@ pub struct CliOfflineArgs {
     nonce: Option<u64>,
     #[structopt(long)]
     block_hash: Option<crate::common::BlobAsBase58String<CryptoHash>>,
+    /// Check and estimate the transaction with a file of `utils export-protocol-config`
+    #[structopt(long, env = crate::protocol_config::PROTOCOL_CONFIG_ENV_VAR)]
+    protocol_config: Option<std::path::PathBuf>,
     #[structopt(subcommand)]
     pub send_from: Option<CliSendFrom>,
 }
@@ -128,6 +185,12 @@ impl From<CliOnlineArgs> for OnlineArgs {
 
 impl From<CliOfflineArgs> for OfflineArgs {
     fn from(item: CliOfflineArgs) -> Self {
+        if let Some(protocol_config) = &item.protocol_config {
+            std::env::set_var(
+                crate::protocol_config::PROTOCOL_CONFIG_ENV_VAR,
+                protocol_config,
+            );
+        }
         let nonce: u64 = match item.nonce {
             Some(cli_nonce) => cli_nonce,
             None => OfflineArgs::input_nonce(),
@@ -153,11 +216,6 @@ impl OfflineArgs {
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
     ) {
-        println!("OfflineArgs process self:\n        {:?}", &self);
-        println!(
-            "OfflineArgs process prepopulated_unsigned_transaction:\n        {:?}",
-            prepopulated_unsigned_transaction
-        );
         let selected_server_url = None;
         let nonce = self.nonce.clone();
         let block_hash = self.block_hash.clone();
@@ -180,13 +238,45 @@ impl OfflineArgs {
             .unwrap()
     }
     fn input_block_hash() -> near_primitives::hash::CryptoHash {
-        let input_block_hash: String = Input::new()
-            .with_prompt("Enter recent block hash:")
-            .interact_text()
-            .unwrap();
-        crate::common::BlobAsBase58String::<CryptoHash>::from_str(&input_block_hash)
-            .unwrap()
-            .into_inner()
+        loop {
+            let input_block_hash: String = Input::new()
+                .with_prompt("Enter recent block hash:")
+                .interact_text()
+                .unwrap();
+            match crate::common::BlobAsBase58String::<CryptoHash>::from_str(&input_block_hash) {
+                Ok(block_hash) => break block_hash.into_inner(),
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DraftArgs {
+    draft_file: std::path::PathBuf,
+}
+
+impl DraftArgs {
+    pub async fn process(self) {
+        let draft = match crate::draft::Draft::load(&self.draft_file) {
+            Ok(draft) => draft,
+            Err(err) => return eprintln!("{}", err),
+        };
+        let unsigned_transaction = match draft.unsigned_transaction() {
+            Ok(unsigned_transaction) => unsigned_transaction,
+            Err(err) => return eprintln!("{}", err),
+        };
+        eprintln!(
+            "Continuing the transaction from <{}> to <{}>",
+            unsigned_transaction.signer_id, unsigned_transaction.receiver_id
+        );
+        for change in &draft.changes {
+            eprintln!("{}", change);
+        }
+        let next_action = super::receiver::NextAction::input_next_action();
+        next_action
+            .process(unsigned_transaction, draft.rpc_url)
+            .await;
     }
 }
 
@@ -195,10 +285,6 @@ impl OnlineArgs {
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
     ) {
-        println!(
-            "OnlineArgs process:\n        {:?}",
-            prepopulated_unsigned_transaction
-        );
         self.selected_server
             .process(prepopulated_unsigned_transaction)
             .await;
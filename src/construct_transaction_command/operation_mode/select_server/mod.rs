@@ -1,10 +1,7 @@
-use dialoguer::{console::Term, theme::ColorfulTheme, Input, Select};
 use structopt::StructOpt;
-use strum::VariantNames;
 use strum_macros::{Display, EnumVariantNames};
 
-use crate::consts;
-use consts::{BETANET_API_SERVER_URL, MAINNET_API_SERVER_URL, TESTNET_API_SERVER_URL};
+use crate::common::ConnectionConfig;
 pub mod server;
 use server::{CliCustomServer, CliServer, SendFrom, Server};
 
@@ -27,15 +24,15 @@ pub enum CliSelectServer {
 impl From<CliSelectServer> for SelectServer {
     fn from(item: CliSelectServer) -> Self {
         match item {
-            CliSelectServer::Testnet(cli_server) => {
-                Self::Testnet(cli_server.into_server(TESTNET_API_SERVER_URL.to_string()))
-            }
-            CliSelectServer::Mainnet(cli_server) => {
-                Self::Mainnet(cli_server.into_server(MAINNET_API_SERVER_URL.to_string()))
-            }
-            CliSelectServer::Betanet(cli_server) => {
-                Self::Betanet(cli_server.into_server(BETANET_API_SERVER_URL.to_string()))
-            }
+            CliSelectServer::Testnet(cli_server) => Self::Testnet(
+                cli_server.into_server(ConnectionConfig::Testnet.rpc_url().to_string()),
+            ),
+            CliSelectServer::Mainnet(cli_server) => Self::Mainnet(
+                cli_server.into_server(ConnectionConfig::Mainnet.rpc_url().to_string()),
+            ),
+            CliSelectServer::Betanet(cli_server) => Self::Betanet(
+                cli_server.into_server(ConnectionConfig::Betanet.rpc_url().to_string()),
+            ),
             CliSelectServer::Custom(cli_custom_server) => {
                 Self::Custom(cli_custom_server.into_server())
             }
@@ -49,50 +46,46 @@ impl SelectServer {
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
     ) {
         match self {
-            SelectServer::Testnet(server) => {
-                server.process(prepopulated_unsigned_transaction).await;
-            }
-            SelectServer::Mainnet(_server) => {}
-            SelectServer::Betanet(_server) => {}
-            SelectServer::Custom(server) => {
+            SelectServer::Testnet(server)
+            | SelectServer::Mainnet(server)
+            | SelectServer::Betanet(server)
+            | SelectServer::Custom(server) => {
                 server.process(prepopulated_unsigned_transaction).await;
             }
         }
     }
+    pub fn with_url(self, url: url::Url) -> Self {
+        let send_from = match self {
+            SelectServer::Testnet(server)
+            | SelectServer::Mainnet(server)
+            | SelectServer::Betanet(server)
+            | SelectServer::Custom(server) => server.send_from,
+        };
+        SelectServer::Custom(Server {
+            url: Some(url),
+            send_from,
+        })
+    }
+    /// The network selection is shared with the other commands (see
+    /// `ConnectionConfig`), so the chain profiles are offered here too
     pub fn select_server() -> Self {
-        println!();
-        let servers = SelectServer::VARIANTS;
-        let select_server = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Select NEAR protocol RPC server:")
-            .items(&servers)
-            .default(0)
-            .interact_on_opt(&Term::stderr())
-            .unwrap();
+        let connection_config = ConnectionConfig::input_connection_config();
         let send_from = SendFrom::send_from();
-        match select_server {
-            Some(0) => SelectServer::Testnet(Server {
-                url: Some(url::Url::parse(TESTNET_API_SERVER_URL).unwrap()),
-                send_from,
-            }),
-            Some(1) => SelectServer::Mainnet(Server {
-                url: Some(url::Url::parse(MAINNET_API_SERVER_URL).unwrap()),
-                send_from,
-            }),
-            Some(2) => SelectServer::Betanet(Server {
-                url: Some(url::Url::parse(BETANET_API_SERVER_URL).unwrap()),
-                send_from,
-            }),
-            Some(3) => SelectServer::Custom(Server {
-                url: {
-                    let url: url::Url = Input::new()
-                        .with_prompt("What is the RPC endpoint?")
-                        .interact_text()
-                        .unwrap();
-                    Some(url)
-                },
-                send_from,
-            }),
-            _ => unreachable!("Error"),
+        SelectServer::from_connection_config(connection_config, send_from)
+    }
+    pub fn from_connection_config(
+        connection_config: ConnectionConfig,
+        send_from: SendFrom,
+    ) -> Self {
+        let server = Server {
+            url: Some(connection_config.rpc_url()),
+            send_from,
+        };
+        match connection_config {
+            ConnectionConfig::Testnet => SelectServer::Testnet(server),
+            ConnectionConfig::Mainnet => SelectServer::Mainnet(server),
+            ConnectionConfig::Betanet => SelectServer::Betanet(server),
+            ConnectionConfig::Custom { .. } => SelectServer::Custom(server),
         }
     }
 }
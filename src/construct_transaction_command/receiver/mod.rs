@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Input, Select};
+use std::str::FromStr;
 use structopt::StructOpt;
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
@@ -10,6 +11,10 @@ use super::transaction_actions::transfer_near_tokens_type::{
 use super::transaction_actions::add_access_key_type::{
     AccessKeyPermission, AddAccessKeyAction, CliAddAccessKeyAction,
 };
+use super::transaction_actions::call_function_type::{CallFunctionAction, CliCallFunctionAction};
+use super::transaction_actions::catalog_operation_type::{
+    CatalogOperationAction, CliCatalogOperationAction,
+};
 use super::transaction_actions::create_account_type::{
     CliCreateAccountAction, CreateAccountAction,
 };
@@ -19,6 +24,9 @@ use super::transaction_actions::delete_access_key_type::{
 use super::transaction_actions::delete_account_type::{
     CliDeleteAccountAction, DeleteAccountAction,
 };
+use super::transaction_actions::deploy_contract_type::{
+    CliDeployContractAction, DeployContractAction,
+};
 
 #[derive(Debug)]
 pub struct Receiver {
@@ -33,6 +41,14 @@ pub enum NextAction {
     AddAction(SelectAction),
     #[strum_discriminants(strum(message = "Skip adding a new action"))]
     Skip(SkipAction),
+    #[strum_discriminants(strum(message = "Save a draft to continue later"))]
+    SaveDraft(SaveDraft),
+}
+
+/// Save the transaction constructed so far, see `construct-transaction --from-draft`
+#[derive(Debug)]
+pub struct SaveDraft {
+    pub draft_file: std::path::PathBuf,
 }
 
 #[derive(Debug)]
@@ -46,7 +62,7 @@ pub enum ActionSubcommand {
     #[strum_discriminants(strum(message = "Transfer NEAR Tokens"))]
     TransferNEARTokens(TransferNEARTokensAction),
     #[strum_discriminants(strum(message = "Call a Function"))]
-    CallFunction,
+    CallFunction(CallFunctionAction),
     #[strum_discriminants(strum(message = "Stake NEAR Tokens"))]
     StakeNEARTokens,
     #[strum_discriminants(strum(message = "Create an Account"))]
@@ -57,6 +73,10 @@ pub enum ActionSubcommand {
     AddAccessKey(AddAccessKeyAction),
     #[strum_discriminants(strum(message = "Detete an Access Key"))]
     DeleteAccessKey(DeleteAccessKeyAction),
+    #[strum_discriminants(strum(message = "Deploy a Contract"))]
+    DeployContract(DeployContractAction),
+    #[strum_discriminants(strum(message = "Use an operation from a catalog"))]
+    CatalogOperation(CatalogOperationAction),
 }
 
 #[derive(Debug, StructOpt)]
@@ -70,6 +90,12 @@ pub struct CliReceiver {
 pub enum CliNextAction {
     AddAction(CliSelectAction),
     Skip(CliSkipAction),
+    SaveDraft(CliSaveDraft),
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliSaveDraft {
+    draft_file: Option<std::path::PathBuf>,
 }
 
 #[derive(Debug, StructOpt)]
@@ -81,12 +107,14 @@ pub struct CliSelectAction {
 #[derive(Debug, StructOpt)]
 pub enum CliActionSubcommand {
     TransferNEARTokens(CliTransferNEARTokensAction),
-    CallFunction,
+    CallFunction(CliCallFunctionAction),
     StakeNEARTokens,
     CreateAccount(CliCreateAccountAction),
     DeleteAccount(CliDeleteAccountAction),
     AddAccessKey(CliAddAccessKeyAction),
     DeleteAccessKey(CliDeleteAccessKeyAction),
+    DeployContract(CliDeployContractAction),
+    CatalogOperation(CliCatalogOperationAction),
 }
 
 #[derive(Debug, StructOpt)]
@@ -100,7 +128,6 @@ impl NextAction {
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         selected_server_url: Option<url::Url>,
     ) {
-        println!("Receiver process: self:\n       {:?}", &self);
         match self {
             NextAction::AddAction(select_action) => {
                 select_action
@@ -112,17 +139,50 @@ impl NextAction {
                     .process(prepopulated_unsigned_transaction, selected_server_url)
                     .await
             }
+            NextAction::SaveDraft(save_draft) => {
+                save_draft.process(prepopulated_unsigned_transaction, selected_server_url)
+            }
+        }
+    }
+}
+
+impl SaveDraft {
+    pub fn process(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        selected_server_url: Option<url::Url>,
+    ) {
+        let draft =
+            crate::draft::Draft::new(&prepopulated_unsigned_transaction, selected_server_url);
+        match draft.save(&self.draft_file) {
+            Ok(()) => eprintln!(
+                "The draft is saved to {:?}, continue with `construct-transaction --from-draft`",
+                self.draft_file
+            ),
+            Err(err) => eprintln!("{}", err),
         }
     }
+    pub fn input_draft_file() -> std::path::PathBuf {
+        let draft_file: String = Input::new()
+            .with_prompt("Enter the path to the draft file")
+            .default("draft.json".to_string())
+            .interact_text()
+            .unwrap();
+        std::path::PathBuf::from(draft_file)
+    }
 }
 
 impl SelectAction {
+    pub fn from_action(action: ActionSubcommandDiscriminants) -> Self {
+        SelectAction {
+            transaction_subcommand: ActionSubcommand::input_action_command(action),
+        }
+    }
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         selected_server_url: Option<url::Url>,
     ) {
-        println!("Receiver process: self:\n       {:?}", &self);
         self.transaction_subcommand
             .process(prepopulated_unsigned_transaction, selected_server_url)
             .await;
@@ -141,7 +201,11 @@ impl ActionSubcommand {
                     .process(prepopulated_unsigned_transaction, selected_server_url)
                     .await
             }
-            // ActionSubcommand::CallFunction(args_function) => {},
+            ActionSubcommand::CallFunction(args_function) => {
+                args_function
+                    .process(prepopulated_unsigned_transaction, selected_server_url)
+                    .await
+            }
             // ActionSubcommand::StakeNEARTokens(args_stake) => {},
             ActionSubcommand::CreateAccount(args_create_account) => {
                 args_create_account
@@ -167,11 +231,21 @@ impl ActionSubcommand {
                     .process(prepopulated_unsigned_transaction, selected_server_url)
                     .await
             }
+            ActionSubcommand::DeployContract(args_deploy_contract) => {
+                args_deploy_contract
+                    .process(prepopulated_unsigned_transaction, selected_server_url)
+                    .await
+            }
+            ActionSubcommand::CatalogOperation(args_catalog_operation) => {
+                args_catalog_operation
+                    .process(prepopulated_unsigned_transaction, selected_server_url)
+                    .await
+            }
             _ => unreachable!("Error"),
         }
     }
     pub fn choose_action_command() -> Self {
-        println!();
+        eprintln!();
         let variants = ActionSubcommandDiscriminants::iter().collect::<Vec<_>>();
         let action_subcommands = variants
             .iter()
@@ -183,16 +257,38 @@ impl ActionSubcommand {
             .default(0)
             .interact()
             .unwrap();
-        match variants[select_action_subcommand] {
+        crate::common::emit_event(
+            "prompt_answered",
+            serde_json::json!({
+                "prompt": "Select an action that you want to add to the action:",
+                "answer": action_subcommands[select_action_subcommand],
+            }),
+        );
+        ActionSubcommand::input_action_command(variants[select_action_subcommand])
+    }
+    /// Asks for the details of an action of the given kind
+    pub fn input_action_command(variant: ActionSubcommandDiscriminants) -> Self {
+        match variant {
             ActionSubcommandDiscriminants::TransferNEARTokens => {
                 let amount: NearBalance = NearBalance::input_amount();
+                let memo: Option<String> = TransferNEARTokensAction::input_memo();
                 let next_action: Box<NextAction> = Box::new(NextAction::input_next_action());
                 ActionSubcommand::TransferNEARTokens(TransferNEARTokensAction {
                     amount,
+                    memo,
                     next_action,
                 })
             }
-            ActionSubcommandDiscriminants::CallFunction => ActionSubcommand::CallFunction,
+            ActionSubcommandDiscriminants::CallFunction => {
+                ActionSubcommand::CallFunction(CallFunctionAction {
+                    method_name: None,
+                    args: None,
+                    gas: None,
+                    deposit: None,
+                    expect_storage_bytes: None,
+                    next_action: None,
+                })
+            }
             ActionSubcommandDiscriminants::StakeNEARTokens => ActionSubcommand::StakeNEARTokens,
             ActionSubcommandDiscriminants::CreateAccount => {
                 let next_action: Box<NextAction> = Box::new(NextAction::input_next_action());
@@ -207,11 +303,12 @@ impl ActionSubcommand {
                 })
             }
             ActionSubcommandDiscriminants::AddAccessKey => {
-                let public_key: String = AddAccessKeyAction::input_public_key();
+                let public_keys: Vec<near_crypto::PublicKey> =
+                    AddAccessKeyAction::input_public_keys();
                 let nonce: near_primitives::types::Nonce = AddAccessKeyAction::input_nonce();
                 let permission: AccessKeyPermission = AccessKeyPermission::choose_permission();
                 ActionSubcommand::AddAccessKey(AddAccessKeyAction {
-                    public_key,
+                    public_keys,
                     nonce,
                     permission,
                 })
@@ -224,6 +321,24 @@ impl ActionSubcommand {
                     next_action,
                 })
             }
+            ActionSubcommandDiscriminants::DeployContract => {
+                let wasm_file = DeployContractAction::input_wasm_file();
+                let record_provenance = DeployContractAction::input_record_provenance();
+                let next_action: Box<NextAction> = Box::new(NextAction::input_next_action());
+                ActionSubcommand::DeployContract(DeployContractAction {
+                    wasm_file,
+                    record_provenance,
+                    builder: None,
+                    next_action,
+                })
+            }
+            ActionSubcommandDiscriminants::CatalogOperation => {
+                ActionSubcommand::CatalogOperation(CatalogOperationAction {
+                    operation_name: None,
+                    params: vec![],
+                    next_action: None,
+                })
+            }
         }
     }
 }
@@ -234,7 +349,12 @@ impl Receiver {
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         selected_server_url: Option<url::Url>,
     ) {
-        println!("Receiver process: self:\n       {:?}", &self);
+        if let Err(err) = crate::common::validate_account_id_for(
+            &self.receiver_account_id,
+            selected_server_url.as_ref(),
+        ) {
+            return eprintln!("{}", crate::style::warning(err));
+        }
         let unsigned_transaction = near_primitives::transaction::Transaction {
             receiver_id: self.receiver_account_id.clone(),
             ..prepopulated_unsigned_transaction
@@ -244,19 +364,33 @@ impl Receiver {
             .await;
     }
     pub fn input_receiver_account_id() -> String {
-        Input::new()
-            .with_prompt("What is the account ID of the receiver?")
+        let receiver_account_id: String = Input::new()
+            .with_prompt("What is the account ID (or the label) of the receiver?")
             .interact_text()
-            .unwrap()
+            .unwrap();
+        match crate::common::resolve_account_id(&receiver_account_id) {
+            Ok(receiver_account_id) => receiver_account_id,
+            Err(err) => {
+                eprintln!("{}", err);
+                Receiver::input_receiver_account_id()
+            }
+        }
     }
 }
 
 impl From<CliReceiver> for Receiver {
     fn from(item: CliReceiver) -> Self {
-        let receiver_account_id: String = match item.receiver_account_id {
-            Some(cli_receiver_account_id) => cli_receiver_account_id,
-            None => Receiver::input_receiver_account_id(),
-        };
+        let receiver_account_id: String =
+            match item.receiver_account_id.map(|cli_receiver_account_id| {
+                crate::common::resolve_account_id(&cli_receiver_account_id)
+            }) {
+                Some(Ok(cli_receiver_account_id)) => cli_receiver_account_id,
+                Some(Err(err)) => {
+                    eprintln!("{}", err);
+                    Receiver::input_receiver_account_id()
+                }
+                None => Receiver::input_receiver_account_id(),
+            };
         let action: NextAction = match item.action {
             Some(cli_next_action) => NextAction::from(cli_next_action),
             None => NextAction::input_next_action(),
@@ -270,7 +404,7 @@ impl From<CliReceiver> for Receiver {
 
 impl NextAction {
     pub fn input_next_action() -> Self {
-        println!();
+        eprintln!();
         let variants = NextActionDiscriminants::iter().collect::<Vec<_>>();
         let next_action = variants
             .iter()
@@ -290,10 +424,15 @@ impl NextAction {
                     transaction_subcommand,
                 })
             }
-            NextActionDiscriminants::Skip => {
-                let sign_option: SignTransaction = SignTransaction::choose_sign_option();
-                NextAction::Skip(SkipAction { sign_option })
-            }
+            NextActionDiscriminants::Skip => NextAction::Skip(SkipAction {
+                gas_split: None,
+                plan_file: None,
+                networks: vec![],
+                sign_option: None,
+            }),
+            NextActionDiscriminants::SaveDraft => NextAction::SaveDraft(SaveDraft {
+                draft_file: SaveDraft::input_draft_file(),
+            }),
         }
     }
 }
@@ -309,6 +448,12 @@ impl From<CliNextAction> for NextAction {
                 let skip_action: SkipAction = SkipAction::from(cli_skip_action);
                 NextAction::Skip(skip_action)
             }
+            CliNextAction::SaveDraft(cli_save_draft) => NextAction::SaveDraft(SaveDraft {
+                draft_file: match cli_save_draft.draft_file {
+                    Some(cli_draft_file) => cli_draft_file,
+                    None => SaveDraft::input_draft_file(),
+                },
+            }),
         }
     }
 }
@@ -333,6 +478,10 @@ impl From<CliActionSubcommand> for ActionSubcommand {
                     TransferNEARTokensAction::from(cli_transfer_near_token);
                 ActionSubcommand::TransferNEARTokens(transfer_near_token)
             }
+            CliActionSubcommand::CallFunction(cli_call_function) => {
+                let call_function: CallFunctionAction = CallFunctionAction::from(cli_call_function);
+                ActionSubcommand::CallFunction(call_function)
+            }
             CliActionSubcommand::CreateAccount(cli_create_account) => {
                 let create_account: CreateAccountAction =
                     CreateAccountAction::from(cli_create_account);
@@ -353,6 +502,16 @@ impl From<CliActionSubcommand> for ActionSubcommand {
                     DeleteAccessKeyAction::from(cli_delete_access_key);
                 ActionSubcommand::DeleteAccessKey(delete_access_key)
             }
+            CliActionSubcommand::DeployContract(cli_deploy_contract) => {
+                let deploy_contract: DeployContractAction =
+                    DeployContractAction::from(cli_deploy_contract);
+                ActionSubcommand::DeployContract(deploy_contract)
+            }
+            CliActionSubcommand::CatalogOperation(cli_catalog_operation) => {
+                let catalog_operation: CatalogOperationAction =
+                    CatalogOperationAction::from(cli_catalog_operation);
+                ActionSubcommand::CatalogOperation(catalog_operation)
+            }
             _ => unreachable!("Error"),
         }
     }
@@ -369,13 +528,152 @@ impl From<CliSkipNextAction> for NextAction {
     }
 }
 
+/// The maximum prepaid gas of a transaction
+const MAX_TRANSACTION_GAS: near_primitives::types::Gas = 300_000_000_000_000;
+
+/// How to distribute the gas budget across the FunctionCall actions:
+/// `equal` or comma-separated weights (e.g. `1,2,1`)
+#[derive(Debug, Clone)]
+pub enum GasSplit {
+    Keep,
+    Equal,
+    Weighted(Vec<u64>),
+}
+
+impl FromStr for GasSplit {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "keep" => Ok(GasSplit::Keep),
+            "equal" => Ok(GasSplit::Equal),
+            weights => weights
+                .split(',')
+                .map(|weight| {
+                    match weight.trim().parse::<u64>() {
+                        // A function call without gas always fails
+                        Ok(0) => Err("A gas weight cannot be zero".to_string()),
+                        Ok(weight) => Ok(weight),
+                        Err(err) => Err(format!("Invalid gas weight {:?}: {}", weight, err)),
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(GasSplit::Weighted),
+        }
+    }
+}
+
+impl GasSplit {
+    pub fn input_gas_split(function_calls_count: usize) -> Self {
+        eprintln!();
+        let choices = vec![
+            "Keep the gas of each function call as it is".to_string(),
+            format!(
+                "Split {} TGas equally across the {} function calls",
+                MAX_TRANSACTION_GAS / 1_000_000_000_000,
+                function_calls_count
+            ),
+            "Split by weights".to_string(),
+        ];
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("How do you want to distribute the gas?")
+            .items(&choices)
+            .default(0)
+            .interact()
+            .unwrap();
+        match selection {
+            0 => GasSplit::Keep,
+            1 => GasSplit::Equal,
+            2 => loop {
+                let weights: String = Input::new()
+                    .with_prompt(format!(
+                        "Enter {} comma-separated weights (e.g. 1,2,1)",
+                        function_calls_count
+                    ))
+                    .interact_text()
+                    .unwrap();
+                match GasSplit::from_str(&weights) {
+                    Ok(gas_split) => break gas_split,
+                    Err(err) => eprintln!("{}", err),
+                }
+            },
+            _ => unreachable!("Error"),
+        }
+    }
+
+    /// Returns the gas for each FunctionCall action, in order
+    pub fn split(
+        &self,
+        current_gas: &[near_primitives::types::Gas],
+    ) -> Result<Vec<near_primitives::types::Gas>, String> {
+        let weights = match self {
+            GasSplit::Keep => {
+                let total_gas: u128 = current_gas.iter().map(|gas| u128::from(*gas)).sum();
+                if total_gas > u128::from(MAX_TRANSACTION_GAS) {
+                    return Err(format!(
+                        "The total gas {} exceeds the limit of {}",
+                        total_gas, MAX_TRANSACTION_GAS
+                    ));
+                }
+                return Ok(current_gas.to_vec());
+            }
+            GasSplit::Equal => vec![1; current_gas.len()],
+            GasSplit::Weighted(weights) => {
+                if weights.len() != current_gas.len() {
+                    return Err(format!(
+                        "There are {} function calls, but {} gas weights",
+                        current_gas.len(),
+                        weights.len()
+                    ));
+                }
+                weights.clone()
+            }
+        };
+        let total_weight: u128 = weights.iter().map(|weight| u128::from(*weight)).sum();
+        if total_weight == 0 {
+            return Err("The gas weights cannot all be zero".to_string());
+        }
+        // Rounding down keeps the total under the limit
+        let gas = weights
+            .iter()
+            .map(|weight| {
+                (u128::from(MAX_TRANSACTION_GAS) * u128::from(*weight) / total_weight)
+                    as near_primitives::types::Gas
+            })
+            .collect::<Vec<_>>();
+        if gas.contains(&0) {
+            return Err("The gas weights leave a function call without gas".to_string());
+        }
+        Ok(gas)
+    }
+}
+
 #[derive(Debug)]
 pub struct SkipAction {
-    pub sign_option: SignTransaction,
+    /// `None` means that it is asked for when the transaction has several
+    /// FunctionCall actions
+    pub gas_split: Option<GasSplit>,
+    /// Save the transaction with its changes there instead of signing it
+    pub plan_file: Option<std::path::PathBuf>,
+    /// Sign and send the same transaction with the keychain on each of these
+    /// networks instead of the selected one
+    pub networks: Vec<String>,
+    /// `None` means that the signing option is taken from the config default for
+    /// the signer or chosen interactively once the signer is known
+    pub sign_option: Option<SignTransaction>,
 }
 
 #[derive(Debug, StructOpt)]
 pub struct CliSkipAction {
+    #[structopt(long)]
+    split_gas: Option<GasSplit>,
+    /// Print the changes and save them to this file without signing, see `utils apply-plan`
+    #[structopt(long)]
+    plan: Option<std::path::PathBuf>,
+    /// Repeat the transaction on each of these networks, e.g. `--networks testnet,mainnet`
+    #[structopt(long, use_delimiter = true)]
+    networks: Vec<String>,
+    #[structopt(long)]
+    choose_signer: bool,
     #[structopt(subcommand)]
     sign_option: Option<CliSignTransaction>,
 }
@@ -386,23 +684,284 @@ impl SkipAction {
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         selected_server_url: Option<url::Url>,
     ) {
-        println!("Skip process:\n       {:?}", &self);
-        println!(
-            "Skip process: prepopulated_unsigned_transaction:\n       {:?}",
-            &prepopulated_unsigned_transaction
-        );
-        self.sign_option
+        let function_calls_gas = prepopulated_unsigned_transaction
+            .actions
+            .iter()
+            .filter_map(|action| match action {
+                near_primitives::transaction::Action::FunctionCall(function_call) => {
+                    Some(function_call.gas)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let mut prepopulated_unsigned_transaction = prepopulated_unsigned_transaction;
+        if function_calls_gas.len() > 1 {
+            let gas_split = match self.gas_split {
+                Some(gas_split) => gas_split,
+                None => GasSplit::input_gas_split(function_calls_gas.len()),
+            };
+            let mut split_gas = match gas_split.split(&function_calls_gas) {
+                Ok(split_gas) => split_gas.into_iter(),
+                Err(err) => return eprintln!("Error distributing the gas:  {}", err),
+            };
+            for action in prepopulated_unsigned_transaction.actions.iter_mut() {
+                if let near_primitives::transaction::Action::FunctionCall(function_call) = action {
+                    function_call.gas = split_gas.next().unwrap();
+                    eprintln!(
+                        "{}: {} TGas",
+                        function_call.method_name,
+                        function_call.gas as f64 / 1_000_000_000_000.0
+                    );
+                }
+            }
+        }
+        if let Some(plan_file) = &self.plan_file {
+            let plan =
+                crate::plan::Plan::new(&prepopulated_unsigned_transaction, selected_server_url);
+            plan.print();
+            return match plan.save(plan_file) {
+                Ok(()) => eprintln!(
+                    "The plan is saved to {:?}, run `utils apply-plan {:?}` to apply it",
+                    plan_file, plan_file
+                ),
+                Err(err) => eprintln!("{}", err),
+            };
+        }
+        if !self.networks.is_empty() {
+            return SkipAction::process_on_networks(
+                prepopulated_unsigned_transaction,
+                self.networks,
+                self.sign_option,
+            )
+            .await;
+        }
+        if let Some(server_url) = &selected_server_url {
+            if !crate::common::confirm_pinned_contract(
+                server_url,
+                &prepopulated_unsigned_transaction,
+            )
+            .await
+            {
+                return eprintln!("The transaction has not been signed");
+            }
+            if !crate::common::confirm_protocol_features(
+                server_url,
+                &prepopulated_unsigned_transaction,
+            )
+            .await
+            {
+                return eprintln!("The transaction has not been signed");
+            }
+        } else if let Some(protocol_config) = crate::protocol_config::ProtocolConfig::offline() {
+            if let Err(err) =
+                SkipAction::check_offline(&protocol_config, &prepopulated_unsigned_transaction)
+            {
+                return eprintln!("{}", err);
+            }
+        }
+        let sign_option: SignTransaction = match self.sign_option {
+            Some(sign_option) => sign_option,
+            None => SignTransaction::choose_sign_option_for(
+                &prepopulated_unsigned_transaction.signer_id,
+            ),
+        };
+        sign_option
             .process(prepopulated_unsigned_transaction, selected_server_url)
             .await;
     }
+    /// Validates and estimates the transaction with the protocol config bundle
+    /// instead of the network
+    fn check_offline(
+        protocol_config: &crate::protocol_config::ProtocolConfig,
+        prepopulated_unsigned_transaction: &near_primitives::transaction::Transaction,
+    ) -> Result<(), String> {
+        let (prepaid_gas, deposits) = prepopulated_unsigned_transaction.actions.iter().fold(
+            (0u128, 0u128),
+            |(prepaid_gas, deposits), action| match action {
+                near_primitives::transaction::Action::FunctionCall(function_call) => (
+                    prepaid_gas + u128::from(function_call.gas),
+                    deposits.saturating_add(function_call.deposit),
+                ),
+                near_primitives::transaction::Action::Transfer(transfer) => {
+                    (prepaid_gas, deposits.saturating_add(transfer.deposit))
+                }
+                _ => (prepaid_gas, deposits),
+            },
+        );
+        if prepaid_gas > u128::from(protocol_config.max_total_prepaid_gas) {
+            return Err(format!(
+                "The transaction attaches {} gas, the limit on {} is {}",
+                prepaid_gas, protocol_config.chain_id, protocol_config.max_total_prepaid_gas
+            ));
+        }
+        eprintln!(
+            "Attached gas: up to {}, deposits: {} (gas price of {} as of the export)",
+            crate::common::format_near_amount(
+                prepaid_gas.saturating_mul(protocol_config.gas_price()?),
+            ),
+            crate::common::format_near_amount(deposits),
+            protocol_config.chain_id
+        );
+        if !crate::common::confirm_protocol_features_for(
+            protocol_config.protocol_version,
+            prepopulated_unsigned_transaction,
+        ) {
+            return Err("The transaction has not been signed".to_string());
+        }
+        Ok(())
+    }
+    async fn process_on_networks(
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        networks: Vec<String>,
+        sign_option: Option<SignTransaction>,
+    ) {
+        // The nonce and the block hash are fetched on each network by the keychain signer
+        let signer_public_key = match sign_option {
+            None => None,
+            Some(SignTransaction::SignKeychain(sign_keychain)) => sign_keychain.signer_public_key,
+            Some(_) => {
+                return eprintln!("Only the keychain can sign the transaction on several networks")
+            }
+        };
+        let mut outcomes = vec![];
+        for network in networks {
+            eprintln!();
+            eprintln!("=== {} ===", &network);
+            let outcome = match crate::common::ConnectionConfig::from_network_name(&network) {
+                Some(connection_config) => {
+                    SkipAction::process_on_network(
+                        prepopulated_unsigned_transaction.clone(),
+                        connection_config.rpc_url(),
+                        signer_public_key.clone(),
+                    )
+                    .await
+                }
+                None => Err("unknown network".to_string()),
+            };
+            outcomes.push((network, outcome));
+        }
+        eprintln!();
+        for (network, outcome) in outcomes {
+            match outcome {
+                Ok(outcome) => println!("{}: {}", network, crate::style::success(outcome)),
+                Err(err) => eprintln!("{}: {}", network, crate::style::warning(err)),
+            }
+        }
+    }
+    /// Drops the AddKey and DeployContract actions that are already applied
+    /// on the network, so running the same setup again is a no-op
+    async fn process_on_network(
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        server_url: url::Url,
+        signer_public_key: Option<String>,
+    ) -> Result<String, String> {
+        let receiver_id = prepopulated_unsigned_transaction.receiver_id.clone();
+        let mut actions = vec![];
+        let mut added_public_keys = vec![];
+        let mut deployed_code_hash = None;
+        for action in prepopulated_unsigned_transaction.actions.iter() {
+            match action {
+                near_primitives::transaction::Action::AddKey(add_key) => {
+                    let public_key = add_key.public_key.to_string();
+                    if crate::common::access_key_exists(&server_url, &receiver_id, &public_key)
+                        .await?
+                    {
+                        eprintln!(
+                            "The access key {} is already added, skipping it",
+                            &public_key
+                        );
+                        continue;
+                    }
+                    added_public_keys.push(public_key);
+                }
+                near_primitives::transaction::Action::DeployContract(deploy_contract) => {
+                    let code_hash = near_primitives::hash::hash(&deploy_contract.code).to_string();
+                    if crate::common::contract_code_hash(&server_url, &receiver_id).await?
+                        == Some(code_hash.clone())
+                    {
+                        eprintln!(
+                            "The contract code {} is already deployed, skipping it",
+                            &code_hash
+                        );
+                        continue;
+                    }
+                    deployed_code_hash = Some(code_hash);
+                }
+                _ => {}
+            }
+            actions.push(action.clone());
+        }
+        if actions.is_empty() {
+            return Ok("already up to date".to_string());
+        }
+        let unsigned_transaction = near_primitives::transaction::Transaction {
+            actions,
+            ..prepopulated_unsigned_transaction
+        };
+        super::sign_transaction::sign_keychain::SignKeychain {
+            signer_public_key,
+            save_to: None,
+        }
+        .sign_and_send(unsigned_transaction, Some(server_url.clone()))
+        .await?;
+        for public_key in added_public_keys {
+            if !crate::common::access_key_exists(&server_url, &receiver_id, &public_key).await? {
+                return Err(format!("the access key {} was not added", public_key));
+            }
+        }
+        if let Some(code_hash) = deployed_code_hash {
+            if crate::common::contract_code_hash(&server_url, &receiver_id).await?
+                != Some(code_hash.clone())
+            {
+                return Err(format!("the contract code {} was not deployed", code_hash));
+            }
+        }
+        Ok("applied".to_string())
+    }
 }
 
 impl From<CliSkipAction> for SkipAction {
     fn from(item: CliSkipAction) -> Self {
-        let sign_option: SignTransaction = match item.sign_option {
-            Some(cli_sign_transaction) => SignTransaction::from(cli_sign_transaction),
-            None => SignTransaction::choose_sign_option(),
+        let sign_option: Option<SignTransaction> = match item.sign_option {
+            Some(cli_sign_transaction) => Some(SignTransaction::from(cli_sign_transaction)),
+            None if item.choose_signer => Some(SignTransaction::choose_sign_option()),
+            None => None,
         };
-        SkipAction { sign_option }
+        SkipAction {
+            gas_split: item.split_gas,
+            plan_file: item.plan,
+            networks: item.networks,
+            sign_option,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gas_split_from_str() {
+        assert!(matches!(GasSplit::from_str("keep"), Ok(GasSplit::Keep)));
+        assert!(matches!(GasSplit::from_str(" equal "), Ok(GasSplit::Equal)));
+        match GasSplit::from_str("1, 2,1") {
+            Ok(GasSplit::Weighted(weights)) => assert_eq!(weights, vec![1, 2, 1]),
+            gas_split => panic!("Unexpected {:?}", gas_split),
+        }
+        for gas_split in &["", "1,,2", "1,-2", "even", "1.5", "0,1", "1, 0"] {
+            assert!(GasSplit::from_str(gas_split).is_err(), "{:?}", gas_split);
+        }
+    }
+
+    #[test]
+    fn gas_split_gives_every_function_call_gas() {
+        assert_eq!(
+            GasSplit::from_str("1,2").unwrap().split(&[0, 0]),
+            Ok(vec![MAX_TRANSACTION_GAS / 3, MAX_TRANSACTION_GAS / 3 * 2])
+        );
+        assert!(GasSplit::Weighted(vec![0, 1]).split(&[0, 0]).is_err());
+        assert!(GasSplit::Weighted(vec![1, u64::MAX])
+            .split(&[0, 0])
+            .is_err());
     }
 }
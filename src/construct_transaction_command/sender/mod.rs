@@ -31,6 +31,12 @@ impl Sender {
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         selected_server_url: Option<url::Url>,
     ) {
+        if let Err(err) = crate::common::validate_account_id_for(
+            &self.sender_account_id,
+            selected_server_url.as_ref(),
+        ) {
+            return eprintln!("{}", crate::style::warning(err));
+        }
         let unsigned_transaction = near_primitives::transaction::Transaction {
             signer_id: self.sender_account_id.clone(),
             ..prepopulated_unsigned_transaction
@@ -40,18 +46,39 @@ impl Sender {
             .await;
     }
     pub fn input_sender_account_id() -> String {
-        println!();
-        Input::new()
-            .with_prompt("What is the account ID of the sender?")
+        if let Ok(env_account_id) = std::env::var(crate::common::ACCOUNT_ID_ENV_VAR) {
+            eprintln!(
+                "Using the sender account ID from ${}",
+                crate::common::ACCOUNT_ID_ENV_VAR
+            );
+            return env_account_id;
+        }
+        eprintln!();
+        let sender_account_id: String = Input::new()
+            .with_prompt("What is the account ID (or the label) of the sender?")
             .interact_text()
-            .unwrap()
+            .unwrap();
+        match crate::common::resolve_account_id(&sender_account_id) {
+            Ok(sender_account_id) => sender_account_id,
+            Err(err) => {
+                eprintln!("{}", err);
+                Sender::input_sender_account_id()
+            }
+        }
     }
 }
 
 impl From<CliSender> for Sender {
     fn from(item: CliSender) -> Self {
-        let sender_account_id: String = match item.sender_account_id {
-            Some(cli_sender_account_id) => cli_sender_account_id,
+        let sender_account_id: String = match item
+            .sender_account_id
+            .map(|cli_sender_account_id| crate::common::resolve_account_id(&cli_sender_account_id))
+        {
+            Some(Ok(cli_sender_account_id)) => cli_sender_account_id,
+            Some(Err(err)) => {
+                eprintln!("{}", err);
+                Sender::input_sender_account_id()
+            }
             None => Sender::input_sender_account_id(),
         };
         let send_to: SendTo = match item.send_to {
@@ -6,8 +6,16 @@ pub mod sign_private_key;
 use sign_private_key::{CliSignPrivateKey, SignPrivateKey};
 pub mod sign_keychain;
 use sign_keychain::{CliSignKeychain, SignKeychain};
+pub mod sign_key_file;
+use sign_key_file::{CliSignKeyFile, SignKeyFile};
+pub mod sign_external;
+use sign_external::{CliSignExternal, SignExternal};
+pub mod sign_remote;
+use sign_remote::{CliSignRemote, SignRemote};
 pub mod sign_manually;
 use sign_manually::{CliSignManually, SignManually};
+pub mod sign_with_wallet;
+use sign_with_wallet::{CliSignWithWallet, SignWithWallet};
 
 #[derive(Debug, EnumDiscriminants)]
 #[strum_discriminants(derive(EnumMessage, EnumIter))]
@@ -18,6 +26,22 @@ pub enum SignTransaction {
     SignPrivateKey(SignPrivateKey),
     #[strum_discriminants(strum(message = "Yes, I want to sign the transaction with keychain"))]
     SignKeychain(SignKeychain),
+    #[strum_discriminants(strum(
+        message = "Yes, I want to sign the transaction with the near-cli JS key file"
+    ))]
+    SignKeyFile(SignKeyFile),
+    #[strum_discriminants(strum(
+        message = "Yes, I want to sign the transaction with an external signer (HSM, KMS)"
+    ))]
+    SignExternal(SignExternal),
+    #[strum_discriminants(strum(
+        message = "Yes, I want to sign the transaction with a remote signer (`signer serve`)"
+    ))]
+    SignRemote(SignRemote),
+    #[strum_discriminants(strum(
+        message = "Yes, I want to sign the transaction with the web wallet"
+    ))]
+    SignWithWallet(SignWithWallet),
     #[strum_discriminants(strum(
         message = "No, I want to construct the transaction and sign it somewhere else"
     ))]
@@ -28,6 +52,10 @@ pub enum SignTransaction {
 pub enum CliSignTransaction {
     SignPrivateKey(CliSignPrivateKey),
     SignKeychain(CliSignKeychain),
+    SignKeyFile(CliSignKeyFile),
+    SignExternal(CliSignExternal),
+    SignRemote(CliSignRemote),
+    SignWithWallet(CliSignWithWallet),
     SignManually(CliSignManually),
 }
 
@@ -37,19 +65,33 @@ impl SignTransaction {
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         selected_server_url: Option<url::Url>,
     ) {
-        println!("SignTransaction process: self:       {:?}", &self);
-        println!(
-            "SignTransaction process: prepopulated_unsigned_transaction:       {:?}",
-            &prepopulated_unsigned_transaction
-        );
         match self {
             SignTransaction::SignPrivateKey(keys) => {
                 keys.process(prepopulated_unsigned_transaction, selected_server_url)
                     .await
             }
             SignTransaction::SignKeychain(chain) => {
-                println!("Сейчас ведется доработка данного модуля")
-                // chain.process(prepopulated_unsigned_transaction, selected_server_url)
+                chain
+                    .process(prepopulated_unsigned_transaction, selected_server_url)
+                    .await
+            }
+            SignTransaction::SignKeyFile(key_file) => {
+                key_file
+                    .process(prepopulated_unsigned_transaction, selected_server_url)
+                    .await
+            }
+            SignTransaction::SignExternal(external) => {
+                external
+                    .process(prepopulated_unsigned_transaction, selected_server_url)
+                    .await
+            }
+            SignTransaction::SignRemote(remote) => {
+                remote
+                    .process(prepopulated_unsigned_transaction, selected_server_url)
+                    .await
+            }
+            SignTransaction::SignWithWallet(wallet) => {
+                wallet.process(prepopulated_unsigned_transaction, selected_server_url)
             }
             SignTransaction::SignManually(args_manually) => {
                 args_manually.process(prepopulated_unsigned_transaction, selected_server_url)
@@ -57,7 +99,7 @@ impl SignTransaction {
         }
     }
     pub fn choose_sign_option() -> Self {
-        println!();
+        eprintln!();
         let variants = SignTransactionDiscriminants::iter().collect::<Vec<_>>();
         let sign_options = variants
             .iter()
@@ -74,18 +116,81 @@ impl SignTransaction {
                 SignTransaction::SignPrivateKey(SignPrivateKey {
                     signer_public_key: SignPrivateKey::signer_public_key(),
                     signer_secret_key: SignPrivateKey::signer_secret_key(),
+                    save_to: None,
                 })
             }
             SignTransactionDiscriminants::SignKeychain => {
-                // SignTransaction::SignKeychain(SignKeychain{key_chain: SignKeychain::input_key_chain()})
-                panic!("This module is under development")
+                SignTransaction::SignKeychain(SignKeychain {
+                    signer_public_key: None,
+                    save_to: None,
+                })
+            }
+            SignTransactionDiscriminants::SignKeyFile => {
+                SignTransaction::SignKeyFile(SignKeyFile {
+                    key_file: None,
+                    save_to: None,
+                })
+            }
+            SignTransactionDiscriminants::SignExternal => {
+                SignTransaction::SignExternal(SignExternal {
+                    command: SignExternal::input_command(),
+                    signer_public_key: SignExternal::input_signer_public_key(),
+                    save_to: None,
+                })
+            }
+            SignTransactionDiscriminants::SignRemote => SignTransaction::SignRemote(SignRemote {
+                signer_url: SignRemote::input_signer_url(),
+                token: SignRemote::input_token(),
+                signer_public_key: None,
+                save_to: None,
+            }),
+            SignTransactionDiscriminants::SignWithWallet => {
+                SignTransaction::SignWithWallet(SignWithWallet { wallet_url: None })
             }
             SignTransactionDiscriminants::SignManually => {
-                SignTransaction::SignManually(SignManually {})
+                SignTransaction::SignManually(SignManually {
+                    signer_public_key: SignManually::input_signer_public_key(),
+                    save_to: SignManually::input_save_to(),
+                    qr: false,
+                    qr_png: None,
+                })
             }
             _ => unreachable!("Error"),
         }
     }
+    pub fn choose_sign_option_for(signer_id: &str) -> Self {
+        match crate::config::Config::load().default_signers.get(signer_id) {
+            Some(default_signer) => {
+                eprintln!(
+                    "Using the default signing option for <{}> from the config: {:?} (pass --choose-signer to choose another one)",
+                    signer_id, default_signer
+                );
+                SignTransaction::from(default_signer.clone())
+            }
+            None => SignTransaction::choose_sign_option(),
+        }
+    }
+}
+
+impl From<crate::config::DefaultSigner> for SignTransaction {
+    fn from(item: crate::config::DefaultSigner) -> Self {
+        match item {
+            crate::config::DefaultSigner::SignKeychain => {
+                SignTransaction::SignKeychain(SignKeychain {
+                    signer_public_key: None,
+                    save_to: None,
+                })
+            }
+            crate::config::DefaultSigner::SignManually => {
+                SignTransaction::SignManually(SignManually {
+                    signer_public_key: None,
+                    save_to: None,
+                    qr: false,
+                    qr_png: None,
+                })
+            }
+        }
+    }
 }
 
 impl From<CliSignTransaction> for SignTransaction {
@@ -99,6 +204,22 @@ impl From<CliSignTransaction> for SignTransaction {
                 let key_chain = SignKeychain::from(cli_key_chain);
                 SignTransaction::SignKeychain(key_chain)
             }
+            CliSignTransaction::SignKeyFile(cli_key_file) => {
+                let key_file = SignKeyFile::from(cli_key_file);
+                SignTransaction::SignKeyFile(key_file)
+            }
+            CliSignTransaction::SignExternal(cli_external) => {
+                let external = SignExternal::from(cli_external);
+                SignTransaction::SignExternal(external)
+            }
+            CliSignTransaction::SignRemote(cli_remote) => {
+                let remote = SignRemote::from(cli_remote);
+                SignTransaction::SignRemote(remote)
+            }
+            CliSignTransaction::SignWithWallet(cli_wallet) => {
+                let wallet = SignWithWallet::from(cli_wallet);
+                SignTransaction::SignWithWallet(wallet)
+            }
             CliSignTransaction::SignManually(cli_manually) => {
                 let manually = SignManually::from(cli_manually);
                 SignTransaction::SignManually(manually)
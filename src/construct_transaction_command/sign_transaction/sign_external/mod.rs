@@ -0,0 +1,265 @@
+use dialoguer::Input;
+use near_primitives::borsh::BorshSerialize;
+use std::io::Write;
+use std::str::FromStr;
+use structopt::StructOpt;
+
+/// Sign with an external program (e.g. an HSM or KMS client): it gets the
+/// borsh-serialized transaction on stdin, has to sign its sha256 hash, and
+/// prints the signature (`ed25519:...` or `secp256k1:...`) on stdout
+#[derive(Debug)]
+pub struct SignExternal {
+    pub command: String,
+    pub signer_public_key: String,
+    /// Offline only: write the signed transaction to this file instead of printing it
+    pub save_to: Option<std::path::PathBuf>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliSignExternal {
+    /// The signer command, run with `sh -c` (`external-signer-command` in the config by default)
+    #[structopt(long)]
+    command: Option<String>,
+    #[structopt(long)]
+    signer_public_key: Option<String>,
+    #[structopt(long)]
+    save_to: Option<std::path::PathBuf>,
+}
+
+impl From<CliSignExternal> for SignExternal {
+    fn from(item: CliSignExternal) -> Self {
+        let command = match item.command {
+            Some(cli_command) => cli_command,
+            None => SignExternal::input_command(),
+        };
+        let signer_public_key = match item.signer_public_key {
+            Some(cli_signer_public_key) => cli_signer_public_key,
+            None => SignExternal::input_signer_public_key(),
+        };
+        SignExternal {
+            command,
+            signer_public_key,
+            save_to: item.save_to,
+        }
+    }
+}
+
+impl SignExternal {
+    pub async fn process(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        selected_server_url: Option<url::Url>,
+    ) {
+        let public_key = match crate::common::parse_public_key(&self.signer_public_key) {
+            Ok(public_key) => public_key,
+            Err(err) => return eprintln!("{}", err),
+        };
+        let _access_key_lock = match lock_signer_access_key(
+            &prepopulated_unsigned_transaction,
+            &public_key,
+            selected_server_url.as_ref(),
+        ) {
+            Ok(access_key_lock) => access_key_lock,
+            Err(err) => return eprintln!("{}", err),
+        };
+        let unsigned_transaction = match transaction_with_nonce(
+            prepopulated_unsigned_transaction,
+            &public_key,
+            selected_server_url.as_ref(),
+        )
+        .await
+        {
+            Ok(unsigned_transaction) => unsigned_transaction,
+            Err(err) => return eprintln!("{}", err),
+        };
+        let signature = match self.run_command(&unsigned_transaction) {
+            Ok(signature) => signature,
+            Err(err) => return eprintln!("Error signing with `{}`:  {}", self.command, err),
+        };
+        if !signature.verify(unsigned_transaction.get_hash().as_ref(), &public_key) {
+            return eprintln!(
+                "The external signer returned a signature that does not match {}",
+                public_key
+            );
+        }
+        let signed_transaction =
+            near_primitives::transaction::SignedTransaction::new(signature, unsigned_transaction);
+        if let Err(err) = send_or_print(
+            signed_transaction,
+            selected_server_url,
+            self.save_to.as_deref(),
+        )
+        .await
+        {
+            eprintln!("{}", err);
+        }
+    }
+    fn run_command(
+        &self,
+        unsigned_transaction: &near_primitives::transaction::Transaction,
+    ) -> Result<near_crypto::Signature, String> {
+        let mut child = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .env("NEAR_SIGNER_ID", &unsigned_transaction.signer_id)
+            .env("NEAR_PUBLIC_KEY", &self.signer_public_key)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .map_err(|err| format!("{:?}", err))?;
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(
+                &unsigned_transaction
+                    .try_to_vec()
+                    .expect("Transaction is not expected to fail on serialization"),
+            )
+            .map_err(|err| format!("{:?}", err))?;
+        let output = child
+            .wait_with_output()
+            .map_err(|err| format!("{:?}", err))?;
+        if !output.status.success() {
+            return Err(format!("the command exited with {}", output.status));
+        }
+        let signature = String::from_utf8_lossy(&output.stdout);
+        near_crypto::Signature::from_str(signature.trim())
+            .map_err(|err| format!("invalid signature {:?}: {:?}", signature.trim(), err))
+    }
+    pub fn input_command() -> String {
+        if let Some(command) = crate::config::Config::load().external_signer_command {
+            eprintln!(
+                "Using the external signer command from the config: {}",
+                command
+            );
+            return command;
+        }
+        Input::new()
+            .with_prompt("Enter the command that signs the transaction")
+            .interact_text()
+            .unwrap()
+    }
+    pub fn input_signer_public_key() -> String {
+        crate::public_key_selector::input_public_key("Enter the public key of the external signer")
+    }
+}
+
+/// Online, locks the access key until the transaction is sent, as
+/// `SignPrivateKey::send_online` does, so parallel processes signing with the
+/// same key do not take the same nonce
+pub fn lock_signer_access_key(
+    prepopulated_unsigned_transaction: &near_primitives::transaction::Transaction,
+    public_key: &near_crypto::PublicKey,
+    selected_server_url: Option<&url::Url>,
+) -> Result<Option<crate::common::FileLock>, String> {
+    match selected_server_url {
+        Some(selected_server_url) => crate::common::lock_access_key(
+            &crate::common::network_dir_name(selected_server_url),
+            &prepopulated_unsigned_transaction.signer_id,
+            &public_key.to_string(),
+        )
+        .map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Sets the public key and, online, the next nonce and a recent block hash
+pub async fn transaction_with_nonce(
+    prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+    public_key: &near_crypto::PublicKey,
+    selected_server_url: Option<&url::Url>,
+) -> Result<near_primitives::transaction::Transaction, String> {
+    let selected_server_url = match selected_server_url {
+        Some(selected_server_url) => selected_server_url,
+        None => {
+            return Ok(near_primitives::transaction::Transaction {
+                public_key: public_key.clone(),
+                ..prepopulated_unsigned_transaction
+            })
+        }
+    };
+    let online_signer_access_key_response =
+        near_jsonrpc_client::new_client(selected_server_url.as_str())
+            .query(near_primitives::rpc::RpcQueryRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+                request: near_primitives::views::QueryRequest::ViewAccessKey {
+                    account_id: prepopulated_unsigned_transaction.signer_id.clone(),
+                    public_key: public_key.clone(),
+                },
+            })
+            .await
+            .map_err(|err| format!("Error online_signer_access_key_response:   {:?}", &err))?;
+    let current_nonce =
+        if let near_primitives::views::QueryResponseKind::AccessKey(online_signer_access_key) =
+            online_signer_access_key_response.kind
+        {
+            online_signer_access_key.nonce
+        } else {
+            return Err("Error current_nonce".to_string());
+        };
+    Ok(near_primitives::transaction::Transaction {
+        public_key: public_key.clone(),
+        block_hash: online_signer_access_key_response.block_hash,
+        nonce: current_nonce + 1,
+        ..prepopulated_unsigned_transaction
+    })
+}
+
+/// Broadcasts the transaction online, prints (or saves) it offline; an error
+/// if it could not be sent or failed
+pub async fn send_or_print(
+    signed_transaction: near_primitives::transaction::SignedTransaction,
+    selected_server_url: Option<url::Url>,
+    save_to: Option<&std::path::Path>,
+) -> Result<(), String> {
+    let serialize_to_base64 = near_primitives::serialize::to_base64(
+        signed_transaction
+            .try_to_vec()
+            .expect("Transaction is not expected to fail on serialization"),
+    );
+    match selected_server_url {
+        None => {
+            match save_to {
+                Some(save_to) => crate::common::save_transaction(save_to, &serialize_to_base64),
+                None => println!("{}", &serialize_to_base64),
+            }
+            crate::common::emit_event(
+                "transaction_signed",
+                crate::common::offline_transaction_data(
+                    &signed_transaction.transaction,
+                    Some(&signed_transaction),
+                ),
+            );
+            crate::common::record_signed_transaction(&signed_transaction, &serialize_to_base64);
+            crate::common::print_offline_transaction_deadline(&signed_transaction.transaction);
+            Ok(())
+        }
+        Some(selected_server_url) => {
+            let transaction_hash = signed_transaction.get_hash().to_string();
+            crate::common::emit_event(
+                "transaction_submitted",
+                serde_json::json!({
+                    "transaction_hash": &transaction_hash,
+                    "rpc_url": selected_server_url.as_str(),
+                }),
+            );
+            let transaction_info = near_jsonrpc_client::new_client(selected_server_url.as_str())
+                .broadcast_tx_commit(serialize_to_base64)
+                .await
+                .map_err(|err| format!("Error transaction:  {:?}", &err))?;
+            crate::common::record_sent_transaction(&signed_transaction, &selected_server_url);
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&transaction_info).unwrap()
+            );
+            match &transaction_info.status {
+                near_primitives::views::FinalExecutionStatus::SuccessValue(_) => {
+                    eprintln!("{}", crate::style::success("Success"));
+                    Ok(())
+                }
+                status => Err(format!("The transaction failed: {:?}", status)),
+            }
+        }
+    }
+}
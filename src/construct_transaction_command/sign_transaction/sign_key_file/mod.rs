@@ -0,0 +1,80 @@
+use structopt::StructOpt;
+
+use super::sign_keychain::SignKeychain;
+use super::sign_private_key::SignPrivateKey;
+
+/// The JavaScript near-cli keeps one key per account in
+/// `~/.near-credentials/<network>/<account>.json`
+#[derive(serde::Deserialize)]
+struct KeyFile {
+    public_key: String,
+    #[serde(alias = "secret_key")]
+    private_key: String,
+}
+
+#[derive(Debug)]
+pub struct SignKeyFile {
+    pub key_file: Option<std::path::PathBuf>,
+    /// Offline only: write the signed transaction to this file instead of printing it
+    pub save_to: Option<std::path::PathBuf>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliSignKeyFile {
+    /// Read the key from this file instead of the near-cli JS credentials of the signer
+    #[structopt(long)]
+    key_file: Option<std::path::PathBuf>,
+    #[structopt(long)]
+    save_to: Option<std::path::PathBuf>,
+}
+
+impl From<CliSignKeyFile> for SignKeyFile {
+    fn from(item: CliSignKeyFile) -> Self {
+        SignKeyFile {
+            key_file: item.key_file,
+            save_to: item.save_to,
+        }
+    }
+}
+
+impl SignKeyFile {
+    pub async fn process(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        selected_server_url: Option<url::Url>,
+    ) {
+        let key_file_path = match self.key_file {
+            Some(key_file_path) => key_file_path,
+            None => {
+                let network_dir_name = match &selected_server_url {
+                    Some(selected_server_url) => {
+                        crate::common::network_dir_name(selected_server_url)
+                    }
+                    None => SignKeychain::input_network_dir_name(),
+                };
+                crate::common::get_credentials_home_dir()
+                    .join(network_dir_name)
+                    .join(format!(
+                        "{}.json",
+                        &prepopulated_unsigned_transaction.signer_id
+                    ))
+            }
+        };
+        let key_file: KeyFile = match std::fs::read_to_string(&key_file_path)
+            .map_err(|err| err.to_string())
+            .and_then(|buf| serde_json::from_str(&buf).map_err(|err| err.to_string()))
+        {
+            Ok(key_file) => key_file,
+            Err(err) => {
+                return eprintln!("Error reading the key file {:?}:  {}", &key_file_path, err)
+            }
+        };
+        SignPrivateKey {
+            signer_public_key: key_file.public_key,
+            signer_secret_key: key_file.private_key,
+            save_to: self.save_to,
+        }
+        .process(prepopulated_unsigned_transaction, selected_server_url)
+        .await
+    }
+}
@@ -1,36 +1,223 @@
-use dialoguer::{console::Term, theme::ColorfulTheme, Input, Select};
-use std::str::FromStr;
+use dialoguer::{theme::ColorfulTheme, Input, Select};
 use structopt::StructOpt;
-use strum::VariantNames;
-use strum_macros::{Display, EnumString, EnumVariantNames};
+
+use super::sign_private_key::SignPrivateKey;
 
 #[derive(Debug)]
 pub struct SignKeychain {
-    pub key_chain: String,
+    pub signer_public_key: Option<String>,
+    /// Offline only: write the signed transaction to this file instead of printing it
+    pub save_to: Option<std::path::PathBuf>,
 }
 
 #[derive(Debug, StructOpt)]
 pub struct CliSignKeychain {
     #[structopt(long)]
-    key_chain: Option<String>,
+    signer_public_key: Option<String>,
+    #[structopt(long)]
+    save_to: Option<std::path::PathBuf>,
 }
 
 impl SignKeychain {
-    pub fn process(
+    pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
-        _selected_server_url: Option<url::Url>,
+        selected_server_url: Option<url::Url>,
     ) {
-        println!("SignKeychain process: self:       {:?}", &self);
-        println!(
-            "SignKeychain process: prepopulated_unsigned_transaction:       {:?}",
-            &prepopulated_unsigned_transaction
+        if let Err(err) = self
+            .sign_and_send(prepopulated_unsigned_transaction, selected_server_url)
+            .await
+        {
+            eprintln!("{}", err);
+        }
+    }
+    /// Signs with the agent or a key of the keychain and, online, submits the
+    /// transaction; an error if it was not signed, not sent or failed
+    pub async fn sign_and_send(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        selected_server_url: Option<url::Url>,
+    ) -> Result<(), String> {
+        let network_dir_name = match &selected_server_url {
+            Some(selected_server_url) => crate::common::network_dir_name(selected_server_url),
+            None => SignKeychain::input_network_dir_name(),
+        };
+        if let Some(mut public_keys) = crate::agent_command::agent_public_keys(
+            &network_dir_name,
+            &prepopulated_unsigned_transaction.signer_id,
+        ) {
+            if let Some(signer_public_key) = &self.signer_public_key {
+                public_keys.retain(|public_key| public_key == signer_public_key);
+            }
+            if !public_keys.is_empty() {
+                eprintln!("Signing with the agent");
+                return self
+                    .process_with_agent(
+                        prepopulated_unsigned_transaction,
+                        selected_server_url,
+                        &network_dir_name,
+                        public_keys,
+                    )
+                    .await;
+            }
+        }
+        let mut access_keys = crate::common::read_access_keys_from_keychain(
+            &network_dir_name,
+            &prepopulated_unsigned_transaction.signer_id,
         );
+        if let Some(signer_public_key) = &self.signer_public_key {
+            access_keys.retain(|access_key| &access_key.public_key == signer_public_key);
+        }
+        let access_key = match access_keys.len() {
+            0 => {
+                return Err(format!(
+                    "There are no access keys for <{}> in the keychain {:?}",
+                    &prepopulated_unsigned_transaction.signer_id,
+                    crate::common::get_credentials_home_dir().join(&network_dir_name)
+                ))
+            }
+            1 => access_keys.remove(0),
+            _ => {
+                let items = access_keys
+                    .iter()
+                    .map(|access_key| match &access_key.label {
+                        Some(label) => format!("{} ({})", access_key.public_key, label),
+                        None => access_key.public_key.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                let selection = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Select the access key to sign the transaction with")
+                    .items(&items)
+                    .default(0)
+                    .interact()
+                    .unwrap();
+                access_keys.remove(selection)
+            }
+        };
+        let selected_server_url = match selected_server_url {
+            Some(selected_server_url) => selected_server_url,
+            None => {
+                return SignPrivateKey {
+                    signer_public_key: access_key.public_key,
+                    signer_secret_key: access_key.private_key,
+                    save_to: self.save_to,
+                }
+                .sign_and_send(prepopulated_unsigned_transaction, None)
+                .await
+            }
+        };
+        let mut access_key = access_key;
+        let mut tried_public_keys = vec![];
+        loop {
+            let sign_private_key = SignPrivateKey {
+                signer_public_key: access_key.public_key,
+                signer_secret_key: access_key.private_key,
+                save_to: None,
+            };
+            let err = match sign_private_key
+                .send_online(
+                    prepopulated_unsigned_transaction.clone(),
+                    &selected_server_url,
+                )
+                .await
+            {
+                Ok(()) => return Ok(()),
+                Err(err) => err,
+            };
+            if !crate::common::is_access_key_permission_error(&err) {
+                return Err(err);
+            }
+            eprintln!("{}", err);
+            tried_public_keys.push(sign_private_key.signer_public_key);
+            let mut other_access_keys = crate::common::read_access_keys_from_keychain(
+                &network_dir_name,
+                &prepopulated_unsigned_transaction.signer_id,
+            );
+            other_access_keys
+                .retain(|access_key| !tried_public_keys.contains(&access_key.public_key));
+            access_key = match SignKeychain::choose_other_access_key(other_access_keys) {
+                Some(access_key) => access_key,
+                None => return Err("The transaction has not been signed".to_string()),
+            };
+        }
     }
 
-    pub fn input_key_chain() -> String {
+    /// Offers to re-sign with another key after the key was not allowed to
+    /// sign the transaction; `None` if there is none or the user gives up
+    fn choose_other_access_key(
+        mut access_keys: Vec<crate::common::KeychainAccessKey>,
+    ) -> Option<crate::common::KeychainAccessKey> {
+        if access_keys.is_empty() {
+            return None;
+        }
+        let mut items = access_keys
+            .iter()
+            .map(|access_key| match &access_key.label {
+                Some(label) => format!("Re-sign with {} ({})", access_key.public_key, label),
+                None => format!("Re-sign with {}", access_key.public_key),
+            })
+            .collect::<Vec<_>>();
+        items.push("Give up".to_string());
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("The key is not allowed to sign this transaction, retry with another key?")
+            .items(&items)
+            .default(0)
+            .interact()
+            .unwrap();
+        if selection < access_keys.len() {
+            Some(access_keys.remove(selection))
+        } else {
+            None
+        }
+    }
+
+    async fn process_with_agent(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        selected_server_url: Option<url::Url>,
+        network_dir_name: &str,
+        mut public_keys: Vec<String>,
+    ) -> Result<(), String> {
+        let public_key = if public_keys.len() == 1 {
+            public_keys.remove(0)
+        } else {
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select the access key to sign the transaction with")
+                .items(&public_keys)
+                .default(0)
+                .interact()
+                .unwrap();
+            public_keys.remove(selection)
+        };
+        let public_key = crate::common::parse_public_key(&public_key)?;
+        let _access_key_lock = super::sign_external::lock_signer_access_key(
+            &prepopulated_unsigned_transaction,
+            &public_key,
+            selected_server_url.as_ref(),
+        )?;
+        let unsigned_transaction = super::sign_external::transaction_with_nonce(
+            prepopulated_unsigned_transaction,
+            &public_key,
+            selected_server_url.as_ref(),
+        )
+        .await?;
+        let signature =
+            crate::agent_command::agent_signature(network_dir_name, &unsigned_transaction)
+                .map_err(|err| format!("The agent refused to sign:  {}", err))?;
+        let signed_transaction =
+            near_primitives::transaction::SignedTransaction::new(signature, unsigned_transaction);
+        super::sign_external::send_or_print(
+            signed_transaction,
+            selected_server_url,
+            self.save_to.as_deref(),
+        )
+        .await
+    }
+
+    pub fn input_network_dir_name() -> String {
         Input::new()
-            .with_prompt("Enter the key chain")
+            .with_prompt("Which network keychain should be used? (e.g. testnet)")
+            .default("testnet".to_string())
             .interact_text()
             .unwrap()
     }
@@ -38,11 +225,9 @@ impl SignKeychain {
 
 impl From<CliSignKeychain> for SignKeychain {
     fn from(item: CliSignKeychain) -> Self {
-        println!("***********.  cli sign alternative {:?}", &item);
-        let key_chain: String = match item.key_chain {
-            Some(cli_key_chain) => cli_key_chain,
-            None => SignKeychain::input_key_chain(),
-        };
-        SignKeychain { key_chain }
+        SignKeychain {
+            signer_public_key: item.signer_public_key,
+            save_to: item.save_to,
+        }
     }
 }
@@ -1,38 +1,122 @@
+use dialoguer::Input;
 use near_primitives::borsh::BorshSerialize;
 use structopt::StructOpt;
 
 #[derive(Debug)]
-pub struct SignManually {}
+pub struct SignManually {
+    /// The key (ED25519 or secp256k1) that is going to sign the transaction;
+    /// `None` keeps the public key of the prepared transaction
+    pub signer_public_key: Option<near_crypto::PublicKey>,
+    /// Write the unsigned transaction to this file instead of printing it
+    pub save_to: Option<std::path::PathBuf>,
+    /// Render the unsigned transaction as QR codes for an air-gapped signer
+    pub qr: bool,
+    pub qr_png: Option<std::path::PathBuf>,
+}
 
 #[derive(Debug, StructOpt)]
-pub struct CliSignManually {}
+pub struct CliSignManually {
+    #[structopt(long, parse(try_from_str = crate::common::parse_public_key))]
+    signer_public_key: Option<near_crypto::PublicKey>,
+    #[structopt(long)]
+    save_to: Option<std::path::PathBuf>,
+    /// Print the unsigned transaction as QR codes in the terminal
+    #[structopt(long)]
+    qr: bool,
+    /// Save the QR codes as PNG images (`<path stem>-<index>.png` when there are several)
+    #[structopt(long)]
+    qr_png: Option<std::path::PathBuf>,
+}
 
 impl From<CliSignManually> for SignManually {
-    fn from(_: CliSignManually) -> Self {
-        SignManually {}
+    fn from(item: CliSignManually) -> Self {
+        let signer_public_key = match item.signer_public_key {
+            Some(cli_signer_public_key) => Some(cli_signer_public_key),
+            None => SignManually::input_signer_public_key(),
+        };
+        let save_to = match item.save_to {
+            Some(cli_save_to) => Some(cli_save_to),
+            None => SignManually::input_save_to(),
+        };
+        SignManually {
+            signer_public_key,
+            save_to,
+            qr: item.qr,
+            qr_png: item.qr_png,
+        }
     }
 }
 
 impl SignManually {
+    pub fn input_signer_public_key() -> Option<near_crypto::PublicKey> {
+        let signer_public_key: String = Input::new()
+            .with_prompt(
+                "Enter the public key that will sign the transaction (leave empty to skip)",
+            )
+            .allow_empty(true)
+            .interact_text()
+            .unwrap();
+        if signer_public_key.trim().is_empty() {
+            return None;
+        }
+        match crate::common::parse_public_key(&signer_public_key) {
+            Ok(signer_public_key) => Some(signer_public_key),
+            Err(err) => {
+                eprintln!("{}", err);
+                SignManually::input_signer_public_key()
+            }
+        }
+    }
+    pub fn input_save_to() -> Option<std::path::PathBuf> {
+        let save_to: String = Input::new()
+            .with_prompt(
+                "Save the transaction to a file? (enter the path, or leave empty to print it)",
+            )
+            .allow_empty(true)
+            .interact_text()
+            .unwrap();
+        if save_to.trim().is_empty() {
+            None
+        } else {
+            Some(std::path::PathBuf::from(save_to.trim()))
+        }
+    }
     pub fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         _selected_server_url: Option<url::Url>,
     ) {
-        println!();
-        println!(
-            "SignManually process: prepopulated_unsigned_transaction:\n {:#?}",
+        let prepopulated_unsigned_transaction = match self.signer_public_key {
+            Some(public_key) => near_primitives::transaction::Transaction {
+                public_key,
+                ..prepopulated_unsigned_transaction
+            },
+            None => prepopulated_unsigned_transaction,
+        };
+        eprintln!();
+        eprintln!(
+            "Unsigned transaction:\n {:#?}",
             &prepopulated_unsigned_transaction
         );
-        println!();
+        eprintln!();
         let serialize_to_base64 = near_primitives::serialize::to_base64(
             prepopulated_unsigned_transaction
                 .try_to_vec()
                 .expect("Transaction is not expected to fail on serialization"),
         );
-        println!(
-            "---  serialize_to_base64:   --- \n   {:#?}",
-            &serialize_to_base64
-        )
+        match &self.save_to {
+            Some(save_to) => crate::common::save_transaction(save_to, &serialize_to_base64),
+            None => println!("{}", &serialize_to_base64),
+        }
+        if self.qr {
+            crate::qr::print_qr_codes(&serialize_to_base64);
+        }
+        if let Some(qr_png) = &self.qr_png {
+            crate::qr::save_qr_pngs(&serialize_to_base64, qr_png);
+        }
+        crate::common::emit_event(
+            "transaction_prepared",
+            crate::common::offline_transaction_data(&prepopulated_unsigned_transaction, None),
+        );
     }
 }
@@ -1,12 +1,32 @@
-use dialoguer::Input;
+use dialoguer::{Input, Password};
 use near_primitives::borsh::BorshSerialize;
 use std::str::FromStr;
 use structopt::StructOpt;
 
-#[derive(Debug)]
+pub const SECRET_KEY_ENV_VAR: &str = "NEAR_CLI_SECRET_KEY";
+
+#[cfg(not(unix))]
+const FD_UNSUPPORTED: &str =
+    "Reading the secret key from a file descriptor is only supported on Unix";
+
 pub struct SignPrivateKey {
     pub signer_public_key: String,
     pub signer_secret_key: String,
+    /// Offline only: write the signed transaction to this file instead of printing it
+    pub save_to: Option<std::path::PathBuf>,
+}
+
+impl std::fmt::Debug for SignPrivateKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignPrivateKey")
+            .field("signer_public_key", &self.signer_public_key)
+            .field(
+                "signer_secret_key",
+                &crate::common::redact_secret(&self.signer_secret_key),
+            )
+            .field("save_to", &self.save_to)
+            .finish()
+    }
 }
 
 #[derive(Debug, StructOpt)]
@@ -15,28 +35,42 @@ pub struct CliSignPrivateKey {
     signer_public_key: Option<String>,
     #[structopt(long)]
     signer_secret_key: Option<String>,
+    /// Read the secret key from this file descriptor (e.g. `3` with `3<secret.key`)
+    #[structopt(long, parse(try_from_str = parse_secret_key_fd))]
+    signer_secret_key_fd: Option<i32>,
+    #[structopt(long)]
+    save_to: Option<std::path::PathBuf>,
 }
 
 impl SignPrivateKey {
-    fn rpc_client(self, selected_server_url: &str) -> near_jsonrpc_client::JsonRpcClient {
-        near_jsonrpc_client::new_client(&selected_server_url)
-    }
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         selected_server_url: Option<url::Url>,
     ) {
-        println!("SignPrivateKey process: self:\n       {:?}", &self);
-        println!(
-            "SignPrivateKey process: prepopulated_unsigned_transaction:\n       {:?}",
-            &prepopulated_unsigned_transaction
-        );
-        println!(
-            "SignPrivateKey process: selected_server_url:\n       {:?}",
-            &selected_server_url
-        );
-        let public_key = near_crypto::PublicKey::from_str(&self.signer_public_key).unwrap();
-        let signer_secret_key = near_crypto::SecretKey::from_str(&self.signer_secret_key).unwrap();
+        if let Err(err) = self
+            .sign_and_send(prepopulated_unsigned_transaction, selected_server_url)
+            .await
+        {
+            eprintln!("{}", err);
+        }
+    }
+    /// Signs the transaction and, online, submits it; offline it prints or saves it
+    pub async fn sign_and_send(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        selected_server_url: Option<url::Url>,
+    ) -> Result<(), String> {
+        let public_key = crate::common::parse_public_key(&self.signer_public_key)?;
+        let signer_secret_key = near_crypto::SecretKey::from_str(&self.signer_secret_key)
+            .map_err(|err| format!("Invalid secret key:  {:?}", err))?;
+        // An ED25519 public key with a secp256k1 secret key (or vice versa) gives a bad signature
+        if signer_secret_key.public_key() != public_key {
+            return Err(format!(
+                "The secret key does not belong to the public key {} (check the key types)",
+                &self.signer_public_key
+            ));
+        }
         match selected_server_url {
             None => {
                 let unsigned_transaction = near_primitives::transaction::Transaction {
@@ -46,76 +80,117 @@ impl SignPrivateKey {
                 let signature = signer_secret_key.sign(unsigned_transaction.get_hash().as_ref());
                 let signed_transaction = near_primitives::transaction::SignedTransaction::new(
                     signature,
-                    unsigned_transaction,
+                    unsigned_transaction.clone(),
                 );
                 let serialize_to_base64 = near_primitives::serialize::to_base64(
                     signed_transaction
                         .try_to_vec()
                         .expect("Transaction is not expected to fail on serialization"),
                 );
-                println!(
-                    "---  serialize_to_base64:   --- \n   {:#?}",
-                    &serialize_to_base64
-                )
+                match &self.save_to {
+                    Some(save_to) => crate::common::save_transaction(save_to, &serialize_to_base64),
+                    None => println!("{}", &serialize_to_base64),
+                }
+                crate::common::emit_event(
+                    "transaction_signed",
+                    crate::common::offline_transaction_data(
+                        &unsigned_transaction,
+                        Some(&signed_transaction),
+                    ),
+                );
+                crate::common::record_signed_transaction(&signed_transaction, &serialize_to_base64);
+                crate::common::print_offline_transaction_deadline(&unsigned_transaction);
+                Ok(())
             }
             Some(selected_server_url) => {
-                let online_signer_access_key_response = self
-                    .rpc_client(&selected_server_url.as_str())
-                    .query(near_primitives::rpc::RpcQueryRequest {
-                        block_reference: near_primitives::types::Finality::Final.into(),
-                        request: near_primitives::views::QueryRequest::ViewAccessKey {
-                            account_id: prepopulated_unsigned_transaction.signer_id.clone(),
-                            public_key: public_key.clone(),
-                        },
-                    })
+                self.send_online(prepopulated_unsigned_transaction, &selected_server_url)
                     .await
-                    .map_err(|err| {
-                        println!("Error online_signer_access_key_response:   {:?}", &err)
-                    })
-                    .unwrap();
-                println!(
-                    "online_signer_access_key_response:\n   {:?}",
-                    &online_signer_access_key_response
-                );
-                let current_nonce = if let near_primitives::views::QueryResponseKind::AccessKey(
-                    online_signer_access_key,
-                ) = online_signer_access_key_response.kind
-                {
-                    online_signer_access_key.nonce
-                } else {
-                    return println!("Error current_nonce");
-                };
-                println!("current_nonce:  {:?}", &current_nonce);
-                let unsigned_transaction = near_primitives::transaction::Transaction {
-                    public_key,
-                    block_hash: online_signer_access_key_response.block_hash,
-                    nonce: current_nonce + 1,
-                    ..prepopulated_unsigned_transaction
-                };
-                println!("unsigned_transaction:  {:#?}", &unsigned_transaction);
-                let signature = signer_secret_key.sign(unsigned_transaction.get_hash().as_ref());
-                let signed_transaction = near_primitives::transaction::SignedTransaction::new(
-                    signature,
-                    unsigned_transaction,
-                );
-                println!(
-                    "---  Signed transaction:   ---    {:#?}",
-                    &signed_transaction
-                );
-                let transaction_info =
-                    near_jsonrpc_client::new_client(&selected_server_url.as_str())
-                        .broadcast_tx_commit(near_primitives::serialize::to_base64(
-                            signed_transaction
-                                .try_to_vec()
-                                .expect("Transaction is not expected to fail on serialization"),
-                        ))
-                        .await
-                        .map_err(|err| println!("Error transaction:  {:?}", &err))
-                        .unwrap();
-                println!("Success: {:#?}", transaction_info);
             }
         }
     }
+    /// Signs the transaction with the current nonce of the key and submits it
+    pub async fn send_online(
+        &self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        selected_server_url: &url::Url,
+    ) -> Result<(), String> {
+        let public_key = crate::common::parse_public_key(&self.signer_public_key)?;
+        let signer_secret_key = near_crypto::SecretKey::from_str(&self.signer_secret_key)
+            .map_err(|err| format!("Invalid secret key:  {:?}", err))?;
+        // Parallel processes signing with the same key would reuse the nonce
+        let _access_key_lock = crate::common::lock_access_key(
+            &crate::common::network_dir_name(selected_server_url),
+            &prepopulated_unsigned_transaction.signer_id,
+            &self.signer_public_key,
+        )?;
+        let online_signer_access_key_response =
+            near_jsonrpc_client::new_client(selected_server_url.as_str())
+                .query(near_primitives::rpc::RpcQueryRequest {
+                    block_reference: near_primitives::types::Finality::Final.into(),
+                    request: near_primitives::views::QueryRequest::ViewAccessKey {
+                        account_id: prepopulated_unsigned_transaction.signer_id.clone(),
+                        public_key: public_key.clone(),
+                    },
+                })
+                .await
+                .map_err(|err| format!("Error online_signer_access_key_response:   {:?}", &err))?;
+        let current_nonce =
+            if let near_primitives::views::QueryResponseKind::AccessKey(online_signer_access_key) =
+                online_signer_access_key_response.kind
+            {
+                online_signer_access_key.nonce
+            } else {
+                return Err("Error current_nonce".to_string());
+            };
+        let unsigned_transaction = near_primitives::transaction::Transaction {
+            public_key,
+            block_hash: online_signer_access_key_response.block_hash,
+            nonce: current_nonce + 1,
+            ..prepopulated_unsigned_transaction
+        };
+        let signature = signer_secret_key.sign(unsigned_transaction.get_hash().as_ref());
+        let signed_transaction =
+            near_primitives::transaction::SignedTransaction::new(signature, unsigned_transaction);
+        let transaction_hash = signed_transaction.get_hash().to_string();
+        crate::common::emit_event(
+            "transaction_signed",
+            serde_json::json!({ "transaction_hash": &transaction_hash }),
+        );
+        crate::common::emit_event(
+            "transaction_submitted",
+            serde_json::json!({
+                "transaction_hash": &transaction_hash,
+                "rpc_url": selected_server_url.as_str(),
+            }),
+        );
+        let transaction_info = near_jsonrpc_client::new_client(selected_server_url.as_str())
+            .broadcast_tx_commit(near_primitives::serialize::to_base64(
+                signed_transaction
+                    .try_to_vec()
+                    .expect("Transaction is not expected to fail on serialization"),
+            ))
+            .await
+            .map_err(|err| format!("Error transaction:  {:?}", &err))?;
+        crate::common::record_sent_transaction(&signed_transaction, selected_server_url);
+        crate::common::emit_event(
+            "finality_reached",
+            serde_json::json!({
+                "transaction_hash": &transaction_hash,
+                "status": serde_json::to_value(&transaction_info.status).unwrap_or_default(),
+            }),
+        );
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&transaction_info).unwrap()
+        );
+        match &transaction_info.status {
+            near_primitives::views::FinalExecutionStatus::SuccessValue(_) => {
+                eprintln!("{}", crate::style::success("Success"));
+                Ok(())
+            }
+            status => Err(format!("The transaction failed: {:?}", status)),
+        }
+    }
     pub fn signer_public_key() -> String {
         Input::new()
             .with_prompt("enter sender's public key")
@@ -123,11 +198,61 @@ impl SignPrivateKey {
             .unwrap()
     }
     pub fn signer_secret_key() -> String {
-        Input::new()
+        if let Ok(env_secret_key) = std::env::var(SECRET_KEY_ENV_VAR) {
+            eprintln!("Using the secret key from ${}", SECRET_KEY_ENV_VAR);
+            return env_secret_key.trim().to_string();
+        }
+        Password::new()
             .with_prompt("enter sender's private key")
-            .interact_text()
+            .interact()
             .unwrap()
     }
+    #[cfg(unix)]
+    fn signer_secret_key_from_fd(fd: i32) -> Result<String, String> {
+        use std::io::Read;
+        use std::os::unix::io::FromRawFd;
+
+        let mut secret_key = String::new();
+        // parse_secret_key_fd has checked that the descriptor is open; it is
+        // borrowed, not closed, so a failed read leaves it as it was
+        let mut file = std::mem::ManuallyDrop::new(unsafe { std::fs::File::from_raw_fd(fd) });
+        file.read_to_string(&mut secret_key)
+            .map_err(|err| format!("Error reading the secret key from fd {}:  {}", fd, err))?;
+        match secret_key.trim() {
+            "" => Err(format!("The file descriptor {} gave no secret key", fd)),
+            secret_key => Ok(secret_key.to_string()),
+        }
+    }
+    #[cfg(not(unix))]
+    fn signer_secret_key_from_fd(_fd: i32) -> Result<String, String> {
+        Err(FD_UNSUPPORTED.to_string())
+    }
+}
+
+#[cfg(unix)]
+fn parse_secret_key_fd(fd: &str) -> Result<i32, String> {
+    let fd: i32 = fd
+        .parse()
+        .ok()
+        .filter(|fd| *fd >= 0)
+        .ok_or_else(|| format!("Invalid file descriptor: {}", fd))?;
+    // stdin, stdout and stderr are needed for the prompts and the output
+    if fd <= 2 {
+        return Err(format!(
+            "The file descriptor {} is one of stdin, stdout and stderr, use 3 or higher",
+            fd
+        ));
+    }
+    // Reading a descriptor that is not open would be undefined behaviour
+    if std::fs::metadata(format!("/dev/fd/{}", fd)).is_err() {
+        return Err(format!("The file descriptor {} is not open", fd));
+    }
+    Ok(fd)
+}
+
+#[cfg(not(unix))]
+fn parse_secret_key_fd(_fd: &str) -> Result<i32, String> {
+    Err(FD_UNSUPPORTED.to_string())
 }
 
 impl From<CliSignPrivateKey> for SignPrivateKey {
@@ -136,13 +261,23 @@ impl From<CliSignPrivateKey> for SignPrivateKey {
             Some(cli_public_key) => cli_public_key,
             None => SignPrivateKey::signer_public_key(),
         };
-        let signer_secret_key: String = match item.signer_secret_key {
-            Some(cli_secret_key) => cli_secret_key,
-            None => SignPrivateKey::signer_secret_key(),
+        let signer_secret_key: String = match (item.signer_secret_key, item.signer_secret_key_fd) {
+            (Some(cli_secret_key), _) => cli_secret_key,
+            (None, Some(cli_secret_key_fd)) => {
+                match SignPrivateKey::signer_secret_key_from_fd(cli_secret_key_fd) {
+                    Ok(secret_key) => secret_key,
+                    Err(err) => {
+                        eprintln!("{}", err);
+                        SignPrivateKey::signer_secret_key()
+                    }
+                }
+            }
+            (None, None) => SignPrivateKey::signer_secret_key(),
         };
         SignPrivateKey {
             signer_public_key,
             signer_secret_key,
+            save_to: item.save_to,
         }
     }
 }
@@ -0,0 +1,199 @@
+use dialoguer::{Input, Password};
+use near_primitives::borsh::BorshSerialize;
+use std::str::FromStr;
+use structopt::StructOpt;
+
+use crate::signer_command::serve_subcommand::TOKEN_ENV_VAR;
+
+/// Sign with the keychain of a `near-cli signer serve` on another machine
+pub struct SignRemote {
+    pub signer_url: url::Url,
+    pub token: String,
+    /// `None` picks the first key the remote signer has for the signer account
+    pub signer_public_key: Option<near_crypto::PublicKey>,
+    /// Offline only: write the signed transaction to this file instead of printing it
+    pub save_to: Option<std::path::PathBuf>,
+}
+
+impl std::fmt::Debug for SignRemote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignRemote")
+            .field("signer_url", &self.signer_url)
+            .field("token", &crate::common::redact_secret(&self.token))
+            .field("signer_public_key", &self.signer_public_key)
+            .field("save_to", &self.save_to)
+            .finish()
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliSignRemote {
+    /// `remote-signer-url` in the config by default
+    #[structopt(long)]
+    signer_url: Option<url::Url>,
+    #[structopt(long, env = TOKEN_ENV_VAR, hide_env_values = true)]
+    token: Option<String>,
+    #[structopt(long, parse(try_from_str = crate::common::parse_public_key))]
+    signer_public_key: Option<near_crypto::PublicKey>,
+    #[structopt(long)]
+    save_to: Option<std::path::PathBuf>,
+}
+
+impl From<CliSignRemote> for SignRemote {
+    fn from(item: CliSignRemote) -> Self {
+        let signer_url = match item.signer_url {
+            Some(cli_signer_url) => cli_signer_url,
+            None => SignRemote::input_signer_url(),
+        };
+        let token = match item.token {
+            Some(cli_token) => cli_token,
+            None => SignRemote::input_token(),
+        };
+        SignRemote {
+            signer_url,
+            token,
+            signer_public_key: item.signer_public_key,
+            save_to: item.save_to,
+        }
+    }
+}
+
+impl SignRemote {
+    pub async fn process(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        selected_server_url: Option<url::Url>,
+    ) {
+        let public_key = match &self.signer_public_key {
+            Some(public_key) => public_key.clone(),
+            None => match self
+                .remote_public_key(&prepopulated_unsigned_transaction.signer_id)
+                .await
+            {
+                Ok(public_key) => public_key,
+                Err(err) => return eprintln!("{}", err),
+            },
+        };
+        let _access_key_lock = match super::sign_external::lock_signer_access_key(
+            &prepopulated_unsigned_transaction,
+            &public_key,
+            selected_server_url.as_ref(),
+        ) {
+            Ok(access_key_lock) => access_key_lock,
+            Err(err) => return eprintln!("{}", err),
+        };
+        let unsigned_transaction = match super::sign_external::transaction_with_nonce(
+            prepopulated_unsigned_transaction,
+            &public_key,
+            selected_server_url.as_ref(),
+        )
+        .await
+        {
+            Ok(unsigned_transaction) => unsigned_transaction,
+            Err(err) => return eprintln!("{}", err),
+        };
+        let signature = match self.remote_signature(&unsigned_transaction).await {
+            Ok(signature) => signature,
+            Err(err) => return eprintln!("Error signing with {}:  {}", self.signer_url, err),
+        };
+        if !signature.verify(unsigned_transaction.get_hash().as_ref(), &public_key) {
+            return eprintln!(
+                "The remote signer returned a signature that does not match {}",
+                public_key
+            );
+        }
+        let signed_transaction =
+            near_primitives::transaction::SignedTransaction::new(signature, unsigned_transaction);
+        if let Err(err) = super::sign_external::send_or_print(
+            signed_transaction,
+            selected_server_url,
+            self.save_to.as_deref(),
+        )
+        .await
+        {
+            eprintln!("{}", err);
+        }
+    }
+    async fn remote_public_key(&self, signer_id: &str) -> Result<near_crypto::PublicKey, String> {
+        let url = self
+            .signer_url
+            .join(&format!("keys/{}", signer_id))
+            .map_err(|err| format!("{:?}", err))?;
+        let mut response = actix_web::client::Client::default()
+            .get(url.as_str())
+            .header("Authorization", format!("Bearer {}", self.token))
+            .send()
+            .await
+            .map_err(|err| format!("{:?}", err))?;
+        if !response.status().is_success() {
+            return Err(format!("{} responded with {}", url, response.status()));
+        }
+        let keys: serde_json::Value = response.json().await.map_err(|err| format!("{:?}", err))?;
+        let public_key = keys["public_keys"][0].as_str().ok_or_else(|| {
+            format!(
+                "The remote signer has no keys for <{}> (pass --signer-public-key?)",
+                signer_id
+            )
+        })?;
+        crate::common::parse_public_key(public_key)
+    }
+    async fn remote_signature(
+        &self,
+        unsigned_transaction: &near_primitives::transaction::Transaction,
+    ) -> Result<near_crypto::Signature, String> {
+        let url = self
+            .signer_url
+            .join("sign")
+            .map_err(|err| format!("{:?}", err))?;
+        let mut response = actix_web::client::Client::default()
+            .post(url.as_str())
+            .header("Authorization", format!("Bearer {}", self.token))
+            .send_json(&serde_json::json!({
+                "unsigned_transaction": near_primitives::serialize::to_base64(
+                    unsigned_transaction
+                        .try_to_vec()
+                        .expect("Transaction is not expected to fail on serialization"),
+                ),
+            }))
+            .await
+            .map_err(|err| format!("{:?}", err))?;
+        if !response.status().is_success() {
+            let body = response.body().await.unwrap_or_default();
+            return Err(format!(
+                "{} ({})",
+                response.status(),
+                String::from_utf8_lossy(&body)
+            ));
+        }
+        let signed: serde_json::Value =
+            response.json().await.map_err(|err| format!("{:?}", err))?;
+        let signature = signed["signature"].as_str().unwrap_or_default();
+        near_crypto::Signature::from_str(signature)
+            .map_err(|err| format!("invalid signature {:?}: {:?}", signature, err))
+    }
+    pub fn input_signer_url() -> url::Url {
+        if let Some(signer_url) = crate::config::Config::load().remote_signer_url {
+            eprintln!("Using the remote signer from the config: {}", signer_url);
+            return signer_url;
+        }
+        loop {
+            let signer_url: String = Input::new()
+                .with_prompt("Enter the URL of the remote signer")
+                .interact_text()
+                .unwrap();
+            match url::Url::parse(&signer_url) {
+                Ok(signer_url) => break signer_url,
+                Err(err) => eprintln!("Invalid URL:  {:?}", err),
+            }
+        }
+    }
+    pub fn input_token() -> String {
+        if let Ok(token) = std::env::var(TOKEN_ENV_VAR) {
+            return token;
+        }
+        Password::new()
+            .with_prompt("Enter the token of the remote signer")
+            .interact()
+            .unwrap()
+    }
+}
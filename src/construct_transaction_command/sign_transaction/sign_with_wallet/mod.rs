@@ -0,0 +1,148 @@
+use near_primitives::borsh::BorshSerialize;
+use std::io::{BufRead, Write};
+use structopt::StructOpt;
+
+/// The web wallet signs the transaction with its own access key (so it picks
+/// the public key, the nonce and the block hash), sends it, and redirects the
+/// browser back to a one-shot callback server on localhost
+#[derive(Debug)]
+pub struct SignWithWallet {
+    pub wallet_url: Option<url::Url>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliSignWithWallet {
+    /// Use this web wallet instead of the one of the selected network
+    #[structopt(long)]
+    wallet_url: Option<url::Url>,
+}
+
+impl From<CliSignWithWallet> for SignWithWallet {
+    fn from(item: CliSignWithWallet) -> Self {
+        SignWithWallet {
+            wallet_url: item.wallet_url,
+        }
+    }
+}
+
+impl SignWithWallet {
+    pub fn process(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        selected_server_url: Option<url::Url>,
+    ) {
+        let wallet_url = match (self.wallet_url, selected_server_url) {
+            (Some(wallet_url), _) => wallet_url,
+            (None, Some(server_url)) => {
+                match crate::common::ConnectionConfig::from_rpc_url(server_url).wallet_url() {
+                    Some(wallet_url) => wallet_url,
+                    None => {
+                        return eprintln!(
+                            "There is no known web wallet for this network, pass --wallet-url"
+                        )
+                    }
+                }
+            }
+            (None, None) => {
+                return eprintln!(
+                    "Pass --wallet-url to sign with the web wallet in the offline mode"
+                )
+            }
+        };
+        let serialize_to_base64 = near_primitives::serialize::to_base64(
+            prepopulated_unsigned_transaction
+                .try_to_vec()
+                .expect("Transaction is not expected to fail on serialization"),
+        );
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")
+            .map_err(|err| eprintln!("Error starting the callback server:  {:?}", &err))
+            .unwrap();
+        let callback_url = url::Url::parse(&format!(
+            "http://{}/callback",
+            listener.local_addr().unwrap()
+        ))
+        .unwrap();
+        let sign_url = crate::common::wallet_sign_url(
+            &wallet_url,
+            &[serialize_to_base64],
+            Some(&callback_url),
+        );
+        eprintln!("Approve the transaction in the web wallet:\n{}", sign_url);
+        if let Err(err) = crate::common::open_in_browser(&sign_url) {
+            eprintln!(
+                "Error opening the browser:  {}, open the link manually",
+                err
+            );
+        }
+        eprintln!(
+            "Waiting for the web wallet to redirect back to {} ...",
+            callback_url
+        );
+        let query = SignWithWallet::wait_for_callback(&listener);
+        let query_value = |key: &str| {
+            query
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.clone())
+        };
+        match (query_value("transactionHashes"), query_value("errorCode")) {
+            (Some(transaction_hashes), _) => {
+                for transaction_hash in transaction_hashes.split(',') {
+                    crate::common::emit_event(
+                        "transaction_signed",
+                        serde_json::json!({ "transaction_hash": transaction_hash }),
+                    );
+                    println!(
+                        "{}: the web wallet sent the transaction {}",
+                        crate::style::success("Success"),
+                        crate::style::hash(transaction_hash)
+                    );
+                }
+            }
+            (None, Some(error_code)) => eprintln!(
+                "The web wallet did not sign the transaction: {} {}",
+                error_code,
+                query_value("errorMessage").unwrap_or_default()
+            ),
+            (None, None) => {
+                eprintln!("The web wallet redirected back without the transaction hashes")
+            }
+        }
+    }
+    /// Serve requests until the wallet redirects to the callback path and
+    /// return its query parameters
+    fn wait_for_callback(listener: &std::net::TcpListener) -> Vec<(String, String)> {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => continue,
+            };
+            let mut request_line = String::new();
+            if std::io::BufReader::new(&stream)
+                .read_line(&mut request_line)
+                .is_err()
+            {
+                continue;
+            }
+            // GET /callback?transactionHashes=... HTTP/1.1
+            let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+            let request_url = match url::Url::parse("http://localhost").unwrap().join(path) {
+                Ok(request_url) if request_url.path() == "/callback" => request_url,
+                _ => {
+                    let _ =
+                        stream.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n");
+                    continue;
+                }
+            };
+            let body = "You can close this page and return to the terminal.";
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            return request_url.query_pairs().into_owned().collect();
+        }
+        unreachable!("Error")
+    }
+}
@@ -1,5 +1,4 @@
 use async_recursion::async_recursion;
-use std::str::FromStr;
 use structopt::StructOpt;
 
 use crate::construct_transaction_command::receiver::{CliSkipNextAction, NextAction};
@@ -32,26 +31,23 @@ impl FullAccessType {
         nonce: near_primitives::types::Nonce,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         selected_server_url: Option<url::Url>,
-        public_key_string: String,
+        public_keys: Vec<near_crypto::PublicKey>,
     ) {
-        println!("FullAccessType process: self:\n       {:?}", &self);
-        println!(
-            "FullAccessType process: prepopulated_unsigned_transaction:\n       {:?}",
-            &prepopulated_unsigned_transaction
-        );
-        let public_key = near_crypto::PublicKey::from_str(&public_key_string).unwrap();
-        let access_key: near_primitives::account::AccessKey = near_primitives::account::AccessKey {
-            nonce,
-            permission: near_primitives::account::AccessKeyPermission::FullAccess,
-        };
-        let action = near_primitives::transaction::Action::AddKey(
-            near_primitives::transaction::AddKeyAction {
-                public_key,
-                access_key,
-            },
-        );
         let mut actions = prepopulated_unsigned_transaction.actions.clone();
-        actions.push(action);
+        for public_key in public_keys {
+            let access_key: near_primitives::account::AccessKey =
+                near_primitives::account::AccessKey {
+                    nonce,
+                    permission: near_primitives::account::AccessKeyPermission::FullAccess,
+                };
+            let action = near_primitives::transaction::Action::AddKey(
+                near_primitives::transaction::AddKeyAction {
+                    public_key,
+                    access_key,
+                },
+            );
+            actions.push(action);
+        }
         let unsigned_transaction = near_primitives::transaction::Transaction {
             actions,
             ..prepopulated_unsigned_transaction
@@ -67,6 +63,9 @@ impl FullAccessType {
                     .process(unsigned_transaction, selected_server_url)
                     .await
             }
+            NextAction::SaveDraft(save_draft) => {
+                save_draft.process(unsigned_transaction, selected_server_url)
+            }
         }
     }
 }
@@ -1,4 +1,3 @@
-use std::num::ParseIntError;
 use std::{str::FromStr, vec};
 use structopt::StructOpt;
 
@@ -75,32 +74,29 @@ impl FunctionCallType {
         nonce: near_primitives::types::Nonce,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         selected_server_url: Option<url::Url>,
-        public_key_string: String,
+        public_keys: Vec<near_crypto::PublicKey>,
     ) {
-        println!("FunctionCallType process: self:\n       {:?}", &self);
-        println!(
-            "FunctionCallType process: prepopulated_unsigned_transaction:\n       {:?}",
-            &prepopulated_unsigned_transaction
-        );
-        let public_key = near_crypto::PublicKey::from_str(&public_key_string).unwrap();
-        let access_key: near_primitives::account::AccessKey = near_primitives::account::AccessKey {
-            nonce,
-            permission: near_primitives::account::AccessKeyPermission::FunctionCall(
-                near_primitives::account::FunctionCallPermission {
-                    allowance: self.allowance.clone(),
-                    receiver_id: self.receiver_id.clone(),
-                    method_names: self.method_names.clone(),
-                },
-            ),
-        };
-        let action = near_primitives::transaction::Action::AddKey(
-            near_primitives::transaction::AddKeyAction {
-                public_key,
-                access_key,
-            },
-        );
         let mut actions = prepopulated_unsigned_transaction.actions.clone();
-        actions.push(action);
+        for public_key in public_keys {
+            let access_key: near_primitives::account::AccessKey =
+                near_primitives::account::AccessKey {
+                    nonce,
+                    permission: near_primitives::account::AccessKeyPermission::FunctionCall(
+                        near_primitives::account::FunctionCallPermission {
+                            allowance: self.allowance.clone(),
+                            receiver_id: self.receiver_id.clone(),
+                            method_names: self.method_names.clone(),
+                        },
+                    ),
+                };
+            let action = near_primitives::transaction::Action::AddKey(
+                near_primitives::transaction::AddKeyAction {
+                    public_key,
+                    access_key,
+                },
+            );
+            actions.push(action);
+        }
         let unsigned_transaction = near_primitives::transaction::Transaction {
             actions,
             ..prepopulated_unsigned_transaction
@@ -116,10 +112,13 @@ impl FunctionCallType {
                     .process(unsigned_transaction, selected_server_url)
                     .await
             }
+            NextAction::SaveDraft(save_draft) => {
+                save_draft.process(unsigned_transaction, selected_server_url)
+            }
         }
     }
     pub fn input_method_names() -> Vec<String> {
-        println!();
+        eprintln!();
         let choose_input = vec![
             "Yes, I want to input a list of method names that can be used",
             "No, I don't to input a list of method names that can be used",
@@ -153,7 +152,7 @@ impl FunctionCallType {
         }
     }
     pub fn input_allowance() -> Option<near_primitives::types::Balance> {
-        println!();
+        eprintln!();
         let choose_input = vec![
             "Yes, I want to input allowance for receiver ID",
             "No, I don't to input allowance for receiver ID",
@@ -165,23 +164,22 @@ impl FunctionCallType {
             .interact_on_opt(&Term::stderr())
             .unwrap();
         match select_choose_input {
-            Some(0) => {
+            Some(0) => loop {
                 let input: String = Input::new()
                     .with_prompt("Enter an allowance which is a balance limit to use by this access key to pay for function call gas and transaction fees.")
                     .interact_text()
                     .unwrap();
-                let allowance_near_balance: NearBalance = NearBalance::from_str(&input).unwrap();
-                let allowance = match allowance_near_balance {
-                    NearBalance(num) => num,
-                };
-                Some(allowance)
-            }
+                match NearBalance::from_str(&input) {
+                    Ok(NearBalance(allowance)) => break Some(allowance),
+                    Err(err) => eprintln!("{}", err),
+                }
+            },
             Some(1) => None,
             _ => unreachable!("Error"),
         }
     }
     pub fn input_receiver_id() -> near_primitives::types::AccountId {
-        println!();
+        eprintln!();
         Input::new()
             .with_prompt("Enter a receiver to use by this access key to pay for function call gas and transaction fees.")
             .interact_text()
@@ -193,19 +191,40 @@ impl FunctionCallType {
 pub struct NearBalance(u128);
 
 impl FromStr for NearBalance {
-    type Err = ParseIntError;
+    type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let number: u128 = s.parse().unwrap_or_else(|ParseIntError| -> u128 {
-            let mut s: String = s.to_string().clone();
-            s.make_ascii_uppercase();
-            match s.contains("NEAR") {
-                true => {
-                    let num: u128 = s.trim_matches(char::is_alphabetic).parse().unwrap();
-                    num * 10u128.pow(24)
-                }
-                _ => 0,
-            }
-        });
+        let number = crate::amount::AmountExpression::from_str(s)?.evaluate(None)?;
         Ok(NearBalance(number))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn near_balance_accepts_amounts_with_units() {
+        assert_eq!(
+            NearBalance::from_str("0.25 NEAR").unwrap().0,
+            crate::common::ONE_NEAR / 4
+        );
+        assert_eq!(NearBalance::from_str("100 yoctoNEAR").unwrap().0, 100);
+        for allowance in &["", "0.5", "1 EUR", "all", "-1 NEAR"] {
+            assert!(NearBalance::from_str(allowance).is_err(), "{:?}", allowance);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn near_balance_round_trips_through_text(allowance in any::<u128>()) {
+            let text = format!("{} yoctoNEAR", allowance);
+            prop_assert_eq!(NearBalance::from_str(&text).map(|balance| balance.0), Ok(allowance));
+        }
+
+        #[test]
+        fn near_balance_never_panics(allowance in "\\PC{0,64}") {
+            NearBalance::from_str(&allowance).ok();
+        }
+    }
+}
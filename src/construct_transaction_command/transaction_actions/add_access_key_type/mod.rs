@@ -12,14 +12,16 @@ use full_access_type::{CliFullAccessType, FullAccessType};
 
 #[derive(Debug)]
 pub struct AddAccessKeyAction {
-    pub public_key: String,
+    pub public_keys: Vec<near_crypto::PublicKey>,
     pub nonce: near_primitives::types::Nonce,
     pub permission: AccessKeyPermission,
 }
 
 #[derive(Debug, StructOpt)]
 pub struct CliAddAccessKeyAction {
-    public_key: Option<String>,
+    public_keys: Option<String>,
+    #[structopt(long)]
+    public_keys_file: Option<std::path::PathBuf>,
     #[structopt(long)]
     nonce: Option<u64>,
     #[structopt(subcommand)]
@@ -43,9 +45,26 @@ pub enum AccessKeyPermission {
 
 impl From<CliAddAccessKeyAction> for AddAccessKeyAction {
     fn from(item: CliAddAccessKeyAction) -> Self {
-        let public_key: near_primitives::types::AccountId = match item.public_key {
-            Some(cli_public_key) => near_primitives::types::AccountId::from(cli_public_key),
-            None => AddAccessKeyAction::input_public_key(),
+        let public_keys = item
+            .public_keys
+            .map_or(Ok(vec![]), |cli_public_keys| {
+                crate::public_key_selector::parse_public_keys(&cli_public_keys)
+            })
+            .and_then(|mut public_keys| {
+                if let Some(cli_public_keys_file) = item.public_keys_file {
+                    public_keys.extend(crate::public_key_selector::read_public_keys_file(
+                        &cli_public_keys_file,
+                    )?);
+                }
+                Ok(public_keys)
+            });
+        let public_keys: Vec<near_crypto::PublicKey> = match public_keys {
+            Ok(public_keys) if !public_keys.is_empty() => public_keys,
+            Ok(_) => AddAccessKeyAction::input_public_keys(),
+            Err(err) => {
+                eprintln!("{}", err);
+                AddAccessKeyAction::input_public_keys()
+            }
         };
         let nonce: near_primitives::types::Nonce = match item.nonce {
             Some(cli_nonce) => near_primitives::types::Nonce::from(cli_nonce),
@@ -56,7 +75,7 @@ impl From<CliAddAccessKeyAction> for AddAccessKeyAction {
             None => AccessKeyPermission::choose_permission(),
         };
         AddAccessKeyAction {
-            public_key,
+            public_keys,
             nonce,
             permission,
         }
@@ -71,11 +90,6 @@ impl AddAccessKeyAction {
         selected_server_url: Option<url::Url>,
         _public_key_string: String,
     ) {
-        println!("AddAccessKeyAction process: self:\n       {:?}", &self);
-        println!(
-            "AddAccessKeyAction process: prepopulated_unsigned_transaction:\n       {:?}",
-            &prepopulated_unsigned_transaction
-        );
         match self.permission {
             AccessKeyPermission::FullAccessAction(full_access_type) => {
                 full_access_type
@@ -83,7 +97,7 @@ impl AddAccessKeyAction {
                         self.nonce,
                         prepopulated_unsigned_transaction,
                         selected_server_url,
-                        self.public_key,
+                        self.public_keys,
                     )
                     .await
             }
@@ -93,7 +107,7 @@ impl AddAccessKeyAction {
                         self.nonce,
                         prepopulated_unsigned_transaction,
                         selected_server_url,
-                        self.public_key,
+                        self.public_keys,
                     )
                     .await
             }
@@ -105,11 +119,8 @@ impl AddAccessKeyAction {
             .interact_text()
             .unwrap()
     }
-    pub fn input_public_key() -> String {
-        Input::new()
-            .with_prompt("Enter a public key for this access key")
-            .interact_text()
-            .unwrap()
+    pub fn input_public_keys() -> Vec<near_crypto::PublicKey> {
+        crate::public_key_selector::input_public_keys("Enter a public key for this access key")
     }
 }
 
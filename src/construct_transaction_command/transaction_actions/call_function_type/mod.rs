@@ -1,27 +1,244 @@
-// use structopt::StructOpt;
-// use std::str::FromStr;
-// use strum_macros::{
-//     Display,
-//     EnumString,
-//     EnumVariantNames,
-// };
-// use strum::VariantNames;
-// use dialoguer::{
-//     Select,
-//     Input,
-//     theme::ColorfulTheme,
-//     console::Term
-// };
-// use super::super::{
-//     ActionSubcommand,
-// };
+use async_recursion::async_recursion;
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use std::str::FromStr;
+use structopt::StructOpt;
 
-// #[derive(Debug, StructOpt)]
-// pub struct CallFunction {
-//     method_name: String,
-//     args: String,
-//     gas: u64,  // default 1000000000
-//     deposit: u128,  // default 0
-//     #[structopt(subcommand)]
-//     next_action: Box<ActionSubcommand>
-// }
+use super::super::receiver::{CliSkipNextAction, NextAction};
+
+const DEFAULT_FUNCTION_CALL_GAS: near_primitives::types::Gas = 30_000_000_000_000;
+
+/// The missing parts are asked in `process`, once the server is selected, so
+/// that the method can be chosen from the ones the contract exports
+#[derive(Debug)]
+pub struct CallFunctionAction {
+    pub method_name: Option<String>,
+    pub args: Option<String>,
+    pub gas: Option<near_primitives::types::Gas>,
+    pub deposit: Option<crate::amount::AmountExpression>,
+    pub expect_storage_bytes: Option<u64>,
+    pub next_action: Option<Box<NextAction>>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliCallFunctionAction {
+    #[structopt(long)]
+    method_name: Option<String>,
+    /// JSON arguments, e.g. '{"account_id": "alice.near"}'
+    #[structopt(long)]
+    args: Option<String>,
+    #[structopt(long)]
+    gas: Option<near_primitives::types::Gas>,
+    #[structopt(long)]
+    deposit: Option<crate::amount::AmountExpression>,
+    /// Bytes of contract storage the call adds, to check that the deposit
+    /// covers their storage stake
+    #[structopt(long)]
+    expect_storage_bytes: Option<u64>,
+    #[structopt(subcommand)]
+    next_action: Option<CliSkipNextAction>,
+}
+
+impl From<CliCallFunctionAction> for CallFunctionAction {
+    fn from(item: CliCallFunctionAction) -> Self {
+        CallFunctionAction {
+            method_name: item.method_name,
+            args: item.args,
+            gas: item.gas,
+            deposit: item.deposit,
+            expect_storage_bytes: item.expect_storage_bytes,
+            next_action: item
+                .next_action
+                .map(|cli_skip_action| Box::new(NextAction::from(cli_skip_action))),
+        }
+    }
+}
+
+impl CallFunctionAction {
+    #[async_recursion(?Send)]
+    pub async fn process(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        selected_server_url: Option<url::Url>,
+    ) {
+        let method_name = match self.method_name {
+            Some(method_name) => method_name,
+            None => {
+                CallFunctionAction::input_method_name(
+                    &prepopulated_unsigned_transaction.receiver_id,
+                    selected_server_url.as_ref(),
+                )
+                .await
+            }
+        };
+        let args = match self.args {
+            Some(args) => args,
+            None => CallFunctionAction::input_args(),
+        };
+        let gas = match self.gas {
+            Some(gas) => gas,
+            None => CallFunctionAction::input_gas(),
+        };
+        let deposit = match self.deposit {
+            Some(deposit) => deposit,
+            None => CallFunctionAction::input_deposit(),
+        };
+        let deposit = match deposit.evaluate(None) {
+            Ok(deposit) => deposit,
+            Err(err) => return eprintln!("Error evaluating the deposit {}:  {}", deposit, err),
+        };
+        if let Some(storage_bytes) = self.expect_storage_bytes {
+            if !CallFunctionAction::confirm_storage_deposit(
+                storage_bytes,
+                deposit,
+                selected_server_url.as_ref(),
+            )
+            .await
+            {
+                return eprintln!("The transaction is not signed");
+            }
+        }
+        let next_action = match self.next_action {
+            Some(next_action) => next_action,
+            None => Box::new(NextAction::input_next_action()),
+        };
+        let action = near_primitives::transaction::Action::FunctionCall(
+            near_primitives::transaction::FunctionCallAction {
+                method_name,
+                args: args.into_bytes(),
+                gas,
+                deposit,
+            },
+        );
+        let mut actions = prepopulated_unsigned_transaction.actions.clone();
+        actions.push(action);
+        let unsigned_transaction = near_primitives::transaction::Transaction {
+            actions,
+            ..prepopulated_unsigned_transaction
+        };
+        match *next_action {
+            NextAction::AddAction(select_action) => {
+                select_action
+                    .process(unsigned_transaction, selected_server_url)
+                    .await
+            }
+            NextAction::Skip(skip_action) => {
+                skip_action
+                    .process(unsigned_transaction, selected_server_url)
+                    .await
+            }
+            NextAction::SaveDraft(save_draft) => {
+                save_draft.process(unsigned_transaction, selected_server_url)
+            }
+        }
+    }
+    /// Offers the functions exported by the deployed contract when online
+    pub async fn input_method_name(
+        contract_id: &str,
+        selected_server_url: Option<&url::Url>,
+    ) -> String {
+        let method_names = match selected_server_url {
+            Some(server_url) => {
+                match crate::common::contract_method_names(server_url, contract_id).await {
+                    Ok(method_names) => method_names,
+                    Err(err) => {
+                        eprintln!("Could not list the methods of <{}>:  {}", contract_id, err);
+                        vec![]
+                    }
+                }
+            }
+            None => vec![],
+        };
+        if method_names.is_empty() {
+            return Input::new()
+                .with_prompt("What is the name of the method?")
+                .interact_text()
+                .unwrap();
+        }
+        eprintln!();
+        let mut items = method_names.clone();
+        items.push("Enter another method name".to_string());
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Which method of <{}> do you want to call?",
+                contract_id
+            ))
+            .items(&items)
+            .default(0)
+            .interact()
+            .unwrap();
+        match method_names.get(selection) {
+            Some(method_name) => method_name.clone(),
+            None => Input::new()
+                .with_prompt("What is the name of the method?")
+                .interact_text()
+                .unwrap(),
+        }
+    }
+    /// Warns about the shortfall when the deposit does not cover the storage
+    /// stake of the bytes the call adds; `false` means the user backed out
+    async fn confirm_storage_deposit(
+        storage_bytes: u64,
+        deposit: near_primitives::types::Balance,
+        selected_server_url: Option<&url::Url>,
+    ) -> bool {
+        let storage_amount_per_byte =
+            match crate::protocol_config::storage_amount_per_byte(selected_server_url).await {
+                Ok(storage_amount_per_byte) => storage_amount_per_byte,
+                Err(err) => {
+                    eprintln!("Could not check the deposit for the storage:  {}", err);
+                    return true;
+                }
+            };
+        let storage_cost = u128::from(storage_bytes) * storage_amount_per_byte;
+        eprintln!(
+            "The storage stake of {} bytes is {}",
+            storage_bytes,
+            crate::common::format_near_amount(storage_cost)
+        );
+        if deposit >= storage_cost {
+            return true;
+        }
+        eprintln!(
+            "{}",
+            crate::style::warning(format!(
+                "The deposit of {} is {} short of the storage stake",
+                crate::common::format_near_amount(deposit),
+                crate::common::format_near_amount(storage_cost - deposit)
+            ))
+        );
+        dialoguer::Confirm::new()
+            .with_prompt("Do you still want to sign the transaction?")
+            .default(false)
+            .interact()
+            .unwrap_or(false)
+    }
+    pub fn input_args() -> String {
+        Input::new()
+            .with_prompt("Enter the arguments as JSON")
+            .default("{}".to_string())
+            .interact_text()
+            .unwrap()
+    }
+    pub fn input_gas() -> near_primitives::types::Gas {
+        Input::new()
+            .with_prompt("How much gas do you want to attach?")
+            .default(DEFAULT_FUNCTION_CALL_GAS)
+            .interact_text()
+            .unwrap()
+    }
+    pub fn input_deposit() -> crate::amount::AmountExpression {
+        loop {
+            let input: String = Input::new()
+                .with_prompt(
+                    "How many NEAR Tokens do you want to attach? (example: 1 yoctoNEAR, 0.1NEAR)",
+                )
+                .default("0".to_string())
+                .interact_text()
+                .unwrap();
+            match crate::amount::AmountExpression::from_str(&input) {
+                Ok(deposit) => break deposit,
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use async_recursion::async_recursion;
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
+use structopt::StructOpt;
+
+use super::super::receiver::{CliSkipNextAction, NextAction};
+use super::call_function_type::CallFunctionAction;
+
+/// A function call predefined in an imported catalog (see `catalog use`); the
+/// operation is chosen in `process`, among the ones for the receiver
+#[derive(Debug)]
+pub struct CatalogOperationAction {
+    pub operation_name: Option<String>,
+    pub params: Vec<String>,
+    pub next_action: Option<Box<NextAction>>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliCatalogOperationAction {
+    #[structopt(long)]
+    operation_name: Option<String>,
+    /// A value of an argument placeholder, e.g. `--param amount=100`
+    #[structopt(long = "param")]
+    params: Vec<String>,
+    #[structopt(subcommand)]
+    next_action: Option<CliSkipNextAction>,
+}
+
+impl From<CliCatalogOperationAction> for CatalogOperationAction {
+    fn from(item: CliCatalogOperationAction) -> Self {
+        CatalogOperationAction {
+            operation_name: item.operation_name,
+            params: item.params,
+            next_action: item
+                .next_action
+                .map(|cli_skip_action| Box::new(NextAction::from(cli_skip_action))),
+        }
+    }
+}
+
+impl CatalogOperationAction {
+    #[async_recursion(?Send)]
+    pub async fn process(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        selected_server_url: Option<url::Url>,
+    ) {
+        let receiver_id = &prepopulated_unsigned_transaction.receiver_id;
+        let mut operations = crate::catalog::Catalog::load_imported()
+            .into_iter()
+            .flat_map(|catalog| {
+                let catalog_name = catalog.name;
+                catalog
+                    .operations
+                    .into_iter()
+                    .map(move |operation| (catalog_name.clone(), operation))
+            })
+            .filter(|(_, operation)| operation.is_for(receiver_id))
+            .collect::<Vec<_>>();
+        if operations.is_empty() {
+            return eprintln!(
+                "There are no catalog operations for <{}>, import a catalog with `catalog use`",
+                receiver_id
+            );
+        }
+        let operation = match &self.operation_name {
+            Some(operation_name) => {
+                match operations
+                    .iter()
+                    .position(|(_, operation)| &operation.name == operation_name)
+                {
+                    Some(index) => operations.remove(index).1,
+                    None => {
+                        return eprintln!(
+                            "There is no catalog operation \"{}\" for <{}>",
+                            operation_name, receiver_id
+                        )
+                    }
+                }
+            }
+            None => CatalogOperationAction::choose_operation(operations),
+        };
+        let mut values = std::collections::HashMap::new();
+        for param in &self.params {
+            match param.find('=') {
+                Some(end) => values.insert(param[..end].to_string(), param[end + 1..].to_string()),
+                None => return eprintln!("The parameter \"{}\" is not name=value", param),
+            };
+        }
+        for placeholder in operation.placeholders() {
+            if !values.contains_key(&placeholder) {
+                let value: String = Input::new()
+                    .with_prompt(format!("Enter the {}", placeholder))
+                    .interact_text()
+                    .unwrap();
+                values.insert(placeholder, value);
+            }
+        }
+        match operation.danger_level {
+            crate::catalog::DangerLevel::Safe => {}
+            crate::catalog::DangerLevel::Caution => eprintln!(
+                "{} \"{}\" needs caution: {}",
+                crate::style::warning("Warning:"),
+                operation.name,
+                operation.description
+            ),
+            crate::catalog::DangerLevel::Dangerous => {
+                eprintln!(
+                    "{} \"{}\" is marked as dangerous: {}",
+                    crate::style::warning("Warning:"),
+                    operation.name,
+                    operation.description
+                );
+                let confirmed = Confirm::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Do you really want to add this operation?")
+                    .default(false)
+                    .interact()
+                    .unwrap();
+                if !confirmed {
+                    return eprintln!("The operation has not been added");
+                }
+            }
+        }
+        let call_function = CallFunctionAction {
+            method_name: Some(operation.method_name.clone()),
+            args: Some(operation.fill_args(&values).to_string()),
+            gas: operation.gas,
+            deposit: operation.deposit.as_ref().map(|deposit| {
+                deposit
+                    .parse()
+                    .expect("The deposits are checked when the catalog is loaded")
+            }),
+            expect_storage_bytes: operation.storage_bytes,
+            next_action: self.next_action,
+        };
+        call_function
+            .process(prepopulated_unsigned_transaction, selected_server_url)
+            .await
+    }
+    fn choose_operation(
+        mut operations: Vec<(String, crate::catalog::CatalogOperation)>,
+    ) -> crate::catalog::CatalogOperation {
+        eprintln!();
+        let items = operations
+            .iter()
+            .map(|(catalog_name, operation)| {
+                format!(
+                    "{}: {} ({})",
+                    catalog_name, operation.name, operation.description
+                )
+            })
+            .collect::<Vec<_>>();
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Which operation do you want to use?")
+            .items(&items)
+            .default(0)
+            .interact()
+            .unwrap();
+        operations.remove(selection).1
+    }
+}
@@ -31,11 +31,29 @@ impl CreateAccountAction {
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         selected_server_url: Option<url::Url>,
     ) {
-        println!("CreateAccountAction process: self:\n       {:?}", &self);
-        println!(
-            "CreateAccountAction process: prepopulated_unsigned_transaction:\n       {:?}",
-            &prepopulated_unsigned_transaction
-        );
+        if let Some(server_url) = &selected_server_url {
+            match crate::common::account_exists(
+                server_url,
+                &prepopulated_unsigned_transaction.receiver_id,
+            )
+            .await
+            {
+                Ok(true) => {
+                    return eprintln!(
+                        "{}",
+                        crate::style::warning(format!(
+                            "Account <{}> already exists, the transaction would fail with AccountAlreadyExists",
+                            &prepopulated_unsigned_transaction.receiver_id
+                        ))
+                    )
+                }
+                Ok(false) => {}
+                Err(err) => eprintln!(
+                    "Could not check whether <{}> already exists:  {}",
+                    &prepopulated_unsigned_transaction.receiver_id, err
+                ),
+            }
+        }
         let action = near_primitives::transaction::Action::CreateAccount(
             near_primitives::transaction::CreateAccountAction {},
         );
@@ -56,6 +74,9 @@ impl CreateAccountAction {
                     .process(unsigned_transaction, selected_server_url)
                     .await
             }
+            NextAction::SaveDraft(save_draft) => {
+                save_draft.process(unsigned_transaction, selected_server_url)
+            }
         }
     }
 }
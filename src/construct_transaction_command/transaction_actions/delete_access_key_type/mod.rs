@@ -1,5 +1,4 @@
 use async_recursion::async_recursion;
-use dialoguer::Input;
 use std::str::FromStr;
 use structopt::StructOpt;
 
@@ -43,11 +42,6 @@ impl DeleteAccessKeyAction {
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         selected_server_url: Option<url::Url>,
     ) {
-        println!("DeleteAccessKeyAction process: self:\n       {:?}", &self);
-        println!(
-            "DeleteAccessKeyAction process: prepopulated_unsigned_transaction:\n       {:?}",
-            &prepopulated_unsigned_transaction
-        );
         let public_key = near_crypto::PublicKey::from_str(&self.public_key).unwrap();
         let action = near_primitives::transaction::Action::DeleteKey(
             near_primitives::transaction::DeleteKeyAction { public_key },
@@ -69,12 +63,12 @@ impl DeleteAccessKeyAction {
                     .process(unsigned_transaction, selected_server_url)
                     .await
             }
+            NextAction::SaveDraft(save_draft) => {
+                save_draft.process(unsigned_transaction, selected_server_url)
+            }
         }
     }
     pub fn input_public_key() -> String {
-        Input::new()
-            .with_prompt("Enter the access key to remove it")
-            .interact_text()
-            .unwrap()
+        crate::public_key_selector::input_public_key("Enter the access key to remove it")
     }
 }
@@ -42,11 +42,6 @@ impl DeleteAccountAction {
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         selected_server_url: Option<url::Url>,
     ) {
-        println!("DeleteAccountAction process: self:\n       {:?}", &self);
-        println!(
-            "DeleteAccountAction process: prepopulated_unsigned_transaction:\n       {:?}",
-            &prepopulated_unsigned_transaction
-        );
         let beneficiary_id: String = self.beneficiary_id.clone();
         let action = near_primitives::transaction::Action::DeleteAccount(
             near_primitives::transaction::DeleteAccountAction { beneficiary_id },
@@ -68,10 +63,13 @@ impl DeleteAccountAction {
                     .process(unsigned_transaction, selected_server_url)
                     .await
             }
+            NextAction::SaveDraft(save_draft) => {
+                save_draft.process(unsigned_transaction, selected_server_url)
+            }
         }
     }
     pub fn input_beneficiary_id() -> String {
-        println!();
+        eprintln!();
         Input::new()
             .with_prompt("Enter the beneficiary ID to delete this account ID")
             .interact_text()
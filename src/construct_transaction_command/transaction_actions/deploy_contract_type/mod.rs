@@ -0,0 +1,152 @@
+use async_recursion::async_recursion;
+use dialoguer::{Confirm, Input};
+use structopt::StructOpt;
+
+use super::super::receiver::{CliSkipNextAction, NextAction};
+
+/// Deploy a wasm file to the receiver account, optionally recording where it
+/// was built from in the local audit log (see `view contract-source-metadata`)
+#[derive(Debug)]
+pub struct DeployContractAction {
+    pub wasm_file: std::path::PathBuf,
+    pub record_provenance: bool,
+    pub builder: Option<String>,
+    pub next_action: Box<NextAction>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliDeployContractAction {
+    #[structopt(long)]
+    wasm_file: Option<std::path::PathBuf>,
+    /// Record the git commit of the wasm file directory, the builder and the
+    /// time of the deployment in the local audit log
+    #[structopt(long)]
+    record_provenance: bool,
+    /// Who built the wasm file ($USER by default)
+    #[structopt(long)]
+    builder: Option<String>,
+    #[structopt(subcommand)]
+    next_action: Option<CliSkipNextAction>,
+}
+
+impl From<CliDeployContractAction> for DeployContractAction {
+    fn from(item: CliDeployContractAction) -> Self {
+        let wasm_file = match item.wasm_file {
+            Some(cli_wasm_file) => cli_wasm_file,
+            None => DeployContractAction::input_wasm_file(),
+        };
+        let next_action: Box<NextAction> = match item.next_action {
+            Some(cli_next_action) => Box::new(NextAction::from(cli_next_action)),
+            None => Box::new(NextAction::input_next_action()),
+        };
+        DeployContractAction {
+            wasm_file,
+            record_provenance: item.record_provenance || item.builder.is_some(),
+            builder: item.builder,
+            next_action,
+        }
+    }
+}
+
+impl DeployContractAction {
+    #[async_recursion(?Send)]
+    pub async fn process(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        selected_server_url: Option<url::Url>,
+    ) {
+        let code = match std::fs::read(&self.wasm_file) {
+            Ok(code) => code,
+            Err(err) => {
+                return eprintln!(
+                    "Error reading the wasm file {:?}:  {:?}",
+                    &self.wasm_file, err
+                )
+            }
+        };
+        let code_hash = near_primitives::hash::hash(&code);
+        eprintln!(
+            "Code: {} bytes, hash {}",
+            code.len(),
+            crate::style::hash(&code_hash)
+        );
+        if self.record_provenance {
+            let builder = self.builder.clone().unwrap_or_else(|| {
+                std::env::var("USER")
+                    .or_else(|_| std::env::var("USERNAME"))
+                    .unwrap_or_default()
+            });
+            let git_commit = git_commit(&self.wasm_file);
+            eprintln!(
+                "Provenance: commit {}, built by {}",
+                git_commit.as_deref().unwrap_or("unknown"),
+                &builder
+            );
+            crate::common::append_to_audit_log(serde_json::json!({
+                "event": "deploy_provenance",
+                "account_id": &prepopulated_unsigned_transaction.receiver_id,
+                "code_hash": code_hash.to_string(),
+                "wasm_file": &self.wasm_file,
+                "git_commit": git_commit,
+                "builder": builder,
+            }));
+        }
+        let action = near_primitives::transaction::Action::DeployContract(
+            near_primitives::transaction::DeployContractAction { code },
+        );
+        let mut actions = prepopulated_unsigned_transaction.actions.clone();
+        actions.push(action);
+        let unsigned_transaction = near_primitives::transaction::Transaction {
+            actions,
+            ..prepopulated_unsigned_transaction
+        };
+        match *self.next_action {
+            NextAction::AddAction(select_action) => {
+                select_action
+                    .process(unsigned_transaction, selected_server_url)
+                    .await
+            }
+            NextAction::Skip(skip_action) => {
+                skip_action
+                    .process(unsigned_transaction, selected_server_url)
+                    .await
+            }
+            NextAction::SaveDraft(save_draft) => {
+                save_draft.process(unsigned_transaction, selected_server_url)
+            }
+        }
+    }
+    pub fn input_wasm_file() -> std::path::PathBuf {
+        let wasm_file: String = Input::new()
+            .with_prompt("What is the path to the wasm file of the contract?")
+            .interact_text()
+            .unwrap();
+        std::path::PathBuf::from(wasm_file)
+    }
+    pub fn input_record_provenance() -> bool {
+        Confirm::new()
+            .with_prompt("Record the git commit and the builder of the wasm file in the audit log?")
+            .default(false)
+            .interact()
+            .unwrap()
+    }
+}
+
+/// The commit checked out in the directory of the wasm file, with `-dirty`
+/// when there are uncommitted changes
+fn git_commit(wasm_file: &std::path::Path) -> Option<String> {
+    let directory = match wasm_file.parent() {
+        Some(directory) if !directory.as_os_str().is_empty() => directory,
+        _ => std::path::Path::new("."),
+    };
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(&["describe", "--always", "--dirty", "--abbrev=40"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
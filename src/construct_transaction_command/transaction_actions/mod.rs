@@ -1,6 +1,8 @@
 pub mod add_access_key_type;
 pub mod call_function_type;
+pub mod catalog_operation_type;
 pub mod create_account_type;
 pub mod delete_access_key_type;
 pub mod delete_account_type;
+pub mod deploy_contract_type;
 pub mod transfer_near_tokens_type;
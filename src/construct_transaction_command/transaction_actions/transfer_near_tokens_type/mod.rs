@@ -1,14 +1,20 @@
 use async_recursion::async_recursion;
 use dialoguer::Input;
-use std::num::ParseIntError;
 use std::str::FromStr;
 use structopt::StructOpt;
 
 use super::super::receiver::{CliSkipNextAction, NextAction};
 
+const MEMO_FUNCTION_CALL_GAS: near_primitives::types::Gas = 30_000_000_000_000;
+/// Gas burnt by a transaction with a single Transfer action, with some headroom
+const TRANSFER_GAS_ESTIMATE: near_primitives::types::Gas = 500_000_000_000;
+/// Used when the genesis config cannot be fetched
+const DEFAULT_STORAGE_AMOUNT_PER_BYTE: near_primitives::types::Balance = 10_000_000_000_000_000_000;
+
 #[derive(Debug)]
 pub struct TransferNEARTokensAction {
     pub amount: NearBalance,
+    pub memo: Option<String>,
     pub next_action: Box<NextAction>,
 }
 
@@ -19,17 +25,104 @@ impl TransferNEARTokensAction {
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         selected_server_url: Option<url::Url>,
     ) {
-        println!("TransferNEARTokens process: self:\n       {:?}", &self);
-        println!(
-            "TransferNEARTokens process: prepopulated_unsigned_transaction:\n       {:?}",
-            &prepopulated_unsigned_transaction
-        );
-        let amount = match self.amount {
-            NearBalance(num) => num,
+        let memo_method_name = match &self.memo {
+            Some(_) => crate::config::Config::load()
+                .memo_contracts
+                .remove(&prepopulated_unsigned_transaction.receiver_id),
+            None => None,
+        };
+        let amount = match (self.amount, &selected_server_url) {
+            (NearBalance::TransferAll, Some(server_url)) => {
+                let gas = if memo_method_name.is_some() {
+                    TRANSFER_GAS_ESTIMATE + MEMO_FUNCTION_CALL_GAS
+                } else {
+                    TRANSFER_GAS_ESTIMATE
+                };
+                match TransferNEARTokensAction::max_transferable_amount(
+                    server_url,
+                    &prepopulated_unsigned_transaction,
+                    gas,
+                )
+                .await
+                {
+                    Ok(amount) => amount,
+                    Err(err) => {
+                        return eprintln!("Error computing the amount to transfer:  {}", err)
+                    }
+                }
+            }
+            (NearBalance::TransferAll, None) => {
+                return eprintln!(
+                    "Transferring the whole balance is only possible in the online mode"
+                )
+            }
+            (NearBalance::Amount(amount_expression), _) => {
+                let balance = match (&selected_server_url, amount_expression.uses_all()) {
+                    (Some(server_url), true) => match TransferNEARTokensAction::fetch_account(
+                        server_url,
+                        &prepopulated_unsigned_transaction.signer_id,
+                    )
+                    .await
+                    {
+                        Ok(account_view) => Some(account_view.amount),
+                        Err(err) => return eprintln!("Error fetching the balance:  {}", err),
+                    },
+                    _ => None,
+                };
+                match amount_expression.evaluate(balance) {
+                    Ok(amount) => {
+                        eprintln!(
+                            "Amount: {} = {} ({} yoctoNEAR)",
+                            amount_expression,
+                            crate::style::amount(crate::common::format_near_amount(amount)),
+                            amount
+                        );
+                        amount
+                    }
+                    Err(err) => {
+                        return eprintln!(
+                            "Error evaluating the amount {}:  {}",
+                            amount_expression, err
+                        )
+                    }
+                }
+            }
+        };
+        let action = match (&self.memo, memo_method_name) {
+            (Some(memo), Some(method_name)) => near_primitives::transaction::Action::FunctionCall(
+                near_primitives::transaction::FunctionCallAction {
+                    method_name,
+                    args: serde_json::json!({ "memo": memo }).to_string().into_bytes(),
+                    gas: MEMO_FUNCTION_CALL_GAS,
+                    deposit: amount,
+                },
+            ),
+            (Some(memo), None) => {
+                eprintln!(
+                    "{}",
+                    crate::style::warning(format!(
+                        "The memo is NOT attached on chain: <{}> is not known to accept memos \
+                         (add it to memo_contracts in the config if it does), so it is saved \
+                         only in the local audit log",
+                        &prepopulated_unsigned_transaction.receiver_id
+                    ))
+                );
+                crate::common::append_to_audit_log(serde_json::json!({
+                    "event": "transfer_memo",
+                    "signer_id": &prepopulated_unsigned_transaction.signer_id,
+                    "receiver_id": &prepopulated_unsigned_transaction.receiver_id,
+                    "amount": amount.to_string(),
+                    "memo": memo,
+                    "on_chain": false,
+                }));
+                near_primitives::transaction::Action::Transfer(
+                    near_primitives::transaction::TransferAction { deposit: amount },
+                )
+            }
+            (None, _) => near_primitives::transaction::Action::Transfer(
+                near_primitives::transaction::TransferAction { deposit: amount },
+            ),
         };
-        let action = near_primitives::transaction::Action::Transfer(
-            near_primitives::transaction::TransferAction { deposit: amount },
-        );
         let mut actions = prepopulated_unsigned_transaction.actions.clone();
         actions.push(action);
         let unsigned_transaction = near_primitives::transaction::Transaction {
@@ -47,6 +140,9 @@ impl TransferNEARTokensAction {
                     .process(unsigned_transaction, selected_server_url)
                     .await
             }
+            NextAction::SaveDraft(save_draft) => {
+                save_draft.process(unsigned_transaction, selected_server_url)
+            }
             _ => unreachable!("Error"),
         }
     }
@@ -55,38 +151,159 @@ impl TransferNEARTokensAction {
 #[derive(Debug, StructOpt)]
 pub struct CliTransferNEARTokensAction {
     amount: Option<NearBalance>,
+    #[structopt(long)]
+    memo: Option<String>,
     #[structopt(subcommand)]
     next_action: Option<CliSkipNextAction>,
 }
 
 impl NearBalance {
     pub fn input_amount() -> Self {
-        let input: String = Input::new()
-            .with_prompt("How many NEAR Tokens do you want to transfer? (example: 10NEAR)")
+        loop {
+            let input: String = Input::new()
+                .with_prompt(
+                    "How many NEAR Tokens do you want to transfer? (example: 10NEAR, 0.1*3 NEAR, all)",
+                )
+                .interact_text()
+                .unwrap();
+            match NearBalance::from_str(&input) {
+                Ok(near_balance) => break near_balance,
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+    }
+}
+
+impl TransferNEARTokensAction {
+    async fn fetch_account(
+        server_url: &url::Url,
+        account_id: &str,
+    ) -> Result<near_primitives::views::AccountView, String> {
+        let account_response = near_jsonrpc_client::new_client(server_url.as_str())
+            .query(near_primitives::rpc::RpcQueryRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+                request: near_primitives::views::QueryRequest::ViewAccount {
+                    account_id: account_id.to_string(),
+                },
+            })
+            .await
+            .map_err(|err| format!("Failed to fetch the account <{}>: {:?}", account_id, err))?;
+        match account_response.kind {
+            near_primitives::views::QueryResponseKind::ViewAccount(account_view) => {
+                Ok(account_view)
+            }
+            kind => Err(format!(
+                "Unexpected response to the account query: {:?}",
+                kind
+            )),
+        }
+    }
+    /// The balance minus the part locked for storage (unless it is covered by
+    /// the staked balance), the deposits of the previous actions and the fees,
+    /// including the gas prepaid for their function calls
+    async fn max_transferable_amount(
+        server_url: &url::Url,
+        prepopulated_unsigned_transaction: &near_primitives::transaction::Transaction,
+        gas: near_primitives::types::Gas,
+    ) -> Result<near_primitives::types::Balance, String> {
+        let account_view = TransferNEARTokensAction::fetch_account(
+            server_url,
+            &prepopulated_unsigned_transaction.signer_id,
+        )
+        .await?;
+        let storage_amount_per_byte = crate::common::call_json_rpc(
+            server_url,
+            "EXPERIMENTAL_genesis_config",
+            serde_json::json!(null),
+        )
+        .await
+        .ok()
+        .and_then(|genesis_config| {
+            genesis_config["runtime_config"]["storage_amount_per_byte"]
+                .as_str()?
+                .parse::<u128>()
+                .ok()
+        })
+        .unwrap_or(DEFAULT_STORAGE_AMOUNT_PER_BYTE);
+        let gas_price =
+            crate::common::call_json_rpc(server_url, "gas_price", serde_json::json!([null]))
+                .await?["gas_price"]
+                .as_str()
+                .and_then(|gas_price| gas_price.parse::<u128>().ok())
+                .ok_or_else(|| "Failed to fetch the gas price".to_string())?;
+
+        let storage_cost = u128::from(account_view.storage_usage) * storage_amount_per_byte;
+        let storage_reserve = storage_cost.saturating_sub(account_view.locked);
+        let previous_deposits: u128 = prepopulated_unsigned_transaction
+            .actions
+            .iter()
+            .map(|action| match action {
+                near_primitives::transaction::Action::Transfer(transfer) => transfer.deposit,
+                near_primitives::transaction::Action::FunctionCall(function_call) => {
+                    function_call.deposit
+                }
+                _ => 0,
+            })
+            .sum();
+        let previous_gas: u128 = prepopulated_unsigned_transaction
+            .actions
+            .iter()
+            .map(|action| match action {
+                near_primitives::transaction::Action::FunctionCall(function_call) => {
+                    u128::from(function_call.gas)
+                }
+                _ => 0,
+            })
+            .sum();
+        let estimated_fee = (u128::from(gas) + previous_gas) * gas_price;
+        eprintln!(
+            "Balance: {}\nReserved for storage: {}\nDeposits of the previous actions: {}\nEstimated fee: {}",
+            crate::style::amount(crate::common::format_near_amount(account_view.amount)),
+            crate::style::amount(crate::common::format_near_amount(storage_reserve)),
+            crate::style::amount(crate::common::format_near_amount(previous_deposits)),
+            crate::style::amount(crate::common::format_near_amount(estimated_fee)),
+        );
+        let amount = account_view
+            .amount
+            .checked_sub(storage_reserve + previous_deposits + estimated_fee)
+            .ok_or_else(|| "The balance does not cover the storage and the fees".to_string())?;
+        eprintln!(
+            "Amount to transfer: {} ({} yoctoNEAR)",
+            crate::style::amount(crate::common::format_near_amount(amount)),
+            amount
+        );
+        Ok(amount)
+    }
+    pub fn input_memo() -> Option<String> {
+        let memo: String = Input::new()
+            .with_prompt("Enter a memo for this transfer (leave empty to skip)")
+            .allow_empty(true)
             .interact_text()
             .unwrap();
-        NearBalance::from_str(&input).unwrap()
+        if memo.trim().is_empty() {
+            None
+        } else {
+            Some(memo)
+        }
     }
 }
 
+/// A bare `all` transfers everything except what has to stay on the account
 #[derive(Debug)]
-pub struct NearBalance(u128);
+pub enum NearBalance {
+    Amount(crate::amount::AmountExpression),
+    TransferAll,
+}
 
 impl FromStr for NearBalance {
-    type Err = ParseIntError;
+    type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let number: u128 = s.parse().unwrap_or_else(|ParseIntError| -> u128 {
-            let mut s: String = s.to_string().clone();
-            s.make_ascii_uppercase();
-            match s.contains("NEAR") {
-                true => {
-                    let num: u128 = s.trim_matches(char::is_alphabetic).parse().unwrap();
-                    num * 10u128.pow(24)
-                }
-                _ => 0,
-            }
-        });
-        Ok(NearBalance(number))
+        if s.trim().eq_ignore_ascii_case("all") {
+            return Ok(NearBalance::TransferAll);
+        }
+        Ok(NearBalance::Amount(
+            crate::amount::AmountExpression::from_str(s)?,
+        ))
     }
 }
 
@@ -102,6 +319,7 @@ impl From<CliTransferNEARTokensAction> for TransferNEARTokensAction {
         };
         TransferNEARTokensAction {
             amount,
+            memo: item.memo,
             next_action,
         }
     }
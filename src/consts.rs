@@ -1,3 +1,16 @@
 pub const TESTNET_API_SERVER_URL: &str = "https://rpc.testnet.near.org";
 pub const MAINNET_API_SERVER_URL: &str = "https://rpc.mainnet.near.org";
 pub const BETANET_API_SERVER_URL: &str = "https://rpc.betanet.near.org";
+pub const TESTNET_ARCHIVAL_API_SERVER_URL: &str = "https://archival-rpc.testnet.near.org";
+pub const MAINNET_ARCHIVAL_API_SERVER_URL: &str = "https://archival-rpc.mainnet.near.org";
+pub const TESTNET_WALLET_URL: &str = "https://wallet.testnet.near.org";
+pub const MAINNET_WALLET_URL: &str = "https://wallet.near.org";
+pub const BETANET_WALLET_URL: &str = "https://wallet.betanet.near.org";
+pub const MAINNET_NEARBLOCKS_API_URL: &str = "https://api.nearblocks.io";
+pub const TESTNET_NEARBLOCKS_API_URL: &str = "https://api-testnet.nearblocks.io";
+pub const MAINNET_ENHANCED_API_URL: &str = "https://near-mainnet.api.pagoda.co/eapi/v1";
+pub const TESTNET_ENHANCED_API_URL: &str = "https://near-testnet.api.pagoda.co/eapi/v1";
+pub const TESTNET_HELPER_URL: &str = "https://helper.testnet.near.org";
+pub const BETANET_HELPER_URL: &str = "https://helper.betanet.near.org";
+pub const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/near/near-cli-rs/releases/latest";
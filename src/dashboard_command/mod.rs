@@ -0,0 +1,364 @@
+use crossterm::event::{Event, KeyCode};
+use dialoguer::Input;
+use structopt::StructOpt;
+use tui::layout::{Constraint, Direction, Layout};
+use tui::style::{Modifier, Style};
+use tui::widgets::{Block, Borders, List, ListItem, Paragraph, Row, Table, TableState};
+
+use crate::construct_transaction_command::operation_mode::OperationMode;
+use crate::construct_transaction_command::ActionSubcommandDiscriminants;
+
+/// Watch the balances, the recent transactions and the validator status of
+/// accounts together with the gas price, and start transfers and function calls
+/// from the keyboard
+#[derive(Debug)]
+pub struct DashboardCommand {
+    pub account_ids: Vec<String>,
+    pub connection_config: crate::common::ConnectionConfig,
+    pub refresh_interval: u64,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliDashboardCommand {
+    account_ids: Vec<String>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+    /// Seconds between the refreshes
+    #[structopt(long, default_value = "10")]
+    refresh_interval: u64,
+}
+
+impl From<CliDashboardCommand> for DashboardCommand {
+    fn from(item: CliDashboardCommand) -> Self {
+        let account_ids = if item.account_ids.is_empty() {
+            DashboardCommand::input_account_ids()
+        } else {
+            item.account_ids
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        DashboardCommand {
+            account_ids,
+            connection_config,
+            refresh_interval: item.refresh_interval,
+        }
+    }
+}
+
+const RECENT_TRANSACTIONS_LIMIT: usize = 5;
+
+struct AccountStatus {
+    account_id: String,
+    /// The balance and the locked (staked) balance
+    balance: Result<(u128, u128), String>,
+    validator: String,
+    recent_transactions: Result<Vec<crate::indexer::AccountActivity>, String>,
+}
+
+struct Snapshot {
+    gas_price: Result<u128, String>,
+    accounts: Vec<AccountStatus>,
+}
+
+/// What to do after the dashboard is closed
+enum DashboardExit {
+    Quit,
+    Action(String, ActionSubcommandDiscriminants),
+}
+
+type DashboardTerminal = tui::Terminal<tui::backend::CrosstermBackend<std::io::Stdout>>;
+
+impl DashboardCommand {
+    pub async fn process(self) {
+        if self.account_ids.is_empty() {
+            return eprintln!("There are no accounts to watch");
+        }
+        let mut terminal = match enter_terminal() {
+            Ok(terminal) => terminal,
+            Err(err) => return eprintln!("Error opening the dashboard:  {}", err),
+        };
+        let result = self.run(&mut terminal).await;
+        if let Err(err) = leave_terminal(&mut terminal) {
+            eprintln!("Error restoring the terminal:  {}", err);
+        }
+        match result {
+            Ok(DashboardExit::Quit) => {}
+            Ok(DashboardExit::Action(sender_account_id, action)) => {
+                let unsigned_transaction = near_primitives::transaction::Transaction {
+                    signer_id: "".to_string(),
+                    public_key: near_crypto::PublicKey::empty(near_crypto::KeyType::ED25519),
+                    nonce: 0,
+                    receiver_id: "".to_string(),
+                    block_hash: Default::default(),
+                    actions: vec![],
+                };
+                OperationMode::online_with_action(self.connection_config, sender_account_id, action)
+                    .process(unsigned_transaction)
+                    .await
+            }
+            Err(err) => eprintln!("Error running the dashboard:  {}", err),
+        }
+    }
+
+    async fn run(&self, terminal: &mut DashboardTerminal) -> Result<DashboardExit, String> {
+        let rpc_url = self.connection_config.rpc_url();
+        // Without an indexer there are just no recent transactions
+        let indexer = crate::indexer::indexer_for(&self.connection_config);
+        let mut table_state = TableState::default();
+        table_state.select(Some(0));
+        loop {
+            let snapshot = fetch_snapshot(&rpc_url, &indexer, &self.account_ids).await;
+            let next_refresh =
+                std::time::Instant::now() + std::time::Duration::from_secs(self.refresh_interval);
+            loop {
+                draw(
+                    terminal,
+                    &self.connection_config,
+                    &snapshot,
+                    &mut table_state,
+                )?;
+                let timeout = next_refresh.saturating_duration_since(std::time::Instant::now());
+                if !crossterm::event::poll(timeout).map_err(|err| err.to_string())? {
+                    break;
+                }
+                let key = match crossterm::event::read().map_err(|err| err.to_string())? {
+                    Event::Key(key) => key,
+                    _ => continue,
+                };
+                let selected = table_state.selected().unwrap_or_default();
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(DashboardExit::Quit),
+                    KeyCode::Char('r') => break,
+                    KeyCode::Up => table_state.select(Some(selected.saturating_sub(1))),
+                    KeyCode::Down if selected + 1 < self.account_ids.len() => {
+                        table_state.select(Some(selected + 1))
+                    }
+                    KeyCode::Char('s') => {
+                        return Ok(DashboardExit::Action(
+                            self.account_ids[selected].clone(),
+                            ActionSubcommandDiscriminants::TransferNEARTokens,
+                        ))
+                    }
+                    KeyCode::Char('c') => {
+                        return Ok(DashboardExit::Action(
+                            self.account_ids[selected].clone(),
+                            ActionSubcommandDiscriminants::CallFunction,
+                        ))
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    pub fn input_account_ids() -> Vec<String> {
+        let account_ids: String = Input::new()
+            .with_prompt("Enter the accounts to watch (comma-separated)")
+            .interact_text()
+            .unwrap();
+        account_ids
+            .split(',')
+            .map(|account_id| account_id.trim().to_string())
+            .filter(|account_id| !account_id.is_empty())
+            .collect()
+    }
+}
+
+fn enter_terminal() -> Result<DashboardTerminal, String> {
+    crossterm::terminal::enable_raw_mode().map_err(|err| err.to_string())?;
+    let mut stdout = std::io::stdout();
+    crossterm::execute!(stdout, crossterm::terminal::EnterAlternateScreen)
+        .map_err(|err| err.to_string())?;
+    tui::Terminal::new(tui::backend::CrosstermBackend::new(stdout)).map_err(|err| err.to_string())
+}
+
+fn leave_terminal(terminal: &mut DashboardTerminal) -> Result<(), String> {
+    crossterm::terminal::disable_raw_mode().map_err(|err| err.to_string())?;
+    crossterm::execute!(
+        terminal.backend_mut(),
+        crossterm::terminal::LeaveAlternateScreen
+    )
+    .map_err(|err| err.to_string())?;
+    terminal.show_cursor().map_err(|err| err.to_string())
+}
+
+async fn fetch_snapshot(
+    rpc_url: &url::Url,
+    indexer: &Result<Box<dyn crate::indexer::IndexerApi>, String>,
+    account_ids: &[String],
+) -> Snapshot {
+    let gas_price = crate::common::call_json_rpc(rpc_url, "gas_price", serde_json::json!([null]))
+        .await
+        .and_then(|result| {
+            result["gas_price"]
+                .as_str()
+                .and_then(|gas_price| gas_price.parse::<u128>().ok())
+                .ok_or_else(|| "The gas price is missing".to_string())
+        });
+    let validators = crate::common::call_json_rpc(rpc_url, "validators", serde_json::json!([null]))
+        .await
+        .ok();
+    let mut accounts = vec![];
+    for account_id in account_ids {
+        let balance = crate::common::call_json_rpc(
+            rpc_url,
+            "query",
+            serde_json::json!({
+                "request_type": "view_account",
+                "finality": "final",
+                "account_id": account_id,
+            }),
+        )
+        .await
+        .map(|account_view| {
+            let parse = |field: &str| {
+                account_view[field]
+                    .as_str()
+                    .and_then(|amount| amount.parse::<u128>().ok())
+                    .unwrap_or_default()
+            };
+            (parse("amount"), parse("locked"))
+        });
+        let recent_transactions = match indexer {
+            Ok(indexer) => {
+                indexer
+                    .account_activity(account_id, RECENT_TRANSACTIONS_LIMIT)
+                    .await
+            }
+            Err(err) => Err(err.clone()),
+        };
+        accounts.push(AccountStatus {
+            account_id: account_id.clone(),
+            balance,
+            validator: validator_status(validators.as_ref(), account_id),
+            recent_transactions,
+        });
+    }
+    Snapshot {
+        gas_price,
+        accounts,
+    }
+}
+
+fn validator_status(validators: Option<&serde_json::Value>, account_id: &str) -> String {
+    let validators = match validators {
+        Some(validators) => validators,
+        None => return "unknown".to_string(),
+    };
+    let find = |list: &str| {
+        validators[list]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|validator| validator["account_id"].as_str() == Some(account_id))
+    };
+    match find("current_validators") {
+        Some(validator) if validator["is_slashed"].as_bool() == Some(true) => "slashed".to_string(),
+        Some(validator) => format!(
+            "validating, {}/{} blocks",
+            validator["num_produced_blocks"]
+                .as_u64()
+                .unwrap_or_default(),
+            validator["num_expected_blocks"]
+                .as_u64()
+                .unwrap_or_default()
+        ),
+        None if find("next_validators").is_some() => "validating next epoch".to_string(),
+        None => "-".to_string(),
+    }
+}
+
+fn draw(
+    terminal: &mut DashboardTerminal,
+    connection_config: &crate::common::ConnectionConfig,
+    snapshot: &Snapshot,
+    table_state: &mut TableState,
+) -> Result<(), String> {
+    let header = format!(
+        "Network: {}    Gas price: {}",
+        connection_config.rpc_url(),
+        match &snapshot.gas_price {
+            Ok(gas_price) => format!("{} yoctoNEAR", gas_price),
+            Err(err) => err.clone(),
+        }
+    );
+    let rows = snapshot
+        .accounts
+        .iter()
+        .map(|account| {
+            let (balance, locked) = match &account.balance {
+                Ok((balance, locked)) => (
+                    crate::common::format_near_amount(*balance),
+                    crate::common::format_near_amount(*locked),
+                ),
+                Err(_) => ("unavailable".to_string(), String::new()),
+            };
+            Row::new(vec![
+                account.account_id.clone(),
+                balance,
+                locked,
+                account.validator.clone(),
+            ])
+        })
+        .collect::<Vec<_>>();
+    let recent_transactions = match table_state
+        .selected()
+        .and_then(|selected| snapshot.accounts.get(selected))
+        .map(|account| &account.recent_transactions)
+    {
+        Some(Ok(recent_transactions)) => recent_transactions
+            .iter()
+            .map(|activity| {
+                ListItem::new(format!(
+                    "{} {} -> {}",
+                    activity.transaction_hash, activity.signer_id, activity.receiver_id
+                ))
+            })
+            .collect::<Vec<_>>(),
+        Some(Err(err)) => vec![ListItem::new(err.clone())],
+        None => vec![],
+    };
+    terminal
+        .draw(|frame| {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    [
+                        Constraint::Length(3),
+                        Constraint::Min(5),
+                        Constraint::Length(RECENT_TRANSACTIONS_LIMIT as u16 + 2),
+                        Constraint::Length(1),
+                    ]
+                    .as_ref(),
+                )
+                .split(frame.size());
+            frame.render_widget(
+                Paragraph::new(header).block(Block::default().borders(Borders::ALL)),
+                chunks[0],
+            );
+            let table = Table::new(rows)
+                .header(Row::new(vec!["Account", "Balance", "Staked", "Validator"]))
+                .block(Block::default().title("Accounts").borders(Borders::ALL))
+                .widths(&[
+                    Constraint::Percentage(30),
+                    Constraint::Percentage(25),
+                    Constraint::Percentage(20),
+                    Constraint::Percentage(25),
+                ])
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+            frame.render_stateful_widget(table, chunks[1], table_state);
+            frame.render_widget(
+                List::new(recent_transactions).block(
+                    Block::default()
+                        .title("Recent transactions")
+                        .borders(Borders::ALL),
+                ),
+                chunks[2],
+            );
+            frame.render_widget(
+                Paragraph::new("↑/↓ select  s send  c call  r refresh  q quit"),
+                chunks[3],
+            );
+        })
+        .map_err(|err| err.to_string())?;
+    Ok(())
+}
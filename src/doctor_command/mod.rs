@@ -0,0 +1,238 @@
+use structopt::StructOpt;
+
+/// Clock skew (in seconds) against the latest block time that is still fine
+const MAX_CLOCK_SKEW_SECS: i64 = 60;
+
+/// Checks the most common reasons for "it doesn't work" and prints how to fix them
+/// (to stderr); exits with 1 if any check fails
+#[derive(Debug)]
+pub struct DoctorCommand {}
+
+#[derive(Debug, StructOpt)]
+pub struct CliDoctorCommand {}
+
+impl From<CliDoctorCommand> for DoctorCommand {
+    fn from(_: CliDoctorCommand) -> Self {
+        DoctorCommand {}
+    }
+}
+
+impl DoctorCommand {
+    pub async fn process(self) {
+        let mut problems = 0;
+        for (name, result) in DoctorCommand::check_rpc_servers().await {
+            problems += report(&name, result);
+        }
+        report_skipped("Hardware wallets", HARDWARE_WALLETS_SKIPPED);
+        problems += report("Credentials directory", check_credentials_dir());
+        problems += report("Version", check_version().await);
+        eprintln!();
+        if problems == 0 {
+            eprintln!("{}", crate::style::success("Everything looks fine"));
+        } else {
+            eprintln!(
+                "{}",
+                crate::style::warning(format!(
+                    "Found {} problem(s), see the fixes above",
+                    problems
+                ))
+            );
+            std::process::exit(1);
+        }
+    }
+
+    async fn check_rpc_servers() -> Vec<(String, Result<String, String>)> {
+        let mut rpc_urls = vec![
+            (
+                "testnet".to_string(),
+                crate::common::ConnectionConfig::Testnet.rpc_url(),
+            ),
+            (
+                "mainnet".to_string(),
+                crate::common::ConnectionConfig::Mainnet.rpc_url(),
+            ),
+        ];
+        // Betanet is often down for resets, so it is only checked when it has keys
+        let betanet = crate::common::ConnectionConfig::Betanet;
+        if crate::common::get_credentials_home_dir()
+            .join(betanet.dir_name())
+            .is_dir()
+        {
+            rpc_urls.push(("betanet".to_string(), betanet.rpc_url()));
+        }
+        for (name, chain_profile) in crate::config::Config::load().chain_profiles {
+            if let Some(rpc_url) = chain_profile.rpc_url {
+                rpc_urls.push((name, rpc_url));
+            }
+        }
+        if let Some(rpc_url) = std::env::var(crate::common::RPC_URL_ENV_VAR)
+            .ok()
+            .and_then(|rpc_url| url::Url::parse(&rpc_url).ok())
+        {
+            rpc_urls.push((format!("${}", crate::common::RPC_URL_ENV_VAR), rpc_url));
+        }
+        let mut results = vec![];
+        for (name, rpc_url) in rpc_urls {
+            let result = check_rpc_server(&rpc_url).await;
+            results.push((format!("RPC {} ({})", name, rpc_url), result));
+        }
+        results
+    }
+}
+
+/// Prints the outcome of one check and returns the number of problems
+fn report(name: &str, result: Result<String, String>) -> usize {
+    match result {
+        Ok(details) => {
+            eprintln!("{} {}: {}", crate::style::success("[ok]"), name, details);
+            0
+        }
+        Err(fix) => {
+            eprintln!("{} {}: {}", crate::style::warning("[!!]"), name, fix);
+            1
+        }
+    }
+}
+
+/// Prints a check that does not apply to this build, it is not a problem
+fn report_skipped(name: &str, reason: &str) {
+    eprintln!("[--] {}: {}", name, reason);
+}
+
+/// Checks that the node responds and that the local clock agrees with the chain
+async fn check_rpc_server(rpc_url: &url::Url) -> Result<String, String> {
+    let status = crate::common::call_json_rpc(rpc_url, "status", serde_json::json!([]))
+        .await
+        .map_err(|err| {
+            format!(
+                "the node is not reachable ({}); \
+                 check your internet connection, proxy settings and the URL",
+                err
+            )
+        })?;
+    let latest_block_time = status["sync_info"]["latest_block_time"]
+        .as_str()
+        .and_then(parse_rfc3339_secs)
+        .ok_or_else(|| "the node returned an unexpected status response".to_string())?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    let clock_skew = now - latest_block_time;
+    if clock_skew.abs() > MAX_CLOCK_SKEW_SECS {
+        return Err(format!(
+            "the local clock differs from the latest block time by {} seconds; \
+             enable time synchronization (NTP) or check whether the node is syncing",
+            clock_skew
+        ));
+    }
+    Ok(format!(
+        "reachable, node version {}",
+        status["version"]["version"].as_str().unwrap_or("unknown")
+    ))
+}
+
+/// Parses `2020-10-16T12:34:56.123456789Z` into seconds since the Unix epoch
+fn parse_rfc3339_secs(value: &str) -> Option<i64> {
+    let date_time = value.get(0..19)?;
+    let number = |range: std::ops::Range<usize>| date_time.get(range)?.parse::<i64>().ok();
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    // Days from the civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    Some(days * 86_400 + hour * 3_600 + minute * 60 + second)
+}
+
+/// Neither device is supported natively: there is no HID transport in this
+/// build, and the Trezor firmware has no NEAR app to sign with
+const HARDWARE_WALLETS_SKIPPED: &str = "skipped, this build does not sign with Ledger or Trezor \
+     (a device bridge can be plugged in with `sign-external`)";
+
+fn check_credentials_dir() -> Result<String, String> {
+    let credentials_dir = crate::common::get_credentials_home_dir();
+    let metadata = match std::fs::metadata(&credentials_dir) {
+        Ok(metadata) => metadata,
+        Err(_) => {
+            return Ok(format!(
+                "{:?} does not exist yet, it is created with the first saved key",
+                credentials_dir
+            ))
+        }
+    };
+    if !metadata.is_dir() {
+        return Err(format!(
+            "{:?} is not a directory; move it away or pass --credentials-dir",
+            credentials_dir
+        ));
+    }
+    if metadata.permissions().readonly() {
+        return Err(format!(
+            "{:?} is read-only, so new keys cannot be saved; make it writable for your user",
+            credentials_dir
+        ));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        if metadata.permissions().mode() & 0o077 != 0 {
+            return Err(format!(
+                "{:?} is accessible by other users; run `chmod -R go-rwx {}`",
+                credentials_dir,
+                credentials_dir.display()
+            ));
+        }
+    }
+    Ok(format!("{:?}", credentials_dir))
+}
+
+async fn check_version() -> Result<String, String> {
+    let current_version = env!("CARGO_PKG_VERSION");
+    let mut response = match actix_web::client::Client::default()
+        .get(crate::consts::LATEST_RELEASE_URL)
+        .header("User-Agent", "near-cli")
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(err) => {
+            return Ok(format!(
+                "{} (could not check for a newer release: {:?})",
+                current_version, err
+            ))
+        }
+    };
+    let latest_release: serde_json::Value = match response.json().await {
+        Ok(latest_release) => latest_release,
+        Err(err) => {
+            return Ok(format!(
+                "{} (could not check for a newer release: {:?})",
+                current_version, err
+            ))
+        }
+    };
+    let latest_version = latest_release["tag_name"]
+        .as_str()
+        .unwrap_or_default()
+        .trim_start_matches('v');
+    let version_numbers = |version: &str| {
+        version
+            .split('.')
+            .map(|number| number.parse::<u64>().unwrap_or_default())
+            .collect::<Vec<_>>()
+    };
+    if !latest_version.is_empty()
+        && version_numbers(latest_version) > version_numbers(current_version)
+    {
+        return Err(format!(
+            "{} is outdated, upgrade to {} as the problem might be already fixed",
+            current_version, latest_version
+        ));
+    }
+    Ok(format!("{} is the latest release", current_version))
+}
@@ -0,0 +1,48 @@
+use near_primitives::borsh::BorshSerialize;
+
+/// A partially constructed transaction saved from the construct-transaction
+/// wizard, so `construct-transaction --from-draft` can continue it later
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Draft {
+    /// The selected RPC server, none in the offline mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<url::Url>,
+    pub unsigned_transaction: String,
+    /// The actions added so far, for a human reader
+    pub changes: Vec<String>,
+}
+
+impl Draft {
+    pub fn new(
+        unsigned_transaction: &near_primitives::transaction::Transaction,
+        rpc_url: Option<url::Url>,
+    ) -> Self {
+        Draft {
+            rpc_url,
+            unsigned_transaction: near_primitives::serialize::to_base64(
+                unsigned_transaction
+                    .try_to_vec()
+                    .expect("Transaction is not expected to fail on serialization"),
+            ),
+            changes: crate::plan::describe_changes(unsigned_transaction),
+        }
+    }
+    pub fn unsigned_transaction(
+        &self,
+    ) -> Result<near_primitives::transaction::Transaction, String> {
+        crate::common::from_base64_borsh(&self.unsigned_transaction)
+            .map_err(|err| format!("Invalid transaction in the draft: {}", err))
+    }
+    pub fn save(&self, path: &std::path::Path) -> Result<(), String> {
+        let buf = serde_json::to_string_pretty(self)
+            .map_err(|err| format!("Failed to serialize the draft: {:?}", err))?;
+        std::fs::write(path, buf)
+            .map_err(|err| format!("Failed to write the file {:?}: {:?}", path, err))
+    }
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let buf = std::fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read the file {:?}: {:?}", path, err))?;
+        serde_json::from_str(&buf)
+            .map_err(|err| format!("Failed to parse the draft {:?}: {:?}", path, err))
+    }
+}
@@ -0,0 +1,113 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+/// Print `export NEAR_CLI_*` lines for `eval`, so the following commands of a
+/// shell script use this network and account without asking for them
+#[derive(Debug)]
+pub struct EnvCommand {
+    pub connection_config: crate::common::ConnectionConfig,
+    pub account_id: String,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliEnvCommand {
+    /// testnet, mainnet or betanet
+    #[structopt(long)]
+    network: Option<String>,
+    #[structopt(long)]
+    account: Option<String>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliEnvCommand> for EnvCommand {
+    fn from(item: CliEnvCommand) -> Self {
+        let connection_config = match item
+            .network
+            .as_deref()
+            .and_then(crate::common::ConnectionConfig::from_network_name)
+        {
+            Some(connection_config) => connection_config,
+            None => {
+                if let Some(network) = &item.network {
+                    eprintln!("Unknown network <{}>", network);
+                }
+                crate::common::ConnectionConfig::from(item.connection_config)
+            }
+        };
+        let account_id: String = match item.account {
+            Some(cli_account) => cli_account,
+            None => EnvCommand::input_account_id(),
+        };
+        EnvCommand {
+            connection_config,
+            account_id,
+        }
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+impl EnvCommand {
+    pub async fn process(self) {
+        // Everything except the export lines goes to stderr, so the output can be eval'ed
+        let rpc_url = self.connection_config.rpc_url();
+        match crate::common::account_exists(&rpc_url, &self.account_id).await {
+            Ok(true) => {}
+            Ok(false) => {
+                return eprintln!(
+                    "Account <{}> does not exist on {}",
+                    self.account_id, &rpc_url
+                )
+            }
+            Err(err) => {
+                return eprintln!("Error checking the account <{}>:  {}", self.account_id, err)
+            }
+        }
+        let keys = crate::common::read_access_keys_from_keychain(
+            &self.connection_config.dir_name(),
+            &self.account_id,
+        );
+        if keys.is_empty() {
+            eprintln!(
+                "{}",
+                crate::style::warning(format!(
+                    "There are no keys for <{}> in {:?}, signing will ask for a key",
+                    self.account_id,
+                    crate::common::get_credentials_home_dir()
+                ))
+            );
+        }
+
+        println!(
+            "export {}={}",
+            crate::common::RPC_URL_ENV_VAR,
+            shell_quote(rpc_url.as_str())
+        );
+        println!(
+            "export {}={}",
+            crate::common::ACCOUNT_ID_ENV_VAR,
+            shell_quote(&self.account_id)
+        );
+        for env_var in &[
+            crate::config::CONFIG_FILE_ENV_VAR,
+            crate::common::CREDENTIALS_DIR_ENV_VAR,
+        ] {
+            if let Some(value) = std::env::var_os(env_var) {
+                println!(
+                    "export {}={}",
+                    env_var,
+                    shell_quote(&value.to_string_lossy())
+                );
+            }
+        }
+    }
+    pub fn input_account_id() -> String {
+        Input::new()
+            .with_prompt("What is the account ID?")
+            .interact_text()
+            .unwrap()
+    }
+}
@@ -0,0 +1,51 @@
+use dialoguer::{theme::ColorfulTheme, Select};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct CliExploreCommand {}
+
+/// Walks down the clap command tree from `app` and returns the arguments of the
+/// chosen command, e.g. `["near-cli", "utils", "wallet-url"]`
+pub fn choose_command_path(app: &structopt::clap::App) -> Vec<String> {
+    let mut path: Vec<&structopt::clap::App> = vec![];
+    loop {
+        let current = path.last().copied().unwrap_or(app);
+        let subcommands = current
+            .p
+            .subcommands
+            .iter()
+            .filter(|subcommand| subcommand.get_name() != "explore")
+            .collect::<Vec<_>>();
+        let command_line = std::iter::once(app.get_name())
+            .chain(path.iter().map(|subcommand| subcommand.get_name()))
+            .map(String::from)
+            .collect::<Vec<_>>();
+        if subcommands.is_empty() {
+            return command_line;
+        }
+        let mut items = subcommands
+            .iter()
+            .map(|subcommand| match subcommand.p.meta.about {
+                Some(about) => format!("{}  -  {}", subcommand.get_name(), about),
+                None => subcommand.get_name().to_string(),
+            })
+            .collect::<Vec<_>>();
+        if !path.is_empty() {
+            items.push(format!("Start `{}` here", command_line.join(" ")));
+            items.push("Back".to_string());
+        }
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(command_line.join(" "))
+            .items(&items)
+            .default(0)
+            .interact()
+            .unwrap();
+        match selection.checked_sub(subcommands.len()) {
+            None => path.push(subcommands[selection]),
+            Some(0) => return command_line,
+            Some(_) => {
+                path.pop();
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+/// `<price of one NEAR> <currency>`, set from the `fiat_price` config before the
+/// command runs (or by hand, to skip the price source)
+pub const FIAT_PRICE_ENV_VAR: &str = "NEAR_CLI_FIAT_PRICE";
+
+/// Resolves the configured price source once per run, so the amounts can be
+/// formatted synchronously; a failing source only leaves out the fiat amounts
+pub async fn load_fiat_price(fiat_price: &crate::config::FiatPrice) {
+    if std::env::var_os(FIAT_PRICE_ENV_VAR).is_some() {
+        return;
+    }
+    match fetch_rate(fiat_price).await {
+        Ok(rate) => std::env::set_var(
+            FIAT_PRICE_ENV_VAR,
+            format!("{} {}", rate, fiat_price.currency),
+        ),
+        Err(err) => eprintln!(
+            "{} the {} amounts are not shown:  {}",
+            crate::style::warning("Warning:"),
+            fiat_price.currency,
+            err
+        ),
+    }
+}
+
+async fn fetch_rate(fiat_price: &crate::config::FiatPrice) -> Result<f64, String> {
+    if let Some(rate) = fiat_price.rate {
+        return Ok(rate);
+    }
+    let url = fiat_price
+        .url
+        .as_ref()
+        .ok_or_else(|| "The fiat price needs either a rate or a URL".to_string())?;
+    let mut response = actix_web::client::Client::default()
+        .get(url.as_str())
+        .send()
+        .await
+        .map_err(|err| format!("Error fetching the price from {}: {:?}", url, err))?;
+    let response: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|err| format!("The price source did not return JSON: {:?}", err))?;
+    let price = response
+        .pointer(fiat_price.json_pointer.as_deref().unwrap_or_default())
+        .ok_or_else(|| format!("There is no price at {:?}", fiat_price.json_pointer))?;
+    match price {
+        serde_json::Value::String(price) => price.parse().ok(),
+        price => price.as_f64(),
+    }
+    .ok_or_else(|| format!("The price {} is not a number", price))
+}
+
+/// The amount in the configured fiat currency, e.g. `≈ 1,234.56 USD`; always
+/// approximate, so never use it for machine-readable output
+pub fn format_fiat_amount(yocto_near: u128) -> Option<String> {
+    let fiat_price = std::env::var(FIAT_PRICE_ENV_VAR).ok()?;
+    let mut fiat_price = fiat_price.splitn(2, ' ');
+    let rate: f64 = fiat_price.next()?.parse().ok()?;
+    let currency = fiat_price.next()?;
+    let cents = (yocto_near as f64 / 1e24 * rate * 100.0).round() as u128;
+    Some(format!(
+        "≈ {} {}",
+        crate::style::format_decimal(cents / 100, &format!("{:02}", cents % 100)),
+        currency
+    ))
+}
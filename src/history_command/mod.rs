@@ -0,0 +1,90 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+use strum::VariantNames;
+
+/// List the transactions recorded in the local audit log with their tags and
+/// notes (`--tag` and `--note` of the command that made them)
+#[derive(Debug)]
+pub struct HistoryCommand {
+    pub tags: Vec<String>,
+    pub format: crate::common::OutputFormat,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliHistoryCommand {
+    /// Only the transactions with this tag (repeatable, all of them must match)
+    #[structopt(long = "tag")]
+    tags: Vec<String>,
+    #[structopt(long, default_value = "plaintext", possible_values = crate::common::OutputFormat::VARIANTS)]
+    format: crate::common::OutputFormat,
+}
+
+impl From<CliHistoryCommand> for HistoryCommand {
+    fn from(item: CliHistoryCommand) -> Self {
+        HistoryCommand {
+            tags: item.tags,
+            format: item.format,
+        }
+    }
+}
+
+impl HistoryCommand {
+    pub fn process(self) {
+        let entries = crate::common::read_audit_log()
+            .into_iter()
+            .filter(|entry| entry["transaction_hash"].is_string())
+            .filter(|entry| {
+                let entry_tags = entry["tags"].as_array().cloned().unwrap_or_default();
+                self.tags
+                    .iter()
+                    .all(|tag| entry_tags.contains(&serde_json::json!(tag)))
+            })
+            .collect::<Vec<_>>();
+        match self.format {
+            crate::common::OutputFormat::Plaintext => {
+                if entries.is_empty() {
+                    return eprintln!("There are no matching transactions in the local history");
+                }
+                for entry in &entries {
+                    let tags = entry["tags"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|tag| tag.as_str())
+                        .collect::<Vec<_>>();
+                    println!(
+                        "{} {:<18} {} <{}> -> <{}>{}{}",
+                        entry["timestamp"].as_u64().unwrap_or_default(),
+                        entry["event"].as_str().unwrap_or_default(),
+                        crate::style::hash(entry["transaction_hash"].as_str().unwrap_or_default()),
+                        entry["signer_id"].as_str().unwrap_or_default(),
+                        entry["receiver_id"].as_str().unwrap_or("?"),
+                        if tags.is_empty() {
+                            String::new()
+                        } else {
+                            format!(" [{}]", tags.join(", "))
+                        },
+                        match entry["note"].as_str() {
+                            Some(note) => format!(" {}", note),
+                            None => String::new(),
+                        }
+                    );
+                }
+            }
+            crate::common::OutputFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&entries).unwrap())
+            }
+        }
+    }
+    pub fn input_tags() -> Vec<String> {
+        let tags: String = Input::new()
+            .with_prompt("Only the transactions with the tags (comma-separated, empty for all)")
+            .allow_empty(true)
+            .interact_text()
+            .unwrap();
+        tags.split(',')
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect()
+    }
+}
@@ -0,0 +1,253 @@
+use futures::future::LocalBoxFuture;
+
+/// A transaction the account took part in
+#[derive(Debug, serde::Serialize)]
+pub struct AccountActivity {
+    pub transaction_hash: String,
+    pub signer_id: String,
+    pub receiver_id: String,
+    /// Nanoseconds since the Unix epoch
+    pub block_timestamp: Option<u64>,
+}
+
+/// The queries RPC nodes cannot answer, served by an indexer chosen in the
+/// `indexer` section of the config file
+pub trait IndexerApi {
+    fn account_activity<'a>(
+        &'a self,
+        account_id: &'a str,
+        limit: usize,
+    ) -> LocalBoxFuture<'a, Result<Vec<AccountActivity>, String>>;
+
+    fn accounts_by_public_key<'a>(
+        &'a self,
+        public_key: &'a str,
+    ) -> LocalBoxFuture<'a, Result<Vec<String>, String>>;
+
+    /// Sub-accounts are found among the receivers of the account's transactions
+    fn sub_accounts<'a>(
+        &'a self,
+        account_id: &'a str,
+    ) -> LocalBoxFuture<'a, Result<Vec<String>, String>> {
+        Box::pin(async move {
+            let suffix = format!(".{}", account_id);
+            let mut sub_accounts = self
+                .account_activity(account_id, SUB_ACCOUNTS_SCAN_LIMIT)
+                .await?
+                .into_iter()
+                .map(|activity| activity.receiver_id)
+                .filter(|receiver_id| receiver_id.ends_with(&suffix))
+                .collect::<Vec<_>>();
+            sub_accounts.sort();
+            sub_accounts.dedup();
+            Ok(sub_accounts)
+        })
+    }
+}
+
+const SUB_ACCOUNTS_SCAN_LIMIT: usize = 1000;
+
+pub struct NearBlocks {
+    pub api_url: url::Url,
+    pub api_key: Option<String>,
+}
+
+pub struct EnhancedApi {
+    pub api_url: url::Url,
+    pub api_key: Option<String>,
+}
+
+/// The indexer of the selected network, `NearBlocks` unless the config says
+/// otherwise
+pub fn indexer_for(
+    connection_config: &crate::common::ConnectionConfig,
+) -> Result<Box<dyn IndexerApi>, String> {
+    let indexer_config = crate::config::Config::load().indexer;
+    let default_api_url = |mainnet_url: &str, testnet_url: &str| match connection_config {
+        crate::common::ConnectionConfig::Mainnet => Ok(url::Url::parse(mainnet_url).unwrap()),
+        crate::common::ConnectionConfig::Testnet => Ok(url::Url::parse(testnet_url).unwrap()),
+        _ => Err(
+            "There is no default indexer for this network, set `indexer.url` in the config file"
+                .to_string(),
+        ),
+    };
+    match indexer_config.backend {
+        crate::config::IndexerBackend::NearBlocks => Ok(Box::new(NearBlocks {
+            api_url: match indexer_config.url {
+                Some(url) => url,
+                None => default_api_url(
+                    crate::consts::MAINNET_NEARBLOCKS_API_URL,
+                    crate::consts::TESTNET_NEARBLOCKS_API_URL,
+                )?,
+            },
+            api_key: indexer_config.api_key,
+        })),
+        crate::config::IndexerBackend::EnhancedApi => Ok(Box::new(EnhancedApi {
+            api_url: match indexer_config.url {
+                Some(url) => url,
+                None => default_api_url(
+                    crate::consts::MAINNET_ENHANCED_API_URL,
+                    crate::consts::TESTNET_ENHANCED_API_URL,
+                )?,
+            },
+            api_key: indexer_config.api_key,
+        })),
+    }
+}
+
+async fn get_json(
+    api_url: &url::Url,
+    path: &str,
+    api_key_header: Option<(&str, &str)>,
+) -> Result<serde_json::Value, String> {
+    let url = format!("{}/{}", api_url.as_str().trim_end_matches('/'), path);
+    let mut request = actix_web::client::Client::default().get(&url);
+    if let Some((header_name, api_key)) = api_key_header {
+        request = request.header(header_name, api_key);
+    }
+    let mut response = request.send().await.map_err(|err| format!("{:?}", err))?;
+    if !response.status().is_success() {
+        return Err(format!("{} responded with {}", url, response.status()));
+    }
+    response
+        .json()
+        .limit(64 * 1024 * 1024)
+        .await
+        .map_err(|err| format!("{:?}", err))
+}
+
+fn string_field(value: &serde_json::Value, names: &[&str]) -> String {
+    names
+        .iter()
+        .find_map(|name| value[*name].as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn timestamp_field(value: &serde_json::Value, name: &str) -> Option<u64> {
+    match &value[name] {
+        serde_json::Value::String(timestamp) => timestamp.parse().ok(),
+        timestamp => timestamp.as_u64(),
+    }
+}
+
+impl NearBlocks {
+    fn api_key_header(&self) -> Option<(&str, &str)> {
+        self.api_key
+            .as_deref()
+            .map(|api_key| ("Authorization", api_key))
+    }
+}
+
+impl IndexerApi for NearBlocks {
+    fn account_activity<'a>(
+        &'a self,
+        account_id: &'a str,
+        limit: usize,
+    ) -> LocalBoxFuture<'a, Result<Vec<AccountActivity>, String>> {
+        Box::pin(async move {
+            let response = get_json(
+                &self.api_url,
+                &format!("v1/account/{}/txns?per_page={}", account_id, limit),
+                self.api_key_header(),
+            )
+            .await?;
+            Ok(response["txns"]
+                .as_array()
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+                .iter()
+                .map(|txn| AccountActivity {
+                    transaction_hash: string_field(txn, &["transaction_hash"]),
+                    signer_id: string_field(txn, &["predecessor_account_id", "signer_account_id"]),
+                    receiver_id: string_field(txn, &["receiver_account_id"]),
+                    block_timestamp: timestamp_field(txn, "block_timestamp"),
+                })
+                .collect())
+        })
+    }
+
+    fn accounts_by_public_key<'a>(
+        &'a self,
+        public_key: &'a str,
+    ) -> LocalBoxFuture<'a, Result<Vec<String>, String>> {
+        Box::pin(async move {
+            let response = get_json(
+                &self.api_url,
+                &format!("v1/kitwallet/publicKey/{}/accounts", public_key),
+                self.api_key_header(),
+            )
+            .await?;
+            Ok(response
+                .as_array()
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|account_id| account_id.as_str().map(String::from))
+                .collect())
+        })
+    }
+}
+
+impl EnhancedApi {
+    fn api_key_header(&self) -> Option<(&str, &str)> {
+        self.api_key
+            .as_deref()
+            .map(|api_key| ("x-api-key", api_key))
+    }
+}
+
+impl IndexerApi for EnhancedApi {
+    fn account_activity<'a>(
+        &'a self,
+        account_id: &'a str,
+        limit: usize,
+    ) -> LocalBoxFuture<'a, Result<Vec<AccountActivity>, String>> {
+        Box::pin(async move {
+            let response = get_json(
+                &self.api_url,
+                &format!("accounts/{}/activity?limit={}", account_id, limit),
+                self.api_key_header(),
+            )
+            .await?;
+            Ok(response["items"]
+                .as_array()
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+                .iter()
+                .map(|item| AccountActivity {
+                    transaction_hash: string_field(item, &["transaction_hash", "receipt_id"]),
+                    signer_id: string_field(item, &["signer_id", "involved_account_id"]),
+                    receiver_id: string_field(item, &["receiver_id", "involved_account_id"]),
+                    block_timestamp: timestamp_field(item, "timestamp"),
+                })
+                .collect())
+        })
+    }
+
+    fn accounts_by_public_key<'a>(
+        &'a self,
+        public_key: &'a str,
+    ) -> LocalBoxFuture<'a, Result<Vec<String>, String>> {
+        Box::pin(async move {
+            let response = get_json(
+                &self.api_url,
+                &format!("public_key/{}/accounts", public_key),
+                self.api_key_header(),
+            )
+            .await?;
+            Ok(response["accounts"]
+                .as_array()
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|account| {
+                    account["account_id"]
+                        .as_str()
+                        .or_else(|| account.as_str())
+                        .map(String::from)
+                })
+                .collect())
+        })
+    }
+}
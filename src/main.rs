@@ -1,13 +1,46 @@
 use dialoguer::{theme::ColorfulTheme, Select};
 use structopt::StructOpt;
-use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
+use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator, VariantNames};
 
+mod agent_command;
+mod amount;
+mod arg_compat;
+mod borsh_schema;
+mod campaign;
+mod catalog;
+mod catalog_command;
 mod common;
+mod config;
+mod doctor_command;
+mod draft;
 mod utils_command;
 use utils_command::{CliUtilType, UtilList, UtilType};
 mod construct_transaction_command;
 mod consts;
+mod dashboard_command;
+mod env_command;
+mod explore_command;
+mod fiat;
+mod history_command;
+mod indexer;
+mod master_password;
+mod plan;
+mod protocol_config;
+mod public_key_selector;
+mod qr;
+mod rpc_benchmark;
+mod signer_command;
+mod style;
+mod view_command;
+use agent_command::{AgentCommand, CliAgentCommand};
+use catalog_command::{CatalogAction, CatalogCommand, CliCatalogCommand};
 use construct_transaction_command::operation_mode::{CliOperationMode, Mode, OperationMode};
+use dashboard_command::{CliDashboardCommand, DashboardCommand};
+use doctor_command::{CliDoctorCommand, DoctorCommand};
+use env_command::{CliEnvCommand, EnvCommand};
+use history_command::{CliHistoryCommand, HistoryCommand};
+use signer_command::{CliSignerCommand, SignerAction, SignerCommand};
+use view_command::{CliViewType, ViewList, ViewType};
 
 #[derive(Debug)]
 struct Args {
@@ -16,12 +49,56 @@ struct Args {
 
 #[derive(Debug, Default, StructOpt)]
 struct CliArgs {
+    /// Print secret keys and seed phrases without masking them
+    #[structopt(long)]
+    show_secrets: bool,
+    /// Stream progress events as JSON Lines (`jsonl`)
+    #[structopt(long, default_value = "plaintext", possible_values = crate::common::ProgressOutputFormat::VARIANTS)]
+    output: crate::common::ProgressOutputFormat,
+    /// Use this config file instead of the default one
+    #[structopt(long)]
+    config: Option<std::path::PathBuf>,
+    /// Use this directory instead of ~/.near-credentials
+    #[structopt(long)]
+    credentials_dir: Option<std::path::PathBuf>,
+    /// How many seconds to wait for the credentials and access key locks held
+    /// by other near-cli processes
+    #[structopt(long, default_value = "10")]
+    lock_timeout: u64,
+    /// Give up after this long (e.g. `120s`), with exit code 124 and a JSON
+    /// summary of the last progress event, for unattended runs
+    #[structopt(long, parse(try_from_str = crate::common::parse_duration))]
+    max_duration: Option<std::time::Duration>,
+    /// Tag the transactions of this command in the local history (repeatable)
+    #[structopt(long = "tag", env = crate::common::TAGS_ENV_VAR, use_delimiter = true)]
+    tags: Vec<String>,
+    /// A note for the transactions of this command in the local history
+    #[structopt(long, env = crate::common::NOTE_ENV_VAR)]
+    note: Option<String>,
     #[structopt(subcommand)]
     subcommand: Option<CliCommand>,
 }
 
 impl From<CliArgs> for Args {
     fn from(item: CliArgs) -> Self {
+        if let Some(config) = &item.config {
+            std::env::set_var(crate::config::CONFIG_FILE_ENV_VAR, config);
+        }
+        if let Some(credentials_dir) = &item.credentials_dir {
+            std::env::set_var(crate::common::CREDENTIALS_DIR_ENV_VAR, credentials_dir);
+        }
+        if !item.tags.is_empty() {
+            std::env::set_var(crate::common::TAGS_ENV_VAR, item.tags.join(","));
+        }
+        if let Some(note) = &item.note {
+            std::env::set_var(crate::common::NOTE_ENV_VAR, note);
+        }
+        crate::common::set_show_secrets(item.show_secrets);
+        crate::common::set_lock_timeout(std::time::Duration::from_secs(item.lock_timeout));
+        crate::common::set_progress_output_format(&item.output);
+        let config = crate::config::Config::load();
+        crate::style::set_theme(&config.theme);
+        crate::style::set_number_format(&config.number_format);
         let subcommand = match item.subcommand {
             Some(cli_subcommand) => ArgsCommand::from(cli_subcommand),
             None => ArgsCommand::choose_command(),
@@ -32,6 +109,9 @@ impl From<CliArgs> for Args {
 
 impl Args {
     async fn process(self) {
+        if let Some(fiat_price) = &crate::config::Config::load().fiat_price {
+            crate::fiat::load_fiat_price(fiat_price).await;
+        }
         match self.subcommand {
             ArgsCommand::ConstructTransaction(mode) => {
                 let unsigned_transaction = near_primitives::transaction::Transaction {
@@ -44,7 +124,15 @@ impl Args {
                 };
                 mode.process(unsigned_transaction).await;
             }
-            ArgsCommand::Utils(util_type) => util_type.process(),
+            ArgsCommand::Utils(util_type) => util_type.process().await,
+            ArgsCommand::View(view_type) => view_type.process().await,
+            ArgsCommand::Env(env_command) => env_command.process().await,
+            ArgsCommand::Doctor(doctor_command) => doctor_command.process().await,
+            ArgsCommand::Signer(signer_command) => signer_command.process().await,
+            ArgsCommand::Agent(agent_command) => agent_command.process().await,
+            ArgsCommand::Dashboard(dashboard_command) => dashboard_command.process().await,
+            ArgsCommand::Catalog(catalog_command) => catalog_command.process(),
+            ArgsCommand::History(history_command) => history_command.process(),
         }
     }
 }
@@ -53,6 +141,16 @@ impl Args {
 pub enum CliCommand {
     ConstructTransaction(CliOperationMode),
     Utils(CliUtilType),
+    View(CliViewType),
+    Env(CliEnvCommand),
+    Doctor(CliDoctorCommand),
+    Signer(CliSignerCommand),
+    Agent(CliAgentCommand),
+    Dashboard(CliDashboardCommand),
+    Catalog(CliCatalogCommand),
+    History(CliHistoryCommand),
+    /// Browse all the commands with their descriptions and start one of them
+    Explore(crate::explore_command::CliExploreCommand),
 }
 
 #[derive(Debug, EnumDiscriminants)]
@@ -62,6 +160,22 @@ pub enum ArgsCommand {
     ConstructTransaction(OperationMode),
     #[strum_discriminants(strum(message = "Helpers"))]
     Utils(UtilType),
+    #[strum_discriminants(strum(message = "View data from the network"))]
+    View(ViewType),
+    #[strum_discriminants(strum(message = "Export a network and an account for shell scripts"))]
+    Env(EnvCommand),
+    #[strum_discriminants(strum(message = "Troubleshoot the setup"))]
+    Doctor(DoctorCommand),
+    #[strum_discriminants(strum(message = "Sign transactions for other machines"))]
+    Signer(SignerCommand),
+    #[strum_discriminants(strum(message = "Keep the keys unlocked for scripts (signing agent)"))]
+    Agent(AgentCommand),
+    #[strum_discriminants(strum(message = "Watch accounts in a live dashboard"))]
+    Dashboard(DashboardCommand),
+    #[strum_discriminants(strum(message = "Import catalogs of predefined contract operations"))]
+    Catalog(CatalogCommand),
+    #[strum_discriminants(strum(message = "List the transactions made from this machine"))]
+    History(HistoryCommand),
 }
 
 impl From<CliCommand> for ArgsCommand {
@@ -75,13 +189,52 @@ impl From<CliCommand> for ArgsCommand {
                 let util_type = UtilType::from(cli_util_type);
                 ArgsCommand::Utils(util_type)
             }
+            CliCommand::View(cli_view_type) => {
+                let view_type = ViewType::from(cli_view_type);
+                ArgsCommand::View(view_type)
+            }
+            CliCommand::Env(cli_env_command) => {
+                let env_command = EnvCommand::from(cli_env_command);
+                ArgsCommand::Env(env_command)
+            }
+            CliCommand::Doctor(cli_doctor_command) => {
+                let doctor_command = DoctorCommand::from(cli_doctor_command);
+                ArgsCommand::Doctor(doctor_command)
+            }
+            CliCommand::Signer(cli_signer_command) => {
+                let signer_command = SignerCommand::from(cli_signer_command);
+                ArgsCommand::Signer(signer_command)
+            }
+            CliCommand::Agent(cli_agent_command) => {
+                let agent_command = AgentCommand::from(cli_agent_command);
+                ArgsCommand::Agent(agent_command)
+            }
+            CliCommand::Dashboard(cli_dashboard_command) => {
+                let dashboard_command = DashboardCommand::from(cli_dashboard_command);
+                ArgsCommand::Dashboard(dashboard_command)
+            }
+            CliCommand::Catalog(cli_catalog_command) => {
+                let catalog_command = CatalogCommand::from(cli_catalog_command);
+                ArgsCommand::Catalog(catalog_command)
+            }
+            CliCommand::History(cli_history_command) => {
+                let history_command = HistoryCommand::from(cli_history_command);
+                ArgsCommand::History(history_command)
+            }
+            CliCommand::Explore(_) => {
+                let command_path = crate::explore_command::choose_command_path(&CliArgs::clap());
+                match CliArgs::from_iter(command_path).subcommand {
+                    Some(cli_subcommand) => ArgsCommand::from(cli_subcommand),
+                    None => ArgsCommand::choose_command(),
+                }
+            }
         }
     }
 }
 
 impl ArgsCommand {
     pub fn choose_command() -> Self {
-        println!();
+        eprintln!();
         let variants = ArgsCommandDiscriminants::iter().collect::<Vec<_>>();
         let commands = variants
             .iter()
@@ -93,6 +246,10 @@ impl ArgsCommand {
             .default(0)
             .interact()
             .unwrap();
+        crate::common::emit_event(
+            "prompt_answered",
+            serde_json::json!({ "prompt": "Choose your action", "answer": commands[selection] }),
+        );
         match variants[selection] {
             ArgsCommandDiscriminants::ConstructTransaction => {
                 Self::ConstructTransaction(OperationMode {
@@ -102,15 +259,56 @@ impl ArgsCommand {
             ArgsCommandDiscriminants::Utils => Self::Utils(UtilType {
                 util: UtilList::choose_util(),
             }),
+            ArgsCommandDiscriminants::View => {
+                crate::common::set_view_finality(&crate::config::Config::load().view_finality);
+                Self::View(ViewType {
+                    query: ViewList::choose_query(),
+                })
+            }
+            ArgsCommandDiscriminants::Env => Self::Env(EnvCommand {
+                connection_config: crate::common::ConnectionConfig::input_connection_config(),
+                account_id: EnvCommand::input_account_id(),
+            }),
+            ArgsCommandDiscriminants::Doctor => Self::Doctor(DoctorCommand {}),
+            ArgsCommandDiscriminants::Signer => Self::Signer(SignerCommand {
+                action: SignerAction::choose_action(),
+            }),
+            ArgsCommandDiscriminants::Agent => Self::Agent(AgentCommand {
+                allowed_receivers: AgentCommand::input_allowed_receivers(),
+                max_deposit: AgentCommand::input_max_deposit(),
+                allow_account_changes: AgentCommand::input_allow_account_changes(),
+            }),
+            ArgsCommandDiscriminants::Dashboard => Self::Dashboard(DashboardCommand {
+                account_ids: DashboardCommand::input_account_ids(),
+                connection_config: crate::common::ConnectionConfig::input_connection_config(),
+                refresh_interval: 10,
+            }),
+            ArgsCommandDiscriminants::Catalog => Self::Catalog(CatalogCommand {
+                action: CatalogAction::choose_action(),
+            }),
+            ArgsCommandDiscriminants::History => Self::History(HistoryCommand {
+                tags: HistoryCommand::input_tags(),
+                format: crate::common::OutputFormat::Plaintext,
+            }),
         }
     }
 }
 
 fn main() {
-    let cli = CliArgs::from_args();
+    let started_at = std::time::Instant::now();
+    let cli = match CliArgs::from_iter_safe(crate::arg_compat::upgrade_args(std::env::args_os())) {
+        Ok(cli) => cli,
+        Err(err) => crate::arg_compat::exit_with_suggestions(&CliArgs::clap(), err),
+    };
+    let max_duration = cli.max_duration;
     let args = Args::from(cli);
 
-    actix::System::builder()
-        .build()
-        .block_on(async move { args.process().await });
+    actix::System::builder().build().block_on(async move {
+        match max_duration {
+            Some(max_duration) => {
+                crate::common::run_with_max_duration(args.process(), started_at, max_duration).await
+            }
+            None => args.process().await,
+        }
+    });
 }
@@ -0,0 +1,320 @@
+/// Stretches the password, so a stolen credentials directory is slow to brute-force
+const PBKDF2_ITERATIONS: usize = 600_000;
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+/// Encrypted with the derived key, to tell a wrong password from a broken key file
+const CHECK_PLAINTEXT: &[u8] = b"near-cli master password";
+const PASSWORD_ATTEMPTS: usize = 3;
+const DEFAULT_UNLOCK_TIMEOUT_SECS: u64 = 15 * 60;
+
+/// The optional master password: when it is set, the private keys in the
+/// credentials directory are encrypted with AES-256-GCM under a key derived
+/// from it, and only the salt and an encrypted check value are stored
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct MasterPassword {
+    iterations: usize,
+    salt: String,
+    check: String,
+}
+
+/// The key derived from the master password once it is entered, shared by all
+/// the threads of the process (the agent and `signer serve` sign on their own)
+static UNLOCKED_KEY: std::sync::atomic::AtomicPtr<[u8; KEY_LEN]> =
+    std::sync::atomic::AtomicPtr::new(std::ptr::null_mut());
+/// Set after the attempts run out, so the remaining keys are not asked for again
+static UNLOCK_FAILED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+fn path() -> std::path::PathBuf {
+    crate::common::get_credentials_home_dir().join("master_password.json")
+}
+
+pub fn is_set() -> bool {
+    path().exists()
+}
+
+fn load() -> Result<MasterPassword, String> {
+    let path = path();
+    let buf = std::fs::read_to_string(&path)
+        .map_err(|err| format!("Failed to read the file {:?}: {:?}", path, err))?;
+    serde_json::from_str(&buf).map_err(|err| format!("The file {:?} is broken: {}", path, err))
+}
+
+fn derive_key(password: &str, salt: &[u8], iterations: usize) -> Result<[u8; KEY_LEN], String> {
+    let mut key = [0; KEY_LEN];
+    openssl::pkcs5::pbkdf2_hmac(
+        password.as_bytes(),
+        salt,
+        iterations,
+        openssl::hash::MessageDigest::sha256(),
+        &mut key,
+    )
+    .map_err(|err| format!("Failed to derive the key: {}", err))?;
+    Ok(key)
+}
+
+/// `aad` ties the ciphertext to its public key, so key files cannot be swapped
+fn encrypt(key: &[u8; KEY_LEN], aad: &[u8], plaintext: &[u8]) -> Result<String, String> {
+    let mut nonce = [0; NONCE_LEN];
+    openssl::rand::rand_bytes(&mut nonce)
+        .map_err(|err| format!("Failed to generate a nonce: {}", err))?;
+    let mut tag = [0; TAG_LEN];
+    let ciphertext = openssl::symm::encrypt_aead(
+        openssl::symm::Cipher::aes_256_gcm(),
+        key,
+        Some(&nonce[..]),
+        aad,
+        plaintext,
+        &mut tag,
+    )
+    .map_err(|err| format!("Failed to encrypt: {}", err))?;
+    Ok(base64::encode(
+        [&nonce[..], &ciphertext[..], &tag[..]].concat(),
+    ))
+}
+
+fn decrypt(key: &[u8; KEY_LEN], aad: &[u8], encrypted: &str) -> Result<Vec<u8>, String> {
+    let encrypted = base64::decode(encrypted).map_err(|err| format!("Invalid base64: {}", err))?;
+    if encrypted.len() < NONCE_LEN + TAG_LEN {
+        return Err("The ciphertext is too short".to_string());
+    }
+    let (nonce, rest) = encrypted.split_at(NONCE_LEN);
+    let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LEN);
+    openssl::symm::decrypt_aead(
+        openssl::symm::Cipher::aes_256_gcm(),
+        key,
+        Some(nonce),
+        aad,
+        ciphertext,
+        tag,
+    )
+    .map_err(|_| "Failed to decrypt (wrong key or tampered data)".to_string())
+}
+
+fn unlocked_key() -> Option<[u8; KEY_LEN]> {
+    let key = UNLOCKED_KEY.load(std::sync::atomic::Ordering::Acquire);
+    if key.is_null() {
+        None
+    } else {
+        // Set once and never freed
+        Some(unsafe { *key })
+    }
+}
+
+/// Asks for the master password once per process; every read and write of an
+/// encrypted key goes through it
+pub fn unlock() -> Result<[u8; KEY_LEN], String> {
+    if let Some(key) = unlocked_key() {
+        return Ok(key);
+    }
+    if UNLOCK_FAILED.load(std::sync::atomic::Ordering::Relaxed) {
+        return Err("The keychain is locked".to_string());
+    }
+    let master_password = load()?;
+    let salt = base64::decode(&master_password.salt)
+        .map_err(|err| format!("The master password salt is broken: {}", err))?;
+    for _ in 0..PASSWORD_ATTEMPTS {
+        let password = match dialoguer::Password::new()
+            .with_prompt("Enter the master password to unlock the keychain")
+            .interact()
+        {
+            Ok(password) => password,
+            Err(err) => {
+                UNLOCK_FAILED.store(true, std::sync::atomic::Ordering::Relaxed);
+                return Err(format!("Failed to read the master password: {:?}", err));
+            }
+        };
+        let key = derive_key(&password, &salt, master_password.iterations)?;
+        let check = decrypt(&key, b"check", &master_password.check);
+        if check.map_or(false, |check| check == CHECK_PLAINTEXT) {
+            let boxed_key = Box::into_raw(Box::new(key));
+            if UNLOCKED_KEY
+                .compare_exchange(
+                    std::ptr::null_mut(),
+                    boxed_key,
+                    std::sync::atomic::Ordering::AcqRel,
+                    std::sync::atomic::Ordering::Acquire,
+                )
+                .is_err()
+            {
+                // Another thread unlocked it meanwhile
+                drop(unsafe { Box::from_raw(boxed_key) });
+            }
+            return Ok(key);
+        }
+        eprintln!("{}", crate::style::warning("The master password is wrong"));
+    }
+    UNLOCK_FAILED.store(true, std::sync::atomic::Ordering::Relaxed);
+    Err("The keychain is locked: the master password is wrong".to_string())
+}
+
+/// Lets the long-running signers ask for the password up front, on the main thread
+pub fn unlock_if_set() -> Result<(), String> {
+    if is_set() {
+        unlock()?;
+    }
+    Ok(())
+}
+
+pub fn encrypt_private_key(public_key: &str, private_key: &str) -> Result<String, String> {
+    encrypt(&unlock()?, public_key.as_bytes(), private_key.as_bytes())
+}
+
+pub fn decrypt_private_key(public_key: &str, encrypted: &str) -> Result<String, String> {
+    let private_key = decrypt(&unlock()?, public_key.as_bytes(), encrypted)?;
+    String::from_utf8(private_key).map_err(|err| format!("{:?}", err))
+}
+
+/// How long the long-running signers (the agent and `signer serve`) keep the
+/// keys unlocked; `None` when there is no master password
+pub fn unlock_timeout() -> Option<std::time::Duration> {
+    if !is_set() {
+        return None;
+    }
+    Some(std::time::Duration::from_secs(
+        crate::config::Config::load()
+            .unlock_timeout_secs
+            .unwrap_or(DEFAULT_UNLOCK_TIMEOUT_SECS),
+    ))
+}
+
+/// Resolves once the unlock timeout has passed, never without a master password
+pub async fn relock() {
+    match unlock_timeout() {
+        Some(unlock_timeout) => actix_rt::time::delay_for(unlock_timeout).await,
+        None => futures::future::pending::<()>().await,
+    }
+}
+
+/// The key files of every account of every network, with the private keys decrypted
+fn read_key_files() -> Result<Vec<(std::path::PathBuf, crate::common::KeychainAccessKey)>, String> {
+    let mut key_files = vec![];
+    let network_dirs = match std::fs::read_dir(crate::common::get_credentials_home_dir()) {
+        Ok(network_dirs) => network_dirs,
+        Err(_) => return Ok(key_files),
+    };
+    for network_dir in network_dirs.filter_map(|entry| entry.ok()) {
+        let account_dirs = match std::fs::read_dir(network_dir.path()) {
+            Ok(account_dirs) => account_dirs,
+            Err(_) => continue,
+        };
+        for account_dir in account_dirs.filter_map(|entry| entry.ok()) {
+            let entries = match std::fs::read_dir(account_dir.path()) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+            for path in entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+            {
+                if path.extension() != Some(std::ffi::OsStr::new("json")) {
+                    continue;
+                }
+                let mut access_key = match std::fs::read_to_string(&path).ok().and_then(|buf| {
+                    serde_json::from_str::<crate::common::KeychainAccessKey>(&buf).ok()
+                }) {
+                    Some(access_key) => access_key,
+                    None => continue,
+                };
+                // The OS keyring protects those itself
+                if access_key.in_os_keyring {
+                    continue;
+                }
+                if access_key.encrypted {
+                    access_key.private_key =
+                        decrypt_private_key(&access_key.public_key, &access_key.private_key)
+                            .map_err(|err| format!("{:?}: {}", path, err))?;
+                    access_key.encrypted = false;
+                }
+                key_files.push((path, access_key));
+            }
+        }
+    }
+    Ok(key_files)
+}
+
+/// Sets, changes (`Some`) or removes (`None`) the master password, encrypting
+/// the stored keys with the new one or decrypting them; returns how many keys
+/// were rewritten
+pub fn change(new_password: Option<&str>) -> Result<usize, String> {
+    unlock_if_set()?;
+    // Every key is decrypted before anything is written, so a key that cannot
+    // be decrypted stops the change instead of being left behind
+    let key_files = read_key_files()?;
+    let new_master_password = match new_password {
+        Some(new_password) => {
+            let mut salt = [0; 16];
+            openssl::rand::rand_bytes(&mut salt)
+                .map_err(|err| format!("Failed to generate a salt: {}", err))?;
+            let key = derive_key(new_password, &salt, PBKDF2_ITERATIONS)?;
+            let master_password = MasterPassword {
+                iterations: PBKDF2_ITERATIONS,
+                salt: base64::encode(&salt),
+                check: encrypt(&key, b"check", CHECK_PLAINTEXT)?,
+            };
+            Some((key, master_password))
+        }
+        None => None,
+    };
+    for (path, access_key) in &key_files {
+        let access_key = match &new_master_password {
+            Some((key, _)) => crate::common::KeychainAccessKey {
+                account_id: access_key.account_id.clone(),
+                public_key: access_key.public_key.clone(),
+                private_key: encrypt(
+                    key,
+                    access_key.public_key.as_bytes(),
+                    access_key.private_key.as_bytes(),
+                )?,
+                label: access_key.label.clone(),
+                in_os_keyring: false,
+                encrypted: true,
+            },
+            None => crate::common::KeychainAccessKey {
+                account_id: access_key.account_id.clone(),
+                public_key: access_key.public_key.clone(),
+                private_key: access_key.private_key.clone(),
+                label: access_key.label.clone(),
+                in_os_keyring: false,
+                encrypted: false,
+            },
+        };
+        let buf = serde_json::to_string_pretty(&access_key)
+            .map_err(|err| format!("Failed to serialize the access key: {:?}", err))?;
+        let _lock = crate::common::FileLock::acquire(path)?;
+        crate::common::write_file_atomically(path, &buf)
+            .map_err(|err| format!("Failed to write the file {:?}: {:?}", path, err))?;
+    }
+    let path = path();
+    match new_master_password {
+        Some((_, master_password)) => {
+            crate::common::create_private_dir_all(path.parent().unwrap())
+                .map_err(|err| format!("Failed to create the credentials directory: {:?}", err))?;
+            let buf = serde_json::to_string_pretty(&master_password)
+                .map_err(|err| format!("Failed to serialize the master password: {:?}", err))?;
+            crate::common::write_file_atomically(&path, &buf)
+                .map_err(|err| format!("Failed to write the file {:?}: {:?}", path, err))?;
+        }
+        None => std::fs::remove_file(&path)
+            .map_err(|err| format!("Failed to remove the file {:?}: {:?}", path, err))?,
+    }
+    Ok(key_files.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypted_private_key_is_bound_to_its_public_key() {
+        let key = derive_key("correct horse", b"salt", 1).unwrap();
+        let encrypted = encrypt(&key, b"ed25519:A", b"ed25519:secret").unwrap();
+        assert_eq!(
+            decrypt(&key, b"ed25519:A", &encrypted).unwrap(),
+            b"ed25519:secret"
+        );
+        assert!(decrypt(&key, b"ed25519:B", &encrypted).is_err());
+        let wrong_key = derive_key("battery staple", b"salt", 1).unwrap();
+        assert!(decrypt(&wrong_key, b"ed25519:A", &encrypted).is_err());
+    }
+}
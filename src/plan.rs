@@ -0,0 +1,135 @@
+use near_primitives::borsh::{BorshDeserialize, BorshSerialize};
+
+/// A transaction saved with `--plan`, so it can be reviewed before
+/// `utils apply-plan` signs and sends it
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Plan {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<url::Url>,
+    pub unsigned_transaction: String,
+    pub changes: Vec<String>,
+}
+
+impl Plan {
+    pub fn new(
+        unsigned_transaction: &near_primitives::transaction::Transaction,
+        rpc_url: Option<url::Url>,
+    ) -> Self {
+        Plan {
+            rpc_url,
+            unsigned_transaction: near_primitives::serialize::to_base64(
+                unsigned_transaction
+                    .try_to_vec()
+                    .expect("Transaction is not expected to fail on serialization"),
+            ),
+            changes: describe_changes(unsigned_transaction),
+        }
+    }
+    pub fn unsigned_transaction(
+        &self,
+    ) -> Result<near_primitives::transaction::Transaction, String> {
+        let unsigned_transaction_borsh = base64::decode(&self.unsigned_transaction)
+            .map_err(|err| format!("Invalid base64 in the plan: {:?}", err))?;
+        near_primitives::transaction::Transaction::try_from_slice(&unsigned_transaction_borsh)
+            .map_err(|err| format!("Invalid transaction in the plan: {:?}", err))
+    }
+    pub fn save(&self, path: &std::path::Path) -> Result<(), String> {
+        let buf = serde_json::to_string_pretty(self)
+            .map_err(|err| format!("Failed to serialize the plan: {:?}", err))?;
+        std::fs::write(path, buf)
+            .map_err(|err| format!("Failed to write the file {:?}: {:?}", path, err))
+    }
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let buf = std::fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read the file {:?}: {:?}", path, err))?;
+        serde_json::from_str(&buf)
+            .map_err(|err| format!("Failed to parse the plan {:?}: {:?}", path, err))
+    }
+    pub fn print(&self) {
+        for change in &self.changes {
+            let styled_change = match change.chars().next() {
+                Some('+') => crate::style::success(change),
+                Some('-') => crate::style::warning(change),
+                _ => change.clone(),
+            };
+            println!("{}", styled_change);
+        }
+    }
+}
+
+/// One line per change: `+` creates, `-` removes, `~` changes
+pub fn describe_changes(
+    unsigned_transaction: &near_primitives::transaction::Transaction,
+) -> Vec<String> {
+    let signer_id = crate::common::format_account_id(&unsigned_transaction.signer_id);
+    let receiver_id = crate::common::format_account_id(&unsigned_transaction.receiver_id);
+    unsigned_transaction
+        .actions
+        .iter()
+        .map(|action| match action {
+            near_primitives::transaction::Action::CreateAccount(_) => {
+                format!("+ account {}", receiver_id)
+            }
+            near_primitives::transaction::Action::DeleteAccount(delete_account) => format!(
+                "- account {} (remaining balance to {})",
+                receiver_id,
+                crate::common::format_account_id(&delete_account.beneficiary_id)
+            ),
+            near_primitives::transaction::Action::DeployContract(deploy_contract) => format!(
+                "~ code {}: sha256 {} ({} bytes)",
+                receiver_id,
+                near_primitives::hash::hash(&deploy_contract.code),
+                deploy_contract.code.len()
+            ),
+            near_primitives::transaction::Action::FunctionCall(function_call) => format!(
+                "~ call {}.{}({}) deposit {} ({} yoctoNEAR), gas {}",
+                receiver_id,
+                function_call.method_name,
+                String::from_utf8_lossy(&function_call.args),
+                crate::common::format_near_amount(function_call.deposit),
+                function_call.deposit,
+                function_call.gas
+            ),
+            near_primitives::transaction::Action::Transfer(transfer) => format!(
+                "~ balance {} -{} / {} +{} ({} yoctoNEAR)",
+                signer_id,
+                crate::common::format_near_amount(transfer.deposit),
+                receiver_id,
+                crate::common::format_near_amount(transfer.deposit),
+                transfer.deposit
+            ),
+            near_primitives::transaction::Action::Stake(stake) => format!(
+                "~ stake {} {} ({} yoctoNEAR) with {}",
+                receiver_id,
+                crate::common::format_near_amount(stake.stake),
+                stake.stake,
+                stake.public_key
+            ),
+            near_primitives::transaction::Action::AddKey(add_key) => {
+                let permission = match &add_key.access_key.permission {
+                    near_primitives::account::AccessKeyPermission::FullAccess => {
+                        "full access".to_string()
+                    }
+                    near_primitives::account::AccessKeyPermission::FunctionCall(permission) => {
+                        format!(
+                            "function call to {} {:?}, allowance {}",
+                            permission.receiver_id,
+                            permission.method_names,
+                            match permission.allowance {
+                                Some(allowance) => crate::common::format_near_amount(allowance),
+                                None => "unlimited".to_string(),
+                            }
+                        )
+                    }
+                };
+                format!(
+                    "+ key {} {} ({})",
+                    receiver_id, add_key.public_key, permission
+                )
+            }
+            near_primitives::transaction::Action::DeleteKey(delete_key) => {
+                format!("- key {} {}", receiver_id, delete_key.public_key)
+            }
+        })
+        .collect()
+}
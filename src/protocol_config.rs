@@ -0,0 +1,148 @@
+/// Set by `--protocol-config` of the offline mode
+pub const PROTOCOL_CONFIG_ENV_VAR: &str = "NEAR_CLI_PROTOCOL_CONFIG";
+
+/// The protocol parameters of a network, exported online with
+/// `utils export-protocol-config` so the offline mode can check and estimate
+/// transactions on an air-gapped machine
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct ProtocolConfig {
+    pub chain_id: String,
+    pub protocol_version: u32,
+    /// yoctoNEAR per gas unit, at the time of the export
+    pub gas_price: String,
+    /// yoctoNEAR per byte of storage
+    pub storage_amount_per_byte: String,
+    pub max_total_prepaid_gas: near_primitives::types::Gas,
+    /// Blocks a transaction stays valid after the block of its block hash
+    #[serde(default = "default_transaction_validity_period")]
+    pub transaction_validity_period: near_primitives::types::BlockHeightDelta,
+    /// Seconds since the Unix epoch
+    pub exported_at: u64,
+}
+
+fn default_transaction_validity_period() -> near_primitives::types::BlockHeightDelta {
+    crate::common::DEFAULT_TRANSACTION_VALIDITY_PERIOD
+}
+
+impl ProtocolConfig {
+    pub async fn fetch(server_url: &url::Url) -> Result<Self, String> {
+        let status =
+            crate::common::call_json_rpc(server_url, "status", serde_json::json!([])).await?;
+        let genesis_config = crate::common::call_json_rpc(
+            server_url,
+            "EXPERIMENTAL_genesis_config",
+            serde_json::json!(null),
+        )
+        .await?;
+        let gas_price =
+            crate::common::call_json_rpc(server_url, "gas_price", serde_json::json!([null]))
+                .await?;
+        let missing = |field: &str| format!("The node did not report the {}", field);
+        Ok(ProtocolConfig {
+            chain_id: status["chain_id"]
+                .as_str()
+                .ok_or_else(|| missing("chain ID"))?
+                .to_string(),
+            protocol_version: status["protocol_version"]
+                .as_u64()
+                .ok_or_else(|| missing("protocol version"))? as u32,
+            gas_price: gas_price["gas_price"]
+                .as_str()
+                .ok_or_else(|| missing("gas price"))?
+                .to_string(),
+            storage_amount_per_byte: genesis_config["runtime_config"]["storage_amount_per_byte"]
+                .as_str()
+                .ok_or_else(|| missing("storage price"))?
+                .to_string(),
+            max_total_prepaid_gas: genesis_config["runtime_config"]["wasm_config"]["limit_config"]
+                ["max_total_prepaid_gas"]
+                .as_u64()
+                .ok_or_else(|| missing("prepaid gas limit"))?,
+            transaction_validity_period: genesis_config["transaction_validity_period"]
+                .as_u64()
+                .ok_or_else(|| missing("transaction validity period"))?,
+            exported_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        })
+    }
+    pub fn save(&self, path: &std::path::Path) -> Result<(), String> {
+        let buf = serde_json::to_string_pretty(self)
+            .map_err(|err| format!("Failed to serialize the protocol config: {:?}", err))?;
+        std::fs::write(path, buf)
+            .map_err(|err| format!("Failed to write the file {:?}: {:?}", path, err))
+    }
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let buf = std::fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read the file {:?}: {:?}", path, err))?;
+        serde_json::from_str(&buf)
+            .map_err(|err| format!("Failed to parse the protocol config {:?}: {:?}", path, err))
+    }
+    /// The bundle passed to the offline mode, if any
+    pub fn offline() -> Option<Self> {
+        let path = std::env::var_os(PROTOCOL_CONFIG_ENV_VAR)?;
+        match ProtocolConfig::load(std::path::Path::new(&path)) {
+            Ok(protocol_config) => Some(protocol_config),
+            Err(err) => {
+                eprintln!("{}", err);
+                None
+            }
+        }
+    }
+    pub fn storage_amount_per_byte(&self) -> Result<u128, String> {
+        self.storage_amount_per_byte
+            .parse()
+            .map_err(|err| format!("Invalid storage price in the protocol config: {}", err))
+    }
+    pub fn gas_price(&self) -> Result<u128, String> {
+        self.gas_price
+            .parse()
+            .map_err(|err| format!("Invalid gas price in the protocol config: {}", err))
+    }
+}
+
+/// The protocol config (`EXPERIMENTAL_protocol_config`) at a block; nodes without
+/// that method give the genesis config instead, whose fees and limits may
+/// predate the protocol upgrades
+pub async fn fetch_protocol_config_view(
+    server_url: &url::Url,
+    block_reference: near_primitives::types::BlockReference,
+) -> Result<serde_json::Value, String> {
+    let params = serde_json::to_value(block_reference).unwrap();
+    match crate::common::call_json_rpc(server_url, "EXPERIMENTAL_protocol_config", params).await {
+        Err(err) if err.to_lowercase().contains("method not found") => {
+            eprintln!("The node has no protocol config, showing the genesis config");
+            crate::common::call_json_rpc(
+                server_url,
+                "EXPERIMENTAL_genesis_config",
+                serde_json::json!(null),
+            )
+            .await
+        }
+        result => result,
+    }
+}
+
+/// The storage price (yoctoNEAR per byte) of the network, or of the exported
+/// protocol config in the offline mode
+pub async fn storage_amount_per_byte(server_url: Option<&url::Url>) -> Result<u128, String> {
+    match server_url {
+        Some(server_url) => {
+            let protocol_config = fetch_protocol_config_view(
+                server_url,
+                near_primitives::types::Finality::Final.into(),
+            )
+            .await?;
+            protocol_config["runtime_config"]["storage_amount_per_byte"]
+                .as_str()
+                .and_then(|storage_amount_per_byte| storage_amount_per_byte.parse().ok())
+                .ok_or_else(|| "The node did not report the storage price".to_string())
+        }
+        None => ProtocolConfig::offline()
+            .ok_or_else(|| {
+                "the storage price is unknown offline without `--protocol-config`".to_string()
+            })?
+            .storage_amount_per_byte(),
+    }
+}
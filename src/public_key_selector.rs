@@ -0,0 +1,228 @@
+use dialoguer::{theme::ColorfulTheme, Input, Password, Select};
+use std::str::FromStr;
+
+use crate::construct_transaction_command::sign_transaction::sign_keychain::SignKeychain;
+
+/// Where the interactive prompts take a public key from; there is no Ledger
+/// source as this CLI does not talk to hardware wallets (see `doctor`)
+#[derive(Debug, Clone, Copy)]
+enum PublicKeySource {
+    Manual,
+    File,
+    Keychain,
+    SeedPhrase,
+}
+
+const PUBLIC_KEY_SOURCES: &[(PublicKeySource, &str)] = &[
+    (PublicKeySource::Manual, "I want to enter the public key"),
+    (
+        PublicKeySource::Keychain,
+        "I want to pick a key from the keychain",
+    ),
+    (
+        PublicKeySource::SeedPhrase,
+        "I want to derive the key from a seed phrase",
+    ),
+];
+
+/// The sources of `input_public_keys`, which can also take several keys at once
+const PUBLIC_KEYS_SOURCES: &[(PublicKeySource, &str)] = &[
+    (
+        PublicKeySource::Manual,
+        "I want to enter a public key (or a comma-separated list of public keys)",
+    ),
+    (
+        PublicKeySource::File,
+        "I want to read public keys from a file (one key per line)",
+    ),
+    (
+        PublicKeySource::Keychain,
+        "I want to pick a key from the keychain",
+    ),
+    (
+        PublicKeySource::SeedPhrase,
+        "I want to derive the key from a seed phrase",
+    ),
+];
+
+fn choose_public_key_source(prompt: &str, sources: &[(PublicKeySource, &str)]) -> PublicKeySource {
+    eprintln!();
+    let items = sources
+        .iter()
+        .map(|(_, message)| *message)
+        .collect::<Vec<_>>();
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .items(&items)
+        .default(0)
+        .interact()
+        .unwrap();
+    sources[selection].0
+}
+
+/// Asks for a public key, entered by hand, picked from the keychain or
+/// derived from a seed phrase; `prompt` is asked for the manual entry
+pub fn input_public_key(prompt: &str) -> String {
+    let public_key = match choose_public_key_source(
+        "How do you want to provide the public key?",
+        PUBLIC_KEY_SOURCES,
+    ) {
+        PublicKeySource::Manual => Ok(Input::new().with_prompt(prompt).interact_text().unwrap()),
+        PublicKeySource::File => unreachable!("Error"),
+        PublicKeySource::Keychain => choose_keychain_public_key(),
+        PublicKeySource::SeedPhrase => derive_public_key(),
+    };
+    match public_key
+        .and_then(|public_key| crate::common::parse_public_key(&public_key).map(|_| public_key))
+    {
+        Ok(public_key) => public_key,
+        Err(err) => {
+            eprintln!("{}", err);
+            input_public_key(prompt)
+        }
+    }
+}
+
+/// Like `input_public_key`, but the manual entry takes a comma-separated list
+/// and the keys can also be read from a file
+pub fn input_public_keys(prompt: &str) -> Vec<near_crypto::PublicKey> {
+    let public_keys = match choose_public_key_source(
+        "How do you want to provide the public keys?",
+        PUBLIC_KEYS_SOURCES,
+    ) {
+        PublicKeySource::Manual => {
+            let public_keys: String = Input::new().with_prompt(prompt).interact_text().unwrap();
+            parse_public_keys(&public_keys)
+        }
+        PublicKeySource::File => {
+            let file_path: String = Input::new()
+                .with_prompt("Enter the path to the file with public keys")
+                .interact_text()
+                .unwrap();
+            read_public_keys_file(std::path::Path::new(&file_path))
+        }
+        PublicKeySource::Keychain => {
+            choose_keychain_public_key().and_then(|public_key| parse_public_keys(&public_key))
+        }
+        PublicKeySource::SeedPhrase => {
+            derive_public_key().and_then(|public_key| parse_public_keys(&public_key))
+        }
+    };
+    match public_keys {
+        Ok(public_keys) if !public_keys.is_empty() => public_keys,
+        Ok(_) => {
+            eprintln!("No public keys were provided, try again");
+            input_public_keys(prompt)
+        }
+        Err(err) => {
+            eprintln!("{}, try again", err);
+            input_public_keys(prompt)
+        }
+    }
+}
+
+/// Comma- or whitespace-separated keys; an invalid key is an error rather
+/// than skipped, so no access key is silently missing from the transaction
+pub fn parse_public_keys(public_keys: &str) -> Result<Vec<near_crypto::PublicKey>, String> {
+    let mut parsed_public_keys: Vec<near_crypto::PublicKey> = vec![];
+    for public_key in public_keys
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(str::trim)
+        .filter(|public_key| !public_key.is_empty())
+    {
+        let public_key = crate::common::parse_public_key(public_key)?;
+        if !parsed_public_keys.contains(&public_key) {
+            parsed_public_keys.push(public_key);
+        }
+    }
+    Ok(parsed_public_keys)
+}
+
+pub fn read_public_keys_file(
+    path: &std::path::Path,
+) -> Result<Vec<near_crypto::PublicKey>, String> {
+    let public_keys = std::fs::read_to_string(path)
+        .map_err(|err| format!("Error reading the file {:?}: {:?}", path, err))?;
+    parse_public_keys(&public_keys).map_err(|err| format!("{} in the file {:?}", err, path))
+}
+
+fn choose_keychain_public_key() -> Result<String, String> {
+    let network_dir_name = SignKeychain::input_network_dir_name();
+    let network_dir = crate::common::get_credentials_home_dir().join(&network_dir_name);
+    let mut account_ids = std::fs::read_dir(&network_dir)
+        .map_err(|err| format!("Error reading {:?}:  {:?}", network_dir, err))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    account_ids.sort();
+    let mut items = vec![];
+    for account_id in account_ids {
+        for access_key in
+            crate::common::read_access_keys_from_keychain(&network_dir_name, &account_id)
+        {
+            let item = match &access_key.label {
+                Some(label) => format!("{} {} ({})", account_id, access_key.public_key, label),
+                None => format!("{} {}", account_id, access_key.public_key),
+            };
+            items.push((access_key.public_key, item));
+        }
+    }
+    if items.is_empty() {
+        return Err(format!(
+            "There are no keys in the {} keychain",
+            network_dir_name
+        ));
+    }
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Select the key")
+        .items(&items.iter().map(|(_, item)| item).collect::<Vec<_>>())
+        .default(0)
+        .interact()
+        .unwrap();
+    Ok(items.remove(selection).0)
+}
+
+fn derive_public_key() -> Result<String, String> {
+    let seed_phrase = Password::new()
+        .with_prompt("Enter the seed phrase")
+        .interact()
+        .unwrap();
+    let passphrase = input_seed_phrase_passphrase();
+    let seed_phrase_hd_path: String = Input::new()
+        .with_prompt("Enter the HD path")
+        .default("m/44'/397'/0'".to_string())
+        .interact_text()
+        .unwrap();
+    public_key_from_seed_phrase(&seed_phrase, &passphrase, &seed_phrase_hd_path)
+}
+
+/// The optional BIP39 passphrase (the "25th word") of a seed phrase
+pub fn input_seed_phrase_passphrase() -> String {
+    Password::new()
+        .with_prompt("Enter the seed phrase passphrase (leave empty if there is none)")
+        .allow_empty_password(true)
+        .interact()
+        .unwrap()
+}
+
+/// Derives the ED25519 public key of a seed phrase (and its BIP39 passphrase)
+/// at the given HD path
+pub fn public_key_from_seed_phrase(
+    seed_phrase: &str,
+    passphrase: &str,
+    seed_phrase_hd_path: &str,
+) -> Result<String, String> {
+    let seed = bip39::Mnemonic::parse(seed_phrase.trim())
+        .map_err(|err| format!("Invalid seed phrase: {}", err))?
+        .to_seed(passphrase);
+    let seed_phrase_hd_path = slip10::BIP32Path::from_str(seed_phrase_hd_path)
+        .map_err(|err| format!("Invalid HD path: {:?}", err))?;
+    let derived_private_key =
+        slip10::derive_key_from_path(&seed, slip10::Curve::Ed25519, &seed_phrase_hd_path)
+            .map_err(|err| format!("Key derivation from path failed: {:?}", err))?;
+    let secret = ed25519_dalek::SecretKey::from_bytes(&derived_private_key.key)
+        .map_err(|err| format!("{:?}", err))?;
+    let public = ed25519_dalek::PublicKey::from(&secret);
+    Ok(format!("ed25519:{}", bs58::encode(&public).into_string()))
+}
@@ -0,0 +1,130 @@
+/// QR codes carry transactions to and from air-gapped machines: a transaction is
+/// split into chunks `NEARTX:<index>/<count>:<base64 part>` that fit into a QR
+/// code each, and the scanned chunks are joined back in any order
+pub const CHUNK_PREFIX: &str = "NEARTX:";
+/// Small enough for a QR code that phone cameras and terminals handle well
+const CHUNK_SIZE: usize = 800;
+
+pub fn chunks(data: &str) -> Vec<String> {
+    let parts = data
+        .as_bytes()
+        .chunks(CHUNK_SIZE)
+        .map(|part| String::from_utf8_lossy(part).to_string())
+        .collect::<Vec<_>>();
+    let count = parts.len();
+    parts
+        .into_iter()
+        .enumerate()
+        .map(|(index, part)| format!("{}{}/{}:{}", CHUNK_PREFIX, index + 1, count, part))
+        .collect()
+}
+
+/// Renders the chunks of the base64-encoded transaction in the terminal
+pub fn print_qr_codes(data: &str) {
+    let chunks = chunks(data);
+    for (index, chunk) in chunks.iter().enumerate() {
+        let code = match qrcode::QrCode::new(chunk.as_bytes()) {
+            Ok(code) => code,
+            Err(err) => return eprintln!("Error encoding the QR code:  {:?}", err),
+        };
+        println!("QR code {}/{}:", index + 1, chunks.len());
+        println!(
+            "{}",
+            code.render::<qrcode::render::unicode::Dense1x2>()
+                .dark_color(qrcode::render::unicode::Dense1x2::Light)
+                .light_color(qrcode::render::unicode::Dense1x2::Dark)
+                .build()
+        );
+    }
+}
+
+/// Saves the chunks as `<path>` (one chunk) or `<path stem>-<index>.png`
+pub fn save_qr_pngs(data: &str, path: &std::path::Path) {
+    let chunks = chunks(data);
+    for (index, chunk) in chunks.iter().enumerate() {
+        let png_path = if chunks.len() == 1 {
+            path.to_path_buf()
+        } else {
+            path.with_file_name(format!(
+                "{}-{}.png",
+                path.file_stem().unwrap_or_default().to_string_lossy(),
+                index + 1
+            ))
+        };
+        let code = match qrcode::QrCode::new(chunk.as_bytes()) {
+            Ok(code) => code,
+            Err(err) => return eprintln!("Error encoding the QR code:  {:?}", err),
+        };
+        match code.render::<image::Luma<u8>>().build().save(&png_path) {
+            Ok(()) => eprintln!(
+                "{} QR code {}/{} to {:?}",
+                crate::style::success("Saved"),
+                index + 1,
+                chunks.len(),
+                png_path
+            ),
+            Err(err) => eprintln!("Error writing the file {:?}:  {:?}", png_path, err),
+        }
+    }
+}
+
+/// Parses `NEARTX:<index>/<count>:<part>`
+fn parse_chunk(chunk: &str) -> Result<(usize, usize, String), String> {
+    let invalid_chunk = || format!("<{}> is not a NEARTX QR code", chunk);
+    let chunk = chunk
+        .trim()
+        .strip_prefix(CHUNK_PREFIX)
+        .ok_or_else(invalid_chunk)?;
+    let mut fields = chunk.splitn(2, ':');
+    let position = fields.next().ok_or_else(invalid_chunk)?;
+    let part = fields.next().ok_or_else(invalid_chunk)?;
+    let mut position = position.splitn(2, '/');
+    let index: usize = position
+        .next()
+        .and_then(|index| index.parse().ok())
+        .ok_or_else(invalid_chunk)?;
+    let count: usize = position
+        .next()
+        .and_then(|count| count.parse().ok())
+        .ok_or_else(invalid_chunk)?;
+    if index == 0 || index > count {
+        return Err(invalid_chunk());
+    }
+    Ok((index, count, part.to_string()))
+}
+
+/// Asks for the text of the scanned QR codes until all the chunks are there;
+/// `first_chunk` is the one that was already entered
+pub fn input_qr_chunks(first_chunk: Option<String>) -> String {
+    let mut parts: std::collections::BTreeMap<usize, String> = Default::default();
+    let mut count = None;
+    let mut next_chunk = first_chunk;
+    loop {
+        let chunk = match next_chunk.take() {
+            Some(chunk) => chunk,
+            None => dialoguer::Input::<String>::new()
+                .with_prompt(format!(
+                    "Enter the text of the scanned QR code ({}/{} received)",
+                    parts.len(),
+                    count
+                        .map(|count: usize| count.to_string())
+                        .unwrap_or_else(|| "?".to_string())
+                ))
+                .interact_text()
+                .unwrap(),
+        };
+        match parse_chunk(&chunk) {
+            Ok((_, chunk_count, _)) if count.map_or(false, |count| count != chunk_count) => {
+                eprintln!("The QR code belongs to another transaction, skipping it")
+            }
+            Ok((index, chunk_count, part)) => {
+                count = Some(chunk_count);
+                parts.insert(index, part);
+            }
+            Err(err) => eprintln!("{}", err),
+        }
+        if count == Some(parts.len()) {
+            return parts.into_iter().map(|(_, part)| part).collect();
+        }
+    }
+}
@@ -0,0 +1,90 @@
+/// Results older than this are not used to pick the RPC endpoint
+const MAX_RESULT_AGE_SECS: u64 = 24 * 60 * 60;
+
+/// Latency percentiles of one kind of request
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ProbeLatency {
+    pub probe: String,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub failures: usize,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct EndpointBenchmark {
+    pub url: url::Url,
+    /// The median latency of all the requests, `None` if any of them failed
+    pub median_ms: Option<u64>,
+    pub probes: Vec<ProbeLatency>,
+}
+
+/// The latest `utils benchmark-rpc` results of a network, fastest healthy
+/// endpoint first
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct NetworkBenchmark {
+    /// Seconds since the Unix epoch
+    pub measured_at: u64,
+    pub endpoints: Vec<EndpointBenchmark>,
+}
+
+/// Kept next to the config file, by network directory name (as in the keychain)
+fn results_file_path() -> std::path::PathBuf {
+    crate::config::Config::config_file_path().with_file_name("rpc-benchmark.json")
+}
+
+type BenchmarkResults = std::collections::BTreeMap<String, NetworkBenchmark>;
+
+thread_local! {
+    /// Read once per run, as every `ConnectionConfig::rpc_url` call looks at it
+    static RESULTS: std::cell::RefCell<Option<BenchmarkResults>> = std::cell::RefCell::new(None);
+}
+
+fn load_results() -> BenchmarkResults {
+    RESULTS.with(|results| {
+        results
+            .borrow_mut()
+            .get_or_insert_with(|| {
+                std::fs::read_to_string(results_file_path())
+                    .ok()
+                    .and_then(|buf| serde_json::from_str(&buf).ok())
+                    .unwrap_or_default()
+            })
+            .clone()
+    })
+}
+
+pub fn save_network_benchmark(
+    network_dir_name: &str,
+    network_benchmark: NetworkBenchmark,
+) -> Result<(), String> {
+    let mut results = load_results();
+    results.insert(network_dir_name.to_string(), network_benchmark);
+    let path = results_file_path();
+    crate::common::write_file_atomically(&path, &serde_json::to_string_pretty(&results).unwrap())
+        .map_err(|err| format!("Error writing the file {:?}:  {:?}", path, err))?;
+    RESULTS.with(|cached_results| *cached_results.borrow_mut() = Some(results));
+    Ok(())
+}
+
+/// The fastest healthy endpoint among the given ones, according to a recent
+/// benchmark of the network
+pub fn fastest_healthy_endpoint(
+    network_dir_name: &str,
+    endpoints: &[url::Url],
+) -> Option<url::Url> {
+    let network_benchmark = load_results().remove(network_dir_name)?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    if now.saturating_sub(network_benchmark.measured_at) > MAX_RESULT_AGE_SECS {
+        return None;
+    }
+    network_benchmark
+        .endpoints
+        .into_iter()
+        .filter(|endpoint| endpoint.median_ms.is_some() && endpoints.contains(&endpoint.url))
+        .min_by_key(|endpoint| endpoint.median_ms)
+        .map(|endpoint| endpoint.url)
+}
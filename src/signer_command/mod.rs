@@ -0,0 +1,87 @@
+use dialoguer::{theme::ColorfulTheme, Select};
+use structopt::StructOpt;
+use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
+
+pub mod serve_subcommand;
+
+/// The remote signer: a hardened machine keeps the keys and signs the
+/// transactions that other machines (e.g. CI) prepare, see `sign-remote`
+#[derive(Debug)]
+pub struct SignerCommand {
+    pub action: SignerAction,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliSignerCommand {
+    #[structopt(subcommand)]
+    action: Option<CliSignerAction>,
+}
+
+impl From<CliSignerCommand> for SignerCommand {
+    fn from(item: CliSignerCommand) -> Self {
+        let action = match item.action {
+            Some(cli_action) => SignerAction::from(cli_action),
+            None => SignerAction::choose_action(),
+        };
+        SignerCommand { action }
+    }
+}
+
+impl SignerCommand {
+    pub async fn process(self) {
+        self.action.process().await
+    }
+}
+
+#[derive(Debug, EnumDiscriminants)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+pub enum SignerAction {
+    #[strum_discriminants(strum(message = "Serve signatures with the keychain keys over HTTP"))]
+    Serve(serve_subcommand::Serve),
+}
+
+#[derive(Debug, StructOpt)]
+pub enum CliSignerAction {
+    Serve(serve_subcommand::CliServe),
+}
+
+impl From<CliSignerAction> for SignerAction {
+    fn from(item: CliSignerAction) -> Self {
+        match item {
+            CliSignerAction::Serve(cli_serve) => {
+                SignerAction::Serve(serve_subcommand::Serve::from(cli_serve))
+            }
+        }
+    }
+}
+
+impl SignerAction {
+    pub async fn process(self) {
+        match self {
+            SignerAction::Serve(serve) => serve.process().await,
+        }
+    }
+    pub fn choose_action() -> Self {
+        eprintln!();
+        let variants = SignerActionDiscriminants::iter().collect::<Vec<_>>();
+        let actions = variants
+            .iter()
+            .map(|p| p.get_message().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Choose your action")
+            .items(&actions)
+            .default(0)
+            .interact()
+            .unwrap();
+        match variants[selection] {
+            SignerActionDiscriminants::Serve => SignerAction::Serve(serve_subcommand::Serve {
+                listen: serve_subcommand::DEFAULT_LISTEN_ADDRESS.to_string(),
+                allow_remote: false,
+                connection_config: crate::common::ConnectionConfig::input_connection_config(),
+                token: serve_subcommand::Serve::generate_token(),
+                allowed_signers: serve_subcommand::Serve::input_allowed_signers(),
+            }),
+        }
+    }
+}
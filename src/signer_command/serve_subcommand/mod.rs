@@ -0,0 +1,292 @@
+use dialoguer::Input;
+use std::str::FromStr;
+use structopt::StructOpt;
+
+pub const DEFAULT_LISTEN_ADDRESS: &str = "127.0.0.1:8765";
+/// The bearer token shared by `signer serve` and `sign-remote`
+pub const TOKEN_ENV_VAR: &str = "NEAR_CLI_SIGNER_TOKEN";
+
+/// Sign prepared transactions with the keychain keys for the clients that know
+/// the token:
+/// `GET /keys/<account ID>` lists the public keys of an account, and
+/// `POST /sign` with `{"unsigned_transaction": "<base64>"}` returns
+/// `{"signature": "ed25519:...", "transaction_hash": "..."}`
+#[derive(Debug)]
+pub struct Serve {
+    pub listen: String,
+    /// Listen on an address other than the loopback one
+    pub allow_remote: bool,
+    pub connection_config: crate::common::ConnectionConfig,
+    pub token: String,
+    /// Only sign for these accounts
+    pub allowed_signers: Vec<String>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliServe {
+    #[structopt(long, default_value = DEFAULT_LISTEN_ADDRESS)]
+    listen: String,
+    /// Allow a `--listen` address reachable from other machines
+    #[structopt(long)]
+    allow_remote: bool,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+    /// A random token is generated and printed when it is not given
+    #[structopt(long, env = TOKEN_ENV_VAR, hide_env_values = true)]
+    token: Option<String>,
+    /// The accounts to sign for (comma-separated)
+    #[structopt(long, use_delimiter = true)]
+    allowed_signers: Vec<String>,
+}
+
+impl From<CliServe> for Serve {
+    fn from(item: CliServe) -> Self {
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        let token = match item.token {
+            Some(cli_token) => cli_token,
+            None => Serve::generate_token(),
+        };
+        let allowed_signers = if item.allowed_signers.is_empty() {
+            Serve::input_allowed_signers()
+        } else {
+            item.allowed_signers
+        };
+        Serve {
+            listen: item.listen,
+            allow_remote: item.allow_remote,
+            connection_config,
+            token,
+            allowed_signers,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ServeState {
+    network_dir_name: String,
+    token: String,
+    allowed_signers: Vec<String>,
+}
+
+impl ServeState {
+    fn is_authorized(&self, request: &actix_web::HttpRequest) -> bool {
+        request
+            .headers()
+            .get("Authorization")
+            .and_then(|authorization| authorization.to_str().ok())
+            .map_or(false, |authorization| {
+                constant_time_eq(
+                    authorization.as_bytes(),
+                    format!("Bearer {}", self.token).as_bytes(),
+                )
+            })
+    }
+    fn is_allowed(&self, account_id: &str) -> bool {
+        self.allowed_signers
+            .iter()
+            .any(|allowed_signer| allowed_signer == account_id)
+    }
+}
+
+/// Compares the whole token whatever the first mismatch, so the response time
+/// does not tell how much of a guessed token is right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// Whether every address `listen` resolves to is a loopback one
+fn is_loopback_address(listen: &str) -> Result<bool, String> {
+    use std::net::ToSocketAddrs;
+
+    let addresses = listen
+        .to_socket_addrs()
+        .map_err(|err| format!("Invalid listen address {}: {}", listen, err))?
+        .collect::<Vec<_>>();
+    Ok(!addresses.is_empty() && addresses.iter().all(|address| address.ip().is_loopback()))
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct SignRequest {
+    unsigned_transaction: String,
+}
+
+async fn keys(
+    state: actix_web::web::Data<ServeState>,
+    request: actix_web::HttpRequest,
+    account_id: actix_web::web::Path<String>,
+) -> actix_web::HttpResponse {
+    if !state.is_authorized(&request) {
+        return actix_web::HttpResponse::Unauthorized().finish();
+    }
+    if !state.is_allowed(&account_id) {
+        return actix_web::HttpResponse::Forbidden().body("The account is not allowed");
+    }
+    let public_keys =
+        crate::common::read_access_keys_from_keychain(&state.network_dir_name, &account_id)
+            .into_iter()
+            .map(|access_key| access_key.public_key)
+            .collect::<Vec<_>>();
+    actix_web::HttpResponse::Ok().json(serde_json::json!({ "public_keys": public_keys }))
+}
+
+async fn sign(
+    state: actix_web::web::Data<ServeState>,
+    request: actix_web::HttpRequest,
+    sign_request: actix_web::web::Json<SignRequest>,
+) -> actix_web::HttpResponse {
+    if !state.is_authorized(&request) {
+        return actix_web::HttpResponse::Unauthorized().finish();
+    }
+    let unsigned_transaction = match crate::common::from_base64_borsh::<
+        near_primitives::transaction::Transaction,
+    >(&sign_request.unsigned_transaction)
+    {
+        Ok(unsigned_transaction) => unsigned_transaction,
+        Err(err) => {
+            return actix_web::HttpResponse::BadRequest()
+                .body(format!("Invalid unsigned transaction: {}", err))
+        }
+    };
+    if !state.is_allowed(&unsigned_transaction.signer_id) {
+        return actix_web::HttpResponse::Forbidden().body("The signer is not allowed");
+    }
+    let public_key = unsigned_transaction.public_key.to_string();
+    let access_key = match crate::common::read_access_keys_from_keychain(
+        &state.network_dir_name,
+        &unsigned_transaction.signer_id,
+    )
+    .into_iter()
+    .find(|access_key| access_key.public_key == public_key)
+    {
+        Some(access_key) => access_key,
+        None => {
+            return actix_web::HttpResponse::NotFound()
+                .body(format!("There is no key {} in the keychain", public_key))
+        }
+    };
+    let signer_secret_key = match near_crypto::SecretKey::from_str(&access_key.private_key) {
+        Ok(signer_secret_key) => signer_secret_key,
+        Err(err) => {
+            return actix_web::HttpResponse::InternalServerError()
+                .body(format!("Invalid secret key in the keychain: {:?}", err))
+        }
+    };
+    let transaction_hash = unsigned_transaction.get_hash();
+    let signature = signer_secret_key.sign(transaction_hash.as_ref());
+    eprintln!(
+        "Signed {} from <{}> to <{}> for {}",
+        crate::style::hash(transaction_hash),
+        unsigned_transaction.signer_id,
+        unsigned_transaction.receiver_id,
+        request
+            .peer_addr()
+            .map(|peer_addr| peer_addr.to_string())
+            .unwrap_or_default()
+    );
+    crate::common::append_to_audit_log(serde_json::json!({
+        "event": "remote_signature",
+        "signer_id": &unsigned_transaction.signer_id,
+        "receiver_id": &unsigned_transaction.receiver_id,
+        "public_key": &public_key,
+        "transaction_hash": transaction_hash.to_string(),
+    }));
+    actix_web::HttpResponse::Ok().json(serde_json::json!({
+        "signature": signature.to_string(),
+        "transaction_hash": transaction_hash.to_string(),
+    }))
+}
+
+impl Serve {
+    pub async fn process(self) {
+        if self.allowed_signers.is_empty() {
+            return eprintln!("Refusing to serve without --allowed-signers");
+        }
+        match is_loopback_address(&self.listen) {
+            Ok(true) => {}
+            Ok(false) if self.allow_remote => eprintln!(
+                "{}",
+                crate::style::warning(format!(
+                    "{} is reachable from other machines; the token is sent in the clear over HTTP",
+                    self.listen
+                ))
+            ),
+            Ok(false) => {
+                return eprintln!(
+                    "Refusing to listen on {}, which other machines can reach (see --allow-remote)",
+                    self.listen
+                )
+            }
+            Err(err) => return eprintln!("{}", err),
+        }
+        // The handlers read the keys on their own threads, so ask for the
+        // master password here
+        if let Err(err) = crate::master_password::unlock_if_set() {
+            return eprintln!("{}", err);
+        }
+        let state = ServeState {
+            network_dir_name: self.connection_config.dir_name(),
+            token: self.token,
+            allowed_signers: self.allowed_signers,
+        };
+        let server = actix_web::HttpServer::new(move || {
+            actix_web::App::new()
+                .data(state.clone())
+                .route("/keys/{account_id}", actix_web::web::get().to(keys))
+                .route("/sign", actix_web::web::post().to(sign))
+        })
+        .bind(&self.listen);
+        let server = match server {
+            Ok(server) => server,
+            Err(err) => return eprintln!("Error listening on {}:  {:?}", self.listen, err),
+        };
+        eprintln!(
+            "{} for the {} keychain on http://{}",
+            crate::style::success("Serving signatures"),
+            self.connection_config.dir_name(),
+            self.listen
+        );
+        match futures::future::select(
+            Box::pin(server.run()),
+            Box::pin(crate::master_password::relock()),
+        )
+        .await
+        {
+            futures::future::Either::Left((Err(err), _)) => eprintln!("Error serving:  {:?}", err),
+            futures::future::Either::Left((Ok(()), _)) => {}
+            futures::future::Either::Right(_) => {
+                eprintln!("The keys are locked again; restart the server to unlock them")
+            }
+        }
+    }
+    pub fn input_allowed_signers() -> Vec<String> {
+        loop {
+            let allowed_signers: String = Input::new()
+                .with_prompt("Which accounts do you want to sign for? (comma-separated)")
+                .interact_text()
+                .unwrap();
+            let allowed_signers = allowed_signers
+                .split(',')
+                .map(|account_id| account_id.trim().to_string())
+                .filter(|account_id| !account_id.is_empty())
+                .collect::<Vec<_>>();
+            if !allowed_signers.is_empty() {
+                return allowed_signers;
+            }
+            eprintln!("Enter at least one account");
+        }
+    }
+    /// Printed once, as the clients have to be configured with it
+    pub fn generate_token() -> String {
+        let token = near_primitives::hash::hash(
+            near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519)
+                .to_string()
+                .as_bytes(),
+        )
+        .to_string();
+        eprintln!(
+            "Generated the token (pass it to `sign-remote` as ${}): {}",
+            TOKEN_ENV_VAR, token
+        );
+        token
+    }
+}
@@ -0,0 +1,146 @@
+use dialoguer::console::style;
+
+static PLAIN_THEME: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+pub fn set_theme(theme: &crate::config::Theme) {
+    let plain_theme = match theme {
+        crate::config::Theme::Colorful => false,
+        crate::config::Theme::Plain => true,
+    };
+    PLAIN_THEME.store(plain_theme, std::sync::atomic::Ordering::Relaxed);
+}
+
+fn styled<T: std::fmt::Display>(
+    value: T,
+    apply: fn(dialoguer::console::StyledObject<T>) -> dialoguer::console::StyledObject<T>,
+) -> String {
+    if PLAIN_THEME.load(std::sync::atomic::Ordering::Relaxed) {
+        value.to_string()
+    } else {
+        apply(style(value)).to_string()
+    }
+}
+
+pub fn success<T: std::fmt::Display>(value: T) -> String {
+    styled(value, |value| value.green())
+}
+
+pub fn warning<T: std::fmt::Display>(value: T) -> String {
+    styled(value, |value| value.yellow())
+}
+
+pub fn amount<T: std::fmt::Display>(value: T) -> String {
+    styled(value, |value| value.cyan())
+}
+
+/// The account ID followed by its address book label, if it has one
+pub fn account_id<T: std::fmt::Display>(value: T) -> String {
+    let account_id = value.to_string();
+    match crate::common::account_label(&account_id) {
+        Some(label) => format!("{} ({})", styled(account_id, |value| value.bold()), label),
+        None => styled(account_id, |value| value.bold()),
+    }
+}
+
+pub fn hash<T: std::fmt::Display>(value: T) -> String {
+    styled(value, |value| value.dim())
+}
+
+/// Stored as `u32` code points so the format can live in plain atomics; 0 means
+/// "derive from the locale"
+static THOUSANDS_SEPARATOR: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+static DECIMAL_SEPARATOR: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+static MAX_DECIMALS: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(u32::MAX);
+
+pub fn set_number_format(number_format: &crate::config::NumberFormat) {
+    let (locale_thousands_separator, locale_decimal_separator) = locale_separators();
+    let thousands_separator = number_format
+        .thousands_separator
+        .unwrap_or(locale_thousands_separator);
+    let decimal_separator = number_format
+        .decimal_separator
+        .unwrap_or(locale_decimal_separator);
+    THOUSANDS_SEPARATOR.store(
+        thousands_separator as u32,
+        std::sync::atomic::Ordering::Relaxed,
+    );
+    DECIMAL_SEPARATOR.store(
+        decimal_separator as u32,
+        std::sync::atomic::Ordering::Relaxed,
+    );
+    MAX_DECIMALS.store(
+        number_format.max_decimals.unwrap_or(u32::MAX),
+        std::sync::atomic::Ordering::Relaxed,
+    );
+}
+
+/// Separators conventionally used with the `LC_ALL`/`LC_NUMERIC`/`LANG` locale
+fn locale_separators() -> (char, char) {
+    let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+    if locale.starts_with("de_CH") {
+        return ('\'', '.');
+    }
+    match locale.get(..2).unwrap_or_default() {
+        "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" => ('.', ','),
+        "fr" | "ru" | "uk" | "pl" | "cs" | "sv" | "fi" | "nb" => (' ', ','),
+        _ => (',', '.'),
+    }
+}
+
+fn load_separator(separator: &std::sync::atomic::AtomicU32, default: char) -> char {
+    match separator.load(std::sync::atomic::Ordering::Relaxed) {
+        0 => default,
+        code => std::char::from_u32(code).unwrap_or(default),
+    }
+}
+
+/// Formats `integer.fraction` for display with the thousands separators and
+/// the decimal precision (rounded half up) from the config
+pub fn format_decimal(integer: u128, fraction: &str) -> String {
+    let thousands_separator = load_separator(&THOUSANDS_SEPARATOR, ',');
+    let decimal_separator = load_separator(&DECIMAL_SEPARATOR, '.');
+    let max_decimals = MAX_DECIMALS.load(std::sync::atomic::Ordering::Relaxed) as usize;
+
+    let mut integer = integer;
+    let mut fraction = fraction.to_string();
+    if fraction.len() > max_decimals {
+        let round_up = fraction.as_bytes()[max_decimals] >= b'5';
+        fraction.truncate(max_decimals);
+        if round_up {
+            let mut digits = fraction.into_bytes();
+            let mut carry = true;
+            for digit in digits.iter_mut().rev() {
+                if *digit == b'9' {
+                    *digit = b'0';
+                } else {
+                    *digit += 1;
+                    carry = false;
+                    break;
+                }
+            }
+            if carry {
+                integer += 1;
+            }
+            fraction = String::from_utf8(digits).unwrap();
+        }
+    }
+    let fraction = fraction.trim_end_matches('0');
+
+    let integer_digits = integer.to_string();
+    let mut grouped = String::new();
+    for (index, digit) in integer_digits.chars().enumerate() {
+        if index > 0 && (integer_digits.len() - index) % 3 == 0 {
+            grouped.push(thousands_separator);
+        }
+        grouped.push(digit);
+    }
+    if fraction.is_empty() {
+        grouped
+    } else {
+        format!("{}{}{}", grouped, decimal_separator, fraction)
+    }
+}
@@ -0,0 +1,67 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+use crate::construct_transaction_command::sign_transaction::SignTransaction;
+
+/// Sign and send a transaction saved with `--plan`, after showing the changes
+/// again (and asking for approval unless `--auto-approve` is given)
+#[derive(Debug)]
+pub struct ApplyPlan {
+    pub plan_file: std::path::PathBuf,
+    pub auto_approve: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliApplyPlan {
+    plan_file: Option<std::path::PathBuf>,
+    #[structopt(long)]
+    auto_approve: bool,
+}
+
+impl From<CliApplyPlan> for ApplyPlan {
+    fn from(item: CliApplyPlan) -> Self {
+        let plan_file = match item.plan_file {
+            Some(cli_plan_file) => cli_plan_file,
+            None => ApplyPlan::input_plan_file(),
+        };
+        ApplyPlan {
+            plan_file,
+            auto_approve: item.auto_approve,
+        }
+    }
+}
+
+impl ApplyPlan {
+    pub async fn process(self) {
+        let plan = match crate::plan::Plan::load(&self.plan_file) {
+            Ok(plan) => plan,
+            Err(err) => return eprintln!("{}", err),
+        };
+        let unsigned_transaction = match plan.unsigned_transaction() {
+            Ok(unsigned_transaction) => unsigned_transaction,
+            Err(err) => return eprintln!("{}", err),
+        };
+        // The plan is re-derived from the transaction, so an edited plan cannot hide a change
+        let plan = crate::plan::Plan::new(&unsigned_transaction, plan.rpc_url);
+        plan.print();
+        if !self.auto_approve
+            && !dialoguer::Confirm::new()
+                .with_prompt("Do you want to apply these changes?")
+                .default(false)
+                .interact()
+                .unwrap_or(false)
+        {
+            return eprintln!("The plan has not been applied");
+        }
+        SignTransaction::choose_sign_option_for(&unsigned_transaction.signer_id)
+            .process(unsigned_transaction, plan.rpc_url)
+            .await
+    }
+    pub fn input_plan_file() -> std::path::PathBuf {
+        let plan_file: String = Input::new()
+            .with_prompt("Enter the path to the plan file")
+            .interact_text()
+            .unwrap();
+        std::path::PathBuf::from(plan_file)
+    }
+}
@@ -0,0 +1,164 @@
+use dialoguer::{Input, Password};
+use std::convert::TryFrom;
+use structopt::StructOpt;
+
+/// Split a seed phrase into Shamir secret shares, any `threshold` of which
+/// restore it with `utils restore-seed` (the BIP39 passphrase is not included)
+#[derive(Debug)]
+pub struct BackupSeed {
+    pub seed_phrase: String,
+    pub shares: u8,
+    pub threshold: u8,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliBackupSeed {
+    #[structopt(long)]
+    seed_phrase: Option<String>,
+    /// How many share codes to print
+    #[structopt(long)]
+    shares: Option<u8>,
+    /// How many share codes restore the seed phrase
+    #[structopt(long)]
+    threshold: Option<u8>,
+}
+
+impl From<CliBackupSeed> for BackupSeed {
+    fn from(item: CliBackupSeed) -> Self {
+        let seed_phrase: String = match item.seed_phrase {
+            Some(cli_seed_phrase) => cli_seed_phrase,
+            None => BackupSeed::input_seed_phrase(),
+        };
+        let shares: u8 = match item.shares {
+            Some(cli_shares) => cli_shares,
+            None => BackupSeed::input_shares(),
+        };
+        let threshold: u8 = match item.threshold {
+            Some(cli_threshold) => cli_threshold,
+            None => BackupSeed::input_threshold(),
+        };
+        BackupSeed {
+            seed_phrase,
+            shares,
+            threshold,
+        }
+    }
+}
+
+/// Share codes carry the threshold and a checksum, so a mistyped code is
+/// reported instead of restoring a wrong seed phrase
+const SHARE_CODE_CHECKSUM_LENGTH: usize = 4;
+
+fn share_code_checksum(payload: &[u8]) -> Vec<u8> {
+    near_primitives::hash::hash(payload).as_ref()[..SHARE_CODE_CHECKSUM_LENGTH].to_vec()
+}
+
+pub fn encode_share_code(threshold: u8, share: &sharks::Share) -> String {
+    let mut payload = vec![threshold];
+    payload.extend(Vec::from(share));
+    let checksum = share_code_checksum(&payload);
+    payload.extend(checksum);
+    bs58::encode(payload).into_string()
+}
+
+/// Returns the threshold and the share
+pub fn decode_share_code(share_code: &str) -> Result<(u8, sharks::Share), String> {
+    let invalid = || format!("<{}> is not a valid share code", share_code);
+    let bytes = bs58::decode(share_code.trim())
+        .into_vec()
+        .map_err(|_| invalid())?;
+    if bytes.len() < 2 + SHARE_CODE_CHECKSUM_LENGTH {
+        return Err(invalid());
+    }
+    let (payload, checksum) = bytes.split_at(bytes.len() - SHARE_CODE_CHECKSUM_LENGTH);
+    if share_code_checksum(payload) != checksum {
+        return Err(format!("The share code <{}> has a typo", share_code));
+    }
+    let share = sharks::Share::try_from(&payload[1..]).map_err(|_| invalid())?;
+    Ok((payload[0], share))
+}
+
+impl BackupSeed {
+    pub fn process(self) {
+        if self.threshold < 2 || self.threshold > self.shares {
+            return eprintln!(
+                "The threshold must be at least 2 and at most the number of shares ({})",
+                self.shares
+            );
+        }
+        let mnemonic = match bip39::Mnemonic::parse(self.seed_phrase.trim()) {
+            Ok(mnemonic) => mnemonic,
+            Err(err) => return eprintln!("Invalid seed phrase: {}", err),
+        };
+        let dealer = sharks::Sharks(self.threshold).dealer(&mnemonic.to_entropy());
+        eprintln!(
+            "{}",
+            crate::style::warning(format!(
+                "Keep the share codes apart, any {} of them restore the seed phrase",
+                self.threshold
+            ))
+        );
+        for (index, share) in dealer.take(usize::from(self.shares)).enumerate() {
+            println!(
+                "Share {}/{}: {}",
+                index + 1,
+                self.shares,
+                encode_share_code(self.threshold, &share)
+            );
+        }
+        crate::common::append_to_audit_log(serde_json::json!({
+            "event": "seed_phrase_backed_up",
+            "shares": self.shares,
+            "threshold": self.threshold,
+        }));
+    }
+    pub fn input_seed_phrase() -> String {
+        Password::new()
+            .with_prompt("Enter the seed phrase to back up")
+            .interact()
+            .unwrap()
+    }
+    pub fn input_shares() -> u8 {
+        Input::new()
+            .with_prompt("How many share codes do you want to create?")
+            .default(5)
+            .interact_text()
+            .unwrap()
+    }
+    pub fn input_threshold() -> u8 {
+        Input::new()
+            .with_prompt("How many share codes should restore the seed phrase?")
+            .default(3)
+            .interact_text()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn share_codes_round_trip() {
+        let dealer = sharks::Sharks(2).dealer(&[7; 16]);
+        for share in dealer.take(3) {
+            let share_code = encode_share_code(2, &share);
+            let (threshold, decoded_share) = decode_share_code(&share_code).unwrap();
+            assert_eq!(threshold, 2);
+            assert_eq!(Vec::from(&decoded_share), Vec::from(&share));
+        }
+    }
+
+    #[test]
+    fn decode_share_code_rejects_typos() {
+        let share = sharks::Sharks(2).dealer(&[7; 16]).next().unwrap();
+        let share_code = encode_share_code(2, &share);
+        let mut typo = share_code.clone().into_bytes();
+        typo[3] = if typo[3] == b'2' { b'3' } else { b'2' };
+        assert!(decode_share_code(&String::from_utf8(typo).unwrap()).is_err());
+        assert!(decode_share_code(&share_code[..share_code.len() - 1]).is_err());
+        for share_code in &["", "0OIl", "11"] {
+            assert!(decode_share_code(share_code).is_err(), "{:?}", share_code);
+        }
+    }
+}
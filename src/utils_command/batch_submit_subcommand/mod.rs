@@ -0,0 +1,304 @@
+use dialoguer::Input;
+use futures::StreamExt;
+use structopt::StructOpt;
+
+const MAX_ATTEMPTS: usize = 5;
+const MAX_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Submit base64-encoded signed transactions from a file (one per line) with
+/// rate limiting and adaptive slowdown on throttling
+#[derive(Debug)]
+pub struct BatchSubmit {
+    pub file: std::path::PathBuf,
+    pub connection_config: crate::common::ConnectionConfig,
+    pub rate: f64,
+    pub max_in_flight: usize,
+    /// Track the progress under this name, see `crate::campaign::Campaign`
+    pub campaign: Option<String>,
+    /// Continue the campaign rather than start it
+    pub resume: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliBatchSubmit {
+    #[structopt(long)]
+    file: Option<std::path::PathBuf>,
+    /// Save the progress as a named campaign that can be resumed
+    #[structopt(long)]
+    campaign: Option<String>,
+    /// Continue a campaign, sending the transactions that have not succeeded yet
+    #[structopt(long, conflicts_with_all = &["file", "campaign"])]
+    resume: Option<String>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+    /// Transactions per second
+    #[structopt(long, default_value = "1")]
+    rate: f64,
+    #[structopt(long, default_value = "1")]
+    max_in_flight: usize,
+}
+
+impl From<CliBatchSubmit> for BatchSubmit {
+    fn from(item: CliBatchSubmit) -> Self {
+        if let Some(campaign_name) = item.resume {
+            let campaign = crate::campaign::Campaign::load(&campaign_name)
+                .map_err(|err| eprintln!("{}", err))
+                .unwrap();
+            return BatchSubmit {
+                file: campaign.file,
+                connection_config: crate::common::ConnectionConfig::from_rpc_url(campaign.rpc_url),
+                rate: item.rate,
+                max_in_flight: item.max_in_flight,
+                campaign: Some(campaign_name),
+                resume: true,
+            };
+        }
+        let file: std::path::PathBuf = match item.file {
+            Some(cli_file) => cli_file,
+            None => BatchSubmit::input_file(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        BatchSubmit {
+            file,
+            connection_config,
+            rate: item.rate,
+            max_in_flight: item.max_in_flight,
+            campaign: item.campaign,
+            resume: false,
+        }
+    }
+}
+
+struct SubmitOutcome {
+    index: usize,
+    attempt: usize,
+    signed_transaction: String,
+    result: Result<near_primitives::views::FinalExecutionOutcomeView, String>,
+}
+
+async fn submit_signed_transaction(
+    server_url: url::Url,
+    index: usize,
+    attempt: usize,
+    signed_transaction: String,
+) -> SubmitOutcome {
+    let result = near_jsonrpc_client::new_client(server_url.as_str())
+        .broadcast_tx_commit(signed_transaction.clone())
+        .await
+        .map_err(|err| format!("{:?}", err));
+    SubmitOutcome {
+        index,
+        attempt,
+        signed_transaction,
+        result,
+    }
+}
+
+fn is_throttled(err: &str) -> bool {
+    let err = err.to_lowercase();
+    err.contains("429") || err.contains("too many requests") || err.contains("timeout")
+}
+
+impl BatchSubmit {
+    pub async fn process(self) {
+        let signed_transactions = std::fs::read_to_string(&self.file)
+            .map_err(|err| eprintln!("Error reading the file {:?}:  {:?}", &self.file, &err))
+            .unwrap();
+        let signed_transactions = signed_transactions
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect::<Vec<_>>();
+        let server_url = self.connection_config.rpc_url();
+        let mut campaign = match &self.campaign {
+            Some(campaign_name) => {
+                let campaign = if self.resume {
+                    crate::campaign::Campaign::load(campaign_name)
+                } else {
+                    crate::campaign::Campaign::new(campaign_name, &self.file, &server_url)
+                };
+                match campaign {
+                    Ok(campaign) => Some(campaign),
+                    Err(err) => return eprintln!("{}", err),
+                }
+            }
+            None => None,
+        };
+        // Resending a transaction that already landed is harmless (its nonce is
+        // used), but there is no point in it
+        let mut pending = signed_transactions
+            .iter()
+            .enumerate()
+            .filter(|(_, signed_transaction)| match &campaign {
+                Some(campaign) => !campaign.is_succeeded(signed_transaction),
+                None => true,
+            })
+            .map(|(index, signed_transaction)| (index, 1, signed_transaction.clone()))
+            .collect::<std::collections::VecDeque<_>>();
+        let total = pending.len();
+        if total < signed_transactions.len() {
+            eprintln!(
+                "Skipping {} transactions that succeeded before",
+                signed_transactions.len() - total
+            );
+        }
+        let min_interval = std::time::Duration::from_secs_f64(1.0 / self.rate.max(0.001));
+        let mut interval = min_interval;
+        let mut next_launch = std::time::Instant::now();
+        let mut in_flight = futures::stream::FuturesUnordered::new();
+        let mut succeeded: usize = 0;
+        let mut failed: usize = 0;
+        eprintln!("Submitting {} transactions to {}", total, &server_url);
+        loop {
+            let now = std::time::Instant::now();
+            if in_flight.len() < self.max_in_flight.max(1) && now >= next_launch {
+                if let Some((index, attempt, signed_transaction)) = pending.pop_front() {
+                    crate::common::emit_event(
+                        "transaction_submitted",
+                        serde_json::json!({ "index": index, "attempt": attempt }),
+                    );
+                    in_flight.push(submit_signed_transaction(
+                        server_url.clone(),
+                        index,
+                        attempt,
+                        signed_transaction,
+                    ));
+                    next_launch = now + interval;
+                    continue;
+                }
+            }
+            if in_flight.is_empty() {
+                if pending.is_empty() {
+                    break;
+                }
+                actix_rt::time::delay_for(next_launch.saturating_duration_since(now)).await;
+                continue;
+            }
+            let wait = if pending.is_empty() || in_flight.len() >= self.max_in_flight.max(1) {
+                MAX_INTERVAL
+            } else {
+                next_launch.saturating_duration_since(now)
+            };
+            let outcome = match futures::future::select(
+                in_flight.next(),
+                Box::pin(actix_rt::time::delay_for(wait)),
+            )
+            .await
+            {
+                futures::future::Either::Left((Some(outcome), _)) => outcome,
+                _ => continue,
+            };
+            match outcome.result {
+                Ok(transaction_info) => {
+                    // A transaction that landed may still have failed on chain
+                    let status = match &transaction_info.status {
+                        near_primitives::views::FinalExecutionStatus::SuccessValue(_) => {
+                            succeeded += 1;
+                            crate::campaign::CampaignItemStatus::Succeeded {
+                                outcome: format!("{:?}", transaction_info.status),
+                            }
+                        }
+                        status => {
+                            failed += 1;
+                            crate::campaign::CampaignItemStatus::Failed {
+                                error: format!("The transaction failed: {:?}", status),
+                            }
+                        }
+                    };
+                    if let Some(campaign) = campaign.as_mut() {
+                        campaign.record(&outcome.signed_transaction, status);
+                    }
+                    interval = std::cmp::max(min_interval, interval / 2);
+                    crate::common::emit_event(
+                        "finality_reached",
+                        serde_json::json!({
+                            "index": outcome.index,
+                            "transaction_hash": transaction_info.transaction.hash.to_string(),
+                            "status": serde_json::to_value(&transaction_info.status).unwrap_or_default(),
+                        }),
+                    );
+                    println!(
+                        "#{}: {} {:?}",
+                        outcome.index,
+                        crate::style::hash(transaction_info.transaction.hash),
+                        transaction_info.status
+                    );
+                }
+                Err(err)
+                    if is_throttled(&err)
+                        && outcome.attempt < MAX_ATTEMPTS
+                        && crate::common::has_time_for(std::cmp::min(
+                            MAX_INTERVAL,
+                            interval * 2,
+                        )) =>
+                {
+                    interval = std::cmp::min(MAX_INTERVAL, interval * 2);
+                    crate::common::emit_event(
+                        "throttled",
+                        serde_json::json!({
+                            "index": outcome.index,
+                            "interval_ms": interval.as_millis() as u64,
+                        }),
+                    );
+                    eprintln!(
+                        "#{}: {}",
+                        outcome.index,
+                        crate::style::warning(format!(
+                            "the RPC server is throttling ({}), slowing down to one transaction per {:?}",
+                            err, interval
+                        ))
+                    );
+                    next_launch = std::time::Instant::now() + interval;
+                    pending.push_back((
+                        outcome.index,
+                        outcome.attempt + 1,
+                        outcome.signed_transaction,
+                    ));
+                }
+                Err(err) => {
+                    failed += 1;
+                    if let Some(campaign) = campaign.as_mut() {
+                        campaign.record(
+                            &outcome.signed_transaction,
+                            crate::campaign::CampaignItemStatus::Failed { error: err.clone() },
+                        );
+                    }
+                    crate::common::emit_event(
+                        "transaction_failed",
+                        serde_json::json!({ "index": outcome.index, "error": &err }),
+                    );
+                    eprintln!("#{}: Error transaction:  {}", outcome.index, err);
+                }
+            }
+        }
+        eprintln!(
+            "Submitted {} transactions: {} succeeded, {} failed",
+            total, succeeded, failed
+        );
+        if let Some(campaign) = &campaign {
+            campaign.print_report(&signed_transactions);
+        }
+    }
+    pub fn input_file() -> std::path::PathBuf {
+        let file: String = Input::new()
+            .with_prompt("Enter the path to the file with signed transactions (one per line)")
+            .interact_text()
+            .unwrap();
+        std::path::PathBuf::from(file)
+    }
+    pub fn input_rate() -> f64 {
+        Input::new()
+            .with_prompt("How many transactions per second should be submitted?")
+            .default(1.0)
+            .interact_text()
+            .unwrap()
+    }
+    pub fn input_max_in_flight() -> usize {
+        Input::new()
+            .with_prompt("How many transactions can be in flight at the same time?")
+            .default(1)
+            .interact_text()
+            .unwrap()
+    }
+}
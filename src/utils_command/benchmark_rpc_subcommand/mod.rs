@@ -0,0 +1,181 @@
+use structopt::StructOpt;
+
+use crate::rpc_benchmark::{EndpointBenchmark, NetworkBenchmark, ProbeLatency};
+
+/// Measure the latency of the RPC endpoints of a network (the main one and the
+/// `fallback_rpc_urls` of its chain profile) and remember the fastest healthy one
+#[derive(Debug)]
+pub struct BenchmarkRpc {
+    pub samples: usize,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliBenchmarkRpc {
+    /// Requests of each kind per endpoint
+    #[structopt(long, default_value = "10")]
+    samples: usize,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliBenchmarkRpc> for BenchmarkRpc {
+    fn from(item: CliBenchmarkRpc) -> Self {
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        BenchmarkRpc {
+            samples: item.samples,
+            connection_config,
+        }
+    }
+}
+
+/// The kinds of requests: `status`, a `query` of a validator account, and a
+/// `broadcast` of an invalid transaction (rejected by the node, so nothing is
+/// submitted, but it goes the way of a real submission)
+const PROBES: &[&str] = &["status", "query", "broadcast"];
+
+impl BenchmarkRpc {
+    pub async fn process(self) {
+        if self.samples == 0 {
+            return eprintln!("There has to be at least one sample");
+        }
+        let rpc_urls = self.connection_config.rpc_urls();
+        let mut endpoints = vec![];
+        for rpc_url in rpc_urls {
+            eprintln!("Measuring {} ...", rpc_url);
+            endpoints.push(self.benchmark_endpoint(rpc_url).await);
+        }
+        endpoints.sort_by_key(|endpoint| (endpoint.median_ms.is_none(), endpoint.median_ms));
+        for endpoint in &endpoints {
+            match endpoint.median_ms {
+                Some(median_ms) => println!("{} (median {} ms)", endpoint.url, median_ms),
+                None => println!("{} ({})", endpoint.url, crate::style::warning("unhealthy")),
+            }
+            for probe in &endpoint.probes {
+                println!(
+                    "  {:<10} p50 {:>5} ms  p90 {:>5} ms  p99 {:>5} ms  {} failed",
+                    probe.probe, probe.p50_ms, probe.p90_ms, probe.p99_ms, probe.failures
+                );
+            }
+        }
+        if endpoints.len() > 1 {
+            println!("Recommended order of the endpoints, the fastest healthy one first:");
+            for endpoint in endpoints
+                .iter()
+                .filter(|endpoint| endpoint.median_ms.is_some())
+            {
+                println!("  {}", endpoint.url);
+            }
+        } else {
+            eprintln!(
+                "Add more endpoints as `fallback_rpc_urls` of the chain profile to compare them"
+            );
+        }
+        let network_benchmark = NetworkBenchmark {
+            measured_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            endpoints,
+        };
+        match crate::rpc_benchmark::save_network_benchmark(
+            &self.connection_config.dir_name(),
+            network_benchmark,
+        ) {
+            Ok(()) => eprintln!(
+                "{} the results, the fastest healthy endpoint is used for a day",
+                crate::style::success("Saved")
+            ),
+            Err(err) => eprintln!("{}", err),
+        }
+    }
+
+    async fn benchmark_endpoint(&self, rpc_url: url::Url) -> EndpointBenchmark {
+        let validator_account_id =
+            match timed_request(&rpc_url, "status", serde_json::json!([])).await {
+                Ok((_, status)) => status["result"]["validators"][0]["account_id"]
+                    .as_str()
+                    .unwrap_or("near")
+                    .to_string(),
+                Err(_) => "near".to_string(),
+            };
+        let mut all_latencies = vec![];
+        let mut healthy = true;
+        let mut probes = vec![];
+        for probe in PROBES {
+            let (method, params) = match *probe {
+                "status" => ("status", serde_json::json!([])),
+                "query" => (
+                    "query",
+                    serde_json::json!({
+                        "request_type": "view_account",
+                        "finality": "final",
+                        "account_id": validator_account_id,
+                    }),
+                ),
+                _ => ("broadcast_tx_async", serde_json::json!(["AA=="])),
+            };
+            let mut latencies = vec![];
+            let mut failures = 0;
+            for _ in 0..self.samples {
+                match timed_request(&rpc_url, method, params.clone()).await {
+                    // The invalid transaction is expected to be rejected
+                    Ok((latency, _)) if *probe == "broadcast" => latencies.push(latency),
+                    Ok((latency, response)) if response.get("error").is_none() => {
+                        latencies.push(latency)
+                    }
+                    _ => failures += 1,
+                }
+            }
+            healthy &= failures == 0;
+            all_latencies.extend(&latencies);
+            latencies.sort_unstable();
+            probes.push(ProbeLatency {
+                probe: probe.to_string(),
+                p50_ms: percentile(&latencies, 50),
+                p90_ms: percentile(&latencies, 90),
+                p99_ms: percentile(&latencies, 99),
+                failures,
+            });
+        }
+        all_latencies.sort_unstable();
+        EndpointBenchmark {
+            url: rpc_url,
+            median_ms: if healthy {
+                Some(percentile(&all_latencies, 50))
+            } else {
+                None
+            },
+            probes,
+        }
+    }
+}
+
+/// Milliseconds and the whole JSON RPC response, errors included
+async fn timed_request(
+    rpc_url: &url::Url,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<(u64, serde_json::Value), String> {
+    let started_at = std::time::Instant::now();
+    let mut response = actix_web::client::Client::default()
+        .post(rpc_url.as_str())
+        .send_json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "dontcare",
+            "method": method,
+            "params": params,
+        }))
+        .await
+        .map_err(|err| format!("{:?}", err))?;
+    let response: serde_json::Value = response.json().await.map_err(|err| format!("{:?}", err))?;
+    Ok((started_at.elapsed().as_millis() as u64, response))
+}
+
+/// Of sorted values; zero if there are none
+fn percentile(sorted_values: &[u64], percentile: usize) -> u64 {
+    if sorted_values.is_empty() {
+        return 0;
+    }
+    sorted_values[(sorted_values.len() - 1) * percentile / 100]
+}
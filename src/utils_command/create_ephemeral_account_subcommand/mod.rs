@@ -0,0 +1,456 @@
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use near_primitives::borsh::BorshSerialize;
+use std::str::FromStr;
+use structopt::StructOpt;
+use strum::VariantNames;
+
+use crate::consts::{BETANET_API_SERVER_URL, TESTNET_API_SERVER_URL};
+
+#[derive(
+    Debug,
+    strum_macros::IntoStaticStr,
+    strum_macros::EnumString,
+    strum_macros::EnumVariantNames,
+    smart_default::SmartDefault,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum EphemeralNetwork {
+    #[default]
+    Testnet,
+    Betanet,
+}
+
+impl EphemeralNetwork {
+    fn rpc_url(&self) -> &'static str {
+        match self {
+            EphemeralNetwork::Testnet => TESTNET_API_SERVER_URL,
+            EphemeralNetwork::Betanet => BETANET_API_SERVER_URL,
+        }
+    }
+    /// The chain profile of the network may point to another helper
+    fn helper_url(&self) -> url::Url {
+        let connection_config = match self {
+            EphemeralNetwork::Testnet => crate::common::ConnectionConfig::Testnet,
+            EphemeralNetwork::Betanet => crate::common::ConnectionConfig::Betanet,
+        };
+        connection_config.helper_url().unwrap()
+    }
+}
+
+/// How many times deleting the account is tried before giving up
+const DELETE_ACCOUNT_ATTEMPTS: u32 = 3;
+
+/// Create a funded throwaway account, optionally run a command with its
+/// credentials in NEAR_* environment variables, and delete the account afterwards;
+/// exits with the status of the command, or 1 when the account is not deleted
+#[derive(Debug)]
+pub struct CreateEphemeralAccount {
+    pub network: EphemeralNetwork,
+    pub beneficiary_id: String,
+    pub command: Option<String>,
+    /// Without a command, keep the account this long before deleting it
+    pub keep_for: Option<std::time::Duration>,
+    pub key_type: crate::common::KeyType,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliCreateEphemeralAccount {
+    #[structopt(long)]
+    network: Option<EphemeralNetwork>,
+    #[structopt(long)]
+    beneficiary_id: Option<String>,
+    #[structopt(long)]
+    command: Option<String>,
+    /// Run without a command: keep the account for this long (e.g. `30m`), then delete it
+    #[structopt(
+        long,
+        parse(try_from_str = crate::common::parse_duration),
+        conflicts_with = "command"
+    )]
+    keep_for: Option<std::time::Duration>,
+    #[structopt(long, possible_values = crate::common::KeyType::VARIANTS)]
+    key_type: Option<crate::common::KeyType>,
+}
+
+impl From<CliCreateEphemeralAccount> for CreateEphemeralAccount {
+    fn from(item: CliCreateEphemeralAccount) -> Self {
+        let network: EphemeralNetwork = match item.network {
+            Some(cli_network) => cli_network,
+            None => CreateEphemeralAccount::input_network(),
+        };
+        let beneficiary_id: String = match item.beneficiary_id {
+            Some(cli_beneficiary_id) => cli_beneficiary_id,
+            None => CreateEphemeralAccount::input_beneficiary_id(&network),
+        };
+        let command: Option<String> = match (item.command, item.keep_for) {
+            (Some(cli_command), _) => Some(cli_command),
+            (None, Some(_)) => None,
+            (None, None) => CreateEphemeralAccount::input_command(),
+        };
+        let key_type = match item.key_type {
+            Some(cli_key_type) => cli_key_type,
+            None => crate::common::KeyType::input_key_type(),
+        };
+        CreateEphemeralAccount {
+            network,
+            beneficiary_id,
+            command,
+            keep_for: item.keep_for,
+            key_type,
+        }
+    }
+}
+
+impl CreateEphemeralAccount {
+    pub async fn process(self) {
+        let network_name: &str = (&self.network).into();
+        let account_id = format!(
+            "ephemeral-{}.{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis(),
+            network_name
+        );
+        let secret_key = near_crypto::SecretKey::from_random((&self.key_type).into());
+        let public_key = secret_key.public_key();
+        // From here on Ctrl-C or a SIGTERM deletes the account instead of
+        // killing near-cli with it
+        let mut termination = listen_for_termination();
+
+        let mut response = actix_web::client::Client::default()
+            .post(format!(
+                "{}/account",
+                self.network.helper_url().as_str().trim_end_matches('/')
+            ))
+            .send_json(&serde_json::json!({
+                "newAccountId": account_id,
+                "newAccountPublicKey": public_key.to_string(),
+            }))
+            .await
+            .map_err(|err| eprintln!("Error creating the account:  {:?}", &err))
+            .unwrap();
+        if !response.status().is_success() {
+            return eprintln!(
+                "The faucet could not create the account {}: {} {:?}",
+                account_id,
+                response.status(),
+                response.body().await
+            );
+        }
+
+        println!(
+            "Ephemeral account has been created:\nAccount ID: {}\nPublic Key: {}\nSECRET KEY: {}",
+            account_id,
+            public_key,
+            crate::common::display_secret(&secret_key.to_string(), crate::common::reveal_secrets())
+        );
+
+        // The account is deleted even when --max-duration is up
+        crate::common::let_command_handle_timeout();
+        let mut exit_code = 0;
+        let mut interruption = None;
+        if let Some(command) = &self.command {
+            let exit_status = std::process::Command::new("sh")
+                .arg("-c")
+                .arg(command)
+                .env("NEAR_NETWORK", network_name)
+                .env("NEAR_RPC_URL", self.network.rpc_url())
+                .env("NEAR_ACCOUNT_ID", &account_id)
+                .env("NEAR_PUBLIC_KEY", public_key.to_string())
+                .env("NEAR_PRIVATE_KEY", secret_key.to_string())
+                .spawn();
+            let exit_status = match exit_status {
+                Ok(mut child) => {
+                    let exit_status = wait_for_child(&mut child, &mut termination).await;
+                    if let Ok(Err(stopped_by)) = &exit_status {
+                        stop_child(&mut child, stopped_by).await;
+                    }
+                    exit_status
+                }
+                Err(err) => Err(err),
+            };
+            exit_code = match exit_status {
+                Ok(Ok(exit_status)) => {
+                    eprintln!("The command finished with {}", exit_status);
+                    // Killed by a signal when there is no code
+                    exit_status.code().unwrap_or(1)
+                }
+                Ok(Err(stopped_by)) => {
+                    eprintln!("The command is stopped: {}", stopped_by);
+                    interruption = Some(stopped_by);
+                    1
+                }
+                Err(err) => {
+                    eprintln!("Error running the command:  {:?}", err);
+                    1
+                }
+            };
+        } else if let Some(keep_for) = self.keep_for {
+            eprintln!("Keeping the account for {:?}", keep_for);
+            if let Err(stopped_by) = sleep_unless_interrupted(keep_for, &mut termination).await {
+                eprintln!("Deleting the account early: {}", stopped_by);
+                interruption = Some(stopped_by);
+            }
+        }
+
+        let mut attempt = 1;
+        while let Err(err) = self
+            .delete_account(&account_id, public_key.clone(), &secret_key)
+            .await
+        {
+            eprintln!(
+                "Error deleting the account {} (attempt {}/{}):  {}",
+                account_id, attempt, DELETE_ACCOUNT_ATTEMPTS, err
+            );
+            let retry_delay = std::time::Duration::from_secs(2);
+            // After a timeout or a signal the retries are still worth the few seconds
+            if attempt == DELETE_ACCOUNT_ATTEMPTS
+                || (interruption.is_none() && !crate::common::has_time_for(retry_delay))
+            {
+                eprintln!(
+                    "{}",
+                    crate::style::warning(format!(
+                        "The account {} was not deleted, delete it with its secret key",
+                        account_id
+                    ))
+                );
+                std::process::exit(1);
+            }
+            attempt += 1;
+            actix_rt::time::delay_for(retry_delay).await;
+        }
+        match interruption {
+            Some(Interruption::Timeout) => crate::common::stop_on_timeout(),
+            // The exit code of a shell for a process killed by the signal
+            Some(Interruption::Signal(_, number)) => std::process::exit(128 + number),
+            None => {}
+        }
+        if exit_code != 0 {
+            std::process::exit(exit_code);
+        }
+    }
+    async fn delete_account(
+        &self,
+        account_id: &str,
+        public_key: near_crypto::PublicKey,
+        secret_key: &near_crypto::SecretKey,
+    ) -> Result<(), String> {
+        let rpc_url = self.network.rpc_url();
+        let access_key_response = near_jsonrpc_client::new_client(rpc_url)
+            .query(near_primitives::rpc::RpcQueryRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+                request: near_primitives::views::QueryRequest::ViewAccessKey {
+                    account_id: account_id.to_string(),
+                    public_key: public_key.clone(),
+                },
+            })
+            .await
+            .map_err(|err| format!("Failed to fetch the access key: {:?}", err))?;
+        let current_nonce =
+            if let near_primitives::views::QueryResponseKind::AccessKey(access_key) =
+                access_key_response.kind
+            {
+                access_key.nonce
+            } else {
+                return Err("Unexpected response to the access key query".to_string());
+            };
+        let unsigned_transaction = near_primitives::transaction::Transaction {
+            signer_id: account_id.to_string(),
+            public_key,
+            nonce: current_nonce + 1,
+            receiver_id: account_id.to_string(),
+            block_hash: access_key_response.block_hash,
+            actions: vec![near_primitives::transaction::Action::DeleteAccount(
+                near_primitives::transaction::DeleteAccountAction {
+                    beneficiary_id: self.beneficiary_id.clone(),
+                },
+            )],
+        };
+        let signature = secret_key.sign(unsigned_transaction.get_hash().as_ref());
+        let signed_transaction =
+            near_primitives::transaction::SignedTransaction::new(signature, unsigned_transaction);
+        let transaction_info = near_jsonrpc_client::new_client(rpc_url)
+            .broadcast_tx_commit(near_primitives::serialize::to_base64(
+                signed_transaction
+                    .try_to_vec()
+                    .expect("Transaction is not expected to fail on serialization"),
+            ))
+            .await
+            .map_err(|err| format!("Failed to send the transaction: {:?}", err))?;
+        match transaction_info.status {
+            near_primitives::views::FinalExecutionStatus::SuccessValue(_) => {
+                println!(
+                    "The account {} has been deleted, the remaining balance went to {}",
+                    account_id, self.beneficiary_id
+                );
+                Ok(())
+            }
+            status => Err(format!("The transaction failed: {:?}", status)),
+        }
+    }
+    pub fn input_network() -> EphemeralNetwork {
+        eprintln!();
+        let networks = EphemeralNetwork::VARIANTS;
+        let select_network = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select the network for the ephemeral account:")
+            .items(&networks)
+            .default(0)
+            .interact()
+            .unwrap();
+        EphemeralNetwork::from_str(networks[select_network]).unwrap()
+    }
+    pub fn input_beneficiary_id(network: &EphemeralNetwork) -> String {
+        let network_name: &str = network.into();
+        Input::new()
+            .with_prompt("Enter the beneficiary ID to receive the remaining balance")
+            .default(network_name.to_string())
+            .interact_text()
+            .unwrap()
+    }
+    pub fn input_command() -> Option<String> {
+        let command: String = Input::new()
+            .with_prompt(
+                "Enter a command to run with the account credentials (leave empty to skip)",
+            )
+            .allow_empty(true)
+            .interact_text()
+            .unwrap();
+        if command.trim().is_empty() {
+            None
+        } else {
+            Some(command)
+        }
+    }
+}
+
+/// Why the account is deleted before the command is done
+#[derive(Debug)]
+enum Interruption {
+    /// `--max-duration` is up
+    Timeout,
+    /// SIGINT or SIGTERM, by its name for `kill` and its number
+    Signal(&'static str, i32),
+}
+
+impl std::fmt::Display for Interruption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Interruption::Timeout => write!(f, "--max-duration is up"),
+            Interruption::Signal(name, _) => write!(f, "got SIG{}", name),
+        }
+    }
+}
+
+type Termination = futures::future::LocalBoxFuture<'static, (&'static str, i32)>;
+
+/// How long the command gets to exit after a forwarded signal before it is killed
+const SIGNAL_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Handles SIGINT and SIGTERM from now on; the future resolves with the first
+/// one received
+#[cfg(unix)]
+fn listen_for_termination() -> Termination {
+    use actix_rt::signal::unix::{signal, SignalKind};
+
+    match (
+        signal(SignalKind::interrupt()),
+        signal(SignalKind::terminate()),
+    ) {
+        (Ok(mut interrupt), Ok(mut terminate)) => Box::pin(async move {
+            match futures::future::select(Box::pin(interrupt.recv()), Box::pin(terminate.recv()))
+                .await
+            {
+                futures::future::Either::Left(_) => ("INT", 2),
+                futures::future::Either::Right(_) => ("TERM", 15),
+            }
+        }),
+        (Err(err), _) | (_, Err(err)) => {
+            eprintln!("Error listening for Ctrl-C:  {:?}", err);
+            Box::pin(futures::future::pending())
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn listen_for_termination() -> Termination {
+    Box::pin(async {
+        if let Err(err) = actix_rt::signal::ctrl_c().await {
+            eprintln!("Error listening for Ctrl-C:  {:?}", err);
+            futures::future::pending::<()>().await;
+        }
+        ("INT", 2)
+    })
+}
+
+/// Sleeps for `wait`, or less when `--max-duration` is up or a termination
+/// signal arrives first
+async fn sleep_unless_interrupted(
+    wait: std::time::Duration,
+    termination: &mut Termination,
+) -> Result<(), Interruption> {
+    let time_left = crate::common::time_left();
+    let sleep = time_left.map_or(wait, |time_left| std::cmp::min(time_left, wait));
+    if let futures::future::Either::Right(((name, number), _)) = futures::future::select(
+        Box::pin(actix_rt::time::delay_for(sleep)),
+        termination.as_mut(),
+    )
+    .await
+    {
+        return Err(Interruption::Signal(name, number));
+    }
+    match time_left {
+        Some(time_left) if time_left <= wait => Err(Interruption::Timeout),
+        _ => Ok(()),
+    }
+}
+
+/// Polls the command instead of blocking on it, so `--max-duration` and a
+/// termination signal can stop the run while it is still running
+async fn wait_for_child(
+    child: &mut std::process::Child,
+    termination: &mut Termination,
+) -> std::io::Result<Result<std::process::ExitStatus, Interruption>> {
+    let poll_interval = std::time::Duration::from_millis(100);
+    loop {
+        if let Some(exit_status) = child.try_wait()? {
+            return Ok(Ok(exit_status));
+        }
+        if let Err(interruption) = sleep_unless_interrupted(poll_interval, termination).await {
+            return Ok(Err(interruption));
+        }
+    }
+}
+
+/// Forwards the signal to the command and gives it some time to exit; kills
+/// it when the time is up or it does not exit
+async fn stop_child(child: &mut std::process::Child, interruption: &Interruption) {
+    if let Interruption::Signal(name, _) = interruption {
+        if forward_signal(child, name) {
+            let started_at = std::time::Instant::now();
+            while started_at.elapsed() < SIGNAL_GRACE_PERIOD {
+                if let Ok(Some(_)) = child.try_wait() {
+                    return;
+                }
+                actix_rt::time::delay_for(std::time::Duration::from_millis(100)).await;
+            }
+        }
+    }
+    child.kill().ok();
+    child.wait().ok();
+}
+
+/// There is no libc dependency to call kill(2) with, so this runs kill(1)
+#[cfg(unix)]
+fn forward_signal(child: &std::process::Child, name: &str) -> bool {
+    std::process::Command::new("kill")
+        .arg(format!("-{}", name))
+        .arg(child.id().to_string())
+        .status()
+        .map_or(false, |status| status.success())
+}
+
+#[cfg(not(unix))]
+fn forward_signal(_child: &std::process::Child, _name: &str) -> bool {
+    false
+}
@@ -0,0 +1,66 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+/// Decode base64-encoded borsh data (e.g. a function call result or a stored
+/// value) into named fields with the help of a BorshSchema JSON file
+#[derive(Debug)]
+pub struct Decode {
+    pub data: String,
+    pub borsh_schema: std::path::PathBuf,
+    pub declaration: Option<String>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliDecode {
+    data: Option<String>,
+    #[structopt(long)]
+    borsh_schema: Option<std::path::PathBuf>,
+    #[structopt(long)]
+    declaration: Option<String>,
+}
+
+impl From<CliDecode> for Decode {
+    fn from(item: CliDecode) -> Self {
+        let data: String = match item.data {
+            Some(cli_data) => cli_data,
+            None => Decode::input_data(),
+        };
+        let borsh_schema = match item.borsh_schema {
+            Some(cli_borsh_schema) => cli_borsh_schema,
+            None => Decode::input_borsh_schema(),
+        };
+        Decode {
+            data,
+            borsh_schema,
+            declaration: item.declaration,
+        }
+    }
+}
+
+impl Decode {
+    pub fn process(self) {
+        let schema = crate::borsh_schema::BorshSchemaContainer::from_file(&self.borsh_schema)
+            .map_err(|err| eprintln!("Error reading the BorshSchema file:  {}", &err))
+            .unwrap();
+        let bytes = base64::decode(&self.data)
+            .map_err(|err| eprintln!("Error decoding the base64 data:  {:?}", &err))
+            .unwrap();
+        match schema.decode(self.declaration.as_deref(), &bytes) {
+            Ok(value) => println!("{}", serde_json::to_string_pretty(&value).unwrap()),
+            Err(err) => eprintln!("Error decoding the data:  {}", err),
+        }
+    }
+    pub fn input_data() -> String {
+        Input::new()
+            .with_prompt("Enter the base64-encoded borsh data")
+            .interact_text()
+            .unwrap()
+    }
+    pub fn input_borsh_schema() -> std::path::PathBuf {
+        let borsh_schema: String = Input::new()
+            .with_prompt("Enter the path to the BorshSchema JSON file")
+            .interact_text()
+            .unwrap();
+        std::path::PathBuf::from(borsh_schema)
+    }
+}
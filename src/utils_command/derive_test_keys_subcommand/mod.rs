@@ -0,0 +1,134 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+use strum::VariantNames;
+
+/// Derive a deterministic set of INSECURE key pairs from a seed string (use it
+/// only for reproducible test environments and documentation examples)
+#[derive(Debug)]
+pub struct DeriveTestKeys {
+    pub seed: String,
+    pub count: usize,
+    pub key_type: crate::common::KeyType,
+    pub format: crate::common::OutputFormat,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliDeriveTestKeys {
+    #[structopt(long)]
+    seed: Option<String>,
+    #[structopt(long)]
+    count: Option<usize>,
+    #[structopt(long, possible_values = crate::common::KeyType::VARIANTS)]
+    key_type: Option<crate::common::KeyType>,
+    #[structopt(long, default_value = "plaintext", possible_values = crate::common::OutputFormat::VARIANTS)]
+    format: crate::common::OutputFormat,
+}
+
+impl From<CliDeriveTestKeys> for DeriveTestKeys {
+    fn from(item: CliDeriveTestKeys) -> Self {
+        let seed: String = match item.seed {
+            Some(cli_seed) => cli_seed,
+            None => DeriveTestKeys::input_seed(),
+        };
+        let count: usize = match item.count {
+            Some(cli_count) => cli_count,
+            None => DeriveTestKeys::input_count(),
+        };
+        let key_type = match item.key_type {
+            Some(cli_key_type) => cli_key_type,
+            None => crate::common::KeyType::input_key_type(),
+        };
+        DeriveTestKeys {
+            seed,
+            count,
+            key_type,
+            format: item.format,
+        }
+    }
+}
+
+impl DeriveTestKeys {
+    pub fn process(self) {
+        let keys = (0..self.count)
+            .map(|index| derive_test_keypair(&self.seed, index, &self.key_type))
+            .collect::<Vec<_>>();
+        match self.format {
+            crate::common::OutputFormat::Plaintext => {
+                eprintln!(
+                    "{}",
+                    crate::style::warning(
+                        "WARNING: these keys are INSECURE, never use them for real funds!"
+                    )
+                );
+                for (index, (implicit_account_id, public_key_str, secret_keypair_str)) in
+                    keys.iter().enumerate()
+                {
+                    println!(
+                        "\nIndex: {}\nImplicit Account ID: {}\nPublic Key: {}\nINSECURE SECRET KEYPAIR: {}",
+                        index,
+                        implicit_account_id
+                            .as_deref()
+                            .unwrap_or("(only ED25519 keys have implicit accounts)"),
+                        public_key_str,
+                        secret_keypair_str,
+                    );
+                }
+            }
+            crate::common::OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::json!({
+                        "insecure": true,
+                        "seed": self.seed,
+                        "keys": keys
+                            .iter()
+                            .map(|(implicit_account_id, public_key_str, secret_keypair_str)| {
+                                serde_json::json!({
+                                    "account_id": implicit_account_id,
+                                    "public_key": public_key_str,
+                                    "private_key": secret_keypair_str,
+                                })
+                            })
+                            .collect::<Vec<_>>(),
+                    })
+                );
+            }
+        }
+    }
+    pub fn input_seed() -> String {
+        Input::new()
+            .with_prompt("Enter the seed string for the INSECURE test keys")
+            .interact_text()
+            .unwrap()
+    }
+    pub fn input_count() -> usize {
+        Input::new()
+            .with_prompt("How many key pairs do you want to derive?")
+            .default(1)
+            .interact_text()
+            .unwrap()
+    }
+}
+
+/// Returns implicit account ID (for ED25519 keys only), public key and secret
+/// key pair strings
+fn derive_test_keypair(
+    seed: &str,
+    index: usize,
+    key_type: &crate::common::KeyType,
+) -> (Option<String>, String, String) {
+    let derived_private_key = near_primitives::hash::hash(format!("{}:{}", seed, index).as_bytes());
+    let mut derived_seed = [0u8; 32];
+    derived_seed.copy_from_slice(derived_private_key.as_ref());
+    let secret_key = key_type.secret_key_from_seed(&derived_seed);
+    let public_key = secret_key.public_key();
+    let implicit_account_id = match &public_key {
+        near_crypto::PublicKey::ED25519(public_key) => Some(hex::encode(public_key.0)),
+        _ => None,
+    };
+    (
+        implicit_account_id,
+        public_key.to_string(),
+        secret_key.to_string(),
+    )
+}
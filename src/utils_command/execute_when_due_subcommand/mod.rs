@@ -0,0 +1,108 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+use super::timelock_request_subcommand::{
+    input_multisig_account_id, sign_multisig_call, unix_timestamp_now,
+};
+
+/// Wait until a request made with `utils timelock-request` is due and confirm
+/// it; the "not before" time is looked up in the audit log unless given
+#[derive(Debug)]
+pub struct ExecuteWhenDue {
+    pub multisig_account_id: String,
+    pub request_id: u64,
+    pub not_before: Option<u64>,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliExecuteWhenDue {
+    multisig_account_id: Option<String>,
+    request_id: Option<u64>,
+    /// Unix time in seconds
+    #[structopt(long)]
+    not_before: Option<u64>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliExecuteWhenDue> for ExecuteWhenDue {
+    fn from(item: CliExecuteWhenDue) -> Self {
+        let multisig_account_id: String = match item.multisig_account_id {
+            Some(cli_multisig_account_id) => cli_multisig_account_id,
+            None => input_multisig_account_id(),
+        };
+        let request_id: u64 = match item.request_id {
+            Some(cli_request_id) => cli_request_id,
+            None => ExecuteWhenDue::input_request_id(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        ExecuteWhenDue {
+            multisig_account_id,
+            request_id,
+            not_before: item.not_before,
+            connection_config,
+        }
+    }
+}
+
+impl ExecuteWhenDue {
+    pub async fn process(self) {
+        let not_before = match self.not_before.or_else(|| {
+            crate::common::read_audit_log()
+                .into_iter()
+                .rev()
+                .find(|entry| {
+                    entry["event"] == "timelock_request"
+                        && entry["multisig_account_id"] == self.multisig_account_id.as_str()
+                        && entry["request_id"] == self.request_id
+                })
+                .and_then(|entry| entry["not_before"].as_u64())
+        }) {
+            Some(not_before) => not_before,
+            None => {
+                return eprintln!(
+                    "Request #{} on <{}> is not in the audit log, pass --not-before",
+                    self.request_id, self.multisig_account_id
+                )
+            }
+        };
+        let now = unix_timestamp_now();
+        if not_before > now {
+            eprintln!(
+                "Request #{} is due in {} seconds, waiting...",
+                self.request_id,
+                not_before - now
+            );
+            actix_rt::time::delay_for(std::time::Duration::from_secs(not_before - now)).await;
+        }
+        let rpc_url = self.connection_config.rpc_url();
+        // The request is gone once it has been executed or deleted
+        if let Err(err) = crate::common::call_view_function(
+            &rpc_url,
+            &self.multisig_account_id,
+            "get_request",
+            serde_json::json!({ "request_id": self.request_id }),
+        )
+        .await
+        {
+            return eprintln!(
+                "Request #{} on <{}> cannot be confirmed:  {}",
+                self.request_id, self.multisig_account_id, err
+            );
+        }
+        sign_multisig_call(
+            &self.multisig_account_id,
+            "confirm",
+            serde_json::json!({ "request_id": self.request_id }),
+            rpc_url,
+        )
+        .await
+    }
+    pub fn input_request_id() -> u64 {
+        Input::new()
+            .with_prompt("What is the ID of the multisig request?")
+            .interact_text()
+            .unwrap()
+    }
+}
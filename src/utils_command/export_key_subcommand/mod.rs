@@ -0,0 +1,159 @@
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
+use structopt::StructOpt;
+use strum::VariantNames;
+
+#[derive(
+    Debug,
+    strum_macros::EnumString,
+    strum_macros::EnumVariantNames,
+    strum_macros::IntoStaticStr,
+    smart_default::SmartDefault,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum ExportFormat {
+    /// The key file of near-api-js and the JavaScript near-cli
+    #[default]
+    NearApiJs,
+    /// The base58 secret key without the key type prefix
+    Base58,
+    SeedPhrase,
+}
+
+/// Print an access key of the keychain for wallets and other tools
+#[derive(Debug)]
+pub struct ExportKey {
+    pub account_id: String,
+    /// `None` exports the only key of the account or asks which one
+    pub public_key: Option<String>,
+    pub format: ExportFormat,
+    pub connection_config: crate::common::ConnectionConfig,
+    pub auto_approve: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliExportKey {
+    account_id: Option<String>,
+    #[structopt(long)]
+    public_key: Option<String>,
+    #[structopt(long, possible_values = ExportFormat::VARIANTS)]
+    format: Option<ExportFormat>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+    /// Skip the confirmation
+    #[structopt(long)]
+    auto_approve: bool,
+}
+
+impl From<CliExportKey> for ExportKey {
+    fn from(item: CliExportKey) -> Self {
+        let account_id: String = match item.account_id {
+            Some(cli_account_id) => cli_account_id,
+            None => ExportKey::input_account_id(),
+        };
+        let format = match item.format {
+            Some(cli_format) => cli_format,
+            None => ExportKey::input_format(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        ExportKey {
+            account_id,
+            public_key: item.public_key,
+            format,
+            connection_config,
+            auto_approve: item.auto_approve,
+        }
+    }
+}
+
+impl ExportKey {
+    pub fn input_account_id() -> String {
+        Input::new()
+            .with_prompt("Which account's key do you want to export?")
+            .interact_text()
+            .unwrap()
+    }
+    pub fn input_format() -> ExportFormat {
+        let formats = ExportFormat::VARIANTS;
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Choose the export format")
+            .items(formats)
+            .default(0)
+            .interact()
+            .unwrap();
+        formats[selection].parse().unwrap()
+    }
+    pub fn process(self) {
+        let network_dir_name = self.connection_config.dir_name();
+        let mut access_keys =
+            crate::common::read_access_keys_from_keychain(&network_dir_name, &self.account_id);
+        if let Some(public_key) = &self.public_key {
+            access_keys.retain(|access_key| &access_key.public_key == public_key);
+        }
+        let access_key = match access_keys.len() {
+            0 => {
+                return eprintln!(
+                    "There are no access keys for <{}> in the {} keychain",
+                    self.account_id, network_dir_name
+                )
+            }
+            1 => access_keys.remove(0),
+            _ => {
+                let items = access_keys
+                    .iter()
+                    .map(|access_key| match &access_key.label {
+                        Some(label) => format!("{} ({})", access_key.public_key, label),
+                        None => access_key.public_key.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                let selection = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Select the access key to export")
+                    .items(&items)
+                    .default(0)
+                    .interact()
+                    .unwrap();
+                access_keys.remove(selection)
+            }
+        };
+        let exported = match self.format {
+            ExportFormat::NearApiJs => serde_json::to_string_pretty(&serde_json::json!({
+                "account_id": &access_key.account_id,
+                "public_key": &access_key.public_key,
+                "private_key": &access_key.private_key,
+            }))
+            .unwrap(),
+            ExportFormat::Base58 => match access_key.private_key.find(':') {
+                Some(index) => access_key.private_key[index + 1..].to_string(),
+                None => access_key.private_key.clone(),
+            },
+            ExportFormat::SeedPhrase => {
+                return eprintln!(
+                    "The keychain keeps no seed phrase for {}, export it in another format",
+                    access_key.public_key
+                )
+            }
+        };
+        eprintln!(
+            "{}",
+            crate::style::warning(format!(
+                "Anyone with the secret key of {} gets its access to <{}>",
+                access_key.public_key, access_key.account_id
+            ))
+        );
+        let confirmed = self.auto_approve
+            || Confirm::new()
+                .with_prompt("Do you want to export the secret key?")
+                .default(false)
+                .interact()
+                .unwrap_or(false);
+        if !confirmed {
+            return eprintln!("The key has not been exported");
+        }
+        crate::common::append_to_audit_log(serde_json::json!({
+            "event": "key_exported",
+            "account_id": &access_key.account_id,
+            "public_key": &access_key.public_key,
+            "format": <&str>::from(&self.format),
+        }));
+        println!("{}", exported);
+    }
+}
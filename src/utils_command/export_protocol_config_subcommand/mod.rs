@@ -0,0 +1,58 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+/// Save the protocol parameters of a network to a file, for the offline mode
+/// (`--protocol-config`) on an air-gapped machine
+#[derive(Debug)]
+pub struct ExportProtocolConfig {
+    pub output_file: std::path::PathBuf,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliExportProtocolConfig {
+    output_file: Option<std::path::PathBuf>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliExportProtocolConfig> for ExportProtocolConfig {
+    fn from(item: CliExportProtocolConfig) -> Self {
+        let output_file = match item.output_file {
+            Some(cli_output_file) => cli_output_file,
+            None => ExportProtocolConfig::input_output_file(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        ExportProtocolConfig {
+            output_file,
+            connection_config,
+        }
+    }
+}
+
+impl ExportProtocolConfig {
+    pub async fn process(self) {
+        let protocol_config =
+            match crate::protocol_config::ProtocolConfig::fetch(&self.connection_config.rpc_url())
+                .await
+            {
+                Ok(protocol_config) => protocol_config,
+                Err(err) => return eprintln!("Error fetching the protocol config:  {}", err),
+            };
+        match protocol_config.save(&self.output_file) {
+            Ok(()) => eprintln!(
+                "The protocol config of {} (protocol version {}) is saved to {:?}",
+                protocol_config.chain_id, protocol_config.protocol_version, self.output_file
+            ),
+            Err(err) => eprintln!("{}", err),
+        }
+    }
+    pub fn input_output_file() -> std::path::PathBuf {
+        let output_file: String = Input::new()
+            .with_prompt("Where do you want to save the protocol config?")
+            .default("protocol-config.json".to_string())
+            .interact_text()
+            .unwrap();
+        std::path::PathBuf::from(output_file)
+    }
+}
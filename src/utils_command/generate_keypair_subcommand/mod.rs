@@ -25,6 +25,8 @@ fn bip32path_to_string(bip32path: &slip10::BIP32Path) -> String {
 #[derive(Debug)]
 pub struct GenerateKeypair {
     pub master_seed_phrase: Option<String>,
+    /// The BIP39 passphrase (the "25th word"), empty for none
+    pub master_seed_phrase_passphrase: String,
     pub new_master_seed_phrase_words_count: usize,
     pub seed_phrase_hd_path: slip10::BIP32Path,
     pub format: crate::common::OutputFormat,
@@ -34,6 +36,7 @@ impl Default for GenerateKeypair {
     fn default() -> Self {
         GenerateKeypair {
             master_seed_phrase: None,
+            master_seed_phrase_passphrase: String::new(),
             new_master_seed_phrase_words_count: 12,
             seed_phrase_hd_path: slip10::BIP32Path::from_str("m/44'/397'/0'").unwrap(),
             format: crate::common::OutputFormat::Json
@@ -42,16 +45,31 @@ impl Default for GenerateKeypair {
 }
 
 impl GenerateKeypair {
+    /// Private chains may use another coin type in the HD path
+    pub fn for_connection_config(connection_config: &crate::common::ConnectionConfig) -> Self {
+        GenerateKeypair {
+            seed_phrase_hd_path: slip10::BIP32Path::from_str(
+                &connection_config.seed_phrase_hd_path(),
+            )
+            .unwrap(),
+            ..Default::default()
+        }
+    }
     pub async fn process(self) -> String {
         let (master_seed_phrase, master_seed) =
             if let Some(ref master_seed_phrase) = self.master_seed_phrase {
                 (
                     master_seed_phrase.clone(),
-                    bip39::Mnemonic::parse(master_seed_phrase).unwrap().to_seed(""),
+                    bip39::Mnemonic::parse(master_seed_phrase)
+                        .unwrap()
+                        .to_seed(&self.master_seed_phrase_passphrase),
                 )
             } else {
                 let mnemonic = bip39::Mnemonic::generate(self.new_master_seed_phrase_words_count).unwrap();
-                (mnemonic.as_str().to_owned(), mnemonic.to_seed(""))
+                (
+                    mnemonic.as_str().to_owned(),
+                    mnemonic.to_seed(&self.master_seed_phrase_passphrase),
+                )
             };
 
         let derived_private_key = slip10::derive_key_from_path(
@@ -61,7 +79,7 @@ impl GenerateKeypair {
         )
         .map_err(|err| {
             // color_eyre::Report::msg(format!("Key derivation from path failed: {:?}", err))
-            println!("Error: {:?}", &err);
+            eprintln!("Error: {:?}", &err);
         })
         .unwrap();
 
@@ -81,6 +99,9 @@ impl GenerateKeypair {
             bs58::encode(secret_keypair.to_bytes()).into_string()
         );
 
+        let reveal_secrets = crate::common::reveal_secrets();
+        let master_seed_phrase = crate::common::display_secret(&master_seed_phrase, reveal_secrets);
+        let secret_keypair_str = crate::common::display_secret(&secret_keypair_str, reveal_secrets);
         match self.format {
             crate::common::OutputFormat::Plaintext => {
                 println!(
@@ -0,0 +1,136 @@
+use std::str::FromStr;
+use structopt::StructOpt;
+
+/// A key file of the JavaScript near-cli,
+/// `~/.near-credentials/<network>/<account>.json`
+#[derive(serde::Deserialize)]
+struct JsKeyFile {
+    account_id: String,
+    public_key: String,
+    #[serde(alias = "secret_key")]
+    private_key: String,
+}
+
+/// Copy the keys of the JavaScript near-cli for a network into the keychain
+#[derive(Debug)]
+pub struct ImportCredentials {
+    /// The JavaScript near-cli credentials directory (`~/.near-credentials` by default)
+    pub from: Option<std::path::PathBuf>,
+    /// The label of the imported keys in the keychain
+    pub label: String,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliImportCredentials {
+    #[structopt(long)]
+    from: Option<std::path::PathBuf>,
+    /// The label of the imported keys
+    #[structopt(long, default_value = "imported from near-cli JS")]
+    label: String,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliImportCredentials> for ImportCredentials {
+    fn from(item: CliImportCredentials) -> Self {
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        ImportCredentials {
+            from: item.from,
+            label: item.label,
+            connection_config,
+        }
+    }
+}
+
+impl ImportCredentials {
+    pub fn process(self) {
+        let network_dir_name = self.connection_config.dir_name();
+        let from = self.from.unwrap_or_else(|| {
+            dirs::home_dir()
+                .expect("Impossible to get your home dir!")
+                .join(".near-credentials")
+        });
+        let key_files_dir = from.join(&network_dir_name);
+        let entries = match std::fs::read_dir(&key_files_dir) {
+            Ok(entries) => entries,
+            Err(err) => return eprintln!("Error reading {:?}:  {:?}", key_files_dir, err),
+        };
+        let mut imported = 0;
+        let mut skipped = 0;
+        let mut failed = 0;
+        for path in entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension() == Some(std::ffi::OsStr::new("json")))
+        {
+            let key_file = match std::fs::read_to_string(&path)
+                .map_err(|err| format!("{:?}", err))
+                .and_then(|buf| {
+                    serde_json::from_str::<JsKeyFile>(&buf).map_err(|err| format!("{}", err))
+                }) {
+                Ok(key_file) => key_file,
+                Err(err) => {
+                    eprintln!("{:?}: {}", path, crate::style::warning(err));
+                    failed += 1;
+                    continue;
+                }
+            };
+            let matches_public_key = near_crypto::SecretKey::from_str(&key_file.private_key)
+                .map(|secret_key| secret_key.public_key().to_string() == key_file.public_key)
+                .unwrap_or(false);
+            if !matches_public_key {
+                eprintln!(
+                    "{:?}: {}",
+                    path,
+                    crate::style::warning("the private key does not match the public key")
+                );
+                failed += 1;
+                continue;
+            }
+            let already_imported = crate::common::read_access_keys_from_keychain(
+                &network_dir_name,
+                &key_file.account_id,
+            )
+            .iter()
+            .any(|access_key| access_key.public_key == key_file.public_key);
+            if already_imported {
+                println!(
+                    "{}: {} is already in the keychain",
+                    key_file.account_id, key_file.public_key
+                );
+                skipped += 1;
+                continue;
+            }
+            match crate::common::save_access_key_to_keychain(
+                &network_dir_name,
+                &crate::common::KeychainAccessKey {
+                    account_id: key_file.account_id.clone(),
+                    public_key: key_file.public_key.clone(),
+                    private_key: key_file.private_key,
+                    label: Some(self.label.clone()),
+                    in_os_keyring: false,
+                    encrypted: false,
+                },
+            ) {
+                Ok(_) => {
+                    println!(
+                        "{}: {} {}",
+                        key_file.account_id,
+                        key_file.public_key,
+                        crate::style::success("imported")
+                    );
+                    imported += 1;
+                }
+                Err(err) => {
+                    eprintln!("{}: {}", key_file.account_id, crate::style::warning(err));
+                    failed += 1;
+                }
+            }
+        }
+        println!(
+            "Imported {}, skipped {} already imported, failed {} ({} keychain)",
+            imported, skipped, failed, network_dir_name
+        );
+    }
+}
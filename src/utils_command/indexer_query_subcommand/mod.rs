@@ -0,0 +1,168 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+use strum::VariantNames;
+
+/// Send a GraphQL query to the `indexer.graphql_url` endpoint of the config
+/// file; `{{name}}` placeholders are replaced with the `--var name=value`
+/// values (`{{account_id}}` defaults to $NEAR_CLI_ACCOUNT_ID)
+#[derive(Debug)]
+pub struct IndexerQuery {
+    pub query: String,
+    pub variables: Vec<(String, String)>,
+    pub format: crate::common::OutputFormat,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliIndexerQuery {
+    #[structopt(long)]
+    query: Option<String>,
+    #[structopt(long)]
+    query_file: Option<std::path::PathBuf>,
+    /// `name=value`, repeat the flag for several variables
+    #[structopt(long = "var", parse(try_from_str = parse_variable))]
+    variables: Vec<(String, String)>,
+    #[structopt(long, default_value = "plaintext", possible_values = crate::common::OutputFormat::VARIANTS)]
+    format: crate::common::OutputFormat,
+}
+
+fn parse_variable(s: &str) -> Result<(String, String), String> {
+    match s.find('=') {
+        Some(index) => Ok((s[..index].trim().to_string(), s[index + 1..].to_string())),
+        None => Err(format!("Expected name=value, got {:?}", s)),
+    }
+}
+
+impl From<CliIndexerQuery> for IndexerQuery {
+    fn from(item: CliIndexerQuery) -> Self {
+        let query: String = match (item.query, item.query_file) {
+            (Some(cli_query), _) => cli_query,
+            (None, Some(cli_query_file)) => std::fs::read_to_string(&cli_query_file)
+                .map_err(|err| {
+                    eprintln!("Error reading the file {:?}:  {:?}", &cli_query_file, &err)
+                })
+                .unwrap(),
+            (None, None) => IndexerQuery::input_query(),
+        };
+        IndexerQuery {
+            query,
+            variables: item.variables,
+            format: item.format,
+        }
+    }
+}
+
+impl IndexerQuery {
+    pub async fn process(self) {
+        let indexer_config = crate::config::Config::load().indexer;
+        let graphql_url = match indexer_config.graphql_url {
+            Some(graphql_url) => graphql_url,
+            None => {
+                return eprintln!(
+                    "Set `indexer.graphql_url` in the config file {:?}",
+                    crate::config::Config::config_file_path()
+                )
+            }
+        };
+        let mut variables = self.variables;
+        if let Ok(account_id) = std::env::var(crate::common::ACCOUNT_ID_ENV_VAR) {
+            variables.push(("account_id".to_string(), account_id));
+        }
+        let query = variables.iter().fold(self.query, |query, (name, value)| {
+            query.replace(&format!("{{{{{}}}}}", name), value)
+        });
+        if let Some(index) = query.find("{{") {
+            let placeholder = query[index..].split("}}").next().unwrap_or_default();
+            return eprintln!(
+                "No value for the placeholder {}}}}}, pass it with --var",
+                placeholder
+            );
+        }
+        let mut request = actix_web::client::Client::default().post(graphql_url.as_str());
+        if let Some(api_key) = &indexer_config.api_key {
+            request = request.header("Authorization", format!("Bearer {}", api_key));
+        }
+        let mut response = request
+            .send_json(&serde_json::json!({ "query": query }))
+            .await
+            .map_err(|err| eprintln!("Error sending the query:  {:?}", &err))
+            .unwrap();
+        let response: serde_json::Value = response
+            .json()
+            .limit(64 * 1024 * 1024)
+            .await
+            .map_err(|err| eprintln!("Error reading the response:  {:?}", &err))
+            .unwrap();
+        if let Some(errors) = response.get("errors") {
+            return eprintln!("The indexer returned errors:  {}", errors);
+        }
+        match self.format {
+            crate::common::OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&response["data"]).unwrap()
+                )
+            }
+            crate::common::OutputFormat::Plaintext => print_tables(&response["data"]),
+        }
+    }
+    pub fn input_query() -> String {
+        Input::new()
+            .with_prompt("Enter the GraphQL query")
+            .interact_text()
+            .unwrap()
+    }
+}
+
+/// Every top-level field of the result becomes a table, one row per object
+fn print_tables(data: &serde_json::Value) {
+    let fields = match data.as_object() {
+        Some(fields) => fields,
+        None => return println!("{}", data),
+    };
+    for (name, value) in fields {
+        println!("{}", crate::style::account_id(name));
+        let rows = match value {
+            serde_json::Value::Array(rows) => rows.clone(),
+            value => vec![value.clone()],
+        };
+        let mut columns: Vec<String> = vec![];
+        for row in &rows {
+            for column in row.as_object().into_iter().flat_map(|row| row.keys()) {
+                if !columns.contains(column) {
+                    columns.push(column.clone());
+                }
+            }
+        }
+        let cell = |row: &serde_json::Value, column: &str| match &row[column] {
+            serde_json::Value::String(value) => value.clone(),
+            serde_json::Value::Null => "".to_string(),
+            value => value.to_string(),
+        };
+        let widths = columns
+            .iter()
+            .map(|column| {
+                rows.iter()
+                    .map(|row| cell(row, column).len())
+                    .chain(std::iter::once(column.len()))
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        let format_row = |cells: Vec<String>| {
+            cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+        };
+        println!("{}", format_row(columns.clone()));
+        for row in &rows {
+            println!(
+                "{}",
+                format_row(columns.iter().map(|column| cell(row, column)).collect())
+            );
+        }
+        eprintln!();
+    }
+}
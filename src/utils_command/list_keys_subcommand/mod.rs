@@ -0,0 +1,156 @@
+use structopt::StructOpt;
+use strum::VariantNames;
+
+/// List the keys of the keychain per network and account; online, also check
+/// that they are still attached to the accounts
+#[derive(Debug)]
+pub struct ListKeys {
+    pub online: bool,
+    pub format: crate::common::OutputFormat,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliListKeys {
+    /// Check on chain whether each key still exists (testnet, mainnet, betanet
+    /// and the chain profiles only)
+    #[structopt(long)]
+    online: bool,
+    #[structopt(long, default_value = "plaintext", possible_values = crate::common::OutputFormat::VARIANTS)]
+    format: crate::common::OutputFormat,
+}
+
+impl From<CliListKeys> for ListKeys {
+    fn from(item: CliListKeys) -> Self {
+        ListKeys {
+            online: item.online,
+            format: item.format,
+        }
+    }
+}
+
+fn sub_dir_names(dir: &std::path::Path) -> Vec<String> {
+    let mut names = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// The creation (or, where it is not tracked, the modification) time of a key file
+fn key_file_created_at(network_dir_name: &str, account_id: &str, public_key: &str) -> Option<u64> {
+    let metadata = std::fs::metadata(
+        crate::common::get_credentials_home_dir()
+            .join(network_dir_name)
+            .join(account_id)
+            .join(format!("{}.json", public_key.replace(":", "_"))),
+    )
+    .ok()?;
+    let created_at = metadata.created().or_else(|_| metadata.modified()).ok()?;
+    Some(
+        created_at
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()?
+            .as_secs(),
+    )
+}
+
+fn format_age(created_at: Option<u64>) -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    match created_at.map(|created_at| now.saturating_sub(created_at)) {
+        Some(age) if age < 60 * 60 => "created just now".to_string(),
+        Some(age) if age < 24 * 60 * 60 => format!("created {} hours ago", age / (60 * 60)),
+        Some(age) => format!("created {} days ago", age / (24 * 60 * 60)),
+        None => "creation time unknown".to_string(),
+    }
+}
+
+impl ListKeys {
+    pub async fn process(self) {
+        let credentials_home_dir = crate::common::get_credentials_home_dir();
+        let mut keys = vec![];
+        for network_dir_name in sub_dir_names(&credentials_home_dir) {
+            let rpc_url = if self.online {
+                crate::common::ConnectionConfig::from_network_name(&network_dir_name)
+                    .map(|connection_config| connection_config.rpc_url())
+            } else {
+                None
+            };
+            for account_id in sub_dir_names(&credentials_home_dir.join(&network_dir_name)) {
+                for access_key in
+                    crate::common::read_access_keys_from_keychain(&network_dir_name, &account_id)
+                {
+                    let on_chain = match &rpc_url {
+                        Some(rpc_url) => crate::common::access_key_exists(
+                            rpc_url,
+                            &account_id,
+                            &access_key.public_key,
+                        )
+                        .await
+                        .ok(),
+                        None => None,
+                    };
+                    let created_at =
+                        key_file_created_at(&network_dir_name, &account_id, &access_key.public_key);
+                    keys.push((network_dir_name.clone(), access_key, created_at, on_chain));
+                }
+            }
+        }
+        match self.format {
+            crate::common::OutputFormat::Plaintext => {
+                if keys.is_empty() {
+                    return eprintln!("There are no keys in {:?}", credentials_home_dir);
+                }
+                let mut current_network = None;
+                for (network_dir_name, access_key, created_at, on_chain) in &keys {
+                    if current_network != Some(network_dir_name) {
+                        println!("=== {} ===", network_dir_name);
+                        current_network = Some(network_dir_name);
+                    }
+                    let status = match on_chain {
+                        Some(true) => crate::style::success("on chain"),
+                        Some(false) => crate::style::warning("not on chain"),
+                        None if self.online => "unknown".to_string(),
+                        None => String::new(),
+                    };
+                    println!(
+                        "{} {}{} ({}) {}",
+                        crate::style::account_id(&access_key.account_id),
+                        access_key.public_key,
+                        access_key
+                            .label
+                            .as_ref()
+                            .map(|label| format!(" \"{}\"", label))
+                            .unwrap_or_default(),
+                        format_age(*created_at),
+                        status
+                    );
+                }
+            }
+            crate::common::OutputFormat::Json => {
+                let keys = keys
+                    .iter()
+                    .map(|(network_dir_name, access_key, created_at, on_chain)| {
+                        serde_json::json!({
+                            "network": network_dir_name,
+                            "account_id": &access_key.account_id,
+                            "public_key": &access_key.public_key,
+                            "label": &access_key.label,
+                            "created_at": created_at,
+                            "on_chain": on_chain,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                println!("{}", serde_json::to_string_pretty(&keys).unwrap());
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use dialoguer::Password;
+use structopt::StructOpt;
+
+/// Set, change or remove the master password that encrypts the private keys
+/// stored in the credentials directory
+#[derive(Debug)]
+pub struct MasterPasswordCommand {
+    pub remove: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliMasterPasswordCommand {
+    /// Decrypt the stored keys and stop asking for a master password
+    #[structopt(long)]
+    remove: bool,
+}
+
+impl From<CliMasterPasswordCommand> for MasterPasswordCommand {
+    fn from(item: CliMasterPasswordCommand) -> Self {
+        MasterPasswordCommand {
+            remove: item.remove,
+        }
+    }
+}
+
+impl MasterPasswordCommand {
+    pub fn process(self) {
+        if self.remove {
+            if !crate::master_password::is_set() {
+                return eprintln!("There is no master password");
+            }
+            return match crate::master_password::change(None) {
+                Ok(keys) => println!(
+                    "{} ({} keys decrypted)",
+                    crate::style::success("The master password is removed"),
+                    keys
+                ),
+                Err(err) => eprintln!("{}", err),
+            };
+        }
+        // Changing the password asks for the current one first
+        if let Err(err) = crate::master_password::unlock_if_set() {
+            return eprintln!("{}", err);
+        }
+        let new_password = Password::new()
+            .with_prompt("Enter the new master password")
+            .with_confirmation(
+                "Repeat the new master password",
+                "The passwords do not match",
+            )
+            .interact()
+            .unwrap();
+        match crate::master_password::change(Some(&new_password)) {
+            Ok(keys) => println!(
+                "{} ({} keys encrypted)",
+                crate::style::success("The master password is set"),
+                keys
+            ),
+            Err(err) => eprintln!("{}", err),
+        }
+    }
+}
@@ -2,8 +2,33 @@ use dialoguer::{theme::ColorfulTheme, Select};
 use structopt::StructOpt;
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
+mod apply_plan_subcommand;
+mod backup_seed_subcommand;
+mod batch_submit_subcommand;
+mod benchmark_rpc_subcommand;
+mod create_ephemeral_account_subcommand;
+mod decode_subcommand;
+mod derive_test_keys_subcommand;
+mod execute_when_due_subcommand;
+mod export_key_subcommand;
+mod export_protocol_config_subcommand;
+mod import_credentials_subcommand;
+mod indexer_query_subcommand;
+mod list_keys_subcommand;
+mod master_password_subcommand;
+mod multisig_subcommand;
+mod nonce_doctor_subcommand;
+mod resolve_receipt_subcommand;
+mod restore_seed_subcommand;
+mod revoke_key_everywhere_subcommand;
+mod rotate_key_subcommand;
+mod scan_hd_paths_subcommand;
+mod send_signed_transaction_subcommand;
 // mod generate_keypair_subcommand;
 mod sign_transaction_subcommand;
+mod suggest_account_name_subcommand;
+mod timelock_request_subcommand;
+mod wallet_url_subcommand;
 
 #[derive(Debug)]
 pub struct UtilType {
@@ -21,11 +46,95 @@ pub struct CliUtilType {
 pub enum UtilList {
     #[strum_discriminants(strum(message = "Sign a transaction"))]
     SignTransactionCommand(sign_transaction_subcommand::SignTransaction),
+    #[strum_discriminants(strum(
+        message = "Create a funded throwaway account and delete it afterwards"
+    ))]
+    CreateEphemeralAccount(create_ephemeral_account_subcommand::CreateEphemeralAccount),
+    #[strum_discriminants(strum(message = "Derive deterministic INSECURE test keys"))]
+    DeriveTestKeys(derive_test_keys_subcommand::DeriveTestKeys),
+    #[strum_discriminants(strum(message = "Submit a batch of signed transactions"))]
+    BatchSubmit(batch_submit_subcommand::BatchSubmit),
+    #[strum_discriminants(strum(message = "Detect and repair stuck transaction nonces"))]
+    NonceDoctor(nonce_doctor_subcommand::NonceDoctor),
+    #[strum_discriminants(strum(message = "Find the transaction that produced a receipt"))]
+    ResolveReceipt(resolve_receipt_subcommand::ResolveReceipt),
+    #[strum_discriminants(strum(message = "Decode borsh data with a BorshSchema"))]
+    Decode(decode_subcommand::Decode),
+    #[strum_discriminants(strum(message = "Build a web-wallet link to sign a transaction"))]
+    WalletUrl(wallet_url_subcommand::WalletUrl),
+    #[strum_discriminants(strum(message = "Add a time-locked multisig request"))]
+    TimelockRequest(timelock_request_subcommand::TimelockRequest),
+    #[strum_discriminants(strum(
+        message = "Confirm a time-locked multisig request when it is due"
+    ))]
+    ExecuteWhenDue(execute_when_due_subcommand::ExecuteWhenDue),
+    #[strum_discriminants(strum(message = "Sign and send a transaction saved with --plan"))]
+    ApplyPlan(apply_plan_subcommand::ApplyPlan),
+    #[strum_discriminants(strum(message = "Send a GraphQL query to the indexer"))]
+    IndexerQuery(indexer_query_subcommand::IndexerQuery),
+    #[strum_discriminants(strum(message = "Collect and submit multisig confirmations"))]
+    Multisig(multisig_subcommand::MultisigCommand),
+    #[strum_discriminants(strum(message = "Send a transaction signed elsewhere"))]
+    SendSignedTransaction(send_signed_transaction_subcommand::SendSignedTransaction),
+    #[strum_discriminants(strum(message = "Delete a compromised key from all the accounts"))]
+    RevokeKeyEverywhere(revoke_key_everywhere_subcommand::RevokeKeyEverywhere),
+    #[strum_discriminants(strum(
+        message = "Set the master password that encrypts the stored keys"
+    ))]
+    MasterPassword(master_password_subcommand::MasterPasswordCommand),
+    #[strum_discriminants(strum(message = "Import the keys of the JavaScript near-cli"))]
+    ImportCredentials(import_credentials_subcommand::ImportCredentials),
+    #[strum_discriminants(strum(message = "Export an access key from the keychain"))]
+    ExportKey(export_key_subcommand::ExportKey),
+    #[strum_discriminants(strum(message = "Replace an access key with a new one"))]
+    RotateKey(rotate_key_subcommand::RotateKey),
+    #[strum_discriminants(strum(message = "List the keys stored in the keychain"))]
+    ListKeys(list_keys_subcommand::ListKeys),
+    #[strum_discriminants(strum(
+        message = "Find the accounts that use the keys of a seed phrase"
+    ))]
+    ScanHdPaths(scan_hd_paths_subcommand::ScanHdPaths),
+    #[strum_discriminants(strum(message = "Split a seed phrase into Shamir share codes"))]
+    BackupSeed(backup_seed_subcommand::BackupSeed),
+    #[strum_discriminants(strum(message = "Restore a seed phrase from Shamir share codes"))]
+    RestoreSeed(restore_seed_subcommand::RestoreSeed),
+    #[strum_discriminants(strum(message = "Suggest free names for a new sub-account"))]
+    SuggestAccountName(suggest_account_name_subcommand::SuggestAccountName),
+    #[strum_discriminants(strum(message = "Save the protocol config for the offline mode"))]
+    ExportProtocolConfig(export_protocol_config_subcommand::ExportProtocolConfig),
+    #[strum_discriminants(strum(message = "Measure the latency of the RPC endpoints"))]
+    BenchmarkRpc(benchmark_rpc_subcommand::BenchmarkRpc),
 }
 
 #[derive(Debug, StructOpt)]
 enum CliUtilList {
+    #[structopt(name = "sign-transaction")]
     SignTransactionCommand(sign_transaction_subcommand::CliSignTransaction),
+    CreateEphemeralAccount(create_ephemeral_account_subcommand::CliCreateEphemeralAccount),
+    DeriveTestKeys(derive_test_keys_subcommand::CliDeriveTestKeys),
+    BatchSubmit(batch_submit_subcommand::CliBatchSubmit),
+    NonceDoctor(nonce_doctor_subcommand::CliNonceDoctor),
+    ResolveReceipt(resolve_receipt_subcommand::CliResolveReceipt),
+    Decode(decode_subcommand::CliDecode),
+    WalletUrl(wallet_url_subcommand::CliWalletUrl),
+    TimelockRequest(timelock_request_subcommand::CliTimelockRequest),
+    ExecuteWhenDue(execute_when_due_subcommand::CliExecuteWhenDue),
+    ApplyPlan(apply_plan_subcommand::CliApplyPlan),
+    IndexerQuery(indexer_query_subcommand::CliIndexerQuery),
+    Multisig(multisig_subcommand::CliMultisigCommand),
+    SendSignedTransaction(send_signed_transaction_subcommand::CliSendSignedTransaction),
+    RevokeKeyEverywhere(revoke_key_everywhere_subcommand::CliRevokeKeyEverywhere),
+    MasterPassword(master_password_subcommand::CliMasterPasswordCommand),
+    ImportCredentials(import_credentials_subcommand::CliImportCredentials),
+    ExportKey(export_key_subcommand::CliExportKey),
+    RotateKey(rotate_key_subcommand::CliRotateKey),
+    ListKeys(list_keys_subcommand::CliListKeys),
+    ScanHdPaths(scan_hd_paths_subcommand::CliScanHdPaths),
+    BackupSeed(backup_seed_subcommand::CliBackupSeed),
+    RestoreSeed(restore_seed_subcommand::CliRestoreSeed),
+    SuggestAccountName(suggest_account_name_subcommand::CliSuggestAccountName),
+    ExportProtocolConfig(export_protocol_config_subcommand::CliExportProtocolConfig),
+    BenchmarkRpc(benchmark_rpc_subcommand::CliBenchmarkRpc),
 }
 
 impl From<CliUtilType> for UtilType {
@@ -39,14 +148,49 @@ impl From<CliUtilType> for UtilType {
 }
 
 impl UtilList {
-    pub fn process(self) {
+    pub async fn process(self) {
         match self {
             UtilList::SignTransactionCommand(sign_transaction) => sign_transaction.process(),
+            UtilList::CreateEphemeralAccount(create_ephemeral_account) => {
+                create_ephemeral_account.process().await
+            }
+            UtilList::DeriveTestKeys(derive_test_keys) => derive_test_keys.process(),
+            UtilList::BatchSubmit(batch_submit) => batch_submit.process().await,
+            UtilList::NonceDoctor(nonce_doctor) => nonce_doctor.process().await,
+            UtilList::ResolveReceipt(resolve_receipt) => resolve_receipt.process().await,
+            UtilList::Decode(decode) => decode.process(),
+            UtilList::WalletUrl(wallet_url) => wallet_url.process(),
+            UtilList::TimelockRequest(timelock_request) => timelock_request.process().await,
+            UtilList::ExecuteWhenDue(execute_when_due) => execute_when_due.process().await,
+            UtilList::ApplyPlan(apply_plan) => apply_plan.process().await,
+            UtilList::IndexerQuery(indexer_query) => indexer_query.process().await,
+            UtilList::Multisig(multisig) => multisig.process().await,
+            UtilList::SendSignedTransaction(send_signed_transaction) => {
+                send_signed_transaction.process().await
+            }
+            UtilList::RevokeKeyEverywhere(revoke_key_everywhere) => {
+                revoke_key_everywhere.process().await
+            }
+            UtilList::MasterPassword(master_password) => master_password.process(),
+            UtilList::ImportCredentials(import_credentials) => import_credentials.process(),
+            UtilList::ExportKey(export_key) => export_key.process(),
+            UtilList::RotateKey(rotate_key) => rotate_key.process().await,
+            UtilList::ListKeys(list_keys) => list_keys.process().await,
+            UtilList::ScanHdPaths(scan_hd_paths) => scan_hd_paths.process().await,
+            UtilList::BackupSeed(backup_seed) => backup_seed.process(),
+            UtilList::RestoreSeed(restore_seed) => restore_seed.process(),
+            UtilList::SuggestAccountName(suggest_account_name) => {
+                suggest_account_name.process().await
+            }
+            UtilList::ExportProtocolConfig(export_protocol_config) => {
+                export_protocol_config.process().await
+            }
+            UtilList::BenchmarkRpc(benchmark_rpc) => benchmark_rpc.process().await,
             _ => unreachable!("Error"),
         }
     }
     pub fn choose_util() -> Self {
-        println!();
+        eprintln!();
         let variants = UtilListDiscriminants::iter().collect::<Vec<_>>();
         let utils = variants
             .iter()
@@ -58,6 +202,10 @@ impl UtilList {
             .default(0)
             .interact()
             .unwrap();
+        crate::common::emit_event(
+            "prompt_answered",
+            serde_json::json!({ "prompt": "Choose your action", "answer": utils[selection] }),
+        );
         match variants[selection] {
             UtilListDiscriminants::SignTransactionCommand => {
                 let signer_secret_key =
@@ -66,7 +214,262 @@ impl UtilList {
                     sign_transaction_subcommand::SignTransaction::input_unsigned_transaction();
                 Self::SignTransactionCommand(sign_transaction_subcommand::SignTransaction {
                     signer_secret_key,
-                    unsigned_transaction,
+                    unsigned_transactions: vec![unsigned_transaction],
+                    output_file: None,
+                    qr: false,
+                })
+            }
+            UtilListDiscriminants::CreateEphemeralAccount => {
+                let network =
+                    create_ephemeral_account_subcommand::CreateEphemeralAccount::input_network();
+                let beneficiary_id =
+                    create_ephemeral_account_subcommand::CreateEphemeralAccount::input_beneficiary_id(
+                        &network,
+                    );
+                let command =
+                    create_ephemeral_account_subcommand::CreateEphemeralAccount::input_command();
+                let key_type = crate::common::KeyType::input_key_type();
+                Self::CreateEphemeralAccount(
+                    create_ephemeral_account_subcommand::CreateEphemeralAccount {
+                        network,
+                        beneficiary_id,
+                        command,
+                        keep_for: None,
+                        key_type,
+                    },
+                )
+            }
+            UtilListDiscriminants::DeriveTestKeys => {
+                let seed = derive_test_keys_subcommand::DeriveTestKeys::input_seed();
+                let count = derive_test_keys_subcommand::DeriveTestKeys::input_count();
+                let key_type = crate::common::KeyType::input_key_type();
+                Self::DeriveTestKeys(derive_test_keys_subcommand::DeriveTestKeys {
+                    seed,
+                    count,
+                    key_type,
+                    format: crate::common::OutputFormat::Plaintext,
+                })
+            }
+            UtilListDiscriminants::BatchSubmit => {
+                let file = batch_submit_subcommand::BatchSubmit::input_file();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                let rate = batch_submit_subcommand::BatchSubmit::input_rate();
+                let max_in_flight = batch_submit_subcommand::BatchSubmit::input_max_in_flight();
+                Self::BatchSubmit(batch_submit_subcommand::BatchSubmit {
+                    file,
+                    connection_config,
+                    rate,
+                    max_in_flight,
+                    campaign: None,
+                    resume: false,
+                })
+            }
+            UtilListDiscriminants::NonceDoctor => {
+                let account_id = nonce_doctor_subcommand::NonceDoctor::input_account_id();
+                let public_key = nonce_doctor_subcommand::NonceDoctor::input_public_key();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::NonceDoctor(nonce_doctor_subcommand::NonceDoctor {
+                    account_id,
+                    public_key,
+                    connection_config,
+                })
+            }
+            UtilListDiscriminants::ResolveReceipt => {
+                let receipt_id = resolve_receipt_subcommand::ResolveReceipt::input_receipt_id();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::ResolveReceipt(resolve_receipt_subcommand::ResolveReceipt {
+                    receipt_id,
+                    connection_config,
+                })
+            }
+            UtilListDiscriminants::Decode => {
+                let data = decode_subcommand::Decode::input_data();
+                let borsh_schema = decode_subcommand::Decode::input_borsh_schema();
+                Self::Decode(decode_subcommand::Decode {
+                    data,
+                    borsh_schema,
+                    declaration: None,
+                })
+            }
+            UtilListDiscriminants::WalletUrl => {
+                let unsigned_transaction =
+                    wallet_url_subcommand::WalletUrl::input_unsigned_transaction();
+                let wallet_url = wallet_url_subcommand::WalletUrl::input_wallet_url();
+                Self::WalletUrl(wallet_url_subcommand::WalletUrl {
+                    unsigned_transactions: vec![unsigned_transaction],
+                    wallet_url,
+                    callback_url: None,
+                    open: false,
+                })
+            }
+            UtilListDiscriminants::TimelockRequest => {
+                let multisig_account_id = timelock_request_subcommand::input_multisig_account_id();
+                let receiver_id = timelock_request_subcommand::TimelockRequest::input_receiver_id();
+                let amount = timelock_request_subcommand::TimelockRequest::input_amount();
+                let delay = timelock_request_subcommand::TimelockRequest::input_delay();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::TimelockRequest(timelock_request_subcommand::TimelockRequest {
+                    multisig_account_id,
+                    receiver_id,
+                    amount,
+                    method_name: None,
+                    args: None,
+                    delay,
+                    connection_config,
+                })
+            }
+            UtilListDiscriminants::ExecuteWhenDue => {
+                let multisig_account_id = timelock_request_subcommand::input_multisig_account_id();
+                let request_id = execute_when_due_subcommand::ExecuteWhenDue::input_request_id();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::ExecuteWhenDue(execute_when_due_subcommand::ExecuteWhenDue {
+                    multisig_account_id,
+                    request_id,
+                    not_before: None,
+                    connection_config,
+                })
+            }
+            UtilListDiscriminants::ApplyPlan => {
+                let plan_file = apply_plan_subcommand::ApplyPlan::input_plan_file();
+                Self::ApplyPlan(apply_plan_subcommand::ApplyPlan {
+                    plan_file,
+                    auto_approve: false,
+                })
+            }
+            UtilListDiscriminants::IndexerQuery => {
+                let query = indexer_query_subcommand::IndexerQuery::input_query();
+                Self::IndexerQuery(indexer_query_subcommand::IndexerQuery {
+                    query,
+                    variables: vec![],
+                    format: crate::common::OutputFormat::Plaintext,
+                })
+            }
+            UtilListDiscriminants::Multisig => {
+                Self::Multisig(multisig_subcommand::MultisigCommand {
+                    action: multisig_subcommand::MultisigAction::choose_action(),
+                })
+            }
+            UtilListDiscriminants::SendSignedTransaction => {
+                let signed_transaction =
+                    send_signed_transaction_subcommand::SendSignedTransaction::input_signed_transaction();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::SendSignedTransaction(
+                    send_signed_transaction_subcommand::SendSignedTransaction {
+                        signed_transaction,
+                        connection_config,
+                    },
+                )
+            }
+            UtilListDiscriminants::RevokeKeyEverywhere => {
+                let public_key =
+                    revoke_key_everywhere_subcommand::RevokeKeyEverywhere::input_public_key();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::RevokeKeyEverywhere(revoke_key_everywhere_subcommand::RevokeKeyEverywhere {
+                    public_key,
+                    connection_config,
+                    auto_approve: false,
+                })
+            }
+            UtilListDiscriminants::MasterPassword => {
+                Self::MasterPassword(master_password_subcommand::MasterPasswordCommand {
+                    remove: false,
+                })
+            }
+            UtilListDiscriminants::ImportCredentials => {
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::ImportCredentials(import_credentials_subcommand::ImportCredentials {
+                    from: None,
+                    label: "imported from near-cli JS".to_string(),
+                    connection_config,
+                })
+            }
+            UtilListDiscriminants::ExportKey => {
+                let account_id = export_key_subcommand::ExportKey::input_account_id();
+                let format = export_key_subcommand::ExportKey::input_format();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::ExportKey(export_key_subcommand::ExportKey {
+                    account_id,
+                    public_key: None,
+                    format,
+                    connection_config,
+                    auto_approve: false,
+                })
+            }
+            UtilListDiscriminants::RotateKey => {
+                let account_id = rotate_key_subcommand::RotateKey::input_account_id();
+                let key_type = crate::common::KeyType::input_key_type();
+                let label = crate::common::input_key_label();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::RotateKey(rotate_key_subcommand::RotateKey {
+                    account_id,
+                    old_public_key: None,
+                    new_public_key: None,
+                    key_type,
+                    label,
+                    connection_config,
+                })
+            }
+            UtilListDiscriminants::ListKeys => Self::ListKeys(list_keys_subcommand::ListKeys {
+                online: true,
+                format: crate::common::OutputFormat::Plaintext,
+            }),
+            UtilListDiscriminants::ScanHdPaths => {
+                let seed_phrase = scan_hd_paths_subcommand::ScanHdPaths::input_seed_phrase();
+                let passphrase = crate::public_key_selector::input_seed_phrase_passphrase();
+                let count = scan_hd_paths_subcommand::ScanHdPaths::input_count();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::ScanHdPaths(scan_hd_paths_subcommand::ScanHdPaths {
+                    seed_phrase,
+                    passphrase,
+                    count,
+                    connection_config,
+                    format: crate::common::OutputFormat::Plaintext,
+                })
+            }
+            UtilListDiscriminants::BackupSeed => {
+                let seed_phrase = backup_seed_subcommand::BackupSeed::input_seed_phrase();
+                let shares = backup_seed_subcommand::BackupSeed::input_shares();
+                let threshold = backup_seed_subcommand::BackupSeed::input_threshold();
+                Self::BackupSeed(backup_seed_subcommand::BackupSeed {
+                    seed_phrase,
+                    shares,
+                    threshold,
+                })
+            }
+            UtilListDiscriminants::RestoreSeed => {
+                Self::RestoreSeed(restore_seed_subcommand::RestoreSeed {
+                    share_codes: restore_seed_subcommand::RestoreSeed::input_share_codes(),
+                })
+            }
+            UtilListDiscriminants::SuggestAccountName => {
+                let parent_account_id =
+                    suggest_account_name_subcommand::SuggestAccountName::input_parent_account_id();
+                let pattern = suggest_account_name_subcommand::SuggestAccountName::input_pattern();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::SuggestAccountName(suggest_account_name_subcommand::SuggestAccountName {
+                    parent_account_id,
+                    pattern,
+                    wordlist: None,
+                    count: 10,
+                    connection_config,
+                })
+            }
+            UtilListDiscriminants::ExportProtocolConfig => {
+                let output_file =
+                    export_protocol_config_subcommand::ExportProtocolConfig::input_output_file();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::ExportProtocolConfig(
+                    export_protocol_config_subcommand::ExportProtocolConfig {
+                        output_file,
+                        connection_config,
+                    },
+                )
+            }
+            UtilListDiscriminants::BenchmarkRpc => {
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::BenchmarkRpc(benchmark_rpc_subcommand::BenchmarkRpc {
+                    samples: 10,
+                    connection_config,
                 })
             }
         }
@@ -81,12 +484,133 @@ impl From<CliUtilList> for UtilList {
                     sign_transaction_subcommand::SignTransaction::from(cli_sign_transaction);
                 UtilList::SignTransactionCommand(sign_transaction)
             }
+            CliUtilList::CreateEphemeralAccount(cli_create_ephemeral_account) => {
+                let create_ephemeral_account =
+                    create_ephemeral_account_subcommand::CreateEphemeralAccount::from(
+                        cli_create_ephemeral_account,
+                    );
+                UtilList::CreateEphemeralAccount(create_ephemeral_account)
+            }
+            CliUtilList::DeriveTestKeys(cli_derive_test_keys) => {
+                let derive_test_keys =
+                    derive_test_keys_subcommand::DeriveTestKeys::from(cli_derive_test_keys);
+                UtilList::DeriveTestKeys(derive_test_keys)
+            }
+            CliUtilList::BatchSubmit(cli_batch_submit) => {
+                let batch_submit = batch_submit_subcommand::BatchSubmit::from(cli_batch_submit);
+                UtilList::BatchSubmit(batch_submit)
+            }
+            CliUtilList::NonceDoctor(cli_nonce_doctor) => {
+                let nonce_doctor = nonce_doctor_subcommand::NonceDoctor::from(cli_nonce_doctor);
+                UtilList::NonceDoctor(nonce_doctor)
+            }
+            CliUtilList::ResolveReceipt(cli_resolve_receipt) => {
+                let resolve_receipt =
+                    resolve_receipt_subcommand::ResolveReceipt::from(cli_resolve_receipt);
+                UtilList::ResolveReceipt(resolve_receipt)
+            }
+            CliUtilList::Decode(cli_decode) => {
+                let decode = decode_subcommand::Decode::from(cli_decode);
+                UtilList::Decode(decode)
+            }
+            CliUtilList::WalletUrl(cli_wallet_url) => {
+                let wallet_url = wallet_url_subcommand::WalletUrl::from(cli_wallet_url);
+                UtilList::WalletUrl(wallet_url)
+            }
+            CliUtilList::TimelockRequest(cli_timelock_request) => {
+                let timelock_request =
+                    timelock_request_subcommand::TimelockRequest::from(cli_timelock_request);
+                UtilList::TimelockRequest(timelock_request)
+            }
+            CliUtilList::ExecuteWhenDue(cli_execute_when_due) => {
+                let execute_when_due =
+                    execute_when_due_subcommand::ExecuteWhenDue::from(cli_execute_when_due);
+                UtilList::ExecuteWhenDue(execute_when_due)
+            }
+            CliUtilList::ApplyPlan(cli_apply_plan) => {
+                let apply_plan = apply_plan_subcommand::ApplyPlan::from(cli_apply_plan);
+                UtilList::ApplyPlan(apply_plan)
+            }
+            CliUtilList::IndexerQuery(cli_indexer_query) => {
+                let indexer_query = indexer_query_subcommand::IndexerQuery::from(cli_indexer_query);
+                UtilList::IndexerQuery(indexer_query)
+            }
+            CliUtilList::Multisig(cli_multisig) => {
+                let multisig = multisig_subcommand::MultisigCommand::from(cli_multisig);
+                UtilList::Multisig(multisig)
+            }
+            CliUtilList::SendSignedTransaction(cli_send_signed_transaction) => {
+                let send_signed_transaction =
+                    send_signed_transaction_subcommand::SendSignedTransaction::from(
+                        cli_send_signed_transaction,
+                    );
+                UtilList::SendSignedTransaction(send_signed_transaction)
+            }
+            CliUtilList::RevokeKeyEverywhere(cli_revoke_key_everywhere) => {
+                let revoke_key_everywhere =
+                    revoke_key_everywhere_subcommand::RevokeKeyEverywhere::from(
+                        cli_revoke_key_everywhere,
+                    );
+                UtilList::RevokeKeyEverywhere(revoke_key_everywhere)
+            }
+            CliUtilList::MasterPassword(cli_master_password) => {
+                let master_password =
+                    master_password_subcommand::MasterPasswordCommand::from(cli_master_password);
+                UtilList::MasterPassword(master_password)
+            }
+            CliUtilList::ImportCredentials(cli_import_credentials) => {
+                let import_credentials =
+                    import_credentials_subcommand::ImportCredentials::from(cli_import_credentials);
+                UtilList::ImportCredentials(import_credentials)
+            }
+            CliUtilList::ExportKey(cli_export_key) => {
+                let export_key = export_key_subcommand::ExportKey::from(cli_export_key);
+                UtilList::ExportKey(export_key)
+            }
+            CliUtilList::RotateKey(cli_rotate_key) => {
+                let rotate_key = rotate_key_subcommand::RotateKey::from(cli_rotate_key);
+                UtilList::RotateKey(rotate_key)
+            }
+            CliUtilList::ListKeys(cli_list_keys) => {
+                let list_keys = list_keys_subcommand::ListKeys::from(cli_list_keys);
+                UtilList::ListKeys(list_keys)
+            }
+            CliUtilList::ScanHdPaths(cli_scan_hd_paths) => {
+                let scan_hd_paths = scan_hd_paths_subcommand::ScanHdPaths::from(cli_scan_hd_paths);
+                UtilList::ScanHdPaths(scan_hd_paths)
+            }
+            CliUtilList::BackupSeed(cli_backup_seed) => {
+                let backup_seed = backup_seed_subcommand::BackupSeed::from(cli_backup_seed);
+                UtilList::BackupSeed(backup_seed)
+            }
+            CliUtilList::RestoreSeed(cli_restore_seed) => {
+                let restore_seed = restore_seed_subcommand::RestoreSeed::from(cli_restore_seed);
+                UtilList::RestoreSeed(restore_seed)
+            }
+            CliUtilList::SuggestAccountName(cli_suggest_account_name) => {
+                let suggest_account_name =
+                    suggest_account_name_subcommand::SuggestAccountName::from(
+                        cli_suggest_account_name,
+                    );
+                UtilList::SuggestAccountName(suggest_account_name)
+            }
+            CliUtilList::ExportProtocolConfig(cli_export_protocol_config) => {
+                let export_protocol_config =
+                    export_protocol_config_subcommand::ExportProtocolConfig::from(
+                        cli_export_protocol_config,
+                    );
+                UtilList::ExportProtocolConfig(export_protocol_config)
+            }
+            CliUtilList::BenchmarkRpc(cli_benchmark_rpc) => {
+                let benchmark_rpc = benchmark_rpc_subcommand::BenchmarkRpc::from(cli_benchmark_rpc);
+                UtilList::BenchmarkRpc(benchmark_rpc)
+            }
         }
     }
 }
 
 impl UtilType {
-    pub fn process(self) {
-        self.util.process()
+    pub async fn process(self) {
+        self.util.process().await
     }
 }
@@ -0,0 +1,111 @@
+use structopt::StructOpt;
+
+use super::super::timelock_request_subcommand::{
+    input_multisig_account_id, multisig_request_action, next_request_id, sign_multisig_call,
+    TimelockRequest,
+};
+use super::MultisigPayload;
+
+/// Add a request to a multisig contract with a keychain key of one of the
+/// members and export it, so the other members can confirm it
+#[derive(Debug)]
+pub struct CreateRequest {
+    pub multisig_account_id: String,
+    pub receiver_id: String,
+    pub amount: crate::amount::AmountExpression,
+    pub method_name: Option<String>,
+    pub args: Option<String>,
+    pub output: Option<std::path::PathBuf>,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliCreateRequest {
+    multisig_account_id: Option<String>,
+    #[structopt(long)]
+    receiver_id: Option<String>,
+    /// Transferred (or attached to the function call) when the request is confirmed
+    #[structopt(long)]
+    amount: Option<crate::amount::AmountExpression>,
+    /// Request a function call instead of a transfer
+    #[structopt(long)]
+    method_name: Option<String>,
+    #[structopt(long)]
+    args: Option<String>,
+    /// Where to export the request (`multisig-<account>-<request id>.json` by default)
+    #[structopt(long)]
+    output: Option<std::path::PathBuf>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliCreateRequest> for CreateRequest {
+    fn from(item: CliCreateRequest) -> Self {
+        let multisig_account_id: String = match item.multisig_account_id {
+            Some(cli_multisig_account_id) => cli_multisig_account_id,
+            None => input_multisig_account_id(),
+        };
+        let receiver_id: String = match item.receiver_id {
+            Some(cli_receiver_id) => cli_receiver_id,
+            None => TimelockRequest::input_receiver_id(),
+        };
+        let amount = match item.amount {
+            Some(cli_amount) => cli_amount,
+            None => TimelockRequest::input_amount(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        CreateRequest {
+            multisig_account_id,
+            receiver_id,
+            amount,
+            method_name: item.method_name,
+            args: item.args,
+            output: item.output,
+            connection_config,
+        }
+    }
+}
+
+impl CreateRequest {
+    pub async fn process(self) {
+        let rpc_url = self.connection_config.rpc_url();
+        let amount = match self.amount.evaluate(None) {
+            Ok(amount) => amount,
+            Err(err) => return eprintln!("Error evaluating the amount {}:  {}", self.amount, err),
+        };
+        let action =
+            multisig_request_action(amount, self.method_name.as_deref(), self.args.as_deref());
+        let request_id = match next_request_id(&rpc_url, &self.multisig_account_id).await {
+            Ok(request_id) => request_id,
+            Err(err) => {
+                return eprintln!(
+                    "Error reading the request nonce of <{}>:  {}",
+                    self.multisig_account_id, err
+                )
+            }
+        };
+        sign_multisig_call(
+            &self.multisig_account_id,
+            "add_request",
+            serde_json::json!({
+                "request": { "receiver_id": &self.receiver_id, "actions": [action] },
+            }),
+            rpc_url.clone(),
+        )
+        .await;
+        let payload =
+            match MultisigPayload::fetch(rpc_url, &self.multisig_account_id, request_id).await {
+                Ok(payload) => payload,
+                Err(err) => return eprintln!("{}", err),
+            };
+        let output = self.output.unwrap_or_else(|| payload.default_path());
+        match payload.save(&output) {
+            Ok(()) => eprintln!(
+                "Request #{} is exported to {:?}, the other members confirm it with \
+                 `utils multisig sign {:?}`",
+                request_id, output, output
+            ),
+            Err(err) => eprintln!("{}", err),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use structopt::StructOpt;
+
+use super::super::execute_when_due_subcommand::ExecuteWhenDue;
+use super::super::timelock_request_subcommand::input_multisig_account_id;
+use super::MultisigPayload;
+
+/// Export a request that is already added to a multisig contract
+#[derive(Debug)]
+pub struct Export {
+    pub multisig_account_id: String,
+    pub request_id: u64,
+    pub output: Option<std::path::PathBuf>,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliExport {
+    multisig_account_id: Option<String>,
+    request_id: Option<u64>,
+    /// `multisig-<account>-<request id>.json` by default
+    #[structopt(long)]
+    output: Option<std::path::PathBuf>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliExport> for Export {
+    fn from(item: CliExport) -> Self {
+        let multisig_account_id: String = match item.multisig_account_id {
+            Some(cli_multisig_account_id) => cli_multisig_account_id,
+            None => input_multisig_account_id(),
+        };
+        let request_id: u64 = match item.request_id {
+            Some(cli_request_id) => cli_request_id,
+            None => ExecuteWhenDue::input_request_id(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        Export {
+            multisig_account_id,
+            request_id,
+            output: item.output,
+            connection_config,
+        }
+    }
+}
+
+impl Export {
+    pub async fn process(self) {
+        let payload = match MultisigPayload::fetch(
+            self.connection_config.rpc_url(),
+            &self.multisig_account_id,
+            self.request_id,
+        )
+        .await
+        {
+            Ok(payload) => payload,
+            Err(err) => return eprintln!("{}", err),
+        };
+        payload.print();
+        let output = self.output.unwrap_or_else(|| payload.default_path());
+        match payload.save(&output) {
+            Ok(()) => eprintln!("The request is exported to {:?}", output),
+            Err(err) => eprintln!("{}", err),
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use structopt::StructOpt;
+use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
+
+use super::execute_when_due_subcommand::ExecuteWhenDue;
+use super::timelock_request_subcommand::{input_multisig_account_id, TimelockRequest};
+
+mod create_request_subcommand;
+mod export_subcommand;
+mod sign_subcommand;
+mod submit_subcommand;
+
+/// A multisig request exported for the other members, who sign their
+/// confirmations offline and hand them back to whoever submits them
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct MultisigPayload {
+    pub rpc_url: url::Url,
+    pub multisig_account_id: String,
+    pub request_id: u64,
+    pub request: serde_json::Value,
+}
+
+impl MultisigPayload {
+    pub async fn fetch(
+        rpc_url: url::Url,
+        multisig_account_id: &str,
+        request_id: u64,
+    ) -> Result<Self, String> {
+        let request = crate::common::call_view_function(
+            &rpc_url,
+            multisig_account_id,
+            "get_request",
+            serde_json::json!({ "request_id": request_id }),
+        )
+        .await
+        .map_err(|err| format!("Error reading the request #{}:  {}", request_id, err))?;
+        Ok(MultisigPayload {
+            rpc_url,
+            multisig_account_id: multisig_account_id.to_string(),
+            request_id,
+            request,
+        })
+    }
+    /// `multisig-<account>-<request id>.json` in the current directory
+    pub fn default_path(&self) -> std::path::PathBuf {
+        std::path::PathBuf::from(format!(
+            "multisig-{}-{}.json",
+            self.multisig_account_id, self.request_id
+        ))
+    }
+    pub fn save(&self, path: &std::path::Path) -> Result<(), String> {
+        let buf = serde_json::to_string_pretty(self)
+            .map_err(|err| format!("Failed to serialize the request: {:?}", err))?;
+        std::fs::write(path, buf)
+            .map_err(|err| format!("Failed to write the file {:?}: {:?}", path, err))
+    }
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let buf = std::fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read the file {:?}: {:?}", path, err))?;
+        serde_json::from_str(&buf)
+            .map_err(|err| format!("Failed to parse the request {:?}: {:?}", path, err))
+    }
+    pub fn print(&self) {
+        println!(
+            "Request #{} on <{}>:\n{}",
+            self.request_id,
+            crate::style::account_id(&self.multisig_account_id),
+            serde_json::to_string_pretty(&self.request).unwrap_or_default()
+        );
+    }
+    /// The `confirm` call each member signs with their own key
+    pub fn confirm_action(&self) -> near_primitives::transaction::Action {
+        near_primitives::transaction::Action::FunctionCall(
+            near_primitives::transaction::FunctionCallAction {
+                method_name: "confirm".to_string(),
+                args: serde_json::json!({ "request_id": self.request_id })
+                    .to_string()
+                    .into_bytes(),
+                gas: super::timelock_request_subcommand::MULTISIG_GAS,
+                deposit: 0,
+            },
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct MultisigCommand {
+    pub action: MultisigAction,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliMultisigCommand {
+    #[structopt(subcommand)]
+    action: Option<CliMultisigAction>,
+}
+
+impl From<CliMultisigCommand> for MultisigCommand {
+    fn from(item: CliMultisigCommand) -> Self {
+        let action = match item.action {
+            Some(cli_action) => MultisigAction::from(cli_action),
+            None => MultisigAction::choose_action(),
+        };
+        MultisigCommand { action }
+    }
+}
+
+impl MultisigCommand {
+    pub async fn process(self) {
+        self.action.process().await
+    }
+}
+
+#[derive(Debug, EnumDiscriminants)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+pub enum MultisigAction {
+    #[strum_discriminants(strum(message = "Add a request and export it for the other members"))]
+    CreateRequest(create_request_subcommand::CreateRequest),
+    #[strum_discriminants(strum(message = "Export an existing request for the other members"))]
+    Export(export_subcommand::Export),
+    #[strum_discriminants(strum(message = "Sign a confirmation of an exported request"))]
+    Sign(sign_subcommand::Sign),
+    #[strum_discriminants(strum(
+        message = "Submit the collected confirmations once there are enough of them"
+    ))]
+    Submit(submit_subcommand::Submit),
+}
+
+#[derive(Debug, StructOpt)]
+pub enum CliMultisigAction {
+    CreateRequest(create_request_subcommand::CliCreateRequest),
+    Export(export_subcommand::CliExport),
+    Sign(sign_subcommand::CliSign),
+    Submit(submit_subcommand::CliSubmit),
+}
+
+impl From<CliMultisigAction> for MultisigAction {
+    fn from(item: CliMultisigAction) -> Self {
+        match item {
+            CliMultisigAction::CreateRequest(cli_create_request) => MultisigAction::CreateRequest(
+                create_request_subcommand::CreateRequest::from(cli_create_request),
+            ),
+            CliMultisigAction::Export(cli_export) => {
+                MultisigAction::Export(export_subcommand::Export::from(cli_export))
+            }
+            CliMultisigAction::Sign(cli_sign) => {
+                MultisigAction::Sign(sign_subcommand::Sign::from(cli_sign))
+            }
+            CliMultisigAction::Submit(cli_submit) => {
+                MultisigAction::Submit(submit_subcommand::Submit::from(cli_submit))
+            }
+        }
+    }
+}
+
+impl MultisigAction {
+    pub async fn process(self) {
+        match self {
+            MultisigAction::CreateRequest(create_request) => create_request.process().await,
+            MultisigAction::Export(export) => export.process().await,
+            MultisigAction::Sign(sign) => sign.process().await,
+            MultisigAction::Submit(submit) => submit.process().await,
+        }
+    }
+    pub fn choose_action() -> Self {
+        eprintln!();
+        let variants = MultisigActionDiscriminants::iter().collect::<Vec<_>>();
+        let actions = variants
+            .iter()
+            .map(|p| p.get_message().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Choose your action")
+            .items(&actions)
+            .default(0)
+            .interact()
+            .unwrap();
+        match variants[selection] {
+            MultisigActionDiscriminants::CreateRequest => {
+                MultisigAction::CreateRequest(create_request_subcommand::CreateRequest {
+                    multisig_account_id: input_multisig_account_id(),
+                    receiver_id: TimelockRequest::input_receiver_id(),
+                    amount: TimelockRequest::input_amount(),
+                    method_name: None,
+                    args: None,
+                    output: None,
+                    connection_config: crate::common::ConnectionConfig::input_connection_config(),
+                })
+            }
+            MultisigActionDiscriminants::Export => {
+                MultisigAction::Export(export_subcommand::Export {
+                    multisig_account_id: input_multisig_account_id(),
+                    request_id: ExecuteWhenDue::input_request_id(),
+                    output: None,
+                    connection_config: crate::common::ConnectionConfig::input_connection_config(),
+                })
+            }
+            MultisigActionDiscriminants::Sign => MultisigAction::Sign(sign_subcommand::Sign {
+                payload_file: input_payload_file(),
+                signer_public_key: None,
+                output: None,
+            }),
+            MultisigActionDiscriminants::Submit => {
+                MultisigAction::Submit(submit_subcommand::Submit {
+                    payload_file: input_payload_file(),
+                    confirmations: submit_subcommand::Submit::input_confirmations(),
+                })
+            }
+        }
+    }
+}
+
+pub fn input_payload_file() -> std::path::PathBuf {
+    let payload_file: String = Input::new()
+        .with_prompt("Enter the path to the exported multisig request")
+        .interact_text()
+        .unwrap();
+    std::path::PathBuf::from(payload_file)
+}
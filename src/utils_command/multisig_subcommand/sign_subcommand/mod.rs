@@ -0,0 +1,125 @@
+use dialoguer::{theme::ColorfulTheme, Select};
+use near_primitives::borsh::BorshSerialize;
+use std::str::FromStr;
+use structopt::StructOpt;
+
+use super::{input_payload_file, MultisigPayload};
+
+/// Sign a confirmation of an exported request with a keychain key of a member;
+/// the signed transaction is not sent, `utils multisig submit` sends it
+#[derive(Debug)]
+pub struct Sign {
+    pub payload_file: std::path::PathBuf,
+    pub signer_public_key: Option<String>,
+    pub output: Option<std::path::PathBuf>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliSign {
+    payload_file: Option<std::path::PathBuf>,
+    #[structopt(long)]
+    signer_public_key: Option<String>,
+    /// Also write the signed confirmation to this file
+    #[structopt(long)]
+    output: Option<std::path::PathBuf>,
+}
+
+impl From<CliSign> for Sign {
+    fn from(item: CliSign) -> Self {
+        let payload_file = match item.payload_file {
+            Some(cli_payload_file) => cli_payload_file,
+            None => input_payload_file(),
+        };
+        Sign {
+            payload_file,
+            signer_public_key: item.signer_public_key,
+            output: item.output,
+        }
+    }
+}
+
+impl Sign {
+    pub async fn process(self) {
+        let payload = match MultisigPayload::load(&self.payload_file) {
+            Ok(payload) => payload,
+            Err(err) => return eprintln!("{}", err),
+        };
+        payload.print();
+        let mut access_keys = crate::common::read_access_keys_from_keychain(
+            &crate::common::network_dir_name(&payload.rpc_url),
+            &payload.multisig_account_id,
+        );
+        if let Some(signer_public_key) = &self.signer_public_key {
+            access_keys.retain(|access_key| &access_key.public_key == signer_public_key);
+        }
+        let access_key = match access_keys.len() {
+            0 => {
+                return eprintln!(
+                    "There are no member keys of <{}> in the keychain",
+                    &payload.multisig_account_id
+                )
+            }
+            1 => access_keys.remove(0),
+            _ => {
+                let items = access_keys
+                    .iter()
+                    .map(|access_key| access_key.public_key.clone())
+                    .collect::<Vec<_>>();
+                let selection = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Select the member key to confirm the request with")
+                    .items(&items)
+                    .default(0)
+                    .interact()
+                    .unwrap();
+                access_keys.remove(selection)
+            }
+        };
+        let public_key = near_crypto::PublicKey::from_str(&access_key.public_key).unwrap();
+        let secret_key = near_crypto::SecretKey::from_str(&access_key.private_key).unwrap();
+        let access_key_response = near_jsonrpc_client::new_client(payload.rpc_url.as_str())
+            .query(near_primitives::rpc::RpcQueryRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+                request: near_primitives::views::QueryRequest::ViewAccessKey {
+                    account_id: payload.multisig_account_id.clone(),
+                    public_key: public_key.clone(),
+                },
+            })
+            .await
+            .map_err(|err| eprintln!("Error access_key_response:   {:?}", &err))
+            .unwrap();
+        let current_nonce =
+            if let near_primitives::views::QueryResponseKind::AccessKey(access_key) =
+                access_key_response.kind
+            {
+                access_key.nonce
+            } else {
+                return eprintln!("Error current_nonce");
+            };
+        let unsigned_transaction = near_primitives::transaction::Transaction {
+            signer_id: payload.multisig_account_id.clone(),
+            public_key,
+            nonce: current_nonce + 1,
+            receiver_id: payload.multisig_account_id.clone(),
+            block_hash: access_key_response.block_hash,
+            actions: vec![payload.confirm_action()],
+        };
+        let signature = secret_key.sign(unsigned_transaction.get_hash().as_ref());
+        let signed_transaction =
+            near_primitives::transaction::SignedTransaction::new(signature, unsigned_transaction);
+        let serialize_to_base64 = near_primitives::serialize::to_base64(
+            signed_transaction
+                .try_to_vec()
+                .expect("Transaction is not expected to fail on serialization"),
+        );
+        println!(
+            "The signed confirmation (it expires with the block hash in about a day):\n{}",
+            &serialize_to_base64
+        );
+        if let Some(output) = &self.output {
+            match std::fs::write(output, &serialize_to_base64) {
+                Ok(()) => eprintln!("The signed confirmation is saved to {:?}", output),
+                Err(err) => eprintln!("Failed to write the file {:?}: {:?}", output, err),
+            }
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use dialoguer::Input;
+use near_primitives::borsh::{BorshDeserialize, BorshSerialize};
+use structopt::StructOpt;
+
+use super::{input_payload_file, MultisigPayload};
+
+/// Check the signed confirmations of an exported request and send them once
+/// they (with the ones already on chain) reach the number of confirmations the
+/// contract requires
+#[derive(Debug)]
+pub struct Submit {
+    pub payload_file: std::path::PathBuf,
+    pub confirmations: Vec<String>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliSubmit {
+    payload_file: Option<std::path::PathBuf>,
+    /// Signed confirmations: files written by `utils multisig sign --output` or base64 strings
+    confirmations: Vec<String>,
+}
+
+impl From<CliSubmit> for Submit {
+    fn from(item: CliSubmit) -> Self {
+        let payload_file = match item.payload_file {
+            Some(cli_payload_file) => cli_payload_file,
+            None => input_payload_file(),
+        };
+        let confirmations = if item.confirmations.is_empty() {
+            Submit::input_confirmations()
+        } else {
+            item.confirmations
+        };
+        Submit {
+            payload_file,
+            confirmations,
+        }
+    }
+}
+
+impl Submit {
+    pub async fn process(self) {
+        let payload = match MultisigPayload::load(&self.payload_file) {
+            Ok(payload) => payload,
+            Err(err) => return eprintln!("{}", err),
+        };
+        let num_confirmations = match crate::common::call_view_function(
+            &payload.rpc_url,
+            &payload.multisig_account_id,
+            "get_num_confirmations",
+            serde_json::json!({}),
+        )
+        .await
+        {
+            Ok(num_confirmations) => num_confirmations.as_u64().unwrap_or_default() as usize,
+            Err(err) => return eprintln!("Error reading the number of confirmations:  {}", err),
+        };
+        let confirmed_public_keys = match crate::common::call_view_function(
+            &payload.rpc_url,
+            &payload.multisig_account_id,
+            "get_confirmations",
+            serde_json::json!({ "request_id": payload.request_id }),
+        )
+        .await
+        {
+            Ok(confirmed_public_keys) => confirmed_public_keys
+                .as_array()
+                .map(|public_keys| {
+                    public_keys
+                        .iter()
+                        .filter_map(|public_key| public_key.as_str().map(String::from))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default(),
+            Err(err) => return eprintln!("Error reading the confirmations:  {}", err),
+        };
+        let mut signed_transactions: Vec<near_primitives::transaction::SignedTransaction> = vec![];
+        for confirmation in &self.confirmations {
+            let signed_transaction = match Submit::read_confirmation(&payload, confirmation) {
+                Ok(signed_transaction) => signed_transaction,
+                Err(err) => {
+                    eprintln!("{}, skipping it", err);
+                    continue;
+                }
+            };
+            let public_key = signed_transaction.transaction.public_key.to_string();
+            if confirmed_public_keys.contains(&public_key) {
+                eprintln!(
+                    "{} has already confirmed the request, skipping it",
+                    public_key
+                );
+            } else if signed_transactions.iter().any(|signed| {
+                signed.transaction.public_key == signed_transaction.transaction.public_key
+            }) {
+                eprintln!("{} is given twice, skipping it", public_key);
+            } else {
+                signed_transactions.push(signed_transaction);
+            }
+        }
+        let total_confirmations = confirmed_public_keys.len() + signed_transactions.len();
+        eprintln!(
+            "Request #{}: {} confirmation(s) on chain, {} new, {} required",
+            payload.request_id,
+            confirmed_public_keys.len(),
+            signed_transactions.len(),
+            num_confirmations
+        );
+        if total_confirmations < num_confirmations {
+            return eprintln!(
+                "{}",
+                crate::style::warning(format!(
+                    "Waiting for {} more confirmation(s), nothing is sent",
+                    num_confirmations - total_confirmations
+                ))
+            );
+        }
+        // The confirmation that reaches the threshold executes the request
+        for signed_transaction in signed_transactions {
+            let transaction_hash = signed_transaction.get_hash().to_string();
+            let transaction_info = near_jsonrpc_client::new_client(payload.rpc_url.as_str())
+                .broadcast_tx_commit(near_primitives::serialize::to_base64(
+                    signed_transaction
+                        .try_to_vec()
+                        .expect("Transaction is not expected to fail on serialization"),
+                ))
+                .await;
+            match transaction_info {
+                Ok(transaction_info) => println!(
+                    "{} {}: {:?}",
+                    crate::style::success("Confirmed"),
+                    crate::style::hash(&transaction_hash),
+                    transaction_info.status
+                ),
+                Err(err) => eprintln!("Error sending {}:  {:?}", transaction_hash, err),
+            }
+        }
+    }
+    /// Accepts a file with a base64 signed transaction or the base64 string itself
+    fn read_confirmation(
+        payload: &MultisigPayload,
+        confirmation: &str,
+    ) -> Result<near_primitives::transaction::SignedTransaction, String> {
+        let encoded =
+            std::fs::read_to_string(confirmation).unwrap_or_else(|_| confirmation.to_string());
+        let signed_transaction = base64::decode(encoded.trim())
+            .map_err(|err| format!("Invalid base64 in the confirmation:  {:?}", err))
+            .and_then(|bytes| {
+                near_primitives::transaction::SignedTransaction::try_from_slice(&bytes)
+                    .map_err(|err| format!("Invalid signed transaction:  {:?}", err))
+            })?;
+        let transaction = &signed_transaction.transaction;
+        if transaction.signer_id != payload.multisig_account_id
+            || transaction.receiver_id != payload.multisig_account_id
+            || transaction.actions != vec![payload.confirm_action()]
+        {
+            return Err(format!(
+                "The confirmation {} does not confirm request #{} on <{}>",
+                signed_transaction.get_hash(),
+                payload.request_id,
+                payload.multisig_account_id
+            ));
+        }
+        if !signed_transaction
+            .signature
+            .verify(transaction.get_hash().as_ref(), &transaction.public_key)
+        {
+            return Err(format!(
+                "The confirmation {} has an invalid signature",
+                signed_transaction.get_hash()
+            ));
+        }
+        Ok(signed_transaction)
+    }
+    pub fn input_confirmations() -> Vec<String> {
+        let confirmations: String = Input::new()
+            .with_prompt("Enter the signed confirmations (files or base64, separated by spaces)")
+            .interact_text()
+            .unwrap();
+        confirmations.split_whitespace().map(String::from).collect()
+    }
+}
@@ -0,0 +1,160 @@
+use dialoguer::Input;
+use near_primitives::borsh::{BorshDeserialize, BorshSerialize};
+use std::str::FromStr;
+use structopt::StructOpt;
+
+/// Compare the on-chain access key nonce with the locally recorded signed
+/// transactions and generate replacements for the ones that can never be included
+#[derive(Debug)]
+pub struct NonceDoctor {
+    pub account_id: String,
+    pub public_key: String,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliNonceDoctor {
+    account_id: Option<String>,
+    public_key: Option<String>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliNonceDoctor> for NonceDoctor {
+    fn from(item: CliNonceDoctor) -> Self {
+        let account_id: String = match item.account_id {
+            Some(cli_account_id) => cli_account_id,
+            None => NonceDoctor::input_account_id(),
+        };
+        let public_key: String = match item.public_key {
+            Some(cli_public_key) => cli_public_key,
+            None => NonceDoctor::input_public_key(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        NonceDoctor {
+            account_id,
+            public_key,
+            connection_config,
+        }
+    }
+}
+
+impl NonceDoctor {
+    pub async fn process(self) {
+        let public_key = near_crypto::PublicKey::from_str(&self.public_key).unwrap();
+        let server_url = self.connection_config.rpc_url();
+        let access_key_response = near_jsonrpc_client::new_client(server_url.as_str())
+            .query(near_primitives::rpc::RpcQueryRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+                request: near_primitives::views::QueryRequest::ViewAccessKey {
+                    account_id: self.account_id.clone(),
+                    public_key: public_key.clone(),
+                },
+            })
+            .await
+            .map_err(|err| eprintln!("Error access_key_response:   {:?}", &err))
+            .unwrap();
+        let current_nonce =
+            if let near_primitives::views::QueryResponseKind::AccessKey(access_key) =
+                access_key_response.kind
+            {
+                access_key.nonce
+            } else {
+                return eprintln!("Error current_nonce");
+            };
+        println!("On-chain nonce: {}", current_nonce);
+
+        let mut recorded_transactions = crate::common::read_audit_log()
+            .into_iter()
+            .filter(|entry| {
+                entry["event"] == "signed_transaction"
+                    && entry["signer_id"] == self.account_id.as_str()
+                    && entry["public_key"] == public_key.to_string().as_str()
+            })
+            .filter_map(|entry| {
+                let signed_transaction_borsh =
+                    base64::decode(entry["signed_transaction"].as_str()?).ok()?;
+                near_primitives::transaction::SignedTransaction::try_from_slice(
+                    &signed_transaction_borsh,
+                )
+                .ok()
+            })
+            .collect::<Vec<_>>();
+        recorded_transactions
+            .sort_by_key(|signed_transaction| signed_transaction.transaction.nonce);
+        if recorded_transactions.is_empty() {
+            return eprintln!("There are no locally recorded signed transactions for this key");
+        }
+
+        let mut next_free_nonce = current_nonce + 1;
+        let mut previous_nonce: Option<u64> = None;
+        for signed_transaction in recorded_transactions {
+            let transaction_hash = signed_transaction.transaction.get_hash();
+            let nonce = signed_transaction.transaction.nonce;
+            if previous_nonce == Some(nonce) {
+                println!(
+                    "{} (nonce {}): DUPLICATE nonce, only one of the transactions with this nonce can be included",
+                    transaction_hash, nonce
+                );
+            }
+            previous_nonce = Some(nonce);
+            if nonce > current_nonce {
+                println!(
+                    "{} (nonce {}): pending, submit it before any transaction with a higher nonce",
+                    transaction_hash, nonce
+                );
+                match crate::common::transaction_blocks_left(
+                    &server_url,
+                    &signed_transaction.transaction,
+                )
+                .await
+                {
+                    Ok(0) => println!("  EXPIRED, its block hash is too old, sign it again"),
+                    Ok(blocks_left) => println!(
+                        "  {} left before it expires",
+                        crate::common::format_blocks_as_time(blocks_left)
+                    ),
+                    Err(err) => println!("  Could not check when it expires:  {}", err),
+                }
+                next_free_nonce = std::cmp::max(next_free_nonce, nonce + 1);
+                continue;
+            }
+            let included = near_jsonrpc_client::new_client(server_url.as_str())
+                .tx(transaction_hash.to_string(), self.account_id.clone())
+                .await
+                .is_ok();
+            if included {
+                println!("{} (nonce {}): included", transaction_hash, nonce);
+                continue;
+            }
+            println!(
+                "{} (nonce {}): STUCK, the nonce has already been used so it can never be included",
+                transaction_hash, nonce
+            );
+            let replacement_transaction = near_primitives::transaction::Transaction {
+                nonce: next_free_nonce,
+                block_hash: access_key_response.block_hash,
+                ..signed_transaction.transaction
+            };
+            next_free_nonce += 1;
+            println!(
+                "  Replacement unsigned transaction with nonce {} (sign it with `utils sign-transaction`):\n  {}",
+                replacement_transaction.nonce,
+                near_primitives::serialize::to_base64(
+                    replacement_transaction
+                        .try_to_vec()
+                        .expect("Transaction is not expected to fail on serialization"),
+                )
+            );
+        }
+    }
+    pub fn input_account_id() -> String {
+        Input::new()
+            .with_prompt("What is the account ID?")
+            .interact_text()
+            .unwrap()
+    }
+    pub fn input_public_key() -> String {
+        crate::public_key_selector::input_public_key("Enter the public key of the access key")
+    }
+}
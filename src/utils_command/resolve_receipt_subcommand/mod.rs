@@ -0,0 +1,123 @@
+use dialoguer::Input;
+use std::str::FromStr;
+use structopt::StructOpt;
+
+/// Find the transaction (and its signer) that produced a given receipt
+#[derive(Debug)]
+pub struct ResolveReceipt {
+    pub receipt_id: near_primitives::hash::CryptoHash,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliResolveReceipt {
+    receipt_id: Option<crate::common::BlobAsBase58String<near_primitives::hash::CryptoHash>>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliResolveReceipt> for ResolveReceipt {
+    fn from(item: CliResolveReceipt) -> Self {
+        let receipt_id = match item.receipt_id {
+            Some(cli_receipt_id) => cli_receipt_id.into_inner(),
+            None => ResolveReceipt::input_receipt_id(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        ResolveReceipt {
+            receipt_id,
+            connection_config,
+        }
+    }
+}
+
+impl ResolveReceipt {
+    pub async fn process(self) {
+        let receipt_id = self.receipt_id.to_string();
+        let receipt = crate::common::with_archival_fallback(&self.connection_config, |url| {
+            let params = serde_json::json!({ "receipt_id": receipt_id });
+            async move { crate::common::call_json_rpc(&url, "EXPERIMENTAL_receipt", params).await }
+        })
+        .await
+        .map_err(|err| eprintln!("Error fetching the receipt:  {}", &err))
+        .unwrap();
+        println!(
+            "Receipt: {} ({} -> {})",
+            crate::style::hash(&receipt_id),
+            crate::style::account_id(receipt["predecessor_id"].as_str().unwrap_or_default()),
+            crate::style::account_id(receipt["receiver_id"].as_str().unwrap_or_default())
+        );
+        let signer_id = receipt["receipt"]["Action"]["signer_id"]
+            .as_str()
+            .map(|signer_id| signer_id.to_string());
+        match &signer_id {
+            Some(signer_id) => println!("Signer: {}", crate::style::account_id(signer_id)),
+            None => println!("Signer: unknown (this is a data receipt)"),
+        }
+
+        let candidates = crate::common::read_audit_log()
+            .into_iter()
+            .filter(|entry| entry["event"] == "signed_transaction")
+            .filter_map(|entry| {
+                let entry_signer_id = entry["signer_id"].as_str()?.to_string();
+                match &signer_id {
+                    Some(signer_id) if signer_id != &entry_signer_id => None,
+                    _ => Some((
+                        entry["transaction_hash"].as_str()?.to_string(),
+                        entry_signer_id,
+                    )),
+                }
+            })
+            .collect::<Vec<_>>();
+        for (transaction_hash, transaction_signer_id) in candidates {
+            let transaction_status =
+                match crate::common::with_archival_fallback(&self.connection_config, |url| {
+                    let params = serde_json::json!([transaction_hash, transaction_signer_id]);
+                    async move {
+                        crate::common::call_json_rpc(&url, "EXPERIMENTAL_tx_status", params).await
+                    }
+                })
+                .await
+                {
+                    Ok(transaction_status) => transaction_status,
+                    Err(_) => continue,
+                };
+            let produced_by_transaction = transaction_status["transaction_outcome"]["outcome"]
+                ["receipt_ids"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .chain(
+                    transaction_status["receipts_outcome"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .map(|receipt_outcome| &receipt_outcome["id"]),
+                )
+                .any(|id| id == receipt_id.as_str());
+            if produced_by_transaction {
+                return println!(
+                    "Originating transaction: {} (signed by {})",
+                    crate::style::success(&transaction_hash),
+                    crate::style::account_id(&transaction_signer_id)
+                );
+            }
+        }
+        eprintln!(
+            "The originating transaction was not found among the locally recorded signed transactions"
+        );
+    }
+    pub fn input_receipt_id() -> near_primitives::hash::CryptoHash {
+        loop {
+            let input_receipt_id: String = Input::new()
+                .with_prompt("Enter the receipt ID")
+                .interact_text()
+                .unwrap();
+            match crate::common::BlobAsBase58String::<near_primitives::hash::CryptoHash>::from_str(
+                &input_receipt_id,
+            ) {
+                Ok(receipt_id) => break receipt_id.into_inner(),
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+use super::backup_seed_subcommand::decode_share_code;
+
+/// Restore a seed phrase from the share codes of `utils backup-seed`
+#[derive(Debug)]
+pub struct RestoreSeed {
+    pub share_codes: Vec<String>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliRestoreSeed {
+    share_codes: Vec<String>,
+}
+
+impl From<CliRestoreSeed> for RestoreSeed {
+    fn from(item: CliRestoreSeed) -> Self {
+        let share_codes = if item.share_codes.is_empty() {
+            RestoreSeed::input_share_codes()
+        } else {
+            item.share_codes
+        };
+        RestoreSeed { share_codes }
+    }
+}
+
+impl RestoreSeed {
+    pub fn process(self) {
+        let mut threshold = None;
+        let mut shares = vec![];
+        for share_code in &self.share_codes {
+            let (share_threshold, share) = match decode_share_code(share_code) {
+                Ok(decoded_share) => decoded_share,
+                Err(err) => return eprintln!("{}", err),
+            };
+            if *threshold.get_or_insert(share_threshold) != share_threshold {
+                return eprintln!("The share codes come from different backups");
+            }
+            shares.push(share);
+        }
+        let threshold = match threshold {
+            Some(threshold) => threshold,
+            None => return eprintln!("There are no share codes"),
+        };
+        if shares.len() < usize::from(threshold) {
+            return eprintln!(
+                "{} share codes are needed, {} are given",
+                threshold,
+                shares.len()
+            );
+        }
+        let entropy = match sharks::Sharks(threshold).recover(&shares) {
+            Ok(entropy) => entropy,
+            Err(err) => return eprintln!("Error restoring the seed phrase:  {}", err),
+        };
+        let mnemonic = match bip39::Mnemonic::from_entropy(&entropy) {
+            Ok(mnemonic) => mnemonic,
+            Err(err) => return eprintln!("Error restoring the seed phrase:  {}", err),
+        };
+        println!(
+            "Seed phrase: {}",
+            crate::common::display_secret(&mnemonic.to_string(), crate::common::reveal_secrets())
+        );
+    }
+    pub fn input_share_codes() -> Vec<String> {
+        let mut share_codes = vec![];
+        loop {
+            let share_code: String = Input::new()
+                .with_prompt("Enter a share code (leave empty when done)")
+                .allow_empty(true)
+                .interact_text()
+                .unwrap();
+            if share_code.trim().is_empty() {
+                break share_codes;
+            }
+            match decode_share_code(&share_code) {
+                Ok(_) => share_codes.push(share_code.trim().to_string()),
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::str::FromStr;
+use structopt::StructOpt;
+
+use crate::construct_transaction_command::sign_transaction::sign_private_key::SignPrivateKey;
+
+/// Delete a (compromised) public key from every account that has it attached
+/// and that the local keychain can sign for
+#[derive(Debug)]
+pub struct RevokeKeyEverywhere {
+    pub public_key: String,
+    pub connection_config: crate::common::ConnectionConfig,
+    pub auto_approve: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliRevokeKeyEverywhere {
+    public_key: Option<String>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+    #[structopt(long)]
+    auto_approve: bool,
+}
+
+impl From<CliRevokeKeyEverywhere> for RevokeKeyEverywhere {
+    fn from(item: CliRevokeKeyEverywhere) -> Self {
+        let public_key: String = match item.public_key {
+            Some(cli_public_key) => cli_public_key,
+            None => RevokeKeyEverywhere::input_public_key(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        RevokeKeyEverywhere {
+            public_key,
+            connection_config,
+            auto_approve: item.auto_approve,
+        }
+    }
+}
+
+impl RevokeKeyEverywhere {
+    pub async fn process(self) {
+        let public_key = match crate::common::parse_public_key(&self.public_key) {
+            Ok(public_key) => public_key,
+            Err(err) => return eprintln!("{}", err),
+        };
+        let rpc_url = self.connection_config.rpc_url();
+        let indexer = match crate::indexer::indexer_for(&self.connection_config) {
+            Ok(indexer) => indexer,
+            Err(err) => return eprintln!("{}", err),
+        };
+        let account_ids = match indexer.accounts_by_public_key(&self.public_key).await {
+            Ok(account_ids) => account_ids,
+            Err(err) => return eprintln!("Error fetching the accounts:  {}", err),
+        };
+        let network_dir_name = self.connection_config.dir_name();
+        let mut revocations = vec![];
+        let mut not_controlled = vec![];
+        for account_id in account_ids {
+            // The indexer may lag behind, so the key may be gone already
+            match crate::common::access_key_exists(&rpc_url, &account_id, &self.public_key).await {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(err) => {
+                    eprintln!("Could not check the keys of <{}>:  {}", account_id, err);
+                    continue;
+                }
+            }
+            let mut access_keys =
+                crate::common::read_access_keys_from_keychain(&network_dir_name, &account_id);
+            // Prefer another key, the compromised one is only the last resort
+            access_keys.sort_by_key(|access_key| access_key.public_key == self.public_key);
+            match access_keys.into_iter().next() {
+                Some(access_key) => revocations.push((account_id, access_key)),
+                None => not_controlled.push(account_id),
+            }
+        }
+        if revocations.is_empty() && not_controlled.is_empty() {
+            return eprintln!("No accounts have the key {}", self.public_key);
+        }
+        println!("The key {} will be deleted from:", self.public_key);
+        for (account_id, access_key) in &revocations {
+            println!(
+                "  {} (signed with {})",
+                crate::style::account_id(account_id),
+                access_key.public_key
+            );
+        }
+        if !not_controlled.is_empty() {
+            println!(
+                "{}",
+                crate::style::warning(
+                    "These accounts have the key too, but the keychain cannot sign for them:"
+                )
+            );
+            for account_id in &not_controlled {
+                println!("  {}", crate::style::account_id(account_id));
+            }
+        }
+        if revocations.is_empty() {
+            return;
+        }
+        if !self.auto_approve
+            && !dialoguer::Confirm::new()
+                .with_prompt(format!(
+                    "Do you want to delete the key from {} account(s)?",
+                    revocations.len()
+                ))
+                .default(false)
+                .interact()
+                .unwrap_or(false)
+        {
+            return eprintln!("No keys have been deleted");
+        }
+        for (account_id, access_key) in revocations {
+            println!();
+            println!("=== {} ===", account_id);
+            let unsigned_transaction = near_primitives::transaction::Transaction {
+                signer_id: account_id.clone(),
+                public_key: near_crypto::PublicKey::from_str(&access_key.public_key).unwrap(),
+                nonce: 0,
+                receiver_id: account_id,
+                block_hash: Default::default(),
+                actions: vec![near_primitives::transaction::Action::DeleteKey(
+                    near_primitives::transaction::DeleteKeyAction {
+                        public_key: public_key.clone(),
+                    },
+                )],
+            };
+            SignPrivateKey {
+                signer_public_key: access_key.public_key,
+                signer_secret_key: access_key.private_key,
+                save_to: None,
+            }
+            .process(unsigned_transaction, Some(rpc_url.clone()))
+            .await;
+        }
+    }
+    pub fn input_public_key() -> String {
+        crate::public_key_selector::input_public_key("Enter the public key to revoke")
+    }
+}
@@ -0,0 +1,242 @@
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use near_primitives::borsh::BorshSerialize;
+use std::str::FromStr;
+use structopt::StructOpt;
+use strum::VariantNames;
+
+/// Replace an access key of the keychain in a single transaction (AddKey of
+/// the new key + DeleteKey of the old one, signed with the old key), so the
+/// account is never left without the key
+#[derive(Debug)]
+pub struct RotateKey {
+    pub account_id: String,
+    /// `None` rotates the only keychain key of the account or asks which one
+    pub old_public_key: Option<String>,
+    /// `None` generates a new key pair and saves it to the keychain
+    pub new_public_key: Option<String>,
+    pub key_type: crate::common::KeyType,
+    /// The label of the new key; `None` keeps the label of the old one
+    pub label: Option<String>,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliRotateKey {
+    account_id: Option<String>,
+    #[structopt(long)]
+    old_public_key: Option<String>,
+    /// A key kept elsewhere (e.g. a hardware wallet); it is not saved to the keychain
+    #[structopt(long)]
+    new_public_key: Option<String>,
+    #[structopt(long, possible_values = crate::common::KeyType::VARIANTS)]
+    key_type: Option<crate::common::KeyType>,
+    /// A label for the new key (e.g. "alice laptop"), the label of the old key by default
+    #[structopt(long)]
+    label: Option<String>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliRotateKey> for RotateKey {
+    fn from(item: CliRotateKey) -> Self {
+        let account_id: String = match item.account_id {
+            Some(cli_account_id) => cli_account_id,
+            None => RotateKey::input_account_id(),
+        };
+        let key_type = match (item.key_type, &item.new_public_key) {
+            (Some(cli_key_type), _) => cli_key_type,
+            (None, Some(_)) => crate::common::KeyType::default(),
+            (None, None) => crate::common::KeyType::input_key_type(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        RotateKey {
+            account_id,
+            old_public_key: item.old_public_key,
+            new_public_key: item.new_public_key,
+            key_type,
+            label: item.label,
+            connection_config,
+        }
+    }
+}
+
+impl RotateKey {
+    pub fn input_account_id() -> String {
+        Input::new()
+            .with_prompt("Which account's key do you want to rotate?")
+            .interact_text()
+            .unwrap()
+    }
+    pub async fn process(self) {
+        let network_dir_name = self.connection_config.dir_name();
+        let rpc_url = self.connection_config.rpc_url();
+        let mut access_keys =
+            crate::common::read_access_keys_from_keychain(&network_dir_name, &self.account_id);
+        if let Some(old_public_key) = &self.old_public_key {
+            access_keys.retain(|access_key| &access_key.public_key == old_public_key);
+        }
+        let old_access_key = match access_keys.len() {
+            0 => {
+                return eprintln!(
+                    "There are no access keys for <{}> in the {} keychain",
+                    self.account_id, network_dir_name
+                )
+            }
+            1 => access_keys.remove(0),
+            _ => {
+                let items = access_keys
+                    .iter()
+                    .map(|access_key| match &access_key.label {
+                        Some(label) => format!("{} ({})", access_key.public_key, label),
+                        None => access_key.public_key.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                let selection = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Select the access key to rotate")
+                    .items(&items)
+                    .default(0)
+                    .interact()
+                    .unwrap();
+                access_keys.remove(selection)
+            }
+        };
+        let old_public_key = near_crypto::PublicKey::from_str(&old_access_key.public_key).unwrap();
+        let old_secret_key = near_crypto::SecretKey::from_str(&old_access_key.private_key).unwrap();
+        let (new_public_key, new_secret_key) = match &self.new_public_key {
+            Some(new_public_key) => match crate::common::parse_public_key(new_public_key) {
+                Ok(new_public_key) => (new_public_key, None),
+                Err(err) => return eprintln!("{}", err),
+            },
+            None => {
+                let new_secret_key = near_crypto::SecretKey::from_random((&self.key_type).into());
+                (new_secret_key.public_key(), Some(new_secret_key))
+            }
+        };
+
+        let _access_key_lock = match crate::common::lock_access_key(
+            &network_dir_name,
+            &self.account_id,
+            &old_access_key.public_key,
+        ) {
+            Ok(access_key_lock) => access_key_lock,
+            Err(err) => return eprintln!("{}", err),
+        };
+        let access_key_response = match near_jsonrpc_client::new_client(rpc_url.as_str())
+            .query(near_primitives::rpc::RpcQueryRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+                request: near_primitives::views::QueryRequest::ViewAccessKey {
+                    account_id: self.account_id.clone(),
+                    public_key: old_public_key.clone(),
+                },
+            })
+            .await
+        {
+            Ok(access_key_response) => access_key_response,
+            Err(err) => return eprintln!("Error fetching the old key:  {:?}", err),
+        };
+        let old_access_key_view = match access_key_response.kind {
+            near_primitives::views::QueryResponseKind::AccessKey(access_key_view) => {
+                access_key_view
+            }
+            _ => return eprintln!("Error fetching the old key"),
+        };
+        // The new key gets the same permission as the old one
+        let unsigned_transaction = near_primitives::transaction::Transaction {
+            signer_id: self.account_id.clone(),
+            public_key: old_public_key.clone(),
+            nonce: old_access_key_view.nonce + 1,
+            receiver_id: self.account_id.clone(),
+            block_hash: access_key_response.block_hash,
+            actions: vec![
+                near_primitives::transaction::Action::AddKey(
+                    near_primitives::transaction::AddKeyAction {
+                        public_key: new_public_key.clone(),
+                        access_key: near_primitives::account::AccessKey {
+                            nonce: 0,
+                            permission: old_access_key_view.permission.into(),
+                        },
+                    },
+                ),
+                near_primitives::transaction::Action::DeleteKey(
+                    near_primitives::transaction::DeleteKeyAction {
+                        public_key: old_public_key.clone(),
+                    },
+                ),
+            ],
+        };
+        eprintln!(
+            "Rotating {} to {} on <{}>",
+            old_public_key, new_public_key, self.account_id
+        );
+        let signature = old_secret_key.sign(unsigned_transaction.get_hash().as_ref());
+        let signed_transaction =
+            near_primitives::transaction::SignedTransaction::new(signature, unsigned_transaction);
+        let transaction_info = match near_jsonrpc_client::new_client(rpc_url.as_str())
+            .broadcast_tx_commit(near_primitives::serialize::to_base64(
+                signed_transaction
+                    .try_to_vec()
+                    .expect("Transaction is not expected to fail on serialization"),
+            ))
+            .await
+        {
+            Ok(transaction_info) => transaction_info,
+            Err(err) => return eprintln!("Error sending the transaction:  {:?}", err),
+        };
+        match &transaction_info.status {
+            near_primitives::views::FinalExecutionStatus::SuccessValue(_) => {}
+            status => {
+                return eprintln!(
+                    "{}: {:?}, the keychain is left unchanged",
+                    crate::style::warning("The rotation failed"),
+                    status
+                )
+            }
+        }
+        println!(
+            "{}: {} replaced {} (transaction {})",
+            crate::style::success("Rotated"),
+            new_public_key,
+            old_public_key,
+            crate::style::hash(&transaction_info.transaction_outcome.id)
+        );
+        match new_secret_key {
+            Some(new_secret_key) => {
+                match crate::common::save_access_key_to_keychain(
+                    &network_dir_name,
+                    &crate::common::KeychainAccessKey {
+                        account_id: self.account_id.clone(),
+                        public_key: new_public_key.to_string(),
+                        private_key: new_secret_key.to_string(),
+                        label: self.label.clone().or_else(|| old_access_key.label.clone()),
+                        in_os_keyring: false,
+                        encrypted: false,
+                    },
+                ) {
+                    Ok(file_path) => println!("The new key is saved to {:?}", file_path),
+                    Err(err) => {
+                        // The old key no longer works, so the new one must not get lost
+                        eprintln!("{}", crate::style::warning(err));
+                        return println!(
+                            "SECRET KEY of {}: {}",
+                            new_public_key,
+                            crate::common::display_secret(&new_secret_key.to_string(), true)
+                        );
+                    }
+                }
+            }
+            None => eprintln!("The new key {} is not in the keychain", new_public_key),
+        }
+        if let Err(err) =
+            crate::common::remove_access_key_from_keychain(&network_dir_name, &old_access_key)
+        {
+            eprintln!("{}", crate::style::warning(err));
+        }
+        crate::common::append_to_audit_log(serde_json::json!({
+            "event": "key_rotated",
+            "account_id": &self.account_id,
+            "old_public_key": old_public_key.to_string(),
+            "new_public_key": new_public_key.to_string(),
+            "transaction_hash": transaction_info.transaction_outcome.id.to_string(),
+        }));
+    }
+}
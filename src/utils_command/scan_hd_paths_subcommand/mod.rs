@@ -0,0 +1,158 @@
+use dialoguer::{Input, Password};
+use structopt::StructOpt;
+use strum::VariantNames;
+
+/// Derive the keys of a seed phrase at `m/44'/397'/0'/0'/N'` (the paths Ledger
+/// uses) and find the accounts that still have them, to recover a forgotten
+/// HD path index
+#[derive(Debug)]
+pub struct ScanHdPaths {
+    pub seed_phrase: String,
+    pub passphrase: String,
+    pub count: u32,
+    pub connection_config: crate::common::ConnectionConfig,
+    pub format: crate::common::OutputFormat,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliScanHdPaths {
+    #[structopt(long)]
+    seed_phrase: Option<String>,
+    /// The BIP39 passphrase of the seed phrase
+    #[structopt(long)]
+    passphrase: Option<String>,
+    /// How many path indexes to scan, starting from 0
+    #[structopt(long)]
+    count: Option<u32>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+    #[structopt(long, default_value = "plaintext", possible_values = crate::common::OutputFormat::VARIANTS)]
+    format: crate::common::OutputFormat,
+}
+
+impl From<CliScanHdPaths> for ScanHdPaths {
+    fn from(item: CliScanHdPaths) -> Self {
+        let seed_phrase: String = match item.seed_phrase {
+            Some(cli_seed_phrase) => cli_seed_phrase,
+            None => ScanHdPaths::input_seed_phrase(),
+        };
+        let passphrase: String = match item.passphrase {
+            Some(cli_passphrase) => cli_passphrase,
+            None => crate::public_key_selector::input_seed_phrase_passphrase(),
+        };
+        let count: u32 = match item.count {
+            Some(cli_count) => cli_count,
+            None => ScanHdPaths::input_count(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        ScanHdPaths {
+            seed_phrase,
+            passphrase,
+            count,
+            connection_config,
+            format: item.format,
+        }
+    }
+}
+
+/// The HD path, its public key and the accounts that have the key attached
+type ScanResult = (String, String, Vec<String>);
+
+impl ScanHdPaths {
+    pub async fn process(self) {
+        let rpc_url = self.connection_config.rpc_url();
+        // Without an indexer only the implicit accounts can be found
+        let indexer = match crate::indexer::indexer_for(&self.connection_config) {
+            Ok(indexer) => Some(indexer),
+            Err(err) => {
+                eprintln!("{}, only the implicit accounts are checked", err);
+                None
+            }
+        };
+        let mut results: Vec<ScanResult> = vec![];
+        for index in 0..self.count {
+            let seed_phrase_hd_path = format!("m/44'/397'/0'/0'/{}'", index);
+            let public_key = match crate::public_key_selector::public_key_from_seed_phrase(
+                &self.seed_phrase,
+                &self.passphrase,
+                &seed_phrase_hd_path,
+            ) {
+                Ok(public_key) => public_key,
+                Err(err) => return eprintln!("{}", err),
+            };
+            eprintln!("Scanning {} ({})", seed_phrase_hd_path, public_key);
+            let mut candidates = match &indexer {
+                Some(indexer) => match indexer.accounts_by_public_key(&public_key).await {
+                    Ok(account_ids) => account_ids,
+                    Err(err) => {
+                        eprintln!("Error fetching the accounts:  {}", err);
+                        vec![]
+                    }
+                },
+                None => vec![],
+            };
+            if let Ok(near_crypto::PublicKey::ED25519(ed25519_public_key)) =
+                crate::common::parse_public_key(&public_key)
+            {
+                candidates.push(hex::encode(ed25519_public_key.0));
+            }
+            candidates.sort();
+            candidates.dedup();
+            let mut account_ids = vec![];
+            for account_id in candidates {
+                // The indexer may lag behind, and the implicit account may not exist
+                match crate::common::access_key_exists(&rpc_url, &account_id, &public_key).await {
+                    Ok(true) => account_ids.push(account_id),
+                    Ok(false) => {}
+                    Err(err) if err.contains("UNKNOWN_ACCOUNT") => {}
+                    Err(err) => eprintln!("Could not check the keys of <{}>:  {}", account_id, err),
+                }
+            }
+            results.push((seed_phrase_hd_path, public_key, account_ids));
+        }
+        match self.format {
+            crate::common::OutputFormat::Plaintext => {
+                let mut found = false;
+                for (seed_phrase_hd_path, public_key, account_ids) in &results {
+                    if account_ids.is_empty() {
+                        continue;
+                    }
+                    found = true;
+                    println!("{} {}", seed_phrase_hd_path, public_key);
+                    for account_id in account_ids {
+                        println!("  {}", crate::style::account_id(account_id));
+                    }
+                }
+                if !found {
+                    eprintln!("None of the first {} HD paths is used on chain", self.count);
+                }
+            }
+            crate::common::OutputFormat::Json => {
+                let results = results
+                    .iter()
+                    .map(|(seed_phrase_hd_path, public_key, account_ids)| {
+                        serde_json::json!({
+                            "hd_path": seed_phrase_hd_path,
+                            "public_key": public_key,
+                            "account_ids": account_ids,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                println!("{}", serde_json::to_string_pretty(&results).unwrap());
+            }
+        }
+    }
+    pub fn input_seed_phrase() -> String {
+        Password::new()
+            .with_prompt("Enter the seed phrase")
+            .interact()
+            .unwrap()
+    }
+    pub fn input_count() -> u32 {
+        Input::new()
+            .with_prompt("How many HD path indexes do you want to scan?")
+            .default(10)
+            .interact_text()
+            .unwrap()
+    }
+}
@@ -0,0 +1,109 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+/// Broadcast a transaction that was signed elsewhere, e.g. on an air-gapped
+/// machine that shows it as QR codes
+#[derive(Debug)]
+pub struct SendSignedTransaction {
+    pub signed_transaction: String,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliSendSignedTransaction {
+    /// Base64-encoded signed transaction
+    #[structopt(long)]
+    signed_transaction: Option<String>,
+    /// Read the signed transaction from this file
+    #[structopt(long, conflicts_with = "signed-transaction")]
+    file: Option<std::path::PathBuf>,
+    /// Enter the text of the scanned QR codes
+    #[structopt(long, conflicts_with_all = &["signed-transaction", "file"])]
+    qr: bool,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliSendSignedTransaction> for SendSignedTransaction {
+    fn from(item: CliSendSignedTransaction) -> Self {
+        let signed_transaction = match (item.signed_transaction, item.file) {
+            (Some(cli_signed_transaction), _) => cli_signed_transaction,
+            (None, Some(cli_file)) => SendSignedTransaction::read_signed_transaction(&cli_file),
+            (None, None) if item.qr => crate::qr::input_qr_chunks(None),
+            (None, None) => SendSignedTransaction::input_signed_transaction(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        SendSignedTransaction {
+            signed_transaction,
+            connection_config,
+        }
+    }
+}
+
+impl SendSignedTransaction {
+    pub async fn process(self) {
+        let signed_transaction = match crate::common::from_base64_borsh::<
+            near_primitives::transaction::SignedTransaction,
+        >(&self.signed_transaction)
+        {
+            Ok(signed_transaction) => signed_transaction,
+            Err(err) => return eprintln!("Error decoding the signed transaction:  {}", err),
+        };
+        eprintln!(
+            "Sending {} (nonce {}) from <{}> to <{}>",
+            crate::style::hash(signed_transaction.get_hash()),
+            signed_transaction.transaction.nonce,
+            signed_transaction.transaction.signer_id,
+            signed_transaction.transaction.receiver_id
+        );
+        let server_url = self.connection_config.rpc_url();
+        match crate::common::transaction_blocks_left(&server_url, &signed_transaction.transaction)
+            .await
+        {
+            Ok(0) => {
+                return eprintln!(
+                    "The transaction has expired: its block hash {} is too old, sign it again",
+                    signed_transaction.transaction.block_hash
+                )
+            }
+            Ok(blocks_left) => eprintln!(
+                "{} left before the transaction expires",
+                crate::common::format_blocks_as_time(blocks_left)
+            ),
+            Err(err) => eprintln!("Could not check when the transaction expires:  {}", err),
+        }
+        let transaction_info = near_jsonrpc_client::new_client(server_url.as_str())
+            .broadcast_tx_commit(self.signed_transaction.trim().to_string())
+            .await
+            .map_err(|err| eprintln!("Error transaction:  {:?}", &err))
+            .unwrap();
+        crate::common::record_sent_transaction(&signed_transaction, &server_url);
+        eprintln!("{}", crate::style::success("Success"));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&transaction_info).unwrap()
+        );
+    }
+    fn read_signed_transaction(file: &std::path::Path) -> String {
+        std::fs::read_to_string(file)
+            .map_err(|err| eprintln!("Error reading the file {:?}:  {:?}", file, &err))
+            .unwrap()
+            .trim()
+            .to_string()
+    }
+    /// The base64 blob, the path of a file with it, or the first scanned QR code
+    pub fn input_signed_transaction() -> String {
+        let signed_transaction: String = Input::new()
+            .with_prompt("Enter the signed transaction (base64, a file path or a scanned QR code)")
+            .interact_text()
+            .unwrap();
+        if signed_transaction.starts_with(crate::qr::CHUNK_PREFIX) {
+            return crate::qr::input_qr_chunks(Some(signed_transaction));
+        }
+        let path = std::path::Path::new(signed_transaction.trim());
+        if path.is_file() {
+            return SendSignedTransaction::read_signed_transaction(path);
+        }
+        signed_transaction
+    }
+}
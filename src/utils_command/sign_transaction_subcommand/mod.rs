@@ -1,14 +1,30 @@
-use dialoguer::Input;
-use near_primitives::borsh::BorshDeserialize;
+use dialoguer::{Input, Password};
 use near_primitives::borsh::BorshSerialize;
 use std::str::FromStr;
 use structopt::StructOpt;
 
-#[derive(Debug)]
 pub struct SignTransaction {
     pub signer_secret_key: String,
     // transaction_format: crate::common::TransactionFormat,
-    pub unsigned_transaction: String,
+    pub unsigned_transactions: Vec<String>,
+    /// Write the signed transactions here (one per line) instead of printing them
+    pub output_file: Option<std::path::PathBuf>,
+    /// Also print the signed transactions as QR codes to scan on the online machine
+    pub qr: bool,
+}
+
+impl std::fmt::Debug for SignTransaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignTransaction")
+            .field(
+                "signer_secret_key",
+                &crate::common::redact_secret(&self.signer_secret_key),
+            )
+            .field("unsigned_transactions", &self.unsigned_transactions)
+            .field("output_file", &self.output_file)
+            .field("qr", &self.qr)
+            .finish()
+    }
 }
 
 #[derive(Debug, StructOpt)]
@@ -17,6 +33,14 @@ pub struct CliSignTransaction {
     signer_secret_key: Option<String>,
     #[structopt(long)]
     unsigned_transaction: Option<String>,
+    /// Sign every base64-encoded unsigned transaction of this file (one per line)
+    #[structopt(long, conflicts_with = "unsigned-transaction")]
+    unsigned_transactions_file: Option<std::path::PathBuf>,
+    #[structopt(long)]
+    output_file: Option<std::path::PathBuf>,
+    /// Print the signed transactions as QR codes in the terminal
+    #[structopt(long)]
+    qr: bool,
 }
 
 impl From<CliSignTransaction> for SignTransaction {
@@ -25,48 +49,140 @@ impl From<CliSignTransaction> for SignTransaction {
             Some(cli_signer_secret_key) => cli_signer_secret_key,
             None => SignTransaction::input_signer_secret_key(),
         };
-        let unsigned_transaction: String = match item.unsigned_transaction {
-            Some(cli_unsigned_transaction) => cli_unsigned_transaction,
-            None => SignTransaction::input_unsigned_transaction(),
-        };
+        let unsigned_transactions: Vec<String> =
+            match (item.unsigned_transaction, item.unsigned_transactions_file) {
+                (Some(cli_unsigned_transaction), _) => vec![cli_unsigned_transaction],
+                (None, Some(cli_unsigned_transactions_file)) => {
+                    SignTransaction::read_unsigned_transactions(&cli_unsigned_transactions_file)
+                }
+                (None, None) => vec![SignTransaction::input_unsigned_transaction()],
+            };
         SignTransaction {
             signer_secret_key,
-            unsigned_transaction,
+            unsigned_transactions,
+            output_file: item.output_file,
+            qr: item.qr,
         }
     }
 }
 
 impl SignTransaction {
     pub fn process(self) {
-        let unsigned_transaction_borsh = base64::decode(&self.unsigned_transaction).unwrap();
-        let unsigned_transaction =
-            near_primitives::transaction::Transaction::try_from_slice(&unsigned_transaction_borsh)
-                .unwrap();
         let signer_secret_key = near_crypto::SecretKey::from_str(&self.signer_secret_key).unwrap();
-        let signature = signer_secret_key.sign(&unsigned_transaction.get_hash().as_ref());
-        let signed_transaction =
-            near_primitives::transaction::SignedTransaction::new(signature, unsigned_transaction);
-        let serialize_to_base64 = near_primitives::serialize::to_base64(
-            signed_transaction
-                .try_to_vec()
-                .expect("Transaction is not expected to fail on serialization"),
-        );
-        println!(
-            "The transaction has been successfully signed:\n{:#?}",
-            signed_transaction
-        );
-        println!("Base64-encoded signed transaction: {}", serialize_to_base64);
+        let mut signed_transactions = vec![];
+        // Validate everything before writing anything, so a typo in line 42 does not
+        // leave a half-signed batch behind
+        for (index, unsigned_transaction) in self.unsigned_transactions.iter().enumerate() {
+            let unsigned_transaction = match crate::common::from_base64_borsh::<
+                near_primitives::transaction::Transaction,
+            >(unsigned_transaction)
+            {
+                Ok(unsigned_transaction) => unsigned_transaction,
+                Err(err) => {
+                    return eprintln!(
+                        "Error decoding the unsigned transaction #{}:  {}",
+                        index, err
+                    )
+                }
+            };
+            let signature = signer_secret_key.sign(&unsigned_transaction.get_hash().as_ref());
+            signed_transactions.push(near_primitives::transaction::SignedTransaction::new(
+                signature,
+                unsigned_transaction,
+            ));
+        }
+        let serialized_to_base64 = signed_transactions
+            .iter()
+            .map(|signed_transaction| {
+                near_primitives::serialize::to_base64(
+                    signed_transaction
+                        .try_to_vec()
+                        .expect("Transaction is not expected to fail on serialization"),
+                )
+            })
+            .collect::<Vec<_>>();
+        match &self.output_file {
+            Some(output_file) => {
+                let mut buf = serialized_to_base64.join("\n");
+                buf.push('\n');
+                crate::common::write_file_atomically(output_file, &buf)
+                    .map_err(|err| {
+                        eprintln!("Error writing the file {:?}:  {:?}", output_file, err)
+                    })
+                    .unwrap();
+                for signed_transaction in &signed_transactions {
+                    eprintln!(
+                        "Signed {} (nonce {}) from <{}> to <{}>",
+                        crate::style::hash(signed_transaction.get_hash()),
+                        signed_transaction.transaction.nonce,
+                        signed_transaction.transaction.signer_id,
+                        signed_transaction.transaction.receiver_id
+                    );
+                }
+                eprintln!(
+                    "{} {} signed transactions to {:?}",
+                    crate::style::success("Wrote"),
+                    signed_transactions.len(),
+                    output_file
+                );
+            }
+            None => {
+                for (signed_transaction, serialize_to_base64) in
+                    signed_transactions.iter().zip(&serialized_to_base64)
+                {
+                    eprintln!(
+                        "The transaction has been successfully signed:\n{:#?}",
+                        signed_transaction
+                    );
+                    println!("{}", serialize_to_base64);
+                }
+            }
+        }
+        let mut block_hashes = std::collections::HashSet::new();
+        for signed_transaction in &signed_transactions {
+            if block_hashes.insert(signed_transaction.transaction.block_hash) {
+                crate::common::print_offline_transaction_deadline(&signed_transaction.transaction);
+            }
+        }
+        if self.qr {
+            for serialize_to_base64 in &serialized_to_base64 {
+                crate::qr::print_qr_codes(serialize_to_base64);
+            }
+        }
+    }
+    fn read_unsigned_transactions(file: &std::path::Path) -> Vec<String> {
+        std::fs::read_to_string(file)
+            .map_err(|err| eprintln!("Error reading the file {:?}:  {:?}", file, &err))
+            .unwrap()
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect()
     }
     pub fn input_signer_secret_key() -> String {
-        Input::new()
+        Password::new()
             .with_prompt("Enter the secret key")
-            .interact_text()
+            .interact()
             .unwrap()
     }
+    /// Either the base64 blob itself or the path of a file with it, as pasting
+    /// long blobs into a terminal is error-prone
     pub fn input_unsigned_transaction() -> String {
-        Input::new()
-            .with_prompt("Enter an unsigned transaction")
+        let unsigned_transaction: String = Input::new()
+            .with_prompt("Enter an unsigned transaction (base64, a file path or a scanned QR code)")
             .interact_text()
-            .unwrap()
+            .unwrap();
+        if unsigned_transaction.starts_with(crate::qr::CHUNK_PREFIX) {
+            return crate::qr::input_qr_chunks(Some(unsigned_transaction));
+        }
+        let path = std::path::Path::new(unsigned_transaction.trim());
+        if path.is_file() {
+            return SignTransaction::read_unsigned_transactions(path)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+        }
+        unsigned_transaction
     }
 }
@@ -0,0 +1,180 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+/// Suggest free sub-account names of a parent account, built from a pattern
+/// with random words and numbers
+#[derive(Debug)]
+pub struct SuggestAccountName {
+    pub parent_account_id: String,
+    pub pattern: String,
+    pub wordlist: Option<std::path::PathBuf>,
+    pub count: usize,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliSuggestAccountName {
+    parent_account_id: Option<String>,
+    /// `{word}` is replaced with a random word and `{number}` with a random number
+    #[structopt(long)]
+    pattern: Option<String>,
+    /// A file with one word per line instead of the built-in words
+    #[structopt(long)]
+    wordlist: Option<std::path::PathBuf>,
+    /// How many candidates to check
+    #[structopt(long, default_value = "10")]
+    count: usize,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliSuggestAccountName> for SuggestAccountName {
+    fn from(item: CliSuggestAccountName) -> Self {
+        let parent_account_id: String = match item.parent_account_id {
+            Some(cli_parent_account_id) => cli_parent_account_id,
+            None => SuggestAccountName::input_parent_account_id(),
+        };
+        let pattern: String = match item.pattern {
+            Some(cli_pattern) => cli_pattern,
+            None => SuggestAccountName::input_pattern(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        SuggestAccountName {
+            parent_account_id,
+            pattern,
+            wordlist: item.wordlist,
+            count: item.count,
+            connection_config,
+        }
+    }
+}
+
+const DEFAULT_PATTERN: &str = "{word}-{word}";
+
+const WORDS: &[&str] = &[
+    "amber", "apple", "arrow", "aurora", "autumn", "bamboo", "beacon", "birch", "blossom",
+    "breeze", "brook", "canyon", "cedar", "cherry", "cloud", "comet", "coral", "crystal", "dawn",
+    "delta", "dune", "echo", "ember", "falcon", "fern", "field", "flint", "forest", "galaxy",
+    "garden", "glacier", "harbor", "hazel", "horizon", "island", "ivy", "jade", "lagoon", "lark",
+    "lemon", "lotus", "maple", "meadow", "meteor", "mint", "moss", "nebula", "oak", "ocean",
+    "orbit", "otter", "pebble", "pine", "planet", "prairie", "quartz", "rain", "raven", "reef",
+    "river", "sage", "shore", "sky", "spark", "spruce", "star", "stone", "summit", "sun", "tide",
+    "valley", "willow", "wind", "zephyr",
+];
+
+/// Random numbers from the OS random number generator (the same source as the
+/// generated keys)
+struct RandomNumbers {
+    buffer: Vec<u8>,
+}
+
+impl RandomNumbers {
+    fn new() -> Self {
+        RandomNumbers { buffer: vec![] }
+    }
+    fn next(&mut self, bound: usize) -> usize {
+        if self.buffer.len() < 4 {
+            self.buffer = near_primitives::hash::hash(
+                near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519)
+                    .to_string()
+                    .as_bytes(),
+            )
+            .as_ref()
+            .to_vec();
+        }
+        let bytes = self.buffer.split_off(self.buffer.len() - 4);
+        let number = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        number as usize % bound.max(1)
+    }
+}
+
+fn fill_placeholder(
+    pattern: &str,
+    placeholder: &str,
+    mut replacement: impl FnMut() -> String,
+) -> String {
+    let mut parts = pattern.split(placeholder);
+    let mut filled = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        filled.push_str(&replacement());
+        filled.push_str(part);
+    }
+    filled
+}
+
+impl SuggestAccountName {
+    pub async fn process(self) {
+        let words = match &self.wordlist {
+            Some(wordlist) => match std::fs::read_to_string(wordlist) {
+                Ok(wordlist) => wordlist
+                    .lines()
+                    .map(|word| word.trim().to_lowercase())
+                    .filter(|word| !word.is_empty())
+                    .collect::<Vec<_>>(),
+                Err(err) => return eprintln!("Failed to read the file {:?}: {:?}", wordlist, err),
+            },
+            None => WORDS.iter().map(|word| word.to_string()).collect(),
+        };
+        if words.is_empty() && self.pattern.contains("{word}") {
+            return eprintln!("The wordlist is empty");
+        }
+        let rules = self.connection_config.account_id_rules();
+        let mut random_numbers = RandomNumbers::new();
+        let mut candidates = vec![];
+        // Small patterns may not have enough distinct names
+        for _ in 0..self.count * 10 {
+            if candidates.len() == self.count {
+                break;
+            }
+            let name = fill_placeholder(&self.pattern, "{word}", || {
+                words[random_numbers.next(words.len())].clone()
+            });
+            let name =
+                fill_placeholder(&name, "{number}", || random_numbers.next(1000).to_string());
+            let account_id = format!("{}.{}", name, self.parent_account_id);
+            if let Err(err) = crate::common::validate_account_id(&account_id, &rules) {
+                return eprintln!("The pattern makes invalid account IDs: {}", err);
+            }
+            if !candidates.contains(&account_id) {
+                candidates.push(account_id);
+            }
+        }
+        let rpc_url = self.connection_config.rpc_url();
+        let availability = futures::future::join_all(
+            candidates
+                .iter()
+                .map(|account_id| crate::common::account_exists(&rpc_url, account_id)),
+        )
+        .await;
+        let mut free_count = 0;
+        for (account_id, exists) in candidates.iter().zip(availability) {
+            match exists {
+                Ok(false) => {
+                    free_count += 1;
+                    println!("{}", crate::style::account_id(account_id));
+                }
+                Ok(true) => eprintln!("<{}> is taken", account_id),
+                Err(err) => eprintln!("Could not check <{}>:  {}", account_id, err),
+            }
+        }
+        eprintln!(
+            "{} of {} names are free under <{}>",
+            free_count,
+            candidates.len(),
+            self.parent_account_id
+        );
+    }
+    pub fn input_parent_account_id() -> String {
+        Input::new()
+            .with_prompt("Under which account do you want the new account?")
+            .interact_text()
+            .unwrap()
+    }
+    pub fn input_pattern() -> String {
+        Input::new()
+            .with_prompt("Enter the name pattern ({word} and {number} are random)")
+            .default(DEFAULT_PATTERN.to_string())
+            .interact_text()
+            .unwrap()
+    }
+}
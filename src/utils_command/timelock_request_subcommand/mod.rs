@@ -0,0 +1,224 @@
+use dialoguer::Input;
+use std::str::FromStr;
+use structopt::StructOpt;
+
+pub const MULTISIG_GAS: near_primitives::types::Gas = 100_000_000_000_000;
+
+/// Add a request to a multisig contract under the "not before" convention: the
+/// members agree not to confirm it before the recorded time, and
+/// `utils execute-when-due` waits for that time before confirming
+#[derive(Debug)]
+pub struct TimelockRequest {
+    pub multisig_account_id: String,
+    pub receiver_id: String,
+    pub amount: crate::amount::AmountExpression,
+    pub method_name: Option<String>,
+    pub args: Option<String>,
+    pub delay: std::time::Duration,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliTimelockRequest {
+    multisig_account_id: Option<String>,
+    #[structopt(long)]
+    receiver_id: Option<String>,
+    /// Transferred (or attached to the function call) when the request is confirmed
+    #[structopt(long)]
+    amount: Option<crate::amount::AmountExpression>,
+    /// Request a function call instead of a transfer
+    #[structopt(long)]
+    method_name: Option<String>,
+    #[structopt(long)]
+    args: Option<String>,
+    /// How long the request stays locked, e.g. `90s`, `30m`, `24h`, `7d`
+    #[structopt(long, parse(try_from_str = crate::common::parse_duration))]
+    delay: Option<std::time::Duration>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliTimelockRequest> for TimelockRequest {
+    fn from(item: CliTimelockRequest) -> Self {
+        let multisig_account_id: String = match item.multisig_account_id {
+            Some(cli_multisig_account_id) => cli_multisig_account_id,
+            None => input_multisig_account_id(),
+        };
+        let receiver_id: String = match item.receiver_id {
+            Some(cli_receiver_id) => cli_receiver_id,
+            None => TimelockRequest::input_receiver_id(),
+        };
+        let amount = match item.amount {
+            Some(cli_amount) => cli_amount,
+            None => TimelockRequest::input_amount(),
+        };
+        let delay = match item.delay {
+            Some(cli_delay) => cli_delay,
+            None => TimelockRequest::input_delay(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        TimelockRequest {
+            multisig_account_id,
+            receiver_id,
+            amount,
+            method_name: item.method_name,
+            args: item.args,
+            delay,
+            connection_config,
+        }
+    }
+}
+
+pub fn unix_timestamp_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// The multisig contract only accepts calls the account makes to itself,
+/// signed with one of the members' keys
+pub async fn sign_multisig_call(
+    multisig_account_id: &str,
+    method_name: &str,
+    args: serde_json::Value,
+    rpc_url: url::Url,
+) {
+    let unsigned_transaction = near_primitives::transaction::Transaction {
+        signer_id: multisig_account_id.to_string(),
+        public_key: near_crypto::PublicKey::empty(near_crypto::KeyType::ED25519),
+        nonce: 0,
+        receiver_id: multisig_account_id.to_string(),
+        block_hash: Default::default(),
+        actions: vec![near_primitives::transaction::Action::FunctionCall(
+            near_primitives::transaction::FunctionCallAction {
+                method_name: method_name.to_string(),
+                args: args.to_string().into_bytes(),
+                gas: MULTISIG_GAS,
+                deposit: 0,
+            },
+        )],
+    };
+    crate::construct_transaction_command::sign_transaction::sign_keychain::SignKeychain {
+        signer_public_key: None,
+        save_to: None,
+    }
+    .process(unsigned_transaction, Some(rpc_url))
+    .await
+}
+
+/// The action of a multisig request: a transfer, or a function call when
+/// `method_name` is given
+pub fn multisig_request_action(
+    amount: near_primitives::types::Balance,
+    method_name: Option<&str>,
+    args: Option<&str>,
+) -> serde_json::Value {
+    match method_name {
+        Some(method_name) => serde_json::json!({
+            "type": "FunctionCall",
+            "method_name": method_name,
+            "args": base64::encode(args.unwrap_or("{}")),
+            "deposit": amount.to_string(),
+            "gas": MULTISIG_GAS.to_string(),
+        }),
+        None => serde_json::json!({ "type": "Transfer", "amount": amount.to_string() }),
+    }
+}
+
+/// The contract assigns the request ID from this counter when the request is added
+pub async fn next_request_id(rpc_url: &url::Url, multisig_account_id: &str) -> Result<u64, String> {
+    crate::common::call_view_function(
+        rpc_url,
+        multisig_account_id,
+        "get_request_nonce",
+        serde_json::json!({}),
+    )
+    .await
+    .map(|request_nonce| request_nonce.as_u64().unwrap_or_default())
+}
+
+impl TimelockRequest {
+    pub async fn process(self) {
+        let rpc_url = self.connection_config.rpc_url();
+        let amount = match self.amount.evaluate(None) {
+            Ok(amount) => amount,
+            Err(err) => return eprintln!("Error evaluating the amount {}:  {}", self.amount, err),
+        };
+        let action =
+            multisig_request_action(amount, self.method_name.as_deref(), self.args.as_deref());
+        let request_id = match next_request_id(&rpc_url, &self.multisig_account_id).await {
+            Ok(request_id) => request_id,
+            Err(err) => {
+                return eprintln!(
+                    "Error reading the request nonce of <{}>:  {}",
+                    self.multisig_account_id, err
+                )
+            }
+        };
+        let not_before = unix_timestamp_now() + self.delay.as_secs();
+        println!(
+            "Request #{} on <{}> must not be confirmed before {} (unix time, in {} seconds)",
+            request_id,
+            crate::style::account_id(&self.multisig_account_id),
+            not_before,
+            self.delay.as_secs()
+        );
+        crate::common::append_to_audit_log(serde_json::json!({
+            "event": "timelock_request",
+            "multisig_account_id": &self.multisig_account_id,
+            "request_id": request_id,
+            "not_before": not_before,
+        }));
+        sign_multisig_call(
+            &self.multisig_account_id,
+            "add_request",
+            serde_json::json!({
+                "request": { "receiver_id": &self.receiver_id, "actions": [action] },
+            }),
+            rpc_url,
+        )
+        .await;
+        eprintln!(
+            "Run `utils execute-when-due {} {}` to confirm it once it is due",
+            self.multisig_account_id, request_id
+        );
+    }
+    pub fn input_receiver_id() -> String {
+        Input::new()
+            .with_prompt("Who should receive the tokens when the request is confirmed?")
+            .interact_text()
+            .unwrap()
+    }
+    pub fn input_amount() -> crate::amount::AmountExpression {
+        loop {
+            let input: String = Input::new()
+                .with_prompt("How many NEAR Tokens should be transferred? (example: 10NEAR)")
+                .interact_text()
+                .unwrap();
+            match crate::amount::AmountExpression::from_str(&input) {
+                Ok(amount) => break amount,
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+    }
+    pub fn input_delay() -> std::time::Duration {
+        loop {
+            let input: String = Input::new()
+                .with_prompt("How long should the request stay locked? (example: 30m, 24h, 7d)")
+                .interact_text()
+                .unwrap();
+            match crate::common::parse_duration(&input) {
+                Ok(delay) => break delay,
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+    }
+}
+
+pub fn input_multisig_account_id() -> String {
+    Input::new()
+        .with_prompt("What is the account ID of the multisig contract?")
+        .interact_text()
+        .unwrap()
+}
@@ -0,0 +1,111 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+/// Build a web-wallet link that asks the wallet to sign and send prepared
+/// transactions, so the final approval happens in the browser
+#[derive(Debug)]
+pub struct WalletUrl {
+    pub unsigned_transactions: Vec<String>,
+    pub wallet_url: url::Url,
+    pub callback_url: Option<url::Url>,
+    pub open: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliWalletUrl {
+    /// Base64-encoded unsigned transactions
+    unsigned_transactions: Vec<String>,
+    /// testnet, mainnet or betanet
+    #[structopt(long)]
+    network: Option<String>,
+    /// Use this web wallet instead of the one of the network
+    #[structopt(long)]
+    wallet_url: Option<url::Url>,
+    /// The wallet redirects here with the transaction hashes once they are sent
+    #[structopt(long)]
+    callback_url: Option<url::Url>,
+    /// Open the link in the default browser
+    #[structopt(long)]
+    open: bool,
+}
+
+impl From<CliWalletUrl> for WalletUrl {
+    fn from(item: CliWalletUrl) -> Self {
+        let unsigned_transactions: Vec<String> = if item.unsigned_transactions.is_empty() {
+            vec![WalletUrl::input_unsigned_transaction()]
+        } else {
+            item.unsigned_transactions
+        };
+        let wallet_url: url::Url = match (item.wallet_url, item.network) {
+            (Some(cli_wallet_url), _) => cli_wallet_url,
+            (None, Some(network)) => {
+                match crate::common::ConnectionConfig::from_network_name(&network)
+                    .and_then(|connection_config| connection_config.wallet_url())
+                {
+                    Some(wallet_url) => wallet_url,
+                    None => {
+                        eprintln!("Unknown network <{}>", network);
+                        WalletUrl::input_wallet_url()
+                    }
+                }
+            }
+            (None, None) => WalletUrl::input_wallet_url(),
+        };
+        WalletUrl {
+            unsigned_transactions,
+            wallet_url,
+            callback_url: item.callback_url,
+            open: item.open,
+        }
+    }
+}
+
+impl WalletUrl {
+    pub fn process(self) {
+        for unsigned_transaction in &self.unsigned_transactions {
+            let transaction = crate::common::from_base64_borsh::<
+                near_primitives::transaction::Transaction,
+            >(unsigned_transaction);
+            match transaction {
+                Ok(transaction) => println!(
+                    "{} -> {} ({} actions)",
+                    crate::style::account_id(&transaction.signer_id),
+                    crate::style::account_id(&transaction.receiver_id),
+                    transaction.actions.len()
+                ),
+                Err(err) => {
+                    return eprintln!(
+                        "Error decoding the unsigned transaction {}:  {}",
+                        unsigned_transaction, err
+                    )
+                }
+            }
+        }
+        let sign_url = crate::common::wallet_sign_url(
+            &self.wallet_url,
+            &self.unsigned_transactions,
+            self.callback_url.as_ref(),
+        );
+        println!("{}", sign_url);
+        if self.open {
+            if let Err(err) = crate::common::open_in_browser(&sign_url) {
+                eprintln!("Error opening the browser:  {}", err);
+            }
+        }
+    }
+    pub fn input_unsigned_transaction() -> String {
+        Input::new()
+            .with_prompt("Enter the base64-encoded unsigned transaction")
+            .interact_text()
+            .unwrap()
+    }
+    pub fn input_wallet_url() -> url::Url {
+        match crate::common::ConnectionConfig::input_connection_config().wallet_url() {
+            Some(wallet_url) => wallet_url,
+            None => Input::new()
+                .with_prompt("What is the web wallet URL?")
+                .interact_text()
+                .unwrap(),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+use strum::VariantNames;
+
+/// List the access keys of an account with their nonces and permissions
+#[derive(Debug)]
+pub struct AccessKeys {
+    pub account_id: String,
+    pub block_id: Option<crate::common::BlockIdArg>,
+    pub connection_config: crate::common::ConnectionConfig,
+    pub format: crate::common::OutputFormat,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliAccessKeys {
+    account_id: Option<String>,
+    /// View the keys as of this block height or hash instead of the latest block
+    #[structopt(long)]
+    block_id: Option<crate::common::BlockIdArg>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+    #[structopt(long, default_value = "plaintext", possible_values = crate::common::OutputFormat::VARIANTS)]
+    format: crate::common::OutputFormat,
+}
+
+impl From<CliAccessKeys> for AccessKeys {
+    fn from(item: CliAccessKeys) -> Self {
+        let account_id: String = match item.account_id {
+            Some(cli_account_id) => cli_account_id,
+            None => AccessKeys::input_account_id(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        AccessKeys {
+            account_id,
+            block_id: item.block_id,
+            connection_config,
+            format: item.format,
+        }
+    }
+}
+
+impl AccessKeys {
+    pub async fn process(self) {
+        let access_key_list_response = match crate::common::view_at_block(
+            &self.connection_config,
+            self.block_id.as_ref(),
+            |url, block_reference| {
+                near_jsonrpc_client::new_client(url.as_str()).query(
+                    near_primitives::rpc::RpcQueryRequest {
+                        block_reference,
+                        request: near_primitives::views::QueryRequest::ViewAccessKeyList {
+                            account_id: self.account_id.clone(),
+                        },
+                    },
+                )
+            },
+        )
+        .await
+        {
+            Ok(access_key_list_response) => access_key_list_response,
+            Err(err) => return eprintln!("Error fetching the access keys:  {:?}", err),
+        };
+        let access_key_list = match access_key_list_response.kind {
+            near_primitives::views::QueryResponseKind::AccessKeyList(access_key_list) => {
+                access_key_list
+            }
+            _ => unreachable!("Error"),
+        };
+        match self.format {
+            crate::common::OutputFormat::Plaintext => {
+                if access_key_list.keys.is_empty() {
+                    return eprintln!("<{}> has no access keys", self.account_id);
+                }
+                println!("{:<54} {:>16}  {}", "Public key", "Nonce", "Permission");
+                for access_key_info in &access_key_list.keys {
+                    let permission = match &access_key_info.access_key.permission {
+                        near_primitives::views::AccessKeyPermissionView::FullAccess => {
+                            "full access".to_string()
+                        }
+                        near_primitives::views::AccessKeyPermissionView::FunctionCall {
+                            allowance,
+                            receiver_id,
+                            method_names,
+                        } => format!(
+                            "function call to <{}>, allowance {}, methods {}",
+                            receiver_id,
+                            allowance
+                                .map(crate::common::format_near_amount)
+                                .unwrap_or_else(|| "unlimited".to_string()),
+                            if method_names.is_empty() {
+                                "any".to_string()
+                            } else {
+                                method_names.join(", ")
+                            }
+                        ),
+                    };
+                    println!(
+                        "{:<54} {:>16}  {}",
+                        access_key_info.public_key.to_string(),
+                        access_key_info.access_key.nonce,
+                        permission
+                    );
+                }
+            }
+            crate::common::OutputFormat::Json => {
+                let keys = access_key_list
+                    .keys
+                    .iter()
+                    .map(|access_key_info| {
+                        let permission = match &access_key_info.access_key.permission {
+                            near_primitives::views::AccessKeyPermissionView::FullAccess => {
+                                serde_json::json!("full_access")
+                            }
+                            near_primitives::views::AccessKeyPermissionView::FunctionCall {
+                                allowance,
+                                receiver_id,
+                                method_names,
+                            } => serde_json::json!({
+                                "receiver_id": receiver_id,
+                                "allowance": allowance.map(|allowance| allowance.to_string()),
+                                "method_names": method_names,
+                            }),
+                        };
+                        serde_json::json!({
+                            "public_key": access_key_info.public_key.to_string(),
+                            "nonce": access_key_info.access_key.nonce,
+                            "permission": permission,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                println!("{}", serde_json::to_string_pretty(&keys).unwrap());
+            }
+        }
+    }
+    pub fn input_account_id() -> String {
+        Input::new()
+            .with_prompt("Which account's access keys do you want to view?")
+            .interact_text()
+            .unwrap()
+    }
+}
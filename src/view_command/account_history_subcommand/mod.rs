@@ -0,0 +1,62 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+/// List the latest transactions of an account from the configured indexer
+#[derive(Debug)]
+pub struct AccountHistory {
+    pub account_id: String,
+    pub limit: usize,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliAccountHistory {
+    account_id: Option<String>,
+    #[structopt(long, default_value = "20")]
+    limit: usize,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliAccountHistory> for AccountHistory {
+    fn from(item: CliAccountHistory) -> Self {
+        let account_id: String = match item.account_id {
+            Some(cli_account_id) => cli_account_id,
+            None => AccountHistory::input_account_id(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        AccountHistory {
+            account_id,
+            limit: item.limit,
+            connection_config,
+        }
+    }
+}
+
+impl AccountHistory {
+    pub async fn process(self) {
+        let indexer = match crate::indexer::indexer_for(&self.connection_config) {
+            Ok(indexer) => indexer,
+            Err(err) => return eprintln!("{}", err),
+        };
+        match indexer.account_activity(&self.account_id, self.limit).await {
+            Ok(account_activity) => {
+                for activity in account_activity {
+                    println!(
+                        "{} {} -> {}",
+                        crate::style::hash(&activity.transaction_hash),
+                        crate::style::account_id(&activity.signer_id),
+                        crate::style::account_id(&activity.receiver_id)
+                    );
+                }
+            }
+            Err(err) => eprintln!("Error fetching the account history:  {}", err),
+        }
+    }
+    pub fn input_account_id() -> String {
+        Input::new()
+            .with_prompt("What is the account ID?")
+            .interact_text()
+            .unwrap()
+    }
+}
@@ -0,0 +1,165 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+/// Show the balance, the staked balance and the storage of an account and, with
+/// `--watch`, keep printing their changes until interrupted
+#[derive(Debug)]
+pub struct AccountSummary {
+    pub account_id: String,
+    pub watch: bool,
+    pub interval: u64,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliAccountSummary {
+    account_id: Option<String>,
+    /// Poll the account and print the changes until interrupted
+    #[structopt(long)]
+    watch: bool,
+    /// Seconds between the polls of `--watch`
+    #[structopt(long, default_value = "5")]
+    interval: u64,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliAccountSummary> for AccountSummary {
+    fn from(item: CliAccountSummary) -> Self {
+        let account_id: String = match item.account_id {
+            Some(cli_account_id) => cli_account_id,
+            None => AccountSummary::input_account_id(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        AccountSummary {
+            account_id,
+            watch: item.watch,
+            interval: item.interval,
+            connection_config,
+        }
+    }
+}
+
+#[derive(PartialEq)]
+struct AccountState {
+    amount: u128,
+    locked: u128,
+    storage_usage: u64,
+    code_hash: String,
+}
+
+impl AccountSummary {
+    pub async fn process(self) {
+        let rpc_url = self.connection_config.rpc_url();
+        let mut state = match fetch_account_state(&rpc_url, &self.account_id).await {
+            Ok(state) => state,
+            Err(err) => return eprintln!("Error fetching <{}>:  {}", self.account_id, err),
+        };
+        println!(
+            "Balance: {}",
+            crate::common::format_near_amount(state.amount)
+        );
+        println!(
+            "Staked: {}",
+            crate::common::format_near_amount(state.locked)
+        );
+        println!("Storage: {} bytes", state.storage_usage);
+        println!("Code hash: {}", crate::style::hash(&state.code_hash));
+        if !self.watch {
+            return;
+        }
+        eprintln!(
+            "Watching <{}> every {}s, press Ctrl+C to stop",
+            self.account_id, self.interval
+        );
+        loop {
+            actix_rt::time::delay_for(std::time::Duration::from_secs(self.interval)).await;
+            let new_state = match fetch_account_state(&rpc_url, &self.account_id).await {
+                Ok(new_state) => new_state,
+                Err(err) => {
+                    eprintln!("Error fetching <{}>:  {}", self.account_id, err);
+                    continue;
+                }
+            };
+            if new_state == state {
+                continue;
+            }
+            if new_state.amount != state.amount {
+                println!(
+                    "Balance: {} ({})",
+                    crate::common::format_near_amount(new_state.amount),
+                    format_delta(state.amount, new_state.amount)
+                );
+            }
+            if new_state.locked != state.locked {
+                println!(
+                    "Staked: {} ({})",
+                    crate::common::format_near_amount(new_state.locked),
+                    format_delta(state.locked, new_state.locked)
+                );
+            }
+            if new_state.storage_usage != state.storage_usage {
+                println!(
+                    "Storage: {} bytes ({:+} bytes)",
+                    new_state.storage_usage,
+                    i128::from(new_state.storage_usage) - i128::from(state.storage_usage)
+                );
+            }
+            if new_state.code_hash != state.code_hash {
+                println!(
+                    "Code hash: {} (new code deployed)",
+                    crate::style::hash(&new_state.code_hash)
+                );
+            }
+            state = new_state;
+        }
+    }
+    pub fn input_account_id() -> String {
+        Input::new()
+            .with_prompt("Which account do you want to view?")
+            .interact_text()
+            .unwrap()
+    }
+}
+
+async fn fetch_account_state(rpc_url: &url::Url, account_id: &str) -> Result<AccountState, String> {
+    let account_view = crate::common::call_json_rpc(
+        rpc_url,
+        "query",
+        serde_json::json!({
+            "request_type": "view_account",
+            "finality": crate::common::view_finality_name(),
+            "account_id": account_id,
+        }),
+    )
+    .await?;
+    let parse = |field: &str| {
+        account_view[field]
+            .as_str()
+            .and_then(|amount| amount.parse::<u128>().ok())
+            .unwrap_or_default()
+    };
+    Ok(AccountState {
+        amount: parse("amount"),
+        locked: parse("locked"),
+        storage_usage: account_view["storage_usage"].as_u64().unwrap_or_default(),
+        code_hash: account_view["code_hash"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+    })
+}
+
+fn format_delta(old_amount: u128, new_amount: u128) -> String {
+    if new_amount >= old_amount {
+        format!(
+            "+{}",
+            crate::common::format_near_amount(new_amount - old_amount)
+        )
+    } else {
+        format!(
+            "-{}",
+            crate::common::format_near_amount(old_amount - new_amount)
+        )
+    }
+}
@@ -0,0 +1,52 @@
+use structopt::StructOpt;
+
+/// Find the accounts that have a public key attached, from the configured indexer
+#[derive(Debug)]
+pub struct AccountsByKey {
+    pub public_key: String,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliAccountsByKey {
+    public_key: Option<String>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliAccountsByKey> for AccountsByKey {
+    fn from(item: CliAccountsByKey) -> Self {
+        let public_key: String = match item.public_key {
+            Some(cli_public_key) => cli_public_key,
+            None => AccountsByKey::input_public_key(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        AccountsByKey {
+            public_key,
+            connection_config,
+        }
+    }
+}
+
+impl AccountsByKey {
+    pub async fn process(self) {
+        let indexer = match crate::indexer::indexer_for(&self.connection_config) {
+            Ok(indexer) => indexer,
+            Err(err) => return eprintln!("{}", err),
+        };
+        match indexer.accounts_by_public_key(&self.public_key).await {
+            Ok(account_ids) if account_ids.is_empty() => {
+                eprintln!("No accounts have the key {}", self.public_key)
+            }
+            Ok(account_ids) => {
+                for account_id in account_ids {
+                    println!("{}", crate::style::account_id(account_id));
+                }
+            }
+            Err(err) => eprintln!("Error fetching the accounts:  {}", err),
+        }
+    }
+    pub fn input_public_key() -> String {
+        crate::public_key_selector::input_public_key("Enter the public key")
+    }
+}
@@ -0,0 +1,154 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+/// The allowance near-api-js asks for when an app requests a login key from
+/// the web wallet
+const WALLET_LOGIN_KEY_ALLOWANCE: near_primitives::types::Balance = 250_000_000_000_000_000_000_000;
+
+/// Classify every access key of an account by where it most likely comes from,
+/// so that the keys of unknown origin can be reviewed
+#[derive(Debug)]
+pub struct AuditKeys {
+    pub account_id: String,
+    pub ledger_public_keys: Vec<String>,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliAuditKeys {
+    account_id: Option<String>,
+    /// A public key read from a Ledger device (repeat the flag for several keys)
+    #[structopt(long = "ledger-public-key")]
+    ledger_public_keys: Vec<String>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliAuditKeys> for AuditKeys {
+    fn from(item: CliAuditKeys) -> Self {
+        let account_id: String = match item.account_id {
+            Some(cli_account_id) => cli_account_id,
+            None => AuditKeys::input_account_id(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        AuditKeys {
+            account_id,
+            ledger_public_keys: item.ledger_public_keys,
+            connection_config,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum KeyProvenance {
+    LocalKeychain(Option<String>),
+    Ledger,
+    WalletLoginKey(String),
+    Unknown,
+}
+
+impl std::fmt::Display for KeyProvenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyProvenance::LocalKeychain(Some(label)) => {
+                write!(f, "local keychain ({})", label)
+            }
+            KeyProvenance::LocalKeychain(None) => write!(f, "local keychain"),
+            KeyProvenance::Ledger => write!(f, "Ledger"),
+            KeyProvenance::WalletLoginKey(receiver_id) => {
+                write!(f, "web wallet login key for <{}>", receiver_id)
+            }
+            KeyProvenance::Unknown => write!(f, "unknown origin"),
+        }
+    }
+}
+
+impl AuditKeys {
+    pub async fn process(self) {
+        let access_key_list_response =
+            near_jsonrpc_client::new_client(self.connection_config.rpc_url().as_str())
+                .query(near_primitives::rpc::RpcQueryRequest {
+                    block_reference: crate::common::view_finality().into(),
+                    request: near_primitives::views::QueryRequest::ViewAccessKeyList {
+                        account_id: self.account_id.clone(),
+                    },
+                })
+                .await
+                .map_err(|err| eprintln!("Error access_key_list_response:   {:?}", &err))
+                .unwrap();
+        let access_key_list = match access_key_list_response.kind {
+            near_primitives::views::QueryResponseKind::AccessKeyList(access_key_list) => {
+                access_key_list
+            }
+            _ => unreachable!("Error"),
+        };
+        let keychain_access_keys = crate::common::read_access_keys_from_keychain(
+            &self.connection_config.dir_name(),
+            &self.account_id,
+        );
+        let mut unknown_keys_count = 0;
+        for access_key_info in access_key_list.keys {
+            let public_key = access_key_info.public_key.to_string();
+            let permission = match &access_key_info.access_key.permission {
+                near_primitives::views::AccessKeyPermissionView::FullAccess => "full access",
+                near_primitives::views::AccessKeyPermissionView::FunctionCall { .. } => {
+                    "function call"
+                }
+            };
+            let provenance = match keychain_access_keys
+                .iter()
+                .find(|keychain_access_key| keychain_access_key.public_key == public_key)
+            {
+                Some(keychain_access_key) => {
+                    KeyProvenance::LocalKeychain(keychain_access_key.label.clone())
+                }
+                None if self.ledger_public_keys.contains(&public_key) => KeyProvenance::Ledger,
+                None => match access_key_info.access_key.permission {
+                    near_primitives::views::AccessKeyPermissionView::FunctionCall {
+                        allowance: Some(WALLET_LOGIN_KEY_ALLOWANCE),
+                        receiver_id,
+                        method_names,
+                    } if method_names.is_empty() => KeyProvenance::WalletLoginKey(receiver_id),
+                    _ => KeyProvenance::Unknown,
+                },
+            };
+            match provenance {
+                KeyProvenance::Unknown => {
+                    unknown_keys_count += 1;
+                    println!(
+                        "{} ({}): {}",
+                        crate::style::hash(&public_key),
+                        permission,
+                        crate::style::warning(format!("{}, review it", provenance))
+                    )
+                }
+                _ => println!(
+                    "{} ({}): {}",
+                    crate::style::hash(&public_key),
+                    permission,
+                    provenance
+                ),
+            }
+        }
+        if unknown_keys_count == 0 {
+            println!(
+                "{}",
+                crate::style::success("All the keys have a known origin")
+            );
+        } else {
+            eprintln!(
+                "{}",
+                crate::style::warning(format!(
+                    "{} key(s) of unknown origin on <{}>",
+                    unknown_keys_count, self.account_id
+                ))
+            );
+        }
+    }
+    pub fn input_account_id() -> String {
+        Input::new()
+            .with_prompt("Which account do you want to audit?")
+            .interact_text()
+            .unwrap()
+    }
+}
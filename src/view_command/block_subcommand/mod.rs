@@ -0,0 +1,150 @@
+use structopt::StructOpt;
+use strum::VariantNames;
+
+/// Show a block header (e.g. to get a recent `block_hash` for an offline
+/// transaction) and, optionally, its chunks
+#[derive(Debug)]
+pub struct Block {
+    pub block_id: Option<crate::common::BlockIdArg>,
+    pub chunks: bool,
+    pub connection_config: crate::common::ConnectionConfig,
+    pub format: crate::common::OutputFormat,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliBlock {
+    /// The block height or hash, the latest block (with the view finality) if unset
+    block_id: Option<crate::common::BlockIdArg>,
+    /// Also show the chunks of the block
+    #[structopt(long)]
+    chunks: bool,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+    #[structopt(long, default_value = "plaintext", possible_values = crate::common::OutputFormat::VARIANTS)]
+    format: crate::common::OutputFormat,
+}
+
+impl From<CliBlock> for Block {
+    fn from(item: CliBlock) -> Self {
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        Block {
+            block_id: item.block_id,
+            chunks: item.chunks,
+            connection_config,
+            format: item.format,
+        }
+    }
+}
+
+impl Block {
+    pub async fn process(self) {
+        let rpc_url = self.connection_config.rpc_url();
+        let block = match crate::common::view_at_block(
+            &self.connection_config,
+            self.block_id.as_ref(),
+            |url, block_reference| async move {
+                let params = serde_json::to_value(block_reference).unwrap();
+                crate::common::call_json_rpc(&url, "block", params).await
+            },
+        )
+        .await
+        {
+            Ok(block) => block,
+            Err(err) => return eprintln!("Error fetching the block:  {}", err),
+        };
+        let mut chunks = vec![];
+        if self.chunks {
+            for chunk_header in block["chunks"].as_array().into_iter().flatten() {
+                let chunk = crate::common::call_json_rpc(
+                    &rpc_url,
+                    "chunk",
+                    serde_json::json!({ "chunk_id": chunk_header["chunk_hash"] }),
+                )
+                .await;
+                match chunk {
+                    Ok(chunk) => chunks.push(chunk),
+                    Err(err) => {
+                        return eprintln!(
+                            "Error fetching the chunk {}:  {}",
+                            chunk_header["chunk_hash"], err
+                        )
+                    }
+                }
+            }
+        }
+        let header = &block["header"];
+        match self.format {
+            crate::common::OutputFormat::Plaintext => {
+                println!("Height: {}", header["height"]);
+                println!("Hash: {}", header["hash"].as_str().unwrap_or_default());
+                println!(
+                    "Previous hash: {}",
+                    header["prev_hash"].as_str().unwrap_or_default()
+                );
+                println!(
+                    "Author: {}",
+                    crate::style::account_id(block["author"].as_str().unwrap_or_default())
+                );
+                println!(
+                    "Timestamp: {} ({})",
+                    header["timestamp_nanosec"].as_str().unwrap_or_default(),
+                    block_age(header)
+                );
+                println!(
+                    "Gas price: {} yoctoNEAR",
+                    header["gas_price"].as_str().unwrap_or_default()
+                );
+                println!("Epoch: {}", header["epoch_id"].as_str().unwrap_or_default());
+                println!(
+                    "Chunk mask: {}",
+                    header["chunk_mask"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .map(|included| if included.as_bool() == Some(true) {
+                            '1'
+                        } else {
+                            '0'
+                        })
+                        .collect::<String>()
+                );
+                for chunk in &chunks {
+                    println!(
+                        "Chunk {} of shard {}: {} transactions, {} receipts, {}/{} gas",
+                        chunk["header"]["chunk_hash"].as_str().unwrap_or_default(),
+                        chunk["header"]["shard_id"],
+                        chunk["transactions"].as_array().map_or(0, Vec::len),
+                        chunk["receipts"].as_array().map_or(0, Vec::len),
+                        chunk["header"]["gas_used"],
+                        chunk["header"]["gas_limit"]
+                    );
+                }
+            }
+            crate::common::OutputFormat::Json => {
+                let mut block = block.clone();
+                if self.chunks {
+                    block["chunks"] = serde_json::Value::Array(chunks);
+                }
+                println!("{}", serde_json::to_string_pretty(&block).unwrap());
+            }
+        }
+    }
+}
+
+fn block_age(header: &serde_json::Value) -> String {
+    let timestamp = header["timestamp_nanosec"]
+        .as_str()
+        .and_then(|timestamp| timestamp.parse::<u64>().ok())
+        .or_else(|| header["timestamp"].as_u64());
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    match timestamp {
+        Some(timestamp) => format!(
+            "{} seconds ago",
+            now.saturating_sub(timestamp / 1_000_000_000)
+        ),
+        None => "unknown".to_string(),
+    }
+}
@@ -0,0 +1,123 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+use super::contract_state_subcommand::ContractState;
+use crate::construct_transaction_command::CallFunctionAction;
+
+/// Call a view method of a contract (a read-only call that needs no
+/// transaction) and print its result; the method is asked in `process`, so it
+/// can be chosen from the ones the contract exports
+#[derive(Debug)]
+pub struct CallFunction {
+    pub contract_id: String,
+    pub method_name: Option<String>,
+    pub args: String,
+    pub block_id: Option<crate::common::BlockIdArg>,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliCallFunction {
+    contract_id: Option<String>,
+    #[structopt(long)]
+    method_name: Option<String>,
+    /// JSON arguments, e.g. '{"account_id": "alice.near"}'
+    #[structopt(long)]
+    args: Option<String>,
+    /// Read the JSON arguments from this file
+    #[structopt(long, conflicts_with = "args")]
+    args_file: Option<std::path::PathBuf>,
+    /// Call the method as of this block height or hash instead of the latest block
+    #[structopt(long)]
+    block_id: Option<crate::common::BlockIdArg>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliCallFunction> for CallFunction {
+    fn from(item: CliCallFunction) -> Self {
+        let contract_id: String = match item.contract_id {
+            Some(cli_contract_id) => cli_contract_id,
+            None => ContractState::input_contract_id(),
+        };
+        let args = match (item.args, item.args_file) {
+            (Some(cli_args), _) => cli_args,
+            (None, Some(cli_args_file)) => std::fs::read_to_string(&cli_args_file)
+                .map_err(|err| eprintln!("Error reading the file {:?}:  {:?}", cli_args_file, err))
+                .unwrap(),
+            (None, None) => CallFunction::input_args(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        CallFunction {
+            contract_id,
+            method_name: item.method_name,
+            args,
+            block_id: item.block_id,
+            connection_config,
+        }
+    }
+}
+
+impl CallFunction {
+    pub async fn process(self) {
+        let rpc_url = self.connection_config.rpc_url();
+        let args: serde_json::Value = match serde_json::from_str(&self.args) {
+            Ok(args) => args,
+            Err(err) => return eprintln!("The arguments are not valid JSON:  {}", err),
+        };
+        let method_name = match self.method_name {
+            Some(method_name) => method_name,
+            None => CallFunctionAction::input_method_name(&self.contract_id, Some(&rpc_url)).await,
+        };
+        let call_result = match crate::common::view_at_block(
+            &self.connection_config,
+            self.block_id.as_ref(),
+            |url, block_reference| {
+                let mut params = serde_json::to_value(block_reference).unwrap();
+                params["request_type"] = serde_json::json!("call_function");
+                params["account_id"] = serde_json::json!(self.contract_id);
+                params["method_name"] = serde_json::json!(method_name);
+                params["args_base64"] = serde_json::json!(base64::encode(args.to_string()));
+                async move { crate::common::call_json_rpc(&url, "query", params).await }
+            },
+        )
+        .await
+        {
+            Ok(call_result) => call_result,
+            Err(err) => return eprintln!("Error calling <{}>:  {}", method_name, err),
+        };
+        if let Some(error) = call_result.get("error") {
+            return eprintln!("The call of <{}> failed:  {}", method_name, error);
+        }
+        for log in call_result["logs"].as_array().into_iter().flatten() {
+            eprintln!("Log: {}", log.as_str().unwrap_or_default());
+        }
+        let result = call_result["result"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|byte| byte.as_u64().unwrap_or_default() as u8)
+            .collect::<Vec<u8>>();
+        match serde_json::from_slice::<serde_json::Value>(&result) {
+            Ok(result) => println!("{}", serde_json::to_string_pretty(&result).unwrap()),
+            Err(_) => match std::str::from_utf8(&result) {
+                Ok(result) => println!("{}", result),
+                Err(_) => println!("0x{}", hex::encode(&result)),
+            },
+        }
+    }
+    pub fn input_args() -> String {
+        let args: String = Input::new()
+            .with_prompt("Enter the arguments as JSON or the path of a JSON file")
+            .default("{}".to_string())
+            .interact_text()
+            .unwrap();
+        let path = std::path::Path::new(args.trim());
+        if path.is_file() {
+            return std::fs::read_to_string(path)
+                .map_err(|err| eprintln!("Error reading the file {:?}:  {:?}", path, err))
+                .unwrap();
+        }
+        args
+    }
+}
@@ -0,0 +1,201 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+/// Download the code of a contract, list its methods with `--methods` and, with
+/// `--verify`, check that it is the code of a local wasm file
+#[derive(Debug)]
+pub struct ContractCode {
+    pub contract_id: String,
+    pub block_id: Option<crate::common::BlockIdArg>,
+    pub save_to: Option<std::path::PathBuf>,
+    pub verify: Option<std::path::PathBuf>,
+    pub methods: bool,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliContractCode {
+    contract_id: Option<String>,
+    /// View the code as of this block height or hash instead of the latest block
+    #[structopt(long)]
+    block_id: Option<crate::common::BlockIdArg>,
+    /// Save the downloaded wasm to this file
+    #[structopt(long)]
+    save_to: Option<std::path::PathBuf>,
+    /// Compare the deployed code with this wasm file
+    #[structopt(long)]
+    verify: Option<std::path::PathBuf>,
+    /// List the methods the code exports
+    #[structopt(long)]
+    methods: bool,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliContractCode> for ContractCode {
+    fn from(item: CliContractCode) -> Self {
+        let contract_id: String = match item.contract_id {
+            Some(cli_contract_id) => cli_contract_id,
+            None => ContractCode::input_contract_id(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        ContractCode {
+            contract_id,
+            block_id: item.block_id,
+            save_to: item.save_to,
+            verify: item.verify,
+            methods: item.methods,
+            connection_config,
+        }
+    }
+}
+
+impl ContractCode {
+    pub async fn process(self) {
+        let contract_id = &self.contract_id;
+        let (code_hash, code) = match crate::common::view_at_block(
+            &self.connection_config,
+            self.block_id.as_ref(),
+            |url, block_reference| async move {
+                let mut params = serde_json::to_value(block_reference).unwrap();
+                params["request_type"] = serde_json::json!("view_account");
+                params["account_id"] = serde_json::json!(contract_id);
+                let account = crate::common::call_json_rpc(&url, "query", params.clone()).await?;
+                params["request_type"] = serde_json::json!("view_code");
+                let code = crate::common::call_json_rpc(&url, "query", params).await?;
+                let code = base64::decode(code["code_base64"].as_str().unwrap_or_default())
+                    .map_err(|err| format!("Invalid contract code: {}", err))?;
+                Ok::<_, String>((
+                    account["code_hash"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                    code,
+                ))
+            },
+        )
+        .await
+        {
+            Ok(code) => code,
+            Err(err) => return eprintln!("Error fetching the code of <{}>:  {}", contract_id, err),
+        };
+        let downloaded_code_hash = near_primitives::hash::hash(&code).to_string();
+        println!("Code hash: {}", crate::style::hash(&code_hash));
+        println!(
+            "Downloaded code: {} bytes, hash {}",
+            code.len(),
+            crate::style::hash(&downloaded_code_hash)
+        );
+        if downloaded_code_hash != code_hash {
+            eprintln!(
+                "{}",
+                crate::style::warning(
+                    "The downloaded code does not match the code hash of the account"
+                )
+            );
+        }
+        if self.methods {
+            let mut method_names = match crate::common::wasm_exported_functions(&code) {
+                Ok(method_names) => method_names,
+                Err(err) => return eprintln!("Error reading the exports of the code:  {}", err),
+            };
+            method_names.sort();
+            let (view_methods, change_methods): (Vec<_>, Vec<_>) = method_names
+                .iter()
+                .partition(|method_name| is_likely_view_method(method_name));
+            println!("Likely view methods:");
+            for method_name in view_methods {
+                println!("  {}", method_name);
+            }
+            println!("Likely change methods:");
+            for method_name in change_methods {
+                println!("  {}", method_name);
+            }
+        }
+        if let Some(save_to) = &self.save_to {
+            match std::fs::write(save_to, &code) {
+                Ok(()) => eprintln!(
+                    "{} the code to {:?}",
+                    crate::style::success("Saved"),
+                    save_to
+                ),
+                Err(err) => eprintln!("Error writing the file {:?}:  {:?}", save_to, err),
+            }
+        }
+        if let Some(verify) = &self.verify {
+            let local_code = match std::fs::read(verify) {
+                Ok(local_code) => local_code,
+                Err(err) => return eprintln!("Error reading the file {:?}:  {:?}", verify, err),
+            };
+            let local_code_hash = near_primitives::hash::hash(&local_code).to_string();
+            println!(
+                "{:?}: {} bytes, hash {}",
+                verify,
+                local_code.len(),
+                crate::style::hash(&local_code_hash)
+            );
+            if local_code_hash == code_hash && local_code == code {
+                println!(
+                    "{}: <{}> runs the code of {:?}",
+                    crate::style::success("Match"),
+                    contract_id,
+                    verify
+                );
+            } else {
+                println!(
+                    "{}",
+                    crate::style::warning(format!(
+                        "Mismatch: <{}> does not run the code of {:?}",
+                        contract_id, verify
+                    ))
+                );
+            }
+        }
+    }
+    pub fn input_contract_id() -> String {
+        Input::new()
+            .with_prompt("Which contract's code do you want to view?")
+            .interact_text()
+            .unwrap()
+    }
+    /// The wasm file to compare the deployed code with, if any
+    pub fn input_verify() -> Option<std::path::PathBuf> {
+        let verify: String = Input::new()
+            .with_prompt("Enter a wasm file to compare the code with (or leave empty)")
+            .allow_empty(true)
+            .interact_text()
+            .unwrap();
+        if verify.trim().is_empty() {
+            None
+        } else {
+            Some(std::path::PathBuf::from(verify.trim()))
+        }
+    }
+}
+
+/// The conventional view methods of the NEP standards (fungible and
+/// non-fungible tokens, storage management, source metadata)
+const VIEW_METHODS: &[&str] = &[
+    "ft_balance_of",
+    "ft_total_supply",
+    "ft_metadata",
+    "nft_token",
+    "nft_tokens",
+    "nft_tokens_for_owner",
+    "nft_supply_for_owner",
+    "nft_total_supply",
+    "nft_metadata",
+    "nft_is_approved",
+    "storage_balance_of",
+    "storage_balance_bounds",
+    "contract_source_metadata",
+];
+
+/// The wasm does not tell view methods from change ones, so this goes by the
+/// names; a contract may break the conventions
+fn is_likely_view_method(method_name: &str) -> bool {
+    VIEW_METHODS.contains(&method_name)
+        || ["get_", "view_", "is_", "has_"]
+            .iter()
+            .any(|prefix| method_name.starts_with(prefix))
+}
@@ -0,0 +1,145 @@
+use structopt::StructOpt;
+
+use super::contract_state_subcommand::ContractState;
+
+/// Show the NEP-330 source metadata of a contract and cross-check the deployed
+/// code with the provenance recorded by `add-action deploy-contract --record-provenance`
+#[derive(Debug)]
+pub struct ContractSourceMetadata {
+    pub contract_id: String,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliContractSourceMetadata {
+    contract_id: Option<String>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliContractSourceMetadata> for ContractSourceMetadata {
+    fn from(item: CliContractSourceMetadata) -> Self {
+        let contract_id: String = match item.contract_id {
+            Some(cli_contract_id) => cli_contract_id,
+            None => ContractState::input_contract_id(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        ContractSourceMetadata {
+            contract_id,
+            connection_config,
+        }
+    }
+}
+
+impl ContractSourceMetadata {
+    pub async fn process(self) {
+        let rpc_url = self.connection_config.rpc_url();
+        let account = match crate::common::call_json_rpc(
+            &rpc_url,
+            "query",
+            serde_json::json!({
+                "request_type": "view_account",
+                "finality": crate::common::view_finality_name(),
+                "account_id": &self.contract_id,
+            }),
+        )
+        .await
+        {
+            Ok(account) => account,
+            Err(err) => return eprintln!("Error fetching <{}>:  {}", self.contract_id, err),
+        };
+        let code_hash = account["code_hash"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        println!("Code hash: {}", crate::style::hash(&code_hash));
+
+        let source_metadata = match crate::common::call_view_function(
+            &rpc_url,
+            &self.contract_id,
+            "contract_source_metadata",
+            serde_json::json!({}),
+        )
+        .await
+        {
+            Ok(source_metadata) => {
+                println!(
+                    "Source metadata:\n{}",
+                    serde_json::to_string_pretty(&source_metadata).unwrap_or_default()
+                );
+                Some(source_metadata)
+            }
+            Err(err) => {
+                eprintln!("The contract has no source metadata (NEP-330):  {}", err);
+                None
+            }
+        };
+
+        let provenances = crate::common::read_audit_log()
+            .into_iter()
+            .filter(|entry| {
+                entry["event"] == "deploy_provenance"
+                    && entry["account_id"] == self.contract_id.as_str()
+            })
+            .collect::<Vec<_>>();
+        let provenance = match provenances
+            .iter()
+            .rev()
+            .find(|entry| entry["code_hash"] == code_hash.as_str())
+        {
+            Some(provenance) => provenance,
+            None if provenances.is_empty() => {
+                return eprintln!("No deployment provenance is recorded for this contract")
+            }
+            None => {
+                return eprintln!(
+                    "{}",
+                    crate::style::warning(format!(
+                        "The deployed code matches none of the {} recorded deployment(s), \
+                         it was deployed from elsewhere",
+                        provenances.len()
+                    ))
+                )
+            }
+        };
+        let git_commit = provenance["git_commit"].as_str().unwrap_or_default();
+        println!(
+            "{}: commit {}, built by {}, deployed at {} (unix time)",
+            crate::style::success("Provenance"),
+            if git_commit.is_empty() {
+                "unknown"
+            } else {
+                git_commit
+            },
+            provenance["builder"].as_str().unwrap_or_default(),
+            provenance["timestamp"]
+        );
+        if git_commit.ends_with("-dirty") {
+            eprintln!(
+                "{}",
+                crate::style::warning("The wasm file was built with uncommitted changes")
+            );
+        }
+        // NEP-330 does not fix where the commit goes, so look for it in any field
+        if let Some(source_metadata) = source_metadata {
+            let commit = git_commit.trim_end_matches("-dirty");
+            if commit.len() >= 7 {
+                let short_commit = &commit[..7];
+                if source_metadata.to_string().contains(short_commit) {
+                    println!(
+                        "{}",
+                        crate::style::success("The source metadata refers to the same commit")
+                    );
+                } else {
+                    eprintln!(
+                        "{}",
+                        crate::style::warning(format!(
+                            "The source metadata does not mention commit {}",
+                            short_commit
+                        ))
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use dialoguer::Input;
+use near_primitives::borsh::BorshDeserialize;
+use structopt::StructOpt;
+use strum::VariantNames;
+
+/// View the contract storage, decoding the well-known collections declared in
+/// the `storage_key_decoders` section of the config file (and their values, if
+/// a BorshSchema JSON file is provided)
+#[derive(Debug)]
+pub struct ContractState {
+    pub contract_id: String,
+    pub prefix: Option<String>,
+    pub prefix_encoding: PrefixEncoding,
+    pub borsh_schema: Option<std::path::PathBuf>,
+    pub to_file: Option<std::path::PathBuf>,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+/// How the `--prefix` of the storage keys is written
+#[derive(
+    Debug,
+    strum_macros::IntoStaticStr,
+    strum_macros::EnumString,
+    strum_macros::EnumVariantNames,
+    smart_default::SmartDefault,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum PrefixEncoding {
+    #[default]
+    Utf8,
+    Base64,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliContractState {
+    contract_id: Option<String>,
+    #[structopt(long)]
+    prefix: Option<String>,
+    #[structopt(long, default_value = "utf8", possible_values = PrefixEncoding::VARIANTS)]
+    prefix_encoding: PrefixEncoding,
+    #[structopt(long)]
+    borsh_schema: Option<std::path::PathBuf>,
+    /// Write the raw (base64) keys and values to this JSON file instead of printing them
+    #[structopt(long)]
+    to_file: Option<std::path::PathBuf>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliContractState> for ContractState {
+    fn from(item: CliContractState) -> Self {
+        let contract_id: String = match item.contract_id {
+            Some(cli_contract_id) => cli_contract_id,
+            None => ContractState::input_contract_id(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        ContractState {
+            contract_id,
+            prefix: item.prefix,
+            prefix_encoding: item.prefix_encoding,
+            borsh_schema: item.borsh_schema,
+            to_file: item.to_file,
+            connection_config,
+        }
+    }
+}
+
+impl ContractState {
+    pub async fn process(self) {
+        let mut decoders = crate::config::Config::load()
+            .storage_key_decoders
+            .remove(&self.contract_id)
+            .unwrap_or_default();
+        decoders.sort_by_key(|decoder| std::cmp::Reverse(decoder.prefix.len()));
+        let schema = self.borsh_schema.as_ref().map(|borsh_schema| {
+            crate::borsh_schema::BorshSchemaContainer::from_file(borsh_schema)
+                .map_err(|err| eprintln!("Error reading the BorshSchema file:  {}", &err))
+                .unwrap()
+        });
+        let prefix = match (&self.prefix, &self.prefix_encoding) {
+            (None, _) => vec![],
+            (Some(prefix), PrefixEncoding::Utf8) => prefix.as_bytes().to_vec(),
+            (Some(prefix), PrefixEncoding::Base64) => match base64::decode(prefix.trim()) {
+                Ok(prefix) => prefix,
+                Err(err) => return eprintln!("The prefix is not valid base64:  {}", err),
+            },
+        };
+        let state = crate::common::call_json_rpc(
+            &self.connection_config.rpc_url(),
+            "query",
+            serde_json::json!({
+                "request_type": "view_state",
+                "finality": crate::common::view_finality_name(),
+                "account_id": self.contract_id,
+                "prefix_base64": base64::encode(&prefix),
+            }),
+        )
+        .await
+        .map_err(|err| eprintln!("Error fetching the contract state:  {}", &err))
+        .unwrap();
+        let values = match state["values"].as_array() {
+            Some(values) if !values.is_empty() => values,
+            _ => return eprintln!("The contract storage has no keys with this prefix"),
+        };
+        if let Some(to_file) = &self.to_file {
+            let buf = serde_json::to_string_pretty(&state["values"]).unwrap();
+            return match crate::common::write_file_atomically(to_file, &buf) {
+                Ok(()) => eprintln!(
+                    "{} {} keys to {:?}",
+                    crate::style::success("Wrote"),
+                    values.len(),
+                    to_file
+                ),
+                Err(err) => eprintln!("Error writing the file {:?}:  {:?}", to_file, err),
+            };
+        }
+        for item in values {
+            let key = base64::decode(item["key"].as_str().unwrap_or_default()).unwrap_or_default();
+            let value =
+                base64::decode(item["value"].as_str().unwrap_or_default()).unwrap_or_default();
+            let (key, value) = decode_storage_entry(&decoders, schema.as_ref(), &key, &value);
+            println!("{}: {}", key, value);
+        }
+    }
+    pub fn input_contract_id() -> String {
+        Input::new()
+            .with_prompt("What is the contract account ID?")
+            .interact_text()
+            .unwrap()
+    }
+    pub fn input_prefix() -> Option<String> {
+        let prefix: String = Input::new()
+            .with_prompt("Enter the storage key prefix (leave empty to view all the keys)")
+            .allow_empty(true)
+            .interact_text()
+            .unwrap();
+        if prefix.is_empty() {
+            None
+        } else {
+            Some(prefix)
+        }
+    }
+}
+
+fn decode_storage_entry(
+    decoders: &[crate::config::StorageKeyDecoder],
+    schema: Option<&crate::borsh_schema::BorshSchemaContainer>,
+    key: &[u8],
+    value: &[u8],
+) -> (String, String) {
+    if key == b"STATE" {
+        let declaration = schema.map(|schema| schema.declaration.as_str());
+        return (
+            "STATE".to_string(),
+            render_value(schema, declaration, value),
+        );
+    }
+    for decoder in decoders {
+        if !key.starts_with(decoder.prefix.as_bytes()) {
+            continue;
+        }
+        let suffix = &key[decoder.prefix.len()..];
+        if let Some(decoded_key) = decode_collection_key(decoder, suffix) {
+            return (
+                decoded_key,
+                render_value(schema, decoder.value_declaration.as_deref(), value),
+            );
+        }
+    }
+    (render_bytes(key), render_bytes(value))
+}
+
+fn decode_collection_key(
+    decoder: &crate::config::StorageKeyDecoder,
+    suffix: &[u8],
+) -> Option<String> {
+    let name = &decoder.name;
+    let (tag, rest) = match suffix.split_first() {
+        Some((tag, rest)) => (*tag, rest),
+        None => (0, suffix),
+    };
+    let decoded_key = match decoder.kind {
+        crate::config::StorageCollectionKind::LookupMap => {
+            format!("{}[{}]", name, render_bytes(suffix))
+        }
+        crate::config::StorageCollectionKind::LookupSet => {
+            format!("{}{{{}}}", name, render_bytes(suffix))
+        }
+        crate::config::StorageCollectionKind::Vector => {
+            format!("{}[{}]", name, u64::try_from_slice(suffix).ok()?)
+        }
+        crate::config::StorageCollectionKind::UnorderedMap => match tag {
+            b'i' => format!("{}.key_index[{}]", name, render_bytes(rest)),
+            b'k' => format!("{}.keys[{}]", name, u64::try_from_slice(rest).ok()?),
+            b'v' => format!("{}.values[{}]", name, u64::try_from_slice(rest).ok()?),
+            _ => return None,
+        },
+        crate::config::StorageCollectionKind::UnorderedSet => match tag {
+            b'i' => format!("{}.element_index[{}]", name, render_bytes(rest)),
+            b'e' => format!("{}.elements[{}]", name, u64::try_from_slice(rest).ok()?),
+            _ => return None,
+        },
+    };
+    Some(decoded_key)
+}
+
+/// Decodes the value with the BorshSchema, if there is a declaration for it
+fn render_value(
+    schema: Option<&crate::borsh_schema::BorshSchemaContainer>,
+    declaration: Option<&str>,
+    value: &[u8],
+) -> String {
+    if let (Some(schema), Some(declaration)) = (schema, declaration) {
+        match schema.decode(Some(declaration), value) {
+            Ok(decoded_value) => return decoded_value.to_string(),
+            Err(err) => eprintln!("Error decoding the value with the BorshSchema:  {}", err),
+        }
+    }
+    render_bytes(value)
+}
+
+/// Renders borsh-encoded strings, JSON, UTF-8 text and borsh-encoded integers,
+/// falling back to hex
+fn render_bytes(bytes: &[u8]) -> String {
+    if let Ok(string) = String::try_from_slice(bytes) {
+        return format!("{:?}", string);
+    }
+    match serde_json::from_slice::<serde_json::Value>(bytes) {
+        Ok(value) if value.is_object() || value.is_array() => return value.to_string(),
+        _ => {}
+    }
+    if let Ok(string) = std::str::from_utf8(bytes) {
+        if !string.is_empty() && !string.chars().any(char::is_control) {
+            return string.to_string();
+        }
+    }
+    match bytes.len() {
+        8 => u64::try_from_slice(bytes).unwrap().to_string(),
+        16 => u128::try_from_slice(bytes).unwrap().to_string(),
+        _ => format!("0x{}", hex::encode(bytes)),
+    }
+}
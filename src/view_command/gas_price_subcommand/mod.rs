@@ -0,0 +1,198 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+
+/// Show the gas price and, for a transaction, estimate its fee with the action
+/// costs of the protocol config
+#[derive(Debug)]
+pub struct GasPrice {
+    pub block_id: Option<crate::common::BlockIdArg>,
+    pub transaction: Option<String>,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliGasPrice {
+    /// The gas price at this block height or hash instead of the latest block
+    #[structopt(long)]
+    block_id: Option<crate::common::BlockIdArg>,
+    /// Base64-encoded unsigned or signed transaction to estimate the fee of
+    #[structopt(long)]
+    transaction: Option<String>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliGasPrice> for GasPrice {
+    fn from(item: CliGasPrice) -> Self {
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        GasPrice {
+            block_id: item.block_id,
+            transaction: item.transaction,
+            connection_config,
+        }
+    }
+}
+
+impl GasPrice {
+    pub async fn process(self) {
+        let rpc_url = self.connection_config.rpc_url();
+        let gas_price = match crate::common::view_at_block(
+            &self.connection_config,
+            self.block_id.as_ref(),
+            |url, block_reference| async move {
+                let block_id = match block_reference {
+                    near_primitives::types::BlockReference::BlockId(block_id) => {
+                        serde_json::to_value(block_id).unwrap()
+                    }
+                    _ => serde_json::Value::Null,
+                };
+                crate::common::call_json_rpc(&url, "gas_price", serde_json::json!([block_id])).await
+            },
+        )
+        .await
+        .and_then(|result| {
+            result["gas_price"]
+                .as_str()
+                .and_then(|gas_price| gas_price.parse::<u128>().ok())
+                .ok_or_else(|| "The gas price is missing".to_string())
+        }) {
+            Ok(gas_price) => gas_price,
+            Err(err) => return eprintln!("Error fetching the gas price:  {}", err),
+        };
+        println!("Gas price: {} yoctoNEAR per gas", gas_price);
+        println!(
+            "1 Tgas costs {}",
+            crate::common::format_near_amount(gas_price * 1_000_000_000_000)
+        );
+        let transaction = match &self.transaction {
+            Some(transaction) => transaction,
+            None => return,
+        };
+        let transaction = match crate::common::from_base64_borsh::<
+            near_primitives::transaction::Transaction,
+        >(transaction)
+        .or_else(|_| {
+            crate::common::from_base64_borsh::<near_primitives::transaction::SignedTransaction>(
+                transaction,
+            )
+            .map(|signed_transaction| signed_transaction.transaction)
+        }) {
+            Ok(transaction) => transaction,
+            Err(err) => return eprintln!("Error decoding the transaction:  {}", err),
+        };
+        let runtime_config = match crate::protocol_config::fetch_protocol_config_view(
+            &rpc_url,
+            crate::common::view_block_reference(None),
+        )
+        .await
+        {
+            Ok(mut protocol_config) => protocol_config["runtime_config"].take(),
+            Err(err) => return eprintln!("Error fetching the runtime config:  {}", err),
+        };
+        let (burnt_gas, prepaid_gas) =
+            estimate_gas(&runtime_config["transaction_costs"], &transaction);
+        println!(
+            "Gas for the transaction and its action receipt: {} ({})",
+            burnt_gas,
+            crate::common::format_near_amount(u128::from(burnt_gas) * gas_price)
+        );
+        if prepaid_gas > 0 {
+            println!(
+                "Prepaid gas of the function calls: up to {} ({}), the unused part is refunded",
+                prepaid_gas,
+                crate::common::format_near_amount(u128::from(prepaid_gas) * gas_price)
+            );
+        }
+        println!(
+            "Estimated fee: up to {}",
+            crate::style::amount(crate::common::format_near_amount(
+                u128::from(burnt_gas + prepaid_gas) * gas_price
+            ))
+        );
+    }
+    pub fn input_transaction() -> Option<String> {
+        let transaction: String = Input::new()
+            .with_prompt("Enter a base64-encoded transaction to estimate its fee (or leave empty)")
+            .allow_empty(true)
+            .interact_text()
+            .unwrap();
+        if transaction.trim().is_empty() {
+            None
+        } else {
+            Some(transaction)
+        }
+    }
+}
+
+/// The send and execution costs of the actions, without the gas prepaid to
+/// the function calls (returned separately); the costs of the cross-contract
+/// calls made by the function calls are not known in advance
+fn estimate_gas(
+    transaction_costs: &serde_json::Value,
+    transaction: &near_primitives::transaction::Transaction,
+) -> (near_primitives::types::Gas, near_primitives::types::Gas) {
+    let sir = transaction.signer_id == transaction.receiver_id;
+    let fee = |fee: &serde_json::Value, count: u64| {
+        let send_fee = if sir {
+            &fee["send_sir"]
+        } else {
+            &fee["send_not_sir"]
+        };
+        (send_fee.as_u64().unwrap_or_default() + fee["execution"].as_u64().unwrap_or_default())
+            * count
+    };
+    let action_costs = &transaction_costs["action_creation_config"];
+    let mut burnt_gas = fee(&transaction_costs["action_receipt_creation_config"], 1);
+    let mut prepaid_gas = 0;
+    for action in &transaction.actions {
+        burnt_gas += match action {
+            near_primitives::transaction::Action::CreateAccount(_) => {
+                fee(&action_costs["create_account_cost"], 1)
+            }
+            near_primitives::transaction::Action::DeployContract(deploy_contract) => {
+                fee(&action_costs["deploy_contract_cost"], 1)
+                    + fee(
+                        &action_costs["deploy_contract_cost_per_byte"],
+                        deploy_contract.code.len() as u64,
+                    )
+            }
+            near_primitives::transaction::Action::FunctionCall(function_call) => {
+                prepaid_gas += function_call.gas;
+                fee(&action_costs["function_call_cost"], 1)
+                    + fee(
+                        &action_costs["function_call_cost_per_byte"],
+                        (function_call.method_name.len() + function_call.args.len()) as u64,
+                    )
+            }
+            near_primitives::transaction::Action::Transfer(_) => {
+                fee(&action_costs["transfer_cost"], 1)
+            }
+            near_primitives::transaction::Action::Stake(_) => fee(&action_costs["stake_cost"], 1),
+            near_primitives::transaction::Action::AddKey(add_key) => {
+                match &add_key.access_key.permission {
+                    near_primitives::account::AccessKeyPermission::FullAccess => {
+                        fee(&action_costs["add_key_cost"]["full_access_cost"], 1)
+                    }
+                    near_primitives::account::AccessKeyPermission::FunctionCall(permission) => {
+                        fee(&action_costs["add_key_cost"]["function_call_cost"], 1)
+                            + fee(
+                                &action_costs["add_key_cost"]["function_call_cost_per_byte"],
+                                permission
+                                    .method_names
+                                    .iter()
+                                    .map(|method_name| method_name.len() as u64)
+                                    .sum(),
+                            )
+                    }
+                }
+            }
+            near_primitives::transaction::Action::DeleteKey(_) => {
+                fee(&action_costs["delete_key_cost"], 1)
+            }
+            near_primitives::transaction::Action::DeleteAccount(_) => {
+                fee(&action_costs["delete_account_cost"], 1)
+            }
+        };
+    }
+    (burnt_gas, prepaid_gas)
+}
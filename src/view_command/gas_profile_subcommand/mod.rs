@@ -0,0 +1,218 @@
+use structopt::StructOpt;
+
+use super::tx_inclusion_subcommand::TxInclusion;
+
+/// Break down the gas burnt by a transaction and all its receipts, so the
+/// expensive step of a cross-contract flow stands out
+#[derive(Debug)]
+pub struct GasProfile {
+    pub transaction_hash: near_primitives::hash::CryptoHash,
+    pub signer_account_id: String,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliGasProfile {
+    /// The transaction hash or a NEAR Explorer / NearBlocks link to it
+    transaction_hash: Option<crate::common::TransactionReference>,
+    #[structopt(long)]
+    signer_account_id: Option<String>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliGasProfile> for GasProfile {
+    fn from(item: CliGasProfile) -> Self {
+        let transaction_reference = match item.transaction_hash {
+            Some(cli_transaction_hash) => cli_transaction_hash,
+            None => TxInclusion::input_transaction_hash(),
+        };
+        let signer_account_id: String = match item.signer_account_id {
+            Some(cli_signer_account_id) => cli_signer_account_id,
+            None => TxInclusion::input_signer_account_id(),
+        };
+        let connection_config = item
+            .connection_config
+            .into_connection_config_or(transaction_reference.connection_config);
+        GasProfile {
+            transaction_hash: transaction_reference.transaction_hash,
+            signer_account_id,
+            connection_config,
+        }
+    }
+}
+
+/// One line of the report: the transaction itself or one of its receipts
+struct GasProfileStep {
+    id: String,
+    executor_id: String,
+    description: String,
+    gas_burnt: u64,
+    /// Per-cost breakdown reported by newer nodes in the outcome metadata
+    costs: Vec<(String, u64)>,
+}
+
+impl GasProfile {
+    pub async fn process(self) {
+        let params =
+            serde_json::json!([self.transaction_hash.to_string(), &self.signer_account_id]);
+        let transaction_status =
+            match crate::common::with_archival_fallback(&self.connection_config, |url| {
+                let params = params.clone();
+                async move {
+                    crate::common::call_json_rpc(&url, "EXPERIMENTAL_tx_status", params).await
+                }
+            })
+            .await
+            {
+                Ok(transaction_status) => transaction_status,
+                Err(err) => return eprintln!("Error fetching the transaction:  {}", err),
+            };
+        let block_hash = transaction_status["transaction_outcome"]["block_hash"].clone();
+        let gas_price =
+            match crate::common::with_archival_fallback(&self.connection_config, |url| {
+                let block_hash = block_hash.clone();
+                async move {
+                    crate::common::call_json_rpc(
+                        &url,
+                        "block",
+                        serde_json::json!({ "block_id": block_hash }),
+                    )
+                    .await
+                }
+            })
+            .await
+            {
+                Ok(block) => block["header"]["gas_price"]
+                    .as_str()
+                    .and_then(|gas_price| gas_price.parse::<u128>().ok())
+                    .unwrap_or_default(),
+                Err(err) => return eprintln!("Error fetching the block:  {}", err),
+            };
+
+        let mut steps = vec![GasProfileStep::from_outcome(
+            &transaction_status["transaction_outcome"],
+            "transaction to receipt conversion".to_string(),
+        )];
+        for receipt_outcome in transaction_status["receipts_outcome"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+        {
+            let receipt = transaction_status["receipts"]
+                .as_array()
+                .and_then(|receipts| {
+                    receipts
+                        .iter()
+                        .find(|receipt| receipt["receipt_id"] == receipt_outcome["id"])
+                })
+                .cloned()
+                .unwrap_or_default();
+            steps.push(GasProfileStep::from_outcome(
+                &receipt_outcome,
+                describe_receipt(&receipt),
+            ));
+        }
+
+        let total_gas_burnt: u64 = steps.iter().map(|step| step.gas_burnt).sum();
+        println!(
+            "Gas price: {} yoctoNEAR per gas unit, total: {} ({})\n",
+            gas_price,
+            format_tgas(total_gas_burnt),
+            crate::style::amount(crate::common::format_near_amount(
+                u128::from(total_gas_burnt) * gas_price
+            ))
+        );
+        for step in steps {
+            let share = if total_gas_burnt == 0 {
+                0.0
+            } else {
+                step.gas_burnt as f64 * 100.0 / total_gas_burnt as f64
+            };
+            println!(
+                "{:>5.1}%  {:>12}  {:>14}  {} {} ({})",
+                share,
+                format_tgas(step.gas_burnt),
+                crate::common::format_near_amount(u128::from(step.gas_burnt) * gas_price),
+                crate::style::hash(&step.id),
+                crate::style::account_id(&step.executor_id),
+                step.description
+            );
+            for (cost, gas_used) in step.costs {
+                println!("{:>21}  {}", format_tgas(gas_used), cost);
+            }
+        }
+    }
+}
+
+impl GasProfileStep {
+    fn from_outcome(outcome: &serde_json::Value, description: String) -> Self {
+        let costs = outcome["outcome"]["metadata"]["gas_profile"]
+            .as_array()
+            .map(|gas_profile| {
+                gas_profile
+                    .iter()
+                    .map(|cost| {
+                        (
+                            format!(
+                                "{} {}",
+                                cost["cost_category"].as_str().unwrap_or_default(),
+                                cost["cost"].as_str().unwrap_or_default()
+                            ),
+                            parse_gas(&cost["gas_used"]),
+                        )
+                    })
+                    .filter(|(_, gas_used)| *gas_used > 0)
+                    .collect()
+            })
+            .unwrap_or_default();
+        GasProfileStep {
+            id: outcome["id"].as_str().unwrap_or_default().to_string(),
+            executor_id: outcome["outcome"]["executor_id"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            description,
+            gas_burnt: parse_gas(&outcome["outcome"]["gas_burnt"]),
+            costs,
+        }
+    }
+}
+
+/// Gas is a number in the outcomes, but a string in the gas profile
+fn parse_gas(gas: &serde_json::Value) -> u64 {
+    gas.as_u64()
+        .or_else(|| gas.as_str().and_then(|gas| gas.parse().ok()))
+        .unwrap_or_default()
+}
+
+fn format_tgas(gas: u64) -> String {
+    format!("{:.3} TGas", gas as f64 / 1_000_000_000_000.0)
+}
+
+/// Function calls are named by their methods, the rest by the action types
+fn describe_receipt(receipt: &serde_json::Value) -> String {
+    let actions = match receipt["receipt"]["Action"]["actions"].as_array() {
+        Some(actions) => actions,
+        None if receipt["receipt"]["Data"].is_object() => return "data receipt".to_string(),
+        None => return "receipt".to_string(),
+    };
+    if receipt["predecessor_id"] == "system" {
+        return "gas refund".to_string();
+    }
+    actions
+        .iter()
+        .map(|action| match action {
+            serde_json::Value::String(action_type) => action_type.clone(),
+            serde_json::Value::Object(action) => match action.get("FunctionCall") {
+                Some(function_call) => format!(
+                    "{}()",
+                    function_call["method_name"].as_str().unwrap_or_default()
+                ),
+                None => action.keys().next().cloned().unwrap_or_default(),
+            },
+            _ => String::new(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,463 @@
+use dialoguer::{theme::ColorfulTheme, Select};
+use structopt::StructOpt;
+use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator, VariantNames};
+
+mod access_keys_subcommand;
+mod account_history_subcommand;
+mod account_summary_subcommand;
+mod accounts_by_key_subcommand;
+mod audit_keys_subcommand;
+mod block_subcommand;
+mod call_function_subcommand;
+mod contract_code_subcommand;
+mod contract_source_metadata_subcommand;
+mod contract_state_subcommand;
+mod gas_price_subcommand;
+mod gas_profile_subcommand;
+mod nfts_subcommand;
+mod protocol_config_subcommand;
+mod sub_accounts_subcommand;
+mod tokens_subcommand;
+mod transaction_status_subcommand;
+mod tx_inclusion_subcommand;
+
+#[derive(Debug)]
+pub struct ViewType {
+    pub query: ViewList,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliViewType {
+    /// `optimistic`, `near-final` or `final` (`view_finality` in the config by default)
+    #[structopt(long, possible_values = crate::config::ViewFinality::VARIANTS)]
+    finality: Option<crate::config::ViewFinality>,
+    #[structopt(subcommand)]
+    query: Option<CliViewList>,
+}
+
+#[derive(Debug, EnumDiscriminants)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+pub enum ViewList {
+    #[strum_discriminants(strum(message = "Find the block and chunk including a transaction"))]
+    TxInclusion(tx_inclusion_subcommand::TxInclusion),
+    #[strum_discriminants(strum(message = "View the contract storage"))]
+    ContractState(contract_state_subcommand::ContractState),
+    #[strum_discriminants(strum(message = "Audit the origin of the access keys of an account"))]
+    AuditKeys(audit_keys_subcommand::AuditKeys),
+    #[strum_discriminants(strum(message = "View the transaction history of an account"))]
+    AccountHistory(account_history_subcommand::AccountHistory),
+    #[strum_discriminants(strum(message = "List the sub-accounts of an account"))]
+    SubAccounts(sub_accounts_subcommand::SubAccounts),
+    #[strum_discriminants(strum(message = "Find the accounts that have a public key"))]
+    AccountsByKey(accounts_by_key_subcommand::AccountsByKey),
+    #[strum_discriminants(strum(message = "Break down the gas burnt by a transaction"))]
+    GasProfile(gas_profile_subcommand::GasProfile),
+    #[strum_discriminants(strum(
+        message = "Cross-check the source metadata of a contract with its recorded provenance"
+    ))]
+    ContractSourceMetadata(contract_source_metadata_subcommand::ContractSourceMetadata),
+    #[strum_discriminants(strum(message = "List the access keys of an account"))]
+    AccessKeys(access_keys_subcommand::AccessKeys),
+    #[strum_discriminants(strum(message = "Call a view method of a contract"))]
+    CallFunction(call_function_subcommand::CallFunction),
+    #[strum_discriminants(strum(message = "View a block and its chunks"))]
+    Block(block_subcommand::Block),
+    #[strum_discriminants(strum(message = "View the gas price and estimate a transaction fee"))]
+    GasPrice(gas_price_subcommand::GasPrice),
+    #[strum_discriminants(strum(
+        message = "View the storage price, the action fees and the runtime limits"
+    ))]
+    ProtocolConfig(protocol_config_subcommand::ProtocolConfig),
+    #[strum_discriminants(strum(message = "View the fungible token balances of an account"))]
+    Tokens(tokens_subcommand::Tokens),
+    #[strum_discriminants(strum(message = "View the non-fungible tokens of an account"))]
+    Nfts(nfts_subcommand::Nfts),
+    #[strum_discriminants(strum(
+        message = "View the code of a contract or verify it against a wasm file"
+    ))]
+    ContractCode(contract_code_subcommand::ContractCode),
+    #[strum_discriminants(strum(message = "View the balance and storage of an account"))]
+    AccountSummary(account_summary_subcommand::AccountSummary),
+    #[strum_discriminants(strum(message = "View the status of a transaction and its receipts"))]
+    TransactionStatus(transaction_status_subcommand::TransactionStatus),
+}
+
+#[derive(Debug, StructOpt)]
+enum CliViewList {
+    TxInclusion(tx_inclusion_subcommand::CliTxInclusion),
+    ContractState(contract_state_subcommand::CliContractState),
+    AuditKeys(audit_keys_subcommand::CliAuditKeys),
+    AccountHistory(account_history_subcommand::CliAccountHistory),
+    SubAccounts(sub_accounts_subcommand::CliSubAccounts),
+    AccountsByKey(accounts_by_key_subcommand::CliAccountsByKey),
+    GasProfile(gas_profile_subcommand::CliGasProfile),
+    ContractSourceMetadata(contract_source_metadata_subcommand::CliContractSourceMetadata),
+    AccessKeys(access_keys_subcommand::CliAccessKeys),
+    CallFunction(call_function_subcommand::CliCallFunction),
+    Block(block_subcommand::CliBlock),
+    GasPrice(gas_price_subcommand::CliGasPrice),
+    ProtocolConfig(protocol_config_subcommand::CliProtocolConfig),
+    Tokens(tokens_subcommand::CliTokens),
+    Nfts(nfts_subcommand::CliNfts),
+    ContractCode(contract_code_subcommand::CliContractCode),
+    AccountSummary(account_summary_subcommand::CliAccountSummary),
+    TransactionStatus(transaction_status_subcommand::CliTransactionStatus),
+}
+
+impl From<CliViewType> for ViewType {
+    fn from(item: CliViewType) -> Self {
+        let finality = match item.finality {
+            Some(cli_finality) => cli_finality,
+            None => crate::config::Config::load().view_finality,
+        };
+        crate::common::set_view_finality(&finality);
+        let query: ViewList = match item.query {
+            Some(cli_query) => ViewList::from(cli_query),
+            None => ViewList::choose_query(),
+        };
+        ViewType { query }
+    }
+}
+
+impl ViewList {
+    pub async fn process(self) {
+        match self {
+            ViewList::TxInclusion(tx_inclusion) => tx_inclusion.process().await,
+            ViewList::ContractState(contract_state) => contract_state.process().await,
+            ViewList::AuditKeys(audit_keys) => audit_keys.process().await,
+            ViewList::AccountHistory(account_history) => account_history.process().await,
+            ViewList::SubAccounts(sub_accounts) => sub_accounts.process().await,
+            ViewList::AccountsByKey(accounts_by_key) => accounts_by_key.process().await,
+            ViewList::GasProfile(gas_profile) => gas_profile.process().await,
+            ViewList::ContractSourceMetadata(contract_source_metadata) => {
+                contract_source_metadata.process().await
+            }
+            ViewList::AccessKeys(access_keys) => access_keys.process().await,
+            ViewList::CallFunction(call_function) => call_function.process().await,
+            ViewList::Block(block) => block.process().await,
+            ViewList::GasPrice(gas_price) => gas_price.process().await,
+            ViewList::ProtocolConfig(protocol_config) => protocol_config.process().await,
+            ViewList::Tokens(tokens) => tokens.process().await,
+            ViewList::Nfts(nfts) => nfts.process().await,
+            ViewList::ContractCode(contract_code) => contract_code.process().await,
+            ViewList::AccountSummary(account_summary) => account_summary.process().await,
+            ViewList::TransactionStatus(transaction_status) => transaction_status.process().await,
+        }
+    }
+    pub fn choose_query() -> Self {
+        eprintln!();
+        let variants = ViewListDiscriminants::iter().collect::<Vec<_>>();
+        let queries = variants
+            .iter()
+            .map(|p| p.get_message().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("What do you want to view?")
+            .items(&queries)
+            .default(0)
+            .interact()
+            .unwrap();
+        crate::common::emit_event(
+            "prompt_answered",
+            serde_json::json!({ "prompt": "What do you want to view?", "answer": queries[selection] }),
+        );
+        match variants[selection] {
+            ViewListDiscriminants::TxInclusion => {
+                let transaction_reference =
+                    tx_inclusion_subcommand::TxInclusion::input_transaction_hash();
+                let signer_account_id =
+                    tx_inclusion_subcommand::TxInclusion::input_signer_account_id();
+                let connection_config = match transaction_reference.connection_config {
+                    Some(connection_config) => connection_config,
+                    None => crate::common::ConnectionConfig::input_connection_config(),
+                };
+                Self::TxInclusion(tx_inclusion_subcommand::TxInclusion {
+                    transaction_hash: transaction_reference.transaction_hash,
+                    signer_account_id,
+                    connection_config,
+                })
+            }
+            ViewListDiscriminants::ContractState => {
+                let contract_id = contract_state_subcommand::ContractState::input_contract_id();
+                let prefix = contract_state_subcommand::ContractState::input_prefix();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::ContractState(contract_state_subcommand::ContractState {
+                    contract_id,
+                    prefix,
+                    prefix_encoding: Default::default(),
+                    borsh_schema: None,
+                    to_file: None,
+                    connection_config,
+                })
+            }
+            ViewListDiscriminants::AuditKeys => {
+                let account_id = audit_keys_subcommand::AuditKeys::input_account_id();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::AuditKeys(audit_keys_subcommand::AuditKeys {
+                    account_id,
+                    ledger_public_keys: vec![],
+                    connection_config,
+                })
+            }
+            ViewListDiscriminants::AccountHistory => {
+                let account_id = account_history_subcommand::AccountHistory::input_account_id();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::AccountHistory(account_history_subcommand::AccountHistory {
+                    account_id,
+                    limit: 20,
+                    connection_config,
+                })
+            }
+            ViewListDiscriminants::SubAccounts => {
+                let account_id = account_history_subcommand::AccountHistory::input_account_id();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::SubAccounts(sub_accounts_subcommand::SubAccounts {
+                    account_id,
+                    connection_config,
+                })
+            }
+            ViewListDiscriminants::AccountsByKey => {
+                let public_key = accounts_by_key_subcommand::AccountsByKey::input_public_key();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::AccountsByKey(accounts_by_key_subcommand::AccountsByKey {
+                    public_key,
+                    connection_config,
+                })
+            }
+            ViewListDiscriminants::GasProfile => {
+                let transaction_reference =
+                    tx_inclusion_subcommand::TxInclusion::input_transaction_hash();
+                let signer_account_id =
+                    tx_inclusion_subcommand::TxInclusion::input_signer_account_id();
+                let connection_config = match transaction_reference.connection_config {
+                    Some(connection_config) => connection_config,
+                    None => crate::common::ConnectionConfig::input_connection_config(),
+                };
+                Self::GasProfile(gas_profile_subcommand::GasProfile {
+                    transaction_hash: transaction_reference.transaction_hash,
+                    signer_account_id,
+                    connection_config,
+                })
+            }
+            ViewListDiscriminants::ContractSourceMetadata => {
+                let contract_id = contract_state_subcommand::ContractState::input_contract_id();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::ContractSourceMetadata(
+                    contract_source_metadata_subcommand::ContractSourceMetadata {
+                        contract_id,
+                        connection_config,
+                    },
+                )
+            }
+            ViewListDiscriminants::AccessKeys => {
+                let account_id = access_keys_subcommand::AccessKeys::input_account_id();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::AccessKeys(access_keys_subcommand::AccessKeys {
+                    account_id,
+                    block_id: None,
+                    connection_config,
+                    format: crate::common::OutputFormat::Plaintext,
+                })
+            }
+            ViewListDiscriminants::CallFunction => {
+                let contract_id = contract_state_subcommand::ContractState::input_contract_id();
+                let args = call_function_subcommand::CallFunction::input_args();
+                let block_id = crate::common::input_block_id();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::CallFunction(call_function_subcommand::CallFunction {
+                    contract_id,
+                    method_name: None,
+                    args,
+                    block_id,
+                    connection_config,
+                })
+            }
+            ViewListDiscriminants::Block => {
+                let block_id = crate::common::input_block_id();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::Block(block_subcommand::Block {
+                    block_id,
+                    chunks: false,
+                    connection_config,
+                    format: crate::common::OutputFormat::Plaintext,
+                })
+            }
+            ViewListDiscriminants::GasPrice => {
+                let block_id = crate::common::input_block_id();
+                let transaction = gas_price_subcommand::GasPrice::input_transaction();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::GasPrice(gas_price_subcommand::GasPrice {
+                    block_id,
+                    transaction,
+                    connection_config,
+                })
+            }
+            ViewListDiscriminants::ProtocolConfig => {
+                let block_id = crate::common::input_block_id();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::ProtocolConfig(protocol_config_subcommand::ProtocolConfig {
+                    block_id,
+                    connection_config,
+                    format: crate::common::OutputFormat::Plaintext,
+                })
+            }
+            ViewListDiscriminants::Tokens => {
+                let account_id = tokens_subcommand::Tokens::input_account_id();
+                let token_contract_ids = tokens_subcommand::Tokens::input_token_contract_ids();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::Tokens(tokens_subcommand::Tokens {
+                    account_id,
+                    token_contract_ids,
+                    connection_config,
+                    format: crate::common::OutputFormat::Plaintext,
+                })
+            }
+            ViewListDiscriminants::Nfts => {
+                let account_id = nfts_subcommand::Nfts::input_account_id();
+                let nft_contract_id = nfts_subcommand::Nfts::input_nft_contract_id();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::Nfts(nfts_subcommand::Nfts {
+                    account_id,
+                    nft_contract_id,
+                    page_size: 50,
+                    connection_config,
+                    format: crate::common::OutputFormat::Plaintext,
+                })
+            }
+            ViewListDiscriminants::ContractCode => {
+                let contract_id = contract_code_subcommand::ContractCode::input_contract_id();
+                let verify = contract_code_subcommand::ContractCode::input_verify();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::ContractCode(contract_code_subcommand::ContractCode {
+                    contract_id,
+                    block_id: None,
+                    save_to: None,
+                    verify,
+                    methods: true,
+                    connection_config,
+                })
+            }
+            ViewListDiscriminants::AccountSummary => {
+                let account_id = account_summary_subcommand::AccountSummary::input_account_id();
+                let connection_config = crate::common::ConnectionConfig::input_connection_config();
+                Self::AccountSummary(account_summary_subcommand::AccountSummary {
+                    account_id,
+                    watch: false,
+                    interval: 5,
+                    connection_config,
+                })
+            }
+            ViewListDiscriminants::TransactionStatus => {
+                let transaction_reference =
+                    tx_inclusion_subcommand::TxInclusion::input_transaction_hash();
+                let signer_account_id =
+                    tx_inclusion_subcommand::TxInclusion::input_signer_account_id();
+                let connection_config = match transaction_reference.connection_config {
+                    Some(connection_config) => connection_config,
+                    None => crate::common::ConnectionConfig::input_connection_config(),
+                };
+                Self::TransactionStatus(transaction_status_subcommand::TransactionStatus {
+                    transaction_hash: transaction_reference.transaction_hash,
+                    signer_account_id,
+                    watch: true,
+                    interval: 2,
+                    connection_config,
+                })
+            }
+        }
+    }
+}
+
+impl From<CliViewList> for ViewList {
+    fn from(item: CliViewList) -> Self {
+        match item {
+            CliViewList::TxInclusion(cli_tx_inclusion) => {
+                let tx_inclusion = tx_inclusion_subcommand::TxInclusion::from(cli_tx_inclusion);
+                ViewList::TxInclusion(tx_inclusion)
+            }
+            CliViewList::ContractState(cli_contract_state) => {
+                let contract_state =
+                    contract_state_subcommand::ContractState::from(cli_contract_state);
+                ViewList::ContractState(contract_state)
+            }
+            CliViewList::AuditKeys(cli_audit_keys) => {
+                let audit_keys = audit_keys_subcommand::AuditKeys::from(cli_audit_keys);
+                ViewList::AuditKeys(audit_keys)
+            }
+            CliViewList::AccountHistory(cli_account_history) => {
+                let account_history =
+                    account_history_subcommand::AccountHistory::from(cli_account_history);
+                ViewList::AccountHistory(account_history)
+            }
+            CliViewList::SubAccounts(cli_sub_accounts) => {
+                let sub_accounts = sub_accounts_subcommand::SubAccounts::from(cli_sub_accounts);
+                ViewList::SubAccounts(sub_accounts)
+            }
+            CliViewList::AccountsByKey(cli_accounts_by_key) => {
+                let accounts_by_key =
+                    accounts_by_key_subcommand::AccountsByKey::from(cli_accounts_by_key);
+                ViewList::AccountsByKey(accounts_by_key)
+            }
+            CliViewList::GasProfile(cli_gas_profile) => {
+                let gas_profile = gas_profile_subcommand::GasProfile::from(cli_gas_profile);
+                ViewList::GasProfile(gas_profile)
+            }
+            CliViewList::ContractSourceMetadata(cli_contract_source_metadata) => {
+                let contract_source_metadata =
+                    contract_source_metadata_subcommand::ContractSourceMetadata::from(
+                        cli_contract_source_metadata,
+                    );
+                ViewList::ContractSourceMetadata(contract_source_metadata)
+            }
+            CliViewList::AccessKeys(cli_access_keys) => {
+                let access_keys = access_keys_subcommand::AccessKeys::from(cli_access_keys);
+                ViewList::AccessKeys(access_keys)
+            }
+            CliViewList::CallFunction(cli_call_function) => {
+                let call_function = call_function_subcommand::CallFunction::from(cli_call_function);
+                ViewList::CallFunction(call_function)
+            }
+            CliViewList::Block(cli_block) => {
+                let block = block_subcommand::Block::from(cli_block);
+                ViewList::Block(block)
+            }
+            CliViewList::GasPrice(cli_gas_price) => {
+                let gas_price = gas_price_subcommand::GasPrice::from(cli_gas_price);
+                ViewList::GasPrice(gas_price)
+            }
+            CliViewList::ProtocolConfig(cli_protocol_config) => {
+                let protocol_config =
+                    protocol_config_subcommand::ProtocolConfig::from(cli_protocol_config);
+                ViewList::ProtocolConfig(protocol_config)
+            }
+            CliViewList::Tokens(cli_tokens) => {
+                let tokens = tokens_subcommand::Tokens::from(cli_tokens);
+                ViewList::Tokens(tokens)
+            }
+            CliViewList::Nfts(cli_nfts) => {
+                let nfts = nfts_subcommand::Nfts::from(cli_nfts);
+                ViewList::Nfts(nfts)
+            }
+            CliViewList::ContractCode(cli_contract_code) => {
+                let contract_code = contract_code_subcommand::ContractCode::from(cli_contract_code);
+                ViewList::ContractCode(contract_code)
+            }
+            CliViewList::AccountSummary(cli_account_summary) => {
+                let account_summary =
+                    account_summary_subcommand::AccountSummary::from(cli_account_summary);
+                ViewList::AccountSummary(account_summary)
+            }
+            CliViewList::TransactionStatus(cli_transaction_status) => {
+                let transaction_status =
+                    transaction_status_subcommand::TransactionStatus::from(cli_transaction_status);
+                ViewList::TransactionStatus(transaction_status)
+            }
+        }
+    }
+}
+
+impl ViewType {
+    pub async fn process(self) {
+        self.query.process().await
+    }
+}
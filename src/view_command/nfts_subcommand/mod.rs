@@ -0,0 +1,143 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+use strum::VariantNames;
+
+/// Show the non-fungible tokens (NEP-171) an account owns in a contract
+#[derive(Debug)]
+pub struct Nfts {
+    pub account_id: String,
+    pub nft_contract_id: String,
+    pub page_size: u64,
+    pub connection_config: crate::common::ConnectionConfig,
+    pub format: crate::common::OutputFormat,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliNfts {
+    account_id: Option<String>,
+    /// The NFT contract, e.g. `x.paras.near`
+    nft_contract_id: Option<String>,
+    /// Tokens fetched per `nft_tokens_for_owner` call
+    #[structopt(long, default_value = "50")]
+    page_size: u64,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+    #[structopt(long, default_value = "plaintext", possible_values = crate::common::OutputFormat::VARIANTS)]
+    format: crate::common::OutputFormat,
+}
+
+impl From<CliNfts> for Nfts {
+    fn from(item: CliNfts) -> Self {
+        let account_id: String = match item.account_id {
+            Some(cli_account_id) => cli_account_id,
+            None => Nfts::input_account_id(),
+        };
+        let nft_contract_id: String = match item.nft_contract_id {
+            Some(cli_nft_contract_id) => cli_nft_contract_id,
+            None => Nfts::input_nft_contract_id(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        Nfts {
+            account_id,
+            nft_contract_id,
+            page_size: item.page_size,
+            connection_config,
+            format: item.format,
+        }
+    }
+}
+
+impl Nfts {
+    pub async fn process(self) {
+        let rpc_url = self.connection_config.rpc_url();
+        let mut tokens = vec![];
+        loop {
+            let page = match crate::common::call_view_function(
+                &rpc_url,
+                &self.nft_contract_id,
+                "nft_tokens_for_owner",
+                serde_json::json!({
+                    "account_id": self.account_id,
+                    "from_index": tokens.len().to_string(),
+                    "limit": self.page_size,
+                }),
+            )
+            .await
+            {
+                Ok(serde_json::Value::Array(page)) => page,
+                Ok(page) => return eprintln!("Unexpected list of tokens: {}", page),
+                Err(err) => {
+                    return eprintln!(
+                        "Error fetching the tokens of <{}>:  {}",
+                        self.account_id, err
+                    )
+                }
+            };
+            let is_last_page = page.is_empty() || (page.len() as u64) < self.page_size;
+            tokens.extend(page);
+            if is_last_page {
+                break;
+            }
+        }
+        match self.format {
+            crate::common::OutputFormat::Plaintext => {
+                if tokens.is_empty() {
+                    return eprintln!(
+                        "<{}> owns no tokens of <{}>",
+                        self.account_id, self.nft_contract_id
+                    );
+                }
+                // Relative media URLs are relative to the base URI of the contract
+                let base_uri = crate::common::call_view_function(
+                    &rpc_url,
+                    &self.nft_contract_id,
+                    "nft_metadata",
+                    serde_json::json!({}),
+                )
+                .await
+                .ok()
+                .and_then(|metadata| metadata["base_uri"].as_str().map(String::from));
+                for token in &tokens {
+                    let metadata = &token["metadata"];
+                    println!(
+                        "{}  {}",
+                        token["token_id"].as_str().unwrap_or_default(),
+                        metadata["title"].as_str().unwrap_or("(untitled)")
+                    );
+                    if let Some(media) = metadata["media"].as_str() {
+                        println!("    {}", media_url(base_uri.as_deref(), media));
+                    }
+                }
+                eprintln!("{} tokens", tokens.len());
+            }
+            crate::common::OutputFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&tokens).unwrap())
+            }
+        }
+    }
+    pub fn input_account_id() -> String {
+        Input::new()
+            .with_prompt("Which account's tokens do you want to view?")
+            .interact_text()
+            .unwrap()
+    }
+    pub fn input_nft_contract_id() -> String {
+        Input::new()
+            .with_prompt("Which NFT contract do you want to look in?")
+            .interact_text()
+            .unwrap()
+    }
+}
+
+fn media_url(base_uri: Option<&str>, media: &str) -> String {
+    match base_uri {
+        Some(base_uri) if !media.contains("://") && !media.starts_with("data:") => {
+            format!(
+                "{}/{}",
+                base_uri.trim_end_matches('/'),
+                media.trim_start_matches('/')
+            )
+        }
+        _ => media.to_string(),
+    }
+}
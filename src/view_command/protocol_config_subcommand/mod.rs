@@ -0,0 +1,238 @@
+use structopt::StructOpt;
+use strum::VariantNames;
+
+/// Show the storage price, the action fees and the runtime limits of a network
+#[derive(Debug)]
+pub struct ProtocolConfig {
+    pub block_id: Option<crate::common::BlockIdArg>,
+    pub connection_config: crate::common::ConnectionConfig,
+    pub format: crate::common::OutputFormat,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliProtocolConfig {
+    /// The block height or hash, the latest block (with the view finality) if unset
+    block_id: Option<crate::common::BlockIdArg>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+    #[structopt(long, default_value = "plaintext", possible_values = crate::common::OutputFormat::VARIANTS)]
+    format: crate::common::OutputFormat,
+}
+
+impl From<CliProtocolConfig> for ProtocolConfig {
+    fn from(item: CliProtocolConfig) -> Self {
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        ProtocolConfig {
+            block_id: item.block_id,
+            connection_config,
+            format: item.format,
+        }
+    }
+}
+
+/// The action fees, as paths in `runtime_config.transaction_costs`
+const ACTION_FEES: &[(&str, &[&str])] = &[
+    ("Action receipt", &["action_receipt_creation_config"]),
+    (
+        "Data receipt",
+        &["data_receipt_creation_config", "base_cost"],
+    ),
+    (
+        "Data receipt, per byte",
+        &["data_receipt_creation_config", "cost_per_byte"],
+    ),
+    (
+        "Create account",
+        &["action_creation_config", "create_account_cost"],
+    ),
+    (
+        "Deploy contract",
+        &["action_creation_config", "deploy_contract_cost"],
+    ),
+    (
+        "Deploy contract, per byte",
+        &["action_creation_config", "deploy_contract_cost_per_byte"],
+    ),
+    (
+        "Function call",
+        &["action_creation_config", "function_call_cost"],
+    ),
+    (
+        "Function call, per byte",
+        &["action_creation_config", "function_call_cost_per_byte"],
+    ),
+    ("Transfer", &["action_creation_config", "transfer_cost"]),
+    ("Stake", &["action_creation_config", "stake_cost"]),
+    (
+        "Add full access key",
+        &["action_creation_config", "add_key_cost", "full_access_cost"],
+    ),
+    (
+        "Add function call key",
+        &[
+            "action_creation_config",
+            "add_key_cost",
+            "function_call_cost",
+        ],
+    ),
+    (
+        "Add function call key, per byte",
+        &[
+            "action_creation_config",
+            "add_key_cost",
+            "function_call_cost_per_byte",
+        ],
+    ),
+    ("Delete key", &["action_creation_config", "delete_key_cost"]),
+    (
+        "Delete account",
+        &["action_creation_config", "delete_account_cost"],
+    ),
+];
+
+enum LimitUnit {
+    Gas,
+    Bytes,
+    Count,
+}
+
+/// The runtime limits, in `runtime_config.wasm_config.limit_config`
+const RUNTIME_LIMITS: &[(&str, &str, LimitUnit)] = &[
+    (
+        "Prepaid gas per transaction",
+        "max_total_prepaid_gas",
+        LimitUnit::Gas,
+    ),
+    ("Gas burnt per call", "max_gas_burnt", LimitUnit::Gas),
+    ("Contract size", "max_contract_size", LimitUnit::Bytes),
+    ("Transaction size", "max_transaction_size", LimitUnit::Bytes),
+    ("Arguments size", "max_arguments_length", LimitUnit::Bytes),
+    (
+        "Method name length",
+        "max_length_method_name",
+        LimitUnit::Bytes,
+    ),
+    (
+        "Returned data size",
+        "max_length_returned_data",
+        LimitUnit::Bytes,
+    ),
+    ("Logs per call", "max_number_logs", LimitUnit::Count),
+    ("Total log size", "max_total_log_length", LimitUnit::Bytes),
+    (
+        "Actions per receipt",
+        "max_actions_per_receipt",
+        LimitUnit::Count,
+    ),
+    (
+        "Memory pages of 64 KiB",
+        "max_memory_pages",
+        LimitUnit::Count,
+    ),
+];
+
+impl ProtocolConfig {
+    pub async fn process(self) {
+        let protocol_config = match crate::common::view_at_block(
+            &self.connection_config,
+            self.block_id.as_ref(),
+            |url, block_reference| async move {
+                crate::protocol_config::fetch_protocol_config_view(&url, block_reference).await
+            },
+        )
+        .await
+        {
+            Ok(protocol_config) => protocol_config,
+            Err(err) => return eprintln!("Error fetching the protocol config:  {}", err),
+        };
+        if let crate::common::OutputFormat::Json = self.format {
+            return println!(
+                "{}",
+                serde_json::to_string_pretty(&protocol_config).unwrap()
+            );
+        }
+        let runtime_config = &protocol_config["runtime_config"];
+        println!(
+            "Chain ID: {}, protocol version {}",
+            protocol_config["chain_id"].as_str().unwrap_or("unknown"),
+            protocol_config["protocol_version"]
+        );
+        if let Some(storage_amount_per_byte) = runtime_config["storage_amount_per_byte"]
+            .as_str()
+            .and_then(|amount| amount.parse::<u128>().ok())
+        {
+            println!(
+                "Storage: {} per byte, {} per 100 KB",
+                crate::common::format_near_amount(storage_amount_per_byte),
+                crate::common::format_near_amount(storage_amount_per_byte * 100_000)
+            );
+        }
+        if let Some(transaction_validity_period) =
+            protocol_config["transaction_validity_period"].as_u64()
+        {
+            println!(
+                "Transaction validity: {} blocks",
+                transaction_validity_period
+            );
+        }
+        if let Some(gas_limit) = protocol_config["gas_limit"].as_u64() {
+            println!("Chunk gas limit: {}", format_gas(gas_limit));
+        }
+
+        println!();
+        println!(
+            "{:<32} {:>14} {:>14} {:>14}",
+            "Action fee", "Send", "Send to self", "Execution"
+        );
+        for (name, path) in ACTION_FEES {
+            let fee = path
+                .iter()
+                .fold(&runtime_config["transaction_costs"], |fee, key| &fee[*key]);
+            if fee.is_null() {
+                continue;
+            }
+            let gas = |field: &str| format_gas(fee[field].as_u64().unwrap_or_default());
+            println!(
+                "{:<32} {:>14} {:>14} {:>14}",
+                name,
+                gas("send_not_sir"),
+                gas("send_sir"),
+                gas("execution")
+            );
+        }
+
+        println!();
+        println!("Runtime limits:");
+        let limit_config = &runtime_config["wasm_config"]["limit_config"];
+        for (name, field, unit) in RUNTIME_LIMITS {
+            let limit = match limit_config[*field].as_u64() {
+                Some(limit) => limit,
+                None => continue,
+            };
+            let limit = match unit {
+                LimitUnit::Gas => format_gas(limit),
+                LimitUnit::Bytes => format_bytes(limit),
+                LimitUnit::Count => limit.to_string(),
+            };
+            println!("  {:<30} {}", name, limit);
+        }
+    }
+}
+
+/// Gas in the largest unit that keeps the value above one
+fn format_gas(gas: u64) -> String {
+    match gas {
+        gas if gas >= 1_000_000_000_000 => format!("{:.3} TGas", gas as f64 / 1e12),
+        gas if gas >= 1_000_000_000 => format!("{:.3} GGas", gas as f64 / 1e9),
+        gas if gas >= 1_000_000 => format!("{:.3} MGas", gas as f64 / 1e6),
+        gas => format!("{} gas", gas),
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        bytes if bytes >= 1024 * 1024 => format!("{:.1} MiB", bytes as f64 / 1024.0 / 1024.0),
+        bytes if bytes >= 1024 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        bytes => format!("{} bytes", bytes),
+    }
+}
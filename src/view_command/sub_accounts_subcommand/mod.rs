@@ -0,0 +1,51 @@
+use structopt::StructOpt;
+
+use super::account_history_subcommand::AccountHistory;
+
+/// List the sub-accounts an account created, from the configured indexer
+#[derive(Debug)]
+pub struct SubAccounts {
+    pub account_id: String,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliSubAccounts {
+    account_id: Option<String>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliSubAccounts> for SubAccounts {
+    fn from(item: CliSubAccounts) -> Self {
+        let account_id: String = match item.account_id {
+            Some(cli_account_id) => cli_account_id,
+            None => AccountHistory::input_account_id(),
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        SubAccounts {
+            account_id,
+            connection_config,
+        }
+    }
+}
+
+impl SubAccounts {
+    pub async fn process(self) {
+        let indexer = match crate::indexer::indexer_for(&self.connection_config) {
+            Ok(indexer) => indexer,
+            Err(err) => return eprintln!("{}", err),
+        };
+        match indexer.sub_accounts(&self.account_id).await {
+            Ok(sub_accounts) if sub_accounts.is_empty() => {
+                eprintln!("<{}> has no sub-accounts", self.account_id)
+            }
+            Ok(sub_accounts) => {
+                for sub_account in sub_accounts {
+                    println!("{}", crate::style::account_id(sub_account));
+                }
+            }
+            Err(err) => eprintln!("Error fetching the sub-accounts:  {}", err),
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use dialoguer::Input;
+use structopt::StructOpt;
+use strum::VariantNames;
+
+/// Show the balances of an account in fungible tokens (NEP-141), formatted with
+/// the decimals of the token metadata
+#[derive(Debug)]
+pub struct Tokens {
+    pub account_id: String,
+    pub token_contract_ids: Vec<String>,
+    pub connection_config: crate::common::ConnectionConfig,
+    pub format: crate::common::OutputFormat,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliTokens {
+    account_id: Option<String>,
+    /// The token contracts, e.g. `usdt.tether-token.near`
+    token_contract_ids: Vec<String>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+    #[structopt(long, default_value = "plaintext", possible_values = crate::common::OutputFormat::VARIANTS)]
+    format: crate::common::OutputFormat,
+}
+
+impl From<CliTokens> for Tokens {
+    fn from(item: CliTokens) -> Self {
+        let account_id: String = match item.account_id {
+            Some(cli_account_id) => cli_account_id,
+            None => Tokens::input_account_id(),
+        };
+        let token_contract_ids = if item.token_contract_ids.is_empty() {
+            Tokens::input_token_contract_ids()
+        } else {
+            item.token_contract_ids
+        };
+        let connection_config = crate::common::ConnectionConfig::from(item.connection_config);
+        Tokens {
+            account_id,
+            token_contract_ids,
+            connection_config,
+            format: item.format,
+        }
+    }
+}
+
+/// The part of `ft_metadata` needed to show a balance
+#[derive(Debug, serde::Deserialize)]
+struct FungibleTokenMetadata {
+    name: String,
+    symbol: String,
+    decimals: u8,
+}
+
+impl Tokens {
+    pub async fn process(self) {
+        let rpc_url = self.connection_config.rpc_url();
+        let mut balances = vec![];
+        for token_contract_id in &self.token_contract_ids {
+            let metadata = crate::common::call_view_function(
+                &rpc_url,
+                token_contract_id,
+                "ft_metadata",
+                serde_json::json!({}),
+            )
+            .await
+            .and_then(|metadata| {
+                serde_json::from_value::<FungibleTokenMetadata>(metadata)
+                    .map_err(|err| format!("Invalid token metadata: {}", err))
+            });
+            let metadata = match metadata {
+                Ok(metadata) => metadata,
+                Err(err) => {
+                    eprintln!(
+                        "Error fetching the metadata of <{}>:  {}",
+                        token_contract_id, err
+                    );
+                    continue;
+                }
+            };
+            let balance = crate::common::call_view_function(
+                &rpc_url,
+                token_contract_id,
+                "ft_balance_of",
+                serde_json::json!({ "account_id": self.account_id }),
+            )
+            .await
+            .and_then(|balance| {
+                balance
+                    .as_str()
+                    .and_then(|balance| balance.parse::<u128>().ok())
+                    .ok_or_else(|| format!("Invalid token balance: {}", balance))
+            });
+            match balance {
+                Ok(balance) => balances.push((token_contract_id, metadata, balance)),
+                Err(err) => eprintln!(
+                    "Error fetching the balance in <{}>:  {}",
+                    token_contract_id, err
+                ),
+            }
+        }
+        match self.format {
+            crate::common::OutputFormat::Plaintext => {
+                for (token_contract_id, metadata, balance) in &balances {
+                    println!(
+                        "{} {} ({}, <{}>)",
+                        crate::style::amount(format_token_amount(*balance, metadata.decimals)),
+                        metadata.symbol,
+                        metadata.name,
+                        token_contract_id
+                    );
+                }
+            }
+            crate::common::OutputFormat::Json => {
+                let balances = balances
+                    .iter()
+                    .map(|(token_contract_id, metadata, balance)| {
+                        serde_json::json!({
+                            "token_contract_id": token_contract_id,
+                            "name": metadata.name,
+                            "symbol": metadata.symbol,
+                            "decimals": metadata.decimals,
+                            "balance": balance.to_string(),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                println!("{}", serde_json::to_string_pretty(&balances).unwrap());
+            }
+        }
+    }
+    pub fn input_account_id() -> String {
+        Input::new()
+            .with_prompt("Which account's token balances do you want to view?")
+            .interact_text()
+            .unwrap()
+    }
+    pub fn input_token_contract_ids() -> Vec<String> {
+        let token_contract_ids: String = Input::new()
+            .with_prompt("Enter the token contracts (comma-separated)")
+            .interact_text()
+            .unwrap();
+        token_contract_ids
+            .split(',')
+            .map(|token_contract_id| token_contract_id.trim().to_string())
+            .filter(|token_contract_id| !token_contract_id.is_empty())
+            .collect()
+    }
+}
+
+/// The balance in whole tokens; a token with more decimals than fit in u128
+/// is shown in its smallest units
+fn format_token_amount(balance: u128, decimals: u8) -> String {
+    match 10u128.checked_pow(u32::from(decimals)) {
+        Some(one_token) => crate::style::format_decimal(
+            balance / one_token,
+            &format!(
+                "{:0width$}",
+                balance % one_token,
+                width = usize::from(decimals)
+            ),
+        ),
+        None => crate::style::format_decimal(balance, ""),
+    }
+}
@@ -0,0 +1,134 @@
+use structopt::StructOpt;
+
+use super::tx_inclusion_subcommand::TxInclusion;
+
+/// Show the status of a transaction and of its receipts and, with `--watch`,
+/// keep printing the status transitions until the outcome is final
+#[derive(Debug)]
+pub struct TransactionStatus {
+    pub transaction_hash: near_primitives::hash::CryptoHash,
+    pub signer_account_id: String,
+    pub watch: bool,
+    pub interval: u64,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliTransactionStatus {
+    /// The transaction hash or a NEAR Explorer / NearBlocks link to it
+    transaction_hash: Option<crate::common::TransactionReference>,
+    #[structopt(long)]
+    signer_account_id: Option<String>,
+    /// Poll the transaction and print the status transitions until it is final
+    #[structopt(long)]
+    watch: bool,
+    /// Seconds between the polls of `--watch`
+    #[structopt(long, default_value = "2")]
+    interval: u64,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliTransactionStatus> for TransactionStatus {
+    fn from(item: CliTransactionStatus) -> Self {
+        let transaction_reference = match item.transaction_hash {
+            Some(cli_transaction_hash) => cli_transaction_hash,
+            None => TxInclusion::input_transaction_hash(),
+        };
+        let signer_account_id: String = match item.signer_account_id {
+            Some(cli_signer_account_id) => cli_signer_account_id,
+            None => TxInclusion::input_signer_account_id(),
+        };
+        let connection_config = item
+            .connection_config
+            .into_connection_config_or(transaction_reference.connection_config);
+        TransactionStatus {
+            transaction_hash: transaction_reference.transaction_hash,
+            signer_account_id,
+            watch: item.watch,
+            interval: item.interval,
+            connection_config,
+        }
+    }
+}
+
+impl TransactionStatus {
+    pub async fn process(self) {
+        let rpc_url = self.connection_config.rpc_url();
+        let params =
+            serde_json::json!([self.transaction_hash.to_string(), &self.signer_account_id]);
+        let mut last_summary = String::new();
+        loop {
+            match crate::common::call_json_rpc(&rpc_url, "EXPERIMENTAL_tx_status", params.clone())
+                .await
+            {
+                Ok(transaction_status) => {
+                    let summary = summarize(&transaction_status);
+                    if summary != last_summary {
+                        println!("{}", summary);
+                        last_summary = summary;
+                    }
+                    if is_final(&transaction_status["status"]) {
+                        return;
+                    }
+                }
+                // A just submitted transaction is not known to the node yet
+                Err(err) if self.watch && crate::common::is_missing_data_error(&err) => {
+                    let summary = "The transaction is not known to the node yet".to_string();
+                    if summary != last_summary {
+                        eprintln!("{}", summary);
+                        last_summary = summary;
+                    }
+                }
+                Err(err) => return eprintln!("Error fetching the transaction:  {}", err),
+            }
+            if !self.watch {
+                return;
+            }
+            actix_rt::time::delay_for(std::time::Duration::from_secs(self.interval)).await;
+        }
+    }
+}
+
+/// `SuccessValue` and `Failure` end the execution; `NotStarted`, `Started` and
+/// `SuccessReceiptId` (the receipts are still running) do not
+fn is_final(status: &serde_json::Value) -> bool {
+    status.get("SuccessValue").is_some() || status.get("Failure").is_some()
+}
+
+fn status_name(status: &serde_json::Value) -> String {
+    match status {
+        serde_json::Value::String(status) => status.clone(),
+        serde_json::Value::Object(status) => status.keys().cloned().collect::<Vec<_>>().join(","),
+        status => status.to_string(),
+    }
+}
+
+fn summarize(transaction_status: &serde_json::Value) -> String {
+    let receipts_outcome = transaction_status["receipts_outcome"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let failed_receipts = receipts_outcome
+        .iter()
+        .filter(|receipt_outcome| {
+            receipt_outcome["outcome"]["status"]
+                .get("Failure")
+                .is_some()
+        })
+        .count();
+    let status = &transaction_status["status"];
+    let status = if is_final(status) && status.get("Failure").is_none() {
+        crate::style::success(status_name(status))
+    } else if status.get("Failure").is_some() {
+        crate::style::warning(format!("Failure: {}", status["Failure"]))
+    } else {
+        status_name(status)
+    };
+    format!(
+        "Status: {}, {} receipt outcome(s), {} failed",
+        status,
+        receipts_outcome.len(),
+        failed_receipts
+    )
+}
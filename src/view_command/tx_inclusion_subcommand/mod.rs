@@ -0,0 +1,165 @@
+use dialoguer::Input;
+use std::str::FromStr;
+use structopt::StructOpt;
+
+/// Find the block, chunk and shard that include a transaction, and check
+/// whether a light client proof is available for it
+#[derive(Debug)]
+pub struct TxInclusion {
+    pub transaction_hash: near_primitives::hash::CryptoHash,
+    pub signer_account_id: String,
+    pub connection_config: crate::common::ConnectionConfig,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CliTxInclusion {
+    /// The transaction hash or a NEAR Explorer / NearBlocks link to it
+    transaction_hash: Option<crate::common::TransactionReference>,
+    #[structopt(long)]
+    signer_account_id: Option<String>,
+    #[structopt(flatten)]
+    connection_config: crate::common::CliConnectionConfig,
+}
+
+impl From<CliTxInclusion> for TxInclusion {
+    fn from(item: CliTxInclusion) -> Self {
+        let transaction_reference = match item.transaction_hash {
+            Some(cli_transaction_hash) => cli_transaction_hash,
+            None => TxInclusion::input_transaction_hash(),
+        };
+        let signer_account_id: String = match item.signer_account_id {
+            Some(cli_signer_account_id) => cli_signer_account_id,
+            None => TxInclusion::input_signer_account_id(),
+        };
+        let connection_config = item
+            .connection_config
+            .into_connection_config_or(transaction_reference.connection_config);
+        TxInclusion {
+            transaction_hash: transaction_reference.transaction_hash,
+            signer_account_id,
+            connection_config,
+        }
+    }
+}
+
+impl TxInclusion {
+    pub async fn process(self) {
+        let server_url = self.connection_config.rpc_url();
+        let transaction_info =
+            crate::common::with_archival_fallback(&self.connection_config, |url| {
+                near_jsonrpc_client::new_client(url.as_str()).tx(
+                    self.transaction_hash.to_string(),
+                    self.signer_account_id.clone(),
+                )
+            })
+            .await
+            .map_err(|err| eprintln!("Error fetching the transaction:  {:?}", &err))
+            .unwrap();
+        let block_hash = transaction_info.transaction_outcome.block_hash;
+        let block = crate::common::with_archival_fallback(&self.connection_config, |url| {
+            near_jsonrpc_client::new_client(url.as_str()).block(
+                near_primitives::types::BlockReference::BlockId(
+                    near_primitives::types::BlockId::Hash(block_hash),
+                ),
+            )
+        })
+        .await
+        .map_err(|err| eprintln!("Error fetching the block:  {:?}", &err))
+        .unwrap();
+        println!(
+            "Block: {} (height {})",
+            crate::style::hash(block.header.hash),
+            block.header.height
+        );
+
+        let mut including_chunk = None;
+        for chunk_header in block.chunks.iter() {
+            let chunk =
+                match crate::common::with_archival_fallback(&self.connection_config, |url| {
+                    near_jsonrpc_client::new_client(url.as_str()).chunk(
+                        near_jsonrpc_client::ChunkId::Hash(chunk_header.chunk_hash.clone()),
+                    )
+                })
+                .await
+                {
+                    Ok(chunk) => chunk,
+                    Err(err) => {
+                        eprintln!(
+                            "Error fetching the chunk {} (shard {}):  {:?}",
+                            chunk_header.chunk_hash, chunk_header.shard_id, err
+                        );
+                        continue;
+                    }
+                };
+            if chunk
+                .transactions
+                .iter()
+                .any(|transaction| transaction.hash == self.transaction_hash)
+            {
+                including_chunk = Some(chunk_header);
+                break;
+            }
+        }
+        match including_chunk {
+            Some(chunk_header) => println!(
+                "Chunk: {} (shard {}, included at height {})",
+                crate::style::hash(&chunk_header.chunk_hash),
+                chunk_header.shard_id,
+                chunk_header.height_included
+            ),
+            None => eprintln!(
+                "{}",
+                crate::style::warning("The transaction was not found in the chunks of this block")
+            ),
+        }
+
+        let final_block = near_jsonrpc_client::new_client(server_url.as_str())
+            .block(near_primitives::types::BlockReference::Finality(
+                near_primitives::types::Finality::Final,
+            ))
+            .await
+            .map_err(|err| eprintln!("Error fetching the final block:  {:?}", &err))
+            .unwrap();
+        let light_client_proof = crate::common::call_json_rpc(
+            &server_url,
+            "EXPERIMENTAL_light_client_proof",
+            serde_json::json!({
+                "type": "transaction",
+                "transaction_hash": self.transaction_hash.to_string(),
+                "sender_id": self.signer_account_id,
+                "light_client_head": final_block.header.hash.to_string(),
+            }),
+        )
+        .await;
+        match light_client_proof {
+            Ok(_) => println!(
+                "Light client proof: {} (light client head {})",
+                crate::style::success("available"),
+                crate::style::hash(final_block.header.hash)
+            ),
+            Err(err) => eprintln!(
+                "Light client proof: {} ({})",
+                crate::style::warning("not available"),
+                err
+            ),
+        }
+    }
+    pub fn input_transaction_hash() -> crate::common::TransactionReference {
+        loop {
+            let input_transaction_hash: String = Input::new()
+                .with_prompt("Enter the transaction hash or an explorer link to it")
+                .interact_text()
+                .unwrap();
+            match crate::common::TransactionReference::from_str(&input_transaction_hash) {
+                Ok(transaction_reference) => break transaction_reference,
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+    }
+    pub fn input_signer_account_id() -> String {
+        Input::new()
+            .with_prompt("What is the account ID of the transaction signer?")
+            .interact_text()
+            .unwrap()
+    }
+}